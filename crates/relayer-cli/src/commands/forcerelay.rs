@@ -1,8 +1,6 @@
 use core::convert::Infallible;
 use core::time::Duration;
 use std::ops::Deref;
-use std::panic::PanicInfo;
-use std::sync::Arc;
 
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
@@ -11,14 +9,18 @@ use tracing::error_span;
 use ibc_relayer::chain::handle::{CachingChainHandle, ChainHandle};
 use ibc_relayer::config::GLOBAL_CONFIG_PATH;
 use ibc_relayer::event::monitor::{Error as EventError, ErrorDetail as EventErrorDetail};
+use ibc_relayer::object::{HeaderRelay, Object};
 use ibc_relayer::registry::SharedRegistry;
-use ibc_relayer::supervisor::forcerelay::handle_eth_ckb_event_batch;
 use ibc_relayer::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
+use ibc_relayer::worker::WorkerMap;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::conclude::Output;
 use crate::prelude::*;
 
+/// Relays the headers of an Ethereum chain to a CKB chain, by running the
+/// same header relay worker the supervisor spawns for the CKB chains
+/// configured with an `eth_chain_id`.
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
 pub struct EthCkbCmd {
     #[clap(
@@ -44,13 +46,13 @@ impl Runnable for EthCkbCmd {
             .set(config_path.clone())
             .expect("fail to set config path");
 
-        let registry = SharedRegistry::<CachingChainHandle>::new(config);
-        let eth = Arc::new(registry.get_or_spawn(&self.eth_chain).unwrap_or_else(|e| {
+        let registry = SharedRegistry::<CachingChainHandle>::new(config.clone());
+        let eth = registry.get_or_spawn(&self.eth_chain).unwrap_or_else(|e| {
             Output::error(format!("Forcerelay failed to start ethereum: {e}")).exit()
-        }));
-        let ckb = Arc::new(registry.get_or_spawn(&self.ckb_chain).unwrap_or_else(|e| {
+        });
+        let ckb = registry.get_or_spawn(&self.ckb_chain).unwrap_or_else(|e| {
             Output::error(format!("Forcerelay failed to start ckb: {e}")).exit()
-        }));
+        });
         let eth_subscription = eth.subscribe().unwrap_or_else(|e| {
             Output::error(format!(
                 "Forcerelay failed to subscribe ethereum events: {e}"
@@ -58,8 +60,21 @@ impl Runnable for EthCkbCmd {
             .exit()
         });
 
-        let src_chain = eth.clone();
-        let dst_chain = ckb.clone();
+        let header_relay = Object::HeaderRelay(HeaderRelay {
+            dst_chain_id: self.ckb_chain.clone(),
+            src_chain_id: self.eth_chain.clone(),
+        });
+        let mut workers = WorkerMap::new();
+        workers.spawn(eth.clone(), ckb.clone(), &header_relay, &config);
+        if workers.header_relays(&self.eth_chain).next().is_none() {
+            Output::error(format!(
+                "Forcerelay failed to start {}",
+                header_relay.short_name()
+            ))
+            .exit()
+        }
+
+        let src_chain_id = self.eth_chain.clone();
         let handle = spawn_background_task(
             error_span!("worker.forcerelay"),
             Some(Duration::from_secs(5)),
@@ -67,7 +82,14 @@ impl Runnable for EthCkbCmd {
                 if let Ok(batch) = eth_subscription.try_recv() {
                     match batch.deref() {
                         Ok(batch) => {
-                            handle_eth_ckb_event_batch(&src_chain, &dst_chain, batch);
+                            for worker in workers.header_relays(&src_chain_id) {
+                                worker.send_events(
+                                    batch.height,
+                                    batch.events.clone(),
+                                    batch.chain_id.clone(),
+                                    batch.tracking_id,
+                                );
+                            }
                         }
                         Err(EventError(EventErrorDetail::SubscriptionCancelled(_), _)) => {
                             warn!("event subscription was cancelled, clearing pending packets");
@@ -89,8 +111,8 @@ impl Runnable for EthCkbCmd {
 
 async fn wait_shutdown<ChainA: ChainHandle, ChainB: ChainHandle>(
     forcerelay: TaskHandle,
-    eth: Arc<ChainA>,
-    ckb: Arc<ChainB>,
+    eth: ChainA,
+    ckb: ChainB,
 ) {
     let ctrl_c_handler = tokio::spawn(async {
         #[cfg(windows)]
@@ -107,28 +129,13 @@ async fn wait_shutdown<ChainA: ChainHandle, ChainB: ChainHandle>(
         }
     });
 
-    // register channel of panic
-    let (panic_sender, mut panic_receiver) = tokio::sync::mpsc::channel(1);
-
-    std::panic::set_hook(Box::new(move |info: &PanicInfo<'_>| {
-        panic_sender
-            .try_send(info.to_string())
-            .expect("panic_receiver is droped");
-    }));
+    // panics of the header relay are recovered by its worker, which
+    // retries the failing batch, so only signals stop the command
+    let _ = ctrl_c_handler.await;
+    warn!("<Ctrl-C> is pressed, quit Forcerelay and shutdown");
 
-    let shutdown = move || {
-        eth.shutdown().expect("shutdown eth");
-        ckb.shutdown().expect("shutdown ckb");
-        forcerelay.shutdown_and_wait();
-    };
-    tokio::select! {
-        _ = ctrl_c_handler => {
-            warn!("<Ctrl-C> is pressed, quit Forcerelay and shutdown");
-            shutdown();
-        }
-        Some(panic_info) = panic_receiver.recv() => {
-            warn!("child thread paniced: {panic_info}");
-            shutdown();
-        }
-    }
+    // the header relay worker is shut down along with the forwarding task
+    forcerelay.shutdown_and_wait();
+    eth.shutdown().expect("shutdown eth");
+    ckb.shutdown().expect("shutdown ckb");
 }
@@ -5,6 +5,7 @@ use abscissa_core::{Command, Runnable};
 mod add;
 mod balance;
 mod delete;
+mod export;
 mod list;
 
 /// `keys` subcommand
@@ -16,6 +17,9 @@ pub enum KeysCmd {
     /// Delete key(s) from a configured chain
     Delete(delete::KeysDeleteCmd),
 
+    /// Export a key of a configured chain into an encrypted keystore file
+    Export(export::KeysExportCmd),
+
     /// List keys configured on a chain
     List(list::KeysListCmd),
 
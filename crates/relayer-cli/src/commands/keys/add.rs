@@ -13,7 +13,8 @@ use ibc_relayer::{
     chain::ChainType,
     config::{AddressType, ChainConfig, Config},
     keyring::{
-        AnySigningKeyPair, KeyRing, Secp256k1KeyPair, SigningKeyPair, SigningKeyPairSized, Store,
+        import_keystore, AnySigningKeyPair, KeyRing, Passphrase, Secp256k1KeyPair, SigningKeyPair,
+        SigningKeyPairSized,
     },
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -38,7 +39,11 @@ use crate::conclude::Output;
 ///
 /// `keys add [OPTIONS] --chain <CHAIN_ID> --secret-file <SECRET_FILE>`
 ///
-/// The key-file, mnemonic-file, secret-file flags can't be given at the same time, this will cause a terminating error.
+/// The command to import a key from an encrypted Ethereum V3 or ckb-cli keystore file:
+///
+/// `keys add [OPTIONS] --chain <CHAIN_ID> --keystore-file <KEYSTORE_FILE>`
+///
+/// The key-file, mnemonic-file, secret-file, keystore-file flags can't be given at the same time, this will cause a terminating error.
 /// If successful the key will be created or restored, depending on which flag was given.
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
 #[clap(
@@ -46,7 +51,9 @@ use crate::conclude::Output;
 
     forcerelay keys add [OPTIONS] --chain <CHAIN_ID> --mnemonic-file <MNEMONIC_FILE>
 
-    forcerelay keys add [OPTIONS] --chain <CHAIN_ID> --secret-file <SECRET_FILE>"
+    forcerelay keys add [OPTIONS] --chain <CHAIN_ID> --secret-file <SECRET_FILE>
+
+    forcerelay keys add [OPTIONS] --chain <CHAIN_ID> --keystore-file <KEYSTORE_FILE>"
 )]
 pub struct KeysAddCmd {
     #[clap(
@@ -87,6 +94,16 @@ pub struct KeysAddCmd {
     )]
     secret_file: Option<PathBuf>,
 
+    #[clap(
+        long = "keystore-file",
+        required = true,
+        value_name = "KEYSTORE_FILE",
+        help_heading = "FLAGS",
+        help = "Path to an encrypted Ethereum V3 or ckb-cli keystore file to import the key from",
+        group = "add-restore"
+    )]
+    keystore_file: Option<PathBuf>,
+
     #[clap(
         long = "key-name",
         value_name = "KEY_NAME",
@@ -147,13 +164,14 @@ impl Runnable for KeysAddCmd {
             Ok(result) => result,
         };
 
-        // Check if --key-file or --mnemonic-file or --secret-file was given as input.
+        // Check if --key-file or --mnemonic-file or --secret-file or --keystore-file was given as input.
         match (
             self.key_file.clone(),
             self.mnemonic_file.clone(),
             self.secret_file.clone(),
+            self.keystore_file.clone(),
         ) {
-            (Some(key_file), _, _) => {
+            (Some(key_file), _, _, _) => {
                 let key = add_key(
                     &opts.config,
                     &opts.name,
@@ -176,7 +194,7 @@ impl Runnable for KeysAddCmd {
                     .exit(),
                 }
             }
-            (_, Some(mnemonic_file), _) => {
+            (_, Some(mnemonic_file), _, _) => {
                 let key = restore_key(
                     &mnemonic_file,
                     &opts.name,
@@ -200,7 +218,7 @@ impl Runnable for KeysAddCmd {
                     .exit(),
                 }
             }
-            (_, _, Some(secret_file)) => {
+            (_, _, Some(secret_file), _) => {
                 let key =
                     parse_key_from_secret(&secret_file, &opts.name, &opts.config, self.overwrite);
 
@@ -219,6 +237,29 @@ impl Runnable for KeysAddCmd {
                     .exit(),
                 }
             }
+            (_, _, _, Some(keystore_file)) => {
+                let key = import_key_from_keystore(
+                    &keystore_file,
+                    &opts.name,
+                    &opts.config,
+                    self.overwrite,
+                );
+
+                match key {
+                    Ok(key) => Output::success_msg(format!(
+                        "Imported key '{}' ({}) on chain {}",
+                        opts.name,
+                        key.account(),
+                        opts.config.id(),
+                    ))
+                    .exit(),
+                    Err(e) => Output::error(format!(
+                        "An error occurred importing the key on chain {} from keystore {:?}: {}",
+                        self.chain_id, keystore_file, e
+                    ))
+                    .exit(),
+                }
+            }
             // This case should never trigger.
             // The 'required' parameter for the flags will trigger an error if both flags have not been given.
             // And the 'group' parameter for the flags will trigger an error if both flags are given.
@@ -245,7 +286,8 @@ pub fn add_key(
         ChainType::Ckb4Ibc => "ckb4ibc",
     };
     let key_pair = {
-        let mut keyring = KeyRing::new_secp256k1(Store::Test, account_prefix, config.id())?;
+        let mut keyring =
+            KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
 
         check_key_exists(&keyring, key_name, overwrite);
 
@@ -284,7 +326,8 @@ pub fn restore_key(
     };
 
     let key_pair = {
-        let mut keyring = KeyRing::new_secp256k1(Store::Test, account_prefix, config.id())?;
+        let mut keyring =
+            KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
 
         check_key_exists(&keyring, key_name, overwrite);
 
@@ -323,7 +366,8 @@ pub fn parse_key_from_secret(
         ChainType::Ckb4Ibc => ("ckb4ibc", AddressType::Ckb),
     };
     let key_pair = {
-        let mut keyring = KeyRing::new_secp256k1(Store::Test, account_prefix, config.id())?;
+        let mut keyring =
+            KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
 
         check_key_exists(&keyring, key_name, overwrite);
 
@@ -335,6 +379,48 @@ pub fn parse_key_from_secret(
     Ok(key_pair)
 }
 
+pub fn import_key_from_keystore(
+    keystore_file: &Path,
+    key_name: &str,
+    config: &ChainConfig,
+    overwrite: bool,
+) -> eyre::Result<AnySigningKeyPair> {
+    let (account_prefix, address_type) = match config.r#type() {
+        ChainType::CosmosSdk => {
+            return Err(eyre!(
+                "keystore files are only supported by Axon and CKB keys"
+            ))
+        }
+        ChainType::Eth => (
+            "eth",
+            AddressType::Ethermint {
+                pk_type: Default::default(),
+            },
+        ),
+        ChainType::Axon => ("axon", AddressType::Axon),
+        ChainType::Ckb => ("ckb", AddressType::Ckb),
+        ChainType::Ckb4Ibc => ("ckb4ibc", AddressType::Ckb),
+    };
+    let key_pair = {
+        let mut keyring =
+            KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
+
+        check_key_exists(&keyring, key_name, overwrite);
+
+        let passphrase =
+            Passphrase::resolve(&format!("Passphrase of {}", keystore_file.display()))?;
+        let secret_key = import_keystore(keystore_file, &passphrase)?;
+        let key_pair = Secp256k1KeyPair::from_secret_key(
+            &secret_key.display_secret().to_string(),
+            &address_type,
+        )?;
+
+        keyring.add_key(key_name, key_pair.clone())?;
+        key_pair.into()
+    };
+    Ok(key_pair)
+}
+
 /// Check if the key with the given key name already exists.
 /// If it already exists and overwrite is false, abort the command with an error.
 /// If overwrite is true, output a warning message informing the key will be overwritten.
@@ -365,6 +451,7 @@ mod tests {
                 key_file: Some(PathBuf::from("key_file")),
                 mnemonic_file: None,
                 secret_file: None,
+                keystore_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: false,
@@ -381,6 +468,7 @@ mod tests {
                 key_file: None,
                 mnemonic_file: Some(PathBuf::from("mnemonic_file")),
                 secret_file: None,
+                keystore_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: false
@@ -403,6 +491,7 @@ mod tests {
                 key_file: None,
                 mnemonic_file: None,
                 secret_file: Some(PathBuf::from("secret_file")),
+                keystore_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: false,
@@ -425,6 +514,7 @@ mod tests {
                 key_file: Some(PathBuf::from("key_file")),
                 mnemonic_file: None,
                 secret_file: None,
+                keystore_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: true,
@@ -448,6 +538,7 @@ mod tests {
                 key_file: None,
                 mnemonic_file: Some(PathBuf::from("mnemonic_file")),
                 secret_file: None,
+                keystore_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: true,
@@ -471,6 +562,7 @@ mod tests {
                 key_file: None,
                 mnemonic_file: None,
                 secret_file: Some(PathBuf::from("secret_file")),
+                keystore_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: true,
@@ -486,6 +578,29 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_keys_add_keystore_file() {
+        assert_eq!(
+            KeysAddCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_file: None,
+                mnemonic_file: None,
+                secret_file: None,
+                keystore_file: Some(PathBuf::from("keystore_file")),
+                key_name: None,
+                hd_path: "m/44'/118'/0'/0/0".to_string(),
+                overwrite: false,
+            },
+            KeysAddCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--keystore-file",
+                "keystore_file",
+            ])
+        )
+    }
+
     #[test]
     fn test_keys_add_no_file_nor_mnemonic() {
         assert!(KeysAddCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err());
@@ -40,7 +40,7 @@ pub struct KeyBalanceCmd {
     #[clap(
         long = "denom",
         value_name = "DENOM",
-        help = "(optional) query the balance for the given denom (defaults to the `denom` defined in the config for the gas price)"
+        help = "(optional) query the balance for the given denom, the address or the denom of an ERC-20 token on Axon or the denom of an sUDT on CKB (defaults to the `denom` defined in the config for the gas price, or the native token)"
     )]
     denom: Option<String>,
 
@@ -78,11 +78,7 @@ fn get_balance(chain: impl ChainHandle, key_name: Option<String>, denom: Option<
                 chain_config.key_name().to_string()
             });
 
-            Output::success_msg(format!(
-                "balance for key `{}`: {} {}",
-                key_name, balance.amount, balance.denom
-            ))
-            .exit()
+            Output::success_msg(format!("balance for key `{key_name}`: {balance}")).exit()
         }
         Err(e) => Output::error(format!("there was a problem querying the balance: {e}")).exit(),
     }
@@ -100,7 +96,7 @@ fn get_balances(chain: impl ChainHandle, key_name: Option<String>) {
 
             let mut pretty_output = format!("Balances for key `{key_name}`:");
             for balance in balances {
-                write!(pretty_output, "\n\t{} {}", balance.amount, balance.denom)
+                write!(pretty_output, "\n\t{balance}")
                     .unwrap_or_else(exit_with_unrecoverable_error);
             }
 
@@ -5,7 +5,7 @@ use eyre::eyre;
 use ibc_relayer::{
     chain::ChainType,
     config::{ChainConfig, Config},
-    keyring::{KeyRing, Store},
+    keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair},
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
@@ -16,6 +16,8 @@ use crate::conclude::Output;
 #[clap(
     override_usage = "forcerelay keys delete --chain <CHAIN_ID> --key-name <KEY_NAME>
 
+    forcerelay keys delete --chain <CHAIN_ID> --address <ADDRESS>
+
     forcerelay keys delete --chain <CHAIN_ID> --all"
 )]
 pub struct KeysDeleteCmd {
@@ -38,6 +40,16 @@ pub struct KeysDeleteCmd {
     )]
     key_name: Option<String>,
 
+    #[clap(
+        long = "address",
+        required = true,
+        value_name = "ADDRESS",
+        group = "delete_mode",
+        help_heading = "FLAGS",
+        help = "Address of the key, as shown by `keys list`"
+    )]
+    address: Option<String>,
+
     #[clap(
         long = "all",
         required = true,
@@ -54,14 +66,17 @@ impl KeysDeleteCmd {
             .find_chain(&self.chain_id)
             .ok_or_else(|| eyre!("chain '{}' not found in configuration file", self.chain_id))?;
 
-        let id = match (self.all, &self.key_name) {
-            (true, None) => KeysDeleteId::All,
-            (false, Some(ref key_name)) => KeysDeleteId::Named(key_name),
+        let id = match (self.all, &self.key_name, &self.address) {
+            (true, None, None) => KeysDeleteId::All,
+            (false, Some(ref key_name), None) => KeysDeleteId::Named(key_name),
+            (false, None, Some(ref address)) => KeysDeleteId::Address(address),
             // This case should never trigger.
-            // The 'required' parameter for the flags will trigger an error if both flags have not been given.
-            // And the 'group' parameter for the flags will trigger an error if both flags are given.
-            _ => Output::error("--key-name and --all can't both be set or both None".to_string())
-                .exit(),
+            // The 'required' parameter for the flags will trigger an error if none of the flags have been given.
+            // And the 'group' parameter for the flags will trigger an error if several flags are given.
+            _ => Output::error(
+                "exactly one of --key-name, --address and --all must be set".to_string(),
+            )
+            .exit(),
         };
 
         Ok(KeysDeleteOptions {
@@ -81,6 +96,7 @@ struct KeysDeleteOptions<'a> {
 enum KeysDeleteId<'a> {
     All,
     Named(&'a str),
+    Address(&'a str),
 }
 
 impl Runnable for KeysDeleteCmd {
@@ -109,49 +125,129 @@ impl Runnable for KeysDeleteCmd {
                 .exit(),
                 Err(e) => Output::error(e).exit(),
             },
+            KeysDeleteId::Address(address) => match delete_key_by_address(&opts.config, address) {
+                Ok(key_name) => Output::success_msg(format!(
+                    "Removed key ({}) of address {} on chain {}",
+                    key_name,
+                    address,
+                    opts.config.id()
+                ))
+                .exit(),
+                Err(e) => Output::error(e).exit(),
+            },
         };
     }
 }
 
 pub fn delete_key(config: &ChainConfig, key_name: &str) -> eyre::Result<()> {
-    match config.r#type() {
-        ChainType::CosmosSdk => {
-            let mut keyring =
-                KeyRing::new_secp256k1(Store::Test, &config.cosmos().account_prefix, config.id())?;
-            keyring.remove_key(key_name)?;
-        }
-        ChainType::Eth => todo!(),
-        ChainType::Axon => todo!(),
-        ChainType::Ckb => todo!(),
-        ChainType::Ckb4Ibc => todo!(),
-    }
+    let mut keyring = keyring(config)?;
+    keyring.remove_key(key_name)?;
     Ok(())
 }
 
+/// Deletes the key of the given address and returns its name
+pub fn delete_key_by_address(config: &ChainConfig, address: &str) -> eyre::Result<String> {
+    remove_key_by_address(&mut keyring(config)?, address)
+}
+
 pub fn delete_all_keys(config: &ChainConfig) -> eyre::Result<()> {
-    match config.r#type() {
-        ChainType::CosmosSdk => {
-            let mut keyring =
-                KeyRing::new_secp256k1(Store::Test, &config.cosmos().account_prefix, config.id())?;
-            let keys = keyring.keys()?;
-            for (key_name, _) in keys {
-                keyring.remove_key(&key_name)?;
-            }
-        }
-        ChainType::Eth => todo!(),
-        ChainType::Axon => todo!(),
-        ChainType::Ckb => todo!(),
-        ChainType::Ckb4Ibc => todo!(),
+    remove_all_keys(&mut keyring(config)?)
+}
+
+fn keyring(config: &ChainConfig) -> eyre::Result<KeyRing<Secp256k1KeyPair>> {
+    let keyring =
+        KeyRing::new_secp256k1(config.key_store_type(), account_prefix(config), config.id())?;
+    Ok(keyring)
+}
+
+fn remove_key_by_address(
+    keyring: &mut KeyRing<Secp256k1KeyPair>,
+    address: &str,
+) -> eyre::Result<String> {
+    let (key_name, _) = keyring
+        .keys()?
+        .into_iter()
+        .find(|(_, key)| key.account() == address)
+        .ok_or_else(|| eyre!("no key of address {address} found"))?;
+    keyring.remove_key(&key_name)?;
+    Ok(key_name)
+}
+
+fn remove_all_keys(keyring: &mut KeyRing<Secp256k1KeyPair>) -> eyre::Result<()> {
+    let keys = keyring.keys()?;
+    for (key_name, _) in keys {
+        keyring.remove_key(&key_name)?;
     }
     Ok(())
 }
 
+fn account_prefix(config: &ChainConfig) -> &str {
+    match config.r#type() {
+        ChainType::CosmosSdk => &config.cosmos().account_prefix,
+        ChainType::Eth => "eth",
+        ChainType::Axon => "axon",
+        ChainType::Ckb => "ckb",
+        ChainType::Ckb4Ibc => "ckb",
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::KeysDeleteCmd;
+    use super::{remove_all_keys, remove_key_by_address, KeysDeleteCmd};
 
     use abscissa_core::clap::Parser;
+    use ibc_relayer::config::AddressType;
+    use ibc_relayer::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair, Test};
     use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use tempfile::TempDir;
+
+    fn test_keyring(dir: &TempDir) -> KeyRing<Secp256k1KeyPair> {
+        let mut keyring = KeyRing::Test(Test::new("ckb".to_owned(), dir.path().to_path_buf()));
+        for (key_name, secret_key) in [("relayer", "01".repeat(32)), ("other", "02".repeat(32))] {
+            let key = Secp256k1KeyPair::from_secret_key(&secret_key, &AddressType::Ckb).unwrap();
+            keyring.add_key(key_name, key).unwrap();
+        }
+        keyring
+    }
+
+    fn key_names(keyring: &KeyRing<Secp256k1KeyPair>) -> Vec<String> {
+        let mut names: Vec<_> = keyring
+            .keys()
+            .unwrap()
+            .into_iter()
+            .map(|(key_name, _)| key_name)
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_delete_key_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut keyring = test_keyring(&dir);
+        keyring.remove_key("relayer").unwrap();
+        assert_eq!(key_names(&keyring), ["other"]);
+        assert!(keyring.remove_key("relayer").is_err());
+    }
+
+    #[test]
+    fn test_delete_key_by_address() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut keyring = test_keyring(&dir);
+        let address = keyring.get_key("relayer").unwrap().account();
+        let key_name = remove_key_by_address(&mut keyring, &address).unwrap();
+        assert_eq!(key_name, "relayer");
+        assert_eq!(key_names(&keyring), ["other"]);
+        assert!(remove_key_by_address(&mut keyring, &address).is_err());
+    }
+
+    #[test]
+    fn test_delete_all_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut keyring = test_keyring(&dir);
+        remove_all_keys(&mut keyring).unwrap();
+        assert!(key_names(&keyring).is_empty());
+    }
 
     #[test]
     fn test_keys_delete_key_name() {
@@ -159,6 +255,7 @@ mod tests {
             KeysDeleteCmd {
                 chain_id: ChainId::from_string("chain_id"),
                 key_name: Some("to_delete".to_owned()),
+                address: None,
                 all: false
             },
             KeysDeleteCmd::parse_from(["test", "--chain", "chain_id", "--key-name", "to_delete"])
@@ -171,12 +268,26 @@ mod tests {
             KeysDeleteCmd {
                 chain_id: ChainId::from_string("chain_id"),
                 key_name: None,
+                address: None,
                 all: true
             },
             KeysDeleteCmd::parse_from(["test", "--chain", "chain_id", "--all"])
         )
     }
 
+    #[test]
+    fn test_keys_delete_address() {
+        assert_eq!(
+            KeysDeleteCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: None,
+                address: Some("ckt1qyq".to_owned()),
+                all: false
+            },
+            KeysDeleteCmd::parse_from(["test", "--chain", "chain_id", "--address", "ckt1qyq"])
+        )
+    }
+
     #[test]
     fn test_keys_delete_only_chain() {
         assert!(KeysDeleteCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err())
@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use eyre::eyre;
+use ibc_relayer::{
+    chain::ChainType,
+    config::ChainConfig,
+    keyring::{export_keystore, KeyRing, KeystoreFormat, Passphrase, Secp256k1KeyPair},
+};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `keys export` CLI command.
+///
+/// `keys export --chain <CHAIN_ID> [--key-name <KEY_NAME>] --output <KEYSTORE_FILE>`
+///
+/// The key is written into an encrypted keystore file, of the Ethereum V3 format for
+/// Axon and Ethereum chains, and of the ckb-cli format for CKB chains.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct KeysExportCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "key-name",
+        value_name = "KEY_NAME",
+        help = "Name of the key (defaults to the `key_name` defined in the config)"
+    )]
+    key_name: Option<String>,
+
+    #[clap(
+        long = "output",
+        required = true,
+        value_name = "KEYSTORE_FILE",
+        help_heading = "REQUIRED",
+        help = "Path of the keystore file to write"
+    )]
+    output: PathBuf,
+}
+
+impl Runnable for KeysExportCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = match config.find_chain(&self.chain_id) {
+            Some(chain_config) => chain_config,
+            None => Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        let key_name = self
+            .key_name
+            .clone()
+            .unwrap_or_else(|| chain_config.key_name().to_string());
+
+        match export_key(chain_config, &key_name, &self.output) {
+            Ok(()) => Output::success_msg(format!(
+                "Exported key '{}' on chain {} to {:?}",
+                key_name, self.chain_id, self.output
+            ))
+            .exit(),
+            Err(e) => Output::error(format!(
+                "An error occurred exporting the key on chain {}: {}",
+                self.chain_id, e
+            ))
+            .exit(),
+        }
+    }
+}
+
+pub fn export_key(config: &ChainConfig, key_name: &str, output: &Path) -> eyre::Result<()> {
+    let (account_prefix, format) = match config.r#type() {
+        ChainType::CosmosSdk => {
+            return Err(eyre!(
+                "keystore files are only supported by Axon and CKB keys"
+            ))
+        }
+        ChainType::Eth => ("eth", KeystoreFormat::Ethereum),
+        ChainType::Axon => ("axon", KeystoreFormat::Ethereum),
+        ChainType::Ckb => ("ckb", KeystoreFormat::CkbCli),
+        ChainType::Ckb4Ibc => ("ckb4ibc", KeystoreFormat::CkbCli),
+    };
+    if output.exists() {
+        return Err(eyre!("file {:?} already exists", output));
+    }
+
+    let keyring: KeyRing<Secp256k1KeyPair> =
+        KeyRing::new(config.key_store_type(), account_prefix, config.id())?;
+    let key_pair = keyring.get_key(key_name)?;
+
+    let passphrase = Passphrase::resolve("Passphrase of the exported keystore")?;
+    export_keystore(output, &key_pair.private_key, format, &passphrase)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::KeysExportCmd;
+    use std::path::PathBuf;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_keys_export() {
+        assert_eq!(
+            KeysExportCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: Some("relayer".to_string()),
+                output: PathBuf::from("keystore.json"),
+            },
+            KeysExportCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--key-name",
+                "relayer",
+                "--output",
+                "keystore.json"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_keys_export_no_output() {
+        assert!(KeysExportCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err());
+    }
+}
@@ -10,10 +10,24 @@ use abscissa_core::{application::fatal_error, Runnable};
 use eyre::eyre;
 use itertools::Itertools;
 use tendermint_rpc::{client::CompatMode, Client, HttpClient};
-use tokio::runtime::Runtime as TokioRuntime;
+use tokio::{runtime::Runtime as TokioRuntime, sync::watch};
 use tracing::{error, info, instrument};
 
-use ibc_relayer::{chain::handle::Subscription, config::ChainConfig, event::monitor::EventMonitor};
+use ibc_relayer::{
+    chain::{
+        axon::{AxonEventMonitor, Checkpoint},
+        ckb::rpc_client::RpcClient,
+        ckb4ibc::{Ckb4IbcEventMonitor, CursorStore},
+        eth::event::monitor::EthEventMonitor,
+        handle::Subscription,
+    },
+    config::{
+        axon::AxonChainConfig, ckb4ibc::ChainConfig as Ckb4IbcChainConfig,
+        cosmos::ChainConfig as CosmosChainConfig, eth::EthChainConfig, ChainConfig,
+    },
+    event::monitor::EventMonitor,
+    light_client::eth::LightClient as EthLightClient,
+};
 use ibc_relayer_types::{core::ics24_host::identifier::ChainId, events::IbcEvent};
 
 use crate::prelude::*;
@@ -100,8 +114,7 @@ impl Runnable for ListenCmd {
 #[instrument(skip_all, level = "error", fields(chain = %config.id()))]
 pub fn listen(config: &ChainConfig, filters: &[EventFilter]) -> eyre::Result<()> {
     let rt = Arc::new(TokioRuntime::new()?);
-    let compat_mode = detect_compatibility_mode(config, rt.clone())?;
-    let rx = subscribe(config, compat_mode, rt)?;
+    let rx = subscribe(config, rt)?;
 
     while let Ok(event_batch) = rx.recv() {
         match event_batch.as_ref() {
@@ -134,20 +147,27 @@ fn event_match(event: &IbcEvent, filters: &[EventFilter]) -> bool {
     filters.iter().any(|f| f.matches(event))
 }
 
-fn subscribe(
-    chain_config: &ChainConfig,
-    compat_mode: CompatMode,
+fn subscribe(chain_config: &ChainConfig, rt: Arc<TokioRuntime>) -> eyre::Result<Subscription> {
+    match chain_config {
+        ChainConfig::Cosmos(config) => subscribe_cosmos(config, rt),
+        ChainConfig::Axon(config) => subscribe_axon(config, rt),
+        ChainConfig::Ckb4Ibc(config) => subscribe_ckb4ibc(config, rt),
+        ChainConfig::Eth(config) => subscribe_eth(config, rt),
+        ChainConfig::Ckb(config) => Err(eyre!(
+            "chain '{}' emits no IBC events, listen to its counterparty chain instead",
+            config.id
+        )),
+    }
+}
+
+fn subscribe_cosmos(
+    chain_config: &CosmosChainConfig,
     rt: Arc<TokioRuntime>,
 ) -> eyre::Result<Subscription> {
+    let compat_mode = detect_compatibility_mode(chain_config, rt.clone())?;
     let (mut event_monitor, tx_cmd) = EventMonitor::new(
-        chain_config.id().clone(),
-        match chain_config {
-            ChainConfig::Cosmos(_) => chain_config.cosmos().websocket_addr.clone(),
-            ChainConfig::Eth(_) => "".parse().unwrap(),
-            ChainConfig::Ckb(_) => "".parse().unwrap(),
-            ChainConfig::Axon(_) => "".parse().unwrap(),
-            ChainConfig::Ckb4Ibc(_) => "".parse().unwrap(),
-        },
+        chain_config.id.clone(),
+        chain_config.websocket_addr.clone(),
         compat_mode,
         rt,
     )
@@ -166,11 +186,113 @@ fn subscribe(
     Ok(subscription)
 }
 
+fn subscribe_axon(
+    chain_config: &AxonChainConfig,
+    rt: Arc<TokioRuntime>,
+) -> eyre::Result<Subscription> {
+    // the checkpoint of a running relayer is left untouched
+    let (event_monitor, tx_cmd) = AxonEventMonitor::new(
+        chain_config.id.clone(),
+        chain_config.websocket_addr.clone(),
+        chain_config.contract_address,
+        chain_config.restore_block_count,
+        Checkpoint::disabled(),
+        chain_config.rescan_from_height,
+        rt,
+    )
+    .map_err(|e| eyre!("could not initialize event monitor: {}", e))?;
+
+    info!(
+        "listening for events of contract {:?}",
+        chain_config.contract_address
+    );
+
+    thread::spawn(move || event_monitor.run());
+
+    let subscription = tx_cmd.subscribe()?;
+    Ok(subscription)
+}
+
+/// The cells scanned by the CKB4IBC event monitor depend on the light client of
+/// the counterparty chain, so a monitor is run for each configured light client
+fn subscribe_ckb4ibc(
+    chain_config: &Ckb4IbcChainConfig,
+    rt: Arc<TokioRuntime>,
+) -> eyre::Result<Subscription> {
+    if chain_config.onchain_light_clients.is_empty() {
+        return Err(eyre!(
+            "chain '{}' has no onchain light client configured",
+            chain_config.id
+        ));
+    }
+    let rpc_client = Arc::new(RpcClient::new(
+        &chain_config.ckb_rpc,
+        &chain_config.ckb_indexer_rpc,
+    ));
+
+    let (tx, rx) = crossbeam_channel::unbounded();
+    for client_type in chain_config.onchain_light_clients.keys().copied() {
+        let (client_type_tx, client_type_rx) = watch::channel(Some(client_type));
+        // the cursors of a running relayer are left untouched
+        let (event_monitor, tx_cmd, _, ibc_transaction_rx) = Ckb4IbcEventMonitor::new(
+            rt.clone(),
+            rpc_client.clone(),
+            chain_config.clone(),
+            client_type_rx,
+            CursorStore::disabled(),
+        );
+        info!("listening for events of the {client_type} light client");
+
+        thread::spawn(move || {
+            let _client_type_tx = client_type_tx;
+            event_monitor.run()
+        });
+        // the IBC transactions are only cached by the chain endpoint
+        thread::spawn(move || for _ in ibc_transaction_rx {});
+
+        let subscription = tx_cmd.subscribe()?;
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for batch in subscription {
+                if tx.send(batch).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    Ok(rx)
+}
+
+fn subscribe_eth(
+    chain_config: &EthChainConfig,
+    rt: Arc<TokioRuntime>,
+) -> eyre::Result<Subscription> {
+    let mut light_client = EthLightClient::from_config(chain_config, rt.clone())?;
+    // subscribe before the bootstrap to also report the headers it syncs
+    let (create_receiver, header_receiver) = light_client.subscribe();
+    light_client.bootstrap()?;
+
+    let (event_monitor, tx_cmd) = EthEventMonitor::new(
+        chain_config.id.clone(),
+        create_receiver,
+        header_receiver,
+        rt,
+    )
+    .map_err(|e| eyre!("could not initialize event monitor: {}", e))?;
+
+    info!("listening for beacon chain headers");
+
+    thread::spawn(move || event_monitor.run());
+
+    let subscription = tx_cmd.subscribe()?;
+    Ok(subscription)
+}
+
 fn detect_compatibility_mode(
-    config: &ChainConfig,
+    config: &CosmosChainConfig,
     rt: Arc<TokioRuntime>,
 ) -> eyre::Result<CompatMode> {
-    let client = HttpClient::new(config.rpc_addr().clone())?;
+    let client = HttpClient::new(config.rpc_addr.clone())?;
     let status = rt.block_on(client.status())?;
     let compat_mode = CompatMode::from_version(status.node_info.version)?;
     Ok(compat_mode)
@@ -178,12 +300,59 @@ fn detect_compatibility_mode(
 
 #[cfg(test)]
 mod tests {
-    use super::{EventFilter, ListenCmd};
+    use super::{subscribe, EventFilter, ListenCmd};
 
+    use alloc::sync::Arc;
     use std::str::FromStr;
 
     use abscissa_core::clap::Parser;
+    use ibc_relayer::config::{ChainConfig, Config};
     use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use tokio::runtime::Runtime as TokioRuntime;
+
+    fn example_chain(chain_id: &str) -> ChainConfig {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../relayer/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config: Config = ibc_relayer::config::load(path).expect("could not parse config");
+        config
+            .find_chain(&ChainId::from_string(chain_id))
+            .cloned()
+            .unwrap()
+    }
+
+    fn subscribe_error(config: &ChainConfig) -> String {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        subscribe(config, rt).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_subscribe_without_ibc_events() {
+        let ckb = example_chain("ckb-multi-client-4-1");
+        assert!(subscribe_error(&ckb).contains("emits no IBC events"));
+
+        let mut ckb4ibc = example_chain("ckb4ibc-0");
+        if let ChainConfig::Ckb4Ibc(config) = &mut ckb4ibc {
+            config.onchain_light_clients.clear();
+        }
+        assert!(subscribe_error(&ckb4ibc).contains("no onchain light client"));
+    }
+
+    #[test]
+    fn test_subscribe_unreachable_chains() {
+        let mut axon = example_chain("axon-0");
+        if let ChainConfig::Axon(config) = &mut axon {
+            config.websocket_addr = "ws://127.0.0.1:1".parse().unwrap();
+        }
+        assert!(subscribe_error(&axon).contains("could not initialize event monitor"));
+
+        let mut eth = example_chain("ibc-eth-0");
+        if let ChainConfig::Eth(config) = &mut eth {
+            config.rpc_addr_pool = vec!["http://127.0.0.1:1".to_owned()];
+        }
+        assert!(subscribe_error(&eth).contains("could not fetch bootstrap"));
+    }
 
     #[test]
     fn test_listen_required_only() {
@@ -1,16 +1,18 @@
 use ibc_relayer::supervisor::SupervisorOptions;
 use std::error::Error;
 use std::io;
+use std::str::FromStr;
 
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 use crossbeam_channel::Sender;
 
 use ibc_relayer::chain::handle::{CachingChainHandle, ChainHandle};
-use ibc_relayer::config::Config;
+use ibc_relayer::config::{ChainConfig, Config};
 use ibc_relayer::registry::SharedRegistry;
 use ibc_relayer::rest;
 use ibc_relayer::supervisor::{cmd::SupervisorCmd, spawn_supervisor, SupervisorHandle};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::conclude::json;
 use crate::conclude::Output;
@@ -23,11 +25,64 @@ pub struct StartCmd {
         help = "Force a full scan of the chains for clients, connections and channels"
     )]
     full_scan: bool,
+
+    /// Make the event monitor of a chain scan events from the given height
+    /// instead of resuming from its checkpoint, can be repeated.
+    #[clap(long = "rescan-from", value_name = "CHAIN_ID:HEIGHT")]
+    rescan_from: Vec<RescanFrom>,
+}
+
+/// Height from which the event monitor of a chain rescans events
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RescanFrom {
+    chain_id: ChainId,
+    height: u64,
+}
+
+impl FromStr for RescanFrom {
+    type Err = Box<dyn Error + Send + Sync + 'static>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (chain_id, height) = s
+            .rsplit_once(':')
+            .ok_or_else(|| format!("expected CHAIN_ID:HEIGHT, found '{s}'"))?;
+        let height = height
+            .parse()
+            .map_err(|e| format!("invalid height '{height}': {e}"))?;
+        Ok(Self {
+            chain_id: ChainId::from_string(chain_id),
+            height,
+        })
+    }
+}
+
+impl StartCmd {
+    fn apply_rescan_from(&self, config: &mut Config) -> Result<(), String> {
+        for RescanFrom { chain_id, height } in &self.rescan_from {
+            match config.find_chain_mut(chain_id) {
+                Some(ChainConfig::Axon(chain_config)) => {
+                    chain_config.rescan_from_height = Some(*height);
+                }
+                Some(ChainConfig::Ckb4Ibc(chain_config)) => {
+                    chain_config.rescan_from_height = Some(*height);
+                }
+                Some(_) => {
+                    return Err(format!(
+                        "rescanning events is not supported by chain '{chain_id}'"
+                    ))
+                }
+                None => return Err(format!("chain '{chain_id}' not found in configuration")),
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Runnable for StartCmd {
     fn run(&self) {
-        let config = (*app_config()).clone();
+        let mut config = (*app_config()).clone();
+        self.apply_rescan_from(&mut config)
+            .unwrap_or_else(|e| Output::error(e).exit());
 
         let supervisor_handle = make_supervisor::<CachingChainHandle>(config, self.full_scan)
             .unwrap_or_else(|e| {
@@ -192,14 +247,18 @@ fn make_supervisor<Chain: ChainHandle>(
 
 #[cfg(test)]
 mod tests {
-    use super::StartCmd;
+    use super::{RescanFrom, StartCmd};
 
     use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
     #[test]
     fn test_start_required_only() {
         assert_eq!(
-            StartCmd { full_scan: false },
+            StartCmd {
+                full_scan: false,
+                rescan_from: vec![]
+            },
             StartCmd::parse_from(["test"])
         )
     }
@@ -207,8 +266,42 @@ mod tests {
     #[test]
     fn test_start_full_scan() {
         assert_eq!(
-            StartCmd { full_scan: true },
+            StartCmd {
+                full_scan: true,
+                rescan_from: vec![]
+            },
             StartCmd::parse_from(["test", "--full-scan"])
         )
     }
+
+    #[test]
+    fn test_start_rescan_from() {
+        assert_eq!(
+            StartCmd {
+                full_scan: false,
+                rescan_from: vec![
+                    RescanFrom {
+                        chain_id: ChainId::from_string("axon-0"),
+                        height: 100
+                    },
+                    RescanFrom {
+                        chain_id: ChainId::from_string("axon-1"),
+                        height: 0
+                    }
+                ]
+            },
+            StartCmd::parse_from([
+                "test",
+                "--rescan-from",
+                "axon-0:100",
+                "--rescan-from",
+                "axon-1:0"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_start_rescan_from_no_height() {
+        assert!(StartCmd::try_parse_from(["test", "--rescan-from", "axon-0"]).is_err())
+    }
 }
@@ -94,7 +94,7 @@ pub struct TxIcs20MsgTransferCmd {
     #[clap(
         long = "denom",
         value_name = "DENOM",
-        help = "Denomination of the coins to send",
+        help = "Denomination of the coins to send, the ERC-20 token denom on Axon or the sUDT denom on CKB",
         default_value = "samoleans"
     )]
     denom: String,
@@ -116,7 +116,7 @@ pub struct TxIcs20MsgTransferCmd {
     #[clap(
         long = "memo",
         value_name = "MEMO",
-        help = "Optional memo included in the transfer"
+        help = "Optional memo included in the transfer, not supported by transfers from Axon or CKB4IBC chains"
     )]
     memo: Option<String>,
 }
@@ -131,7 +131,7 @@ impl Override<Config> for TxIcs20MsgTransferCmd {
         })?;
 
         if let Some(ref key_name) = self.key_name {
-            src_chain_config.cosmos_mut().key_name = key_name.to_string();
+            src_chain_config.set_key_name(key_name.to_string());
         }
 
         Ok(config)
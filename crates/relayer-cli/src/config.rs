@@ -8,7 +8,7 @@ use alloc::collections::BTreeSet;
 use std::path::PathBuf;
 
 use flex_error::{define_error, TraceError};
-use ibc_relayer::config::{ChainConfig, Config, ModeConfig};
+use ibc_relayer::config::{eth::EthNetwork, ChainConfig, Config, ModeConfig};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tendermint_light_client_verifier::types::TrustThreshold;
 use tracing_subscriber::filter::ParseError;
@@ -73,6 +73,28 @@ define_error! {
                     e.chain_id, e.gas_adjustment, e.gas_multiplier
                 )
             },
+
+        ZeroIndexerPageSize
+            { chain_id: ChainId }
+            |e| {
+                format!("config file specifies `indexer_page_size = 0` for the chain '{0}'",
+                    e.chain_id)
+            },
+
+        EthNetworkMismatch
+            {
+                chain_id: ChainId,
+                eth_network: EthNetwork,
+                eth_chain_id: ChainId,
+                network: EthNetwork,
+            }
+            |e| {
+                format!(
+                    "config file specifies `eth_network = {1}` for the chain '{0}', \
+                    but its Ethereum chain '{2}' is on {3} network",
+                    e.chain_id, e.eth_network, e.eth_chain_id, e.network
+                )
+            },
     }
 }
 
@@ -103,6 +125,16 @@ pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
 
         // Validate gas-related settings
         validate_gas_settings(c.id(), c)?;
+
+        if let ChainConfig::Ckb4Ibc(ckb4ibc) = c {
+            if ckb4ibc.indexer_page_size == 0 {
+                return Err(Diagnostic::Error(Error::zero_indexer_page_size(
+                    c.id().clone(),
+                )));
+            }
+        }
+
+        validate_eth_network(config, c)?;
     }
 
     // Check for invalid mode config
@@ -163,6 +195,28 @@ fn validate_trust_threshold(
     Ok(())
 }
 
+/// Check that a CKB chain relays the headers of the network of its Ethereum
+/// chain.
+fn validate_eth_network(config: &Config, chain: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    let ChainConfig::Ckb(ckb) = chain else {
+        return Ok(());
+    };
+    let Some(eth_chain_id) = &ckb.eth_chain_id else {
+        return Ok(());
+    };
+    if let Some(ChainConfig::Eth(eth)) = config.find_chain(eth_chain_id) {
+        if eth.network != ckb.eth_network {
+            return Err(Diagnostic::Error(Error::eth_network_mismatch(
+                ckb.id.clone(),
+                ckb.eth_network,
+                eth_chain_id.clone(),
+                eth.network,
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn validate_gas_settings(id: &ChainId, config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
     // Check that the gas_adjustment option is not set
     if let ChainConfig::Cosmos(_) = config {
@@ -179,3 +233,48 @@ fn validate_gas_settings(id: &ChainId, config: &ChainConfig) -> Result<(), Diagn
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_config() -> Config {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../relayer/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        ibc_relayer::config::load(path).expect("could not parse config")
+    }
+
+    #[test]
+    fn test_eth_network_mismatch() {
+        let mut config = example_config();
+        let ckb_id = ChainId::from_string("ckb-multi-client-4-1");
+        let ckb = config.find_chain(&ckb_id).unwrap().clone();
+        validate_eth_network(&config, &ckb).unwrap();
+
+        if let Some(ChainConfig::Ckb(ckb)) = config.find_chain_mut(&ckb_id) {
+            ckb.eth_network = EthNetwork::Sepolia;
+        }
+        let ckb = config.find_chain(&ckb_id).unwrap();
+        assert!(matches!(
+            validate_eth_network(&config, ckb),
+            Err(Diagnostic::Error(_))
+        ));
+    }
+
+    #[test]
+    fn test_zero_indexer_page_size() {
+        let mut config = example_config();
+        validate_config(&config).unwrap();
+
+        let ckb4ibc_id = ChainId::from_string("ckb4ibc-0");
+        if let Some(ChainConfig::Ckb4Ibc(ckb4ibc)) = config.find_chain_mut(&ckb4ibc_id) {
+            ckb4ibc.indexer_page_size = 0;
+        }
+        assert!(matches!(
+            validate_config(&config),
+            Err(Diagnostic::Error(_))
+        ));
+    }
+}
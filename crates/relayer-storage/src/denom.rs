@@ -0,0 +1,27 @@
+//! Types of the ICS-20 denom trace registry.
+
+use serde::{Deserialize, Serialize};
+
+/// The origin of an ICS-20 asset on CKB, keyed by the denom used in packets
+/// on the CKB side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DenomTraceRecord {
+    /// The `port/channel` hops the asset travelled through, empty for assets
+    /// issued on CKB.
+    pub path: String,
+    /// The denom on the chain where the asset was issued.
+    pub base_denom: String,
+    /// The serialized type script of the sUDT cells holding the asset.
+    pub sudt_type_script: Vec<u8>,
+}
+
+impl DenomTraceRecord {
+    /// The full denom in the `{path}/{base_denom}` format.
+    pub fn full_denom(&self) -> String {
+        if self.path.is_empty() {
+            self.base_denom.clone()
+        } else {
+            format!("{}/{}", self.path, self.base_denom)
+        }
+    }
+}
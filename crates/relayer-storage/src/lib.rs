@@ -1,3 +1,4 @@
+pub mod denom;
 pub mod error;
 pub mod prelude;
 pub mod schemas;
@@ -5,4 +6,4 @@ pub mod schemas;
 pub type Slot = u64;
 
 mod storage;
-pub use storage::Storage;
+pub use storage::{ReadOnlyStorage, Storage};
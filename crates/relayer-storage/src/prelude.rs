@@ -1,3 +1,6 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
 use eth2_types::EthSpec;
 use eth_light_client_in_ckb_verification::{
     mmr::{self, ClientRootMMR},
@@ -5,6 +8,7 @@ use eth_light_client_in_ckb_verification::{
 };
 
 use crate::{
+    denom::DenomTraceRecord,
     error::{Error, Result},
     Slot, Storage,
 };
@@ -12,6 +16,8 @@ use crate::{
 pub trait StorageReader<S: EthSpec>: Send + Sync + Sized {
     fn get_base_beacon_header_slot(&self) -> Result<Option<Slot>>;
     fn get_tip_beacon_header_slot(&self) -> Result<Option<Slot>>;
+    fn get_multi_client_type_id(&self) -> Result<Option<packed::Hash>>;
+    fn get_eth_network(&self) -> Result<Option<String>>;
 
     fn get_beacon_header_digest(&self, position: u64) -> Result<Option<packed::HeaderDigest>>;
 }
@@ -19,11 +25,28 @@ pub trait StorageReader<S: EthSpec>: Send + Sync + Sized {
 pub trait StorageWriter<S: EthSpec>: Send + Sync + Sized {
     fn put_base_beacon_header_slot(&self, slot: Slot) -> Result<()>;
     fn put_tip_beacon_header_slot(&self, slot: Slot) -> Result<()>;
+    fn put_multi_client_type_id(&self, type_id: &packed::Hash) -> Result<()>;
+    fn put_eth_network(&self, network: &str) -> Result<()>;
 
     fn delete_base_beacon_header_slot(&self) -> Result<()>;
     fn delete_tip_beacon_header_slot(&self) -> Result<()>;
 
     fn put_beacon_header_digest(&self, position: u64, digest: &packed::HeaderDigest) -> Result<()>;
+    /// Writes the MMR nodes at the given positions along with the new tip slot,
+    /// all at once.
+    fn put_beacon_header_digests(
+        &self,
+        digests: &[(u64, packed::HeaderDigest)],
+        tip_slot: Slot,
+    ) -> Result<()>;
+}
+
+/// Registry of the origins of ICS-20 assets, keyed by their denoms.
+pub trait DenomTraceStore: Send + Sync {
+    fn get_denom_trace(&self, denom: &str) -> Result<Option<DenomTraceRecord>>;
+    fn put_denom_trace(&self, denom: &str, record: &DenomTraceRecord) -> Result<()>;
+
+    fn get_all_denom_traces(&self) -> Result<Vec<(String, DenomTraceRecord)>>;
 }
 
 pub trait StorageAsMMRStore<S: EthSpec>:
@@ -57,6 +80,29 @@ pub trait StorageAsMMRStore<S: EthSpec>:
         Ok(())
     }
 
+    /// Appends the digests of the headers following the tip slot `last_slot`
+    /// to the MMR. The new MMR nodes are buffered in memory and then written
+    /// along with the new tip slot in one write batch.
+    fn append_beacon_header_digests(
+        &self,
+        last_slot: Slot,
+        headers: &[(Slot, packed::HeaderDigest)],
+    ) -> Result<()> {
+        let Some(base) = self.get_base_beacon_header_slot()? else {
+            return Err(Error::data("no headers"));
+        };
+        let store = MMRBatchStore::new(self.clone());
+        let mmr_size = mmr::lib::leaf_index_to_mmr_size(last_slot - base);
+        let mut mmr = ClientRootMMR::new(mmr_size, store.clone());
+        let mut tip_slot = last_slot;
+        for (slot, digest) in headers {
+            mmr.push(digest.clone())?;
+            tip_slot = *slot;
+        }
+        mmr.commit()?;
+        self.put_beacon_header_digests(&store.take_pending(), tip_slot)
+    }
+
     /// Returns the chain root MMR for a provided slot.
     fn chain_root_mmr(&self, curr: Slot) -> Result<ClientRootMMR<Self>> {
         if let Some(base) = self.get_base_beacon_header_slot()? {
@@ -71,3 +117,49 @@ pub trait StorageAsMMRStore<S: EthSpec>:
 }
 
 impl<S: EthSpec> StorageAsMMRStore<S> for Storage<S> {}
+
+/// MMR store buffering the appended nodes in memory on top of a storage,
+/// the clones of a store share the same buffer.
+#[derive(Clone)]
+pub struct MMRBatchStore<T> {
+    store: T,
+    pending: Arc<RwLock<BTreeMap<u64, packed::HeaderDigest>>>,
+}
+
+impl<T> MMRBatchStore<T> {
+    pub fn new(store: T) -> Self {
+        Self {
+            store,
+            pending: Default::default(),
+        }
+    }
+
+    /// Takes the buffered nodes, ordered by their positions.
+    pub fn take_pending(&self) -> Vec<(u64, packed::HeaderDigest)> {
+        let mut pending = self.pending.write().unwrap_or_else(|e| e.into_inner());
+        std::mem::take(&mut *pending).into_iter().collect()
+    }
+}
+
+impl<T> mmr::lib::MMRStoreReadOps<packed::HeaderDigest> for MMRBatchStore<T>
+where
+    T: mmr::lib::MMRStoreReadOps<packed::HeaderDigest>,
+{
+    fn get_elem(&self, pos: u64) -> mmr::lib::Result<Option<packed::HeaderDigest>> {
+        let pending = self.pending.read().unwrap_or_else(|e| e.into_inner());
+        match pending.get(&pos) {
+            Some(elem) => Ok(Some(elem.clone())),
+            None => self.store.get_elem(pos),
+        }
+    }
+}
+
+impl<T> mmr::lib::MMRStoreWriteOps<packed::HeaderDigest> for MMRBatchStore<T> {
+    fn append(&mut self, pos: u64, elems: Vec<packed::HeaderDigest>) -> mmr::lib::Result<()> {
+        let mut pending = self.pending.write().unwrap_or_else(|e| e.into_inner());
+        for (offset, elem) in elems.into_iter().enumerate() {
+            pending.insert(pos + offset as u64, elem);
+        }
+        Ok(())
+    }
+}
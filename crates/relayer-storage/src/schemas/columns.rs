@@ -4,7 +4,10 @@
 pub type Column = &'static str;
 
 /// Total column number
-pub const COUNT: usize = 1;
+pub const COUNT: usize = 2;
 
 /// Column to store MMR for beacon headers
 pub const COLUMN_BEACON_HEADER_MMR: Column = "beacon-header-mmr";
+
+/// Column to store ICS-20 denom traces of the assets on CKB
+pub const COLUMN_DENOM_TRACE: Column = "denom-trace";
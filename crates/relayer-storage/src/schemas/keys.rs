@@ -7,3 +7,7 @@ pub const MIGRATION_VERSION_KEY: &[u8] = b"db-version";
 pub const BASE_BEACON_HEADER_SLOT: &[u8] = b"base-beacon-header-slot";
 /// The current tip beacon header.
 pub const TIP_BEACON_HEADER_SLOT: &[u8] = b"tip-beacon-header-slot";
+/// The name of the Ethereum network whose headers are stored.
+pub const ETH_NETWORK: &[u8] = b"eth-network";
+/// The type id of the multi-client cells on CKB.
+pub const MULTI_CLIENT_TYPE_ID: &[u8] = b"multi-client-type-id";
@@ -0,0 +1,37 @@
+use crate::{
+    denom::DenomTraceRecord,
+    error::{Error, Result},
+    prelude::DenomTraceStore,
+    schemas::columns,
+    Storage,
+};
+
+pub(super) fn decode_record(raw: &[u8]) -> Result<DenomTraceRecord> {
+    serde_json::from_slice(raw).map_err(Error::data)
+}
+
+impl<S> DenomTraceStore for Storage<S>
+where
+    S: Send + Sync,
+{
+    fn get_denom_trace(&self, denom: &str) -> Result<Option<DenomTraceRecord>> {
+        self.get_cf(columns::COLUMN_DENOM_TRACE, denom)?
+            .map(|raw| decode_record(&raw))
+            .transpose()
+    }
+
+    fn put_denom_trace(&self, denom: &str, record: &DenomTraceRecord) -> Result<()> {
+        let value = serde_json::to_vec(record).map_err(Error::data)?;
+        self.put_cf(columns::COLUMN_DENOM_TRACE, denom, value)
+    }
+
+    fn get_all_denom_traces(&self) -> Result<Vec<(String, DenomTraceRecord)>> {
+        self.iter_cf(columns::COLUMN_DENOM_TRACE)?
+            .into_iter()
+            .map(|(key, value)| {
+                let denom = String::from_utf8(key.into_vec()).map_err(Error::data)?;
+                Ok((denom, decode_record(&value)?))
+            })
+            .collect()
+    }
+}
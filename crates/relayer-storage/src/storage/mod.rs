@@ -2,10 +2,10 @@ use std::{marker::PhantomData, path::Path, sync::Arc};
 
 use rocksdb::{
     prelude::{
-        Delete as _, GetColumnFamilys as _, GetPinned as _, GetPinnedCF as _, OpenCF as _,
-        Put as _, PutCF as _,
+        Delete as _, GetColumnFamilys, GetPinned as _, GetPinnedCF as _, IterateCF as _,
+        OpenCF as _, Put as _, PutCF as _, WriteOps as _,
     },
-    ColumnFamily, ColumnFamilyDescriptor, DBPinnableSlice, Options, DB,
+    ColumnFamily, ColumnFamilyDescriptor, DBPinnableSlice, IteratorMode, Options, WriteBatch, DB,
 };
 
 use crate::{
@@ -14,11 +14,19 @@ use crate::{
 };
 
 mod cache;
+mod denom;
 mod mmr;
+mod read_only;
 mod reader;
 mod writer;
 
 use cache::Cache;
+pub use read_only::ReadOnlyStorage;
+
+const CF_NAMES: [Column; columns::COUNT] = [
+    columns::COLUMN_BEACON_HEADER_MMR,
+    columns::COLUMN_DENOM_TRACE,
+];
 
 #[derive(Clone)]
 pub struct Storage<S> {
@@ -29,14 +37,9 @@ pub struct Storage<S> {
 
 impl<S> Storage<S> {
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let cf_names = {
-            let mut cf_names = Vec::with_capacity(columns::COUNT);
-            cf_names.push(columns::COLUMN_BEACON_HEADER_MMR.to_string());
-            cf_names
-        };
-        let cf_descriptors: Vec<_> = cf_names
+        let cf_descriptors: Vec<_> = CF_NAMES
             .iter()
-            .map(|c| ColumnFamilyDescriptor::new(c, Options::default()))
+            .map(|c| ColumnFamilyDescriptor::new(*c, Options::default()))
             .collect();
 
         let opts = {
@@ -91,9 +94,19 @@ impl<S> Storage<S> {
             .put_cf(cf, key.as_ref(), value.as_ref())
             .map_err(Into::into)
     }
+
+    pub(crate) fn write(&self, batch: &WriteBatch) -> Result<()> {
+        self.db.write(batch).map_err(Into::into)
+    }
+
+    pub(crate) fn iter_cf(&self, col: Column) -> Result<Vec<(Box<[u8]>, Box<[u8]>)>> {
+        let cf = cf_handle(&self.db, col)?;
+        let iter = self.db.iterator_cf(cf, IteratorMode::Start)?;
+        Ok(iter.collect())
+    }
 }
 
-pub(crate) fn cf_handle(db: &DB, col: Column) -> Result<&ColumnFamily> {
+pub(crate) fn cf_handle<D: GetColumnFamilys>(db: &D, col: Column) -> Result<&ColumnFamily> {
     db.cf_handle(col)
         .ok_or_else(|| Error::storage(format!("column {} not found", col)))
 }
@@ -0,0 +1,60 @@
+use std::{path::Path, sync::Arc};
+
+use rocksdb::{
+    prelude::{GetPinnedCF as _, IterateCF as _, OpenCF as _},
+    ColumnFamilyDescriptor, IteratorMode, Options, ReadOnlyDB,
+};
+
+use super::{cf_handle, denom::decode_record, CF_NAMES};
+use crate::{
+    denom::DenomTraceRecord,
+    error::{Error, Result},
+    prelude::DenomTraceStore,
+    schemas::columns,
+};
+
+/// A read-only view of a [`Storage`](super::Storage), which can be opened
+/// while another process holds the storage, e.g. by the CLI while the relayer
+/// is running. The view is a snapshot of the storage at the time it's opened.
+#[derive(Clone)]
+pub struct ReadOnlyStorage {
+    db: Arc<ReadOnlyDB>,
+}
+
+impl ReadOnlyStorage {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let cf_descriptors: Vec<_> = CF_NAMES
+            .iter()
+            .map(|c| ColumnFamilyDescriptor::new(*c, Options::default()))
+            .collect();
+        let db = ReadOnlyDB::open_cf_descriptors(&Options::default(), path, cf_descriptors)?;
+        Ok(Self { db: Arc::new(db) })
+    }
+}
+
+impl DenomTraceStore for ReadOnlyStorage {
+    fn get_denom_trace(&self, denom: &str) -> Result<Option<DenomTraceRecord>> {
+        let cf = cf_handle(&*self.db, columns::COLUMN_DENOM_TRACE)?;
+        self.db
+            .get_pinned_cf(cf, denom)?
+            .map(|raw| decode_record(&raw))
+            .transpose()
+    }
+
+    fn put_denom_trace(&self, denom: &str, _record: &DenomTraceRecord) -> Result<()> {
+        Err(Error::storage(format!(
+            "cannot record the denom trace of {denom} in a read-only storage"
+        )))
+    }
+
+    fn get_all_denom_traces(&self) -> Result<Vec<(String, DenomTraceRecord)>> {
+        let cf = cf_handle(&*self.db, columns::COLUMN_DENOM_TRACE)?;
+        self.db
+            .iterator_cf(cf, IteratorMode::Start)?
+            .map(|(key, value)| {
+                let denom = String::from_utf8(key.into_vec()).map_err(Error::data)?;
+                Ok((denom, decode_record(&value)?))
+            })
+            .collect()
+    }
+}
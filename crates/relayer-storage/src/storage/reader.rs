@@ -43,6 +43,19 @@ where
             .map_err(Into::into)
     }
 
+    fn get_multi_client_type_id(&self) -> Result<Option<packed::Hash>> {
+        self.get(keys::MULTI_CLIENT_TYPE_ID)?
+            .map(|raw| packed::HashReader::from_slice(&raw).map(|reader| reader.to_entity()))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    fn get_eth_network(&self) -> Result<Option<String>> {
+        self.get(keys::ETH_NETWORK)?
+            .map(|raw| String::from_utf8(raw.to_vec()).map_err(Error::data))
+            .transpose()
+    }
+
     fn get_beacon_header_digest(&self, position: u64) -> Result<Option<packed::HeaderDigest>> {
         let key: packed::Uint64 = position.pack();
         self.get_cf(columns::COLUMN_BEACON_HEADER_MMR, key.as_slice())?
@@ -1,5 +1,6 @@
 use eth2_types::EthSpec;
 use eth_light_client_in_ckb_verification::types::{packed, prelude::*};
+use rocksdb::WriteBatch;
 
 use crate::{
     error::{Error, Result},
@@ -8,6 +9,8 @@ use crate::{
     Slot, Storage,
 };
 
+use super::cf_handle;
+
 impl<S> StorageWriter<S> for Storage<S>
 where
     S: EthSpec,
@@ -29,6 +32,14 @@ where
         self.put(keys::TIP_BEACON_HEADER_SLOT, value.as_slice())
     }
 
+    fn put_multi_client_type_id(&self, type_id: &packed::Hash) -> Result<()> {
+        self.put(keys::MULTI_CLIENT_TYPE_ID, type_id.as_slice())
+    }
+
+    fn put_eth_network(&self, network: &str) -> Result<()> {
+        self.put(keys::ETH_NETWORK, network.as_bytes())
+    }
+
     fn delete_base_beacon_header_slot(&self) -> Result<()> {
         let mut writer = self
             .cache
@@ -52,4 +63,20 @@ where
             digest.as_slice(),
         )
     }
+
+    fn put_beacon_header_digests(
+        &self,
+        digests: &[(u64, packed::HeaderDigest)],
+        tip_slot: Slot,
+    ) -> Result<()> {
+        let cf = cf_handle(&self.db, columns::COLUMN_BEACON_HEADER_MMR)?;
+        let mut batch = WriteBatch::default();
+        for (position, digest) in digests {
+            let key: packed::Uint64 = position.pack();
+            batch.put_cf(cf, key.as_slice(), digest.as_slice())?;
+        }
+        let value = tip_slot.pack();
+        batch.put(keys::TIP_BEACON_HEADER_SLOT, value.as_slice())?;
+        self.write(&batch)
+    }
 }
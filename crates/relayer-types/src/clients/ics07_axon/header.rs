@@ -2,15 +2,35 @@ use crate::core::ics02_client::{self, error::Error as Ics02Error};
 use crate::prelude::*;
 use crate::timestamp::Timestamp;
 use crate::Height;
+use ethereum_types::H256;
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::protobuf::{Error as ProtoError, Protobuf};
 use serde::{Deserialize, Serialize};
 
 pub const AXON_HEADER_TYPE_URL: &str = "/axon.v1.header";
 
-// FIXME: useless header which cannot be ignored by Hermes runtime
-#[derive(Clone, PartialEq, Eq, Deserialize, Serialize, Debug)]
-pub struct AxonHeader {}
+/// An Axon block header together with the commit proof signed by the
+/// validators of its epoch.
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize, Debug, Default)]
+pub struct AxonHeader {
+    pub height: Height,
+    pub timestamp: Timestamp,
+    pub block_hash: H256,
+    pub state_root: H256,
+    pub previous_state_root: H256,
+    /// First block number of the epoch whose validators signed this header
+    pub epoch_start: u64,
+    pub proof_round: u64,
+    pub proof_signature: Vec<u8>,
+    pub proof_bitmap: Vec<u8>,
+}
+
+impl AxonHeader {
+    /// Whether two headers of the same height commit to different blocks.
+    pub fn conflicts_with(&self, other: &AxonHeader) -> bool {
+        self.height == other.height && self.block_hash != other.block_hash
+    }
+}
 
 impl ics02_client::header::Header for AxonHeader {
     fn client_type(&self) -> ics02_client::client_type::ClientType {
@@ -18,11 +38,11 @@ impl ics02_client::header::Header for AxonHeader {
     }
 
     fn height(&self) -> Height {
-        Height::default()
+        self.height
     }
 
     fn timestamp(&self) -> Timestamp {
-        Timestamp::none()
+        self.timestamp
     }
 }
 
@@ -1,2 +1,9 @@
+use super::header::AxonHeader;
+use crate::prelude::*;
+
 #[derive(Debug, Clone, Default)]
-pub struct AxonLightBlock {}
+pub struct AxonLightBlock {
+    pub header: AxonHeader,
+    /// Addresses of the validators which were active at the block's epoch
+    pub validators: Vec<[u8; 20]>,
+}
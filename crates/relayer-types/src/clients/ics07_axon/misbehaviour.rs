@@ -1,13 +1,92 @@
 use crate::prelude::*;
 
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::{Error as ProtoError, Protobuf};
 use serde::{Deserialize, Serialize};
 
-use crate::clients::ics07_tendermint::header::Header;
+use crate::clients::ics07_axon::header::AxonHeader;
+use crate::core::ics02_client::error::Error as Ics02Error;
 use crate::core::ics24_host::identifier::ClientId;
+use crate::Height;
 
+pub const AXON_MISBEHAVIOUR_TYPE_URL: &str = "/axon.v1.misbehaviour";
+
+/// Two validly signed Axon headers which commit to different blocks at the
+/// same height.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Misbehaviour {
     pub client_id: ClientId,
-    pub header1: Header,
-    pub header2: Header,
+    pub header1: AxonHeader,
+    pub header2: AxonHeader,
+}
+
+impl crate::core::ics02_client::misbehaviour::Misbehaviour for Misbehaviour {
+    fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    fn height(&self) -> Height {
+        self.header1.height
+    }
+}
+
+impl Protobuf<Any> for Misbehaviour {}
+
+impl TryFrom<Any> for Misbehaviour {
+    type Error = Ics02Error;
+
+    fn try_from(any: Any) -> Result<Self, Self::Error> {
+        if any.type_url != AXON_MISBEHAVIOUR_TYPE_URL {
+            return Err(Ics02Error::unknown_misbehaviour_type(any.type_url));
+        }
+        serde_json::from_slice(&any.value).map_err(|e| {
+            Ics02Error::decode_raw_misbehaviour(ProtoError::try_from_protobuf(e.to_string()))
+        })
+    }
+}
+
+impl From<Misbehaviour> for Any {
+    fn from(misbehaviour: Misbehaviour) -> Self {
+        let json = serde_json::to_string(&misbehaviour).expect("jsonify axon misbehaviour");
+        Any {
+            type_url: AXON_MISBEHAVIOUR_TYPE_URL.to_owned(),
+            value: json.into_bytes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethereum_types::H256;
+
+    use super::*;
+
+    #[test]
+    fn test_axon_misbehaviour_any() {
+        let header1 = AxonHeader {
+            height: Height::new(0, 10).unwrap(),
+            block_hash: H256::repeat_byte(1),
+            proof_bitmap: vec![0xe0],
+            ..Default::default()
+        };
+        let header2 = AxonHeader {
+            block_hash: H256::repeat_byte(2),
+            ..header1.clone()
+        };
+        assert!(header1.conflicts_with(&header2));
+        assert!(!header1.conflicts_with(&header1));
+        let later = AxonHeader {
+            height: Height::new(0, 11).unwrap(),
+            ..header2.clone()
+        };
+        assert!(!header1.conflicts_with(&later));
+
+        let misbehaviour = Misbehaviour {
+            client_id: ClientId::default(),
+            header1,
+            header2,
+        };
+        let any: Any = misbehaviour.clone().into();
+        assert_eq!(Misbehaviour::try_from(any).unwrap(), misbehaviour);
+    }
 }
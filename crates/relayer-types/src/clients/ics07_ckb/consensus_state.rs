@@ -10,8 +10,28 @@ use crate::core::ics02_client::error::Error as Ics02Error;
 
 pub const CKB_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.lightclients.ckb.v1.ConsensusState";
 
+/// Consensus state of a light client living in a cell on CKB, fields are
+/// defaulted to stay compatible with the empty state stored by older relayers
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct CkbConsensusState {}
+pub struct CkbConsensusState {
+    #[serde(default = "empty_root")]
+    pub root: CommitmentRoot,
+    #[serde(default)]
+    pub timestamp: Timestamp,
+}
+
+impl Default for CkbConsensusState {
+    fn default() -> Self {
+        Self {
+            root: empty_root(),
+            timestamp: Timestamp::none(),
+        }
+    }
+}
+
+fn empty_root() -> CommitmentRoot {
+    CommitmentRoot::from_bytes(&[])
+}
 
 impl crate::core::ics02_client::consensus_state::ConsensusState for CkbConsensusState {
     fn client_type(&self) -> ClientType {
@@ -19,11 +39,11 @@ impl crate::core::ics02_client::consensus_state::ConsensusState for CkbConsensus
     }
 
     fn root(&self) -> &CommitmentRoot {
-        todo!()
+        &self.root
     }
 
     fn timestamp(&self) -> Timestamp {
-        Timestamp::none()
+        self.timestamp
     }
 }
 
@@ -32,13 +52,38 @@ impl Protobuf<Any> for CkbConsensusState {}
 impl TryFrom<Any> for CkbConsensusState {
     type Error = Ics02Error;
 
-    fn try_from(_value: Any) -> Result<Self, Self::Error> {
-        todo!()
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            CKB_CONSENSUS_STATE_TYPE_URL => serde_json::from_slice(&raw.value)
+                .map_err(|_| Ics02Error::unknown_consensus_state_type(raw.type_url)),
+            _ => Err(Ics02Error::unknown_consensus_state_type(raw.type_url)),
+        }
     }
 }
 
 impl From<CkbConsensusState> for Any {
-    fn from(_value: CkbConsensusState) -> Self {
-        todo!()
+    fn from(value: CkbConsensusState) -> Self {
+        Any {
+            type_url: CKB_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: serde_json::to_vec(&value).expect("encoding to `Any` from `CkbConsensusState`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ckb_consensus_state_serde() {
+        let state: CkbConsensusState = serde_json::from_str("{}").unwrap();
+        assert_eq!(state, CkbConsensusState::default());
+
+        let state = CkbConsensusState {
+            root: CommitmentRoot::from_bytes(&[1, 2, 3]),
+            timestamp: Timestamp::from_nanoseconds(1_000).unwrap(),
+        };
+        let any: Any = state.clone().into();
+        assert_eq!(CkbConsensusState::try_from(any).unwrap(), state);
     }
 }
@@ -80,6 +80,76 @@ impl From<EthClientState> for Any {
     }
 }
 
+pub const ETH_MULTI_CLIENT_STATE_TYPE_URL: &str = "/eth.client.v1.multi_state";
+
+/// State of the Ethereum multi-client deployed on CKB, which holds the
+/// finalized headers of slots in `[minimal_slot, maximal_slot]`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EthMultiClientState {
+    pub chain_id: ChainId,
+    pub minimal_slot: u64,
+    pub maximal_slot: u64,
+}
+
+impl Ics02ClientState for EthMultiClientState {
+    fn chain_id(&self) -> ChainId {
+        self.chain_id.clone()
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::Eth
+    }
+
+    fn latest_height(&self) -> crate::Height {
+        // an empty client holds no slot yet, so the first one stands in for it
+        let slot = self.maximal_slot.max(1);
+        crate::Height::new(slot / 32, slot).expect("non-zero maximal slot")
+    }
+
+    fn frozen_height(&self) -> Option<crate::Height> {
+        None
+    }
+
+    fn expired(&self, _elapsed: core::time::Duration) -> bool {
+        false
+    }
+
+    /// The finalized slots don't change across an upgrade of the chain, so
+    /// only the chain id is replaced
+    fn upgrade(
+        &mut self,
+        _upgrade_height: crate::Height,
+        _upgrade_options: &dyn UpgradeOptions,
+        chain_id: ChainId,
+    ) {
+        self.chain_id = chain_id;
+    }
+}
+
+impl Protobuf<Any> for EthMultiClientState {}
+
+impl TryFrom<Any> for EthMultiClientState {
+    type Error = Ics02Error;
+
+    fn try_from(any: Any) -> Result<Self, Self::Error> {
+        if any.type_url != ETH_MULTI_CLIENT_STATE_TYPE_URL {
+            return Err(Ics02Error::unknown_client_state_type(any.type_url));
+        }
+        serde_json::from_slice(&any.value)
+            .map_err(|e| Ics02Error::unknown_client_state_type(e.to_string()))
+    }
+}
+
+impl From<EthMultiClientState> for Any {
+    fn from(client: EthMultiClientState) -> Self {
+        let json = serde_json::to_string(&client).expect("jsonify multi-client state");
+        Any {
+            type_url: ETH_MULTI_CLIENT_STATE_TYPE_URL.to_owned(),
+            value: json.into_bytes(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,4 +163,49 @@ mod tests {
         let any: Any = client_state.into();
         let _: EthClientState = any.try_into().expect("serde error");
     }
+
+    #[test]
+    fn test_eth_multi_client_state_serde() {
+        let client_state = EthMultiClientState {
+            chain_id: ChainId::new("eth".to_owned(), 0),
+            minimal_slot: 6_000_000,
+            maximal_slot: 6_008_191,
+        };
+        let any: Any = client_state.clone().into();
+        let decoded: EthMultiClientState = any.try_into().expect("serde error");
+        assert_eq!(decoded, client_state);
+        assert_eq!(decoded.latest_height().revision_height(), 6_008_191);
+    }
+
+    #[test]
+    fn test_eth_multi_client_state_without_slots() {
+        let client_state = EthMultiClientState {
+            chain_id: ChainId::new("eth".to_owned(), 0),
+            minimal_slot: 0,
+            maximal_slot: 0,
+        };
+        assert_eq!(
+            client_state.latest_height(),
+            crate::Height::new(0, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_eth_multi_client_state_upgrade() {
+        let mut client_state = EthMultiClientState {
+            chain_id: ChainId::new("eth".to_owned(), 0),
+            minimal_slot: 6_000_000,
+            maximal_slot: 6_008_191,
+        };
+        let upgrade_options = crate::clients::ics07_tendermint::client_state::UpgradeOptions {
+            unbonding_period: core::time::Duration::from_secs(3600),
+        };
+        client_state.upgrade(
+            crate::Height::new(0, 6_008_192).unwrap(),
+            &upgrade_options,
+            ChainId::new("eth".to_owned(), 1),
+        );
+        assert_eq!(client_state.chain_id, ChainId::new("eth".to_owned(), 1));
+        assert_eq!(client_state.maximal_slot, 6_008_191);
+    }
 }
@@ -7,7 +7,7 @@ use ibc_proto::ibc::core::commitment::v1::MerkleRoot;
 use ics23::commitment_proof::Proof;
 use ics23::{
     calculate_existence_root, verify_membership, verify_non_membership, CommitmentProof,
-    NonExistenceProof,
+    ExistenceProof, NonExistenceProof,
 };
 
 use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
@@ -67,6 +67,48 @@ impl From<MerkleProof> for RawMerkleProof {
     }
 }
 
+/// Proof of a storage slot of a store which is not an ICS-23 tree, like the
+/// storage of an EVM chain, in the encoding of the chain which stores it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageProof {
+    /// Key of the proven storage slot
+    pub key: Vec<u8>,
+    pub proof: Vec<u8>,
+}
+
+/// The storage proof is carried as a single existence proof without a leaf
+/// operation, which never verifies as an ICS-23 proof
+impl From<StorageProof> for MerkleProof {
+    fn from(proof: StorageProof) -> Self {
+        let existence_proof = ExistenceProof {
+            key: proof.key,
+            value: proof.proof,
+            leaf: None,
+            path: vec![],
+        };
+        Self {
+            proofs: vec![CommitmentProof {
+                proof: Some(Proof::Exist(existence_proof)),
+            }],
+        }
+    }
+}
+
+impl StorageProof {
+    /// Extracts the storage proof carried by `proof`, if it carries one
+    pub fn from_merkle_proof(proof: &MerkleProof) -> Option<Self> {
+        match proof.proofs.as_slice() {
+            [CommitmentProof {
+                proof: Some(Proof::Exist(existence_proof)),
+            }] if existence_proof.leaf.is_none() && existence_proof.path.is_empty() => Some(Self {
+                key: existence_proof.key.clone(),
+                proof: existence_proof.value.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
 impl MerkleProof {
     pub fn verify_membership(
         &self,
@@ -262,3 +304,31 @@ pub fn convert_tm_to_ics_merkle_proof(tm_proof: &TendermintProof) -> Result<Merk
 
     Ok(MerkleProof::from(RawMerkleProof { proofs }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ics23_commitment::error::ErrorDetail;
+
+    #[test]
+    fn test_storage_proof_round_trip() {
+        let storage_proof = StorageProof {
+            key: vec![1; 32],
+            proof: vec![2; 64],
+        };
+        let proof = MerkleProof::from(storage_proof.clone());
+        assert_eq!(StorageProof::from_merkle_proof(&proof), Some(storage_proof));
+
+        // it never passes as an ICS-23 proof
+        let root = MerkleRoot { hash: vec![3; 32] };
+        let keys = MerklePath {
+            key_path: vec!["ibc".to_owned()],
+        };
+        let specs = ProofSpecs::from(vec![ics23::iavl_spec()]);
+        let result = proof.verify_membership(&specs, root, keys, vec![2; 64], 0);
+        assert!(matches!(
+            result.unwrap_err().detail(),
+            ErrorDetail::InvalidMerkleProof(_)
+        ));
+    }
+}
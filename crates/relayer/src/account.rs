@@ -1,5 +1,7 @@
 //! Data structures related to the accounts used by the relayer.
 
+use core::fmt;
+
 use serde::{Deserialize, Serialize};
 
 /// The balance for a specific denom
@@ -9,4 +11,71 @@ pub struct Balance {
     pub amount: String,
     /// The denomination for that coin
     pub denom: String,
+    /// Symbol of the coin, for chains providing token metadata
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Number of decimals of the coin, `amount` being in its smallest unit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+}
+
+impl Balance {
+    pub fn new(amount: String, denom: String) -> Self {
+        Self {
+            amount,
+            denom,
+            symbol: None,
+            decimals: None,
+        }
+    }
+}
+
+impl fmt::Display for Balance {
+    /// Formats the balance as `<amount> <denom>`, followed by the amount in
+    /// whole coins if the token metadata is known
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.denom)?;
+        if let (Some(symbol), Some(decimals)) = (&self.symbol, self.decimals) {
+            write!(f, " ({} {symbol})", to_whole_units(&self.amount, decimals))?;
+        }
+        Ok(())
+    }
+}
+
+/// Converts a decimal `amount` of the smallest unit of a coin with `decimals`
+/// decimals into whole coins, e.g. `1500` with 3 decimals is `1.5`
+fn to_whole_units(amount: &str, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let amount = amount.trim_start_matches('0');
+    let padded = format!("{amount:0>width$}", width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_owned()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_whole_units() {
+        assert_eq!(to_whole_units("1500", 3), "1.5");
+        assert_eq!(to_whole_units("1500", 0), "1500");
+        assert_eq!(to_whole_units("0", 18), "0");
+        assert_eq!(to_whole_units("25", 4), "0.0025");
+        assert_eq!(to_whole_units("1000000000000000000", 18), "1");
+    }
+
+    #[test]
+    fn test_display_balance() {
+        let mut balance = Balance::new("1230000".to_owned(), "usdc".to_owned());
+        assert_eq!(balance.to_string(), "1230000 usdc");
+        balance.symbol = Some("USDC".to_owned());
+        balance.decimals = Some(6);
+        assert_eq!(balance.to_string(), "1230000 usdc (1.23 USDC)");
+    }
 }
@@ -1,12 +1,11 @@
 use std::{str::FromStr, sync::Arc, thread, time::Duration};
 
-use axon_tools::types::{Block as AxonBlock, Proof as AxonProof, ValidatorExtend};
 use ckb_ics_axon::{
     axon_client::{commitment_slot, AxonCommitmentProof},
     commitment::{channel_path, connection_path},
 };
-use eth2_types::Hash256;
 use k256::ecdsa::SigningKey;
+use prost::Message as _;
 use tracing::{debug, warn};
 
 use crate::{
@@ -16,15 +15,18 @@ use crate::{
         requests::{Qualified, QueryHeight},
     },
     client_state::{AnyClientState, IdentifiedAnyClientState},
-    config::{axon::AxonChainConfig, ChainConfig},
+    config::{
+        axon::{AxonChainConfig, AxonToken},
+        ChainConfig,
+    },
     connection::ConnectionMsgType,
     consensus_state::AnyConsensusState,
-    denom::DenomTrace,
-    error::Error,
+    denom::{parse_denom_trace, DenomTrace},
+    error::{Error, ErrorDetail},
     event::{monitor::TxMonitorCmd, IbcEventWithHeight},
     ibc_contract::OwnableIBCHandlerEvents,
     keyring::{KeyRing, Secp256k1KeyPair},
-    light_client::{axon::LightClient as AxonLightClient, LightClient},
+    light_client::{axon::LightClient as AxonLightClient, LightClient, Verified},
     misbehaviour::MisbehaviourEvidence,
 };
 use ethers::{
@@ -34,10 +36,18 @@ use ethers::{
 };
 use ibc_proto::{
     google::protobuf::Any,
-    ibc::apps::fee::v1::{QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse},
+    ibc::{
+        apps::fee::v1::{QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse},
+        core::{
+            channel::v1::Channel as RawChannel, connection::v1::ConnectionEnd as RawConnectionEnd,
+        },
+    },
 };
 use ibc_relayer_types::{
-    applications::ics31_icq::response::CrossChainQueryResponse,
+    applications::{
+        ics31_icq::response::CrossChainQueryResponse,
+        transfer::msgs::transfer::{self, MsgTransfer},
+    },
     clients::ics07_axon::{
         client_state::AxonClientState, consensus_state::AxonConsensusState, header::AxonHeader,
         light_block::AxonLightBlock,
@@ -54,17 +64,22 @@ use ibc_relayer_types::{
         },
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd, Order},
+            events::TimeoutOnClosePacket,
             msgs::{
                 acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack,
                 chan_open_confirm, chan_open_init, chan_open_try, recv_packet, timeout,
+                timeout_on_close,
             },
             packet::{PacketMsgType, Sequence},
         },
         ics23_commitment::{
             commitment::{CommitmentPrefix, CommitmentRoot},
-            merkle::MerkleProof,
+            merkle::{MerkleProof, StorageProof},
+        },
+        ics24_host::{
+            identifier::{ChannelId, ClientId, ConnectionId, PortId},
+            path::{ClientConsensusStatePath, ClientStatePath, ReceiptsPath, SeqRecvsPath},
         },
-        ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
     },
     events::{IbcEvent, WithBlockDataType},
     proofs::{ConsensusProof, Proofs},
@@ -75,13 +90,18 @@ use ibc_relayer_types::{
 };
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 
-use self::{contract::OwnableIBCHandler, monitor::AxonEventMonitor};
+use self::contract::OwnableIBCHandler;
 
 type ContractProvider = SignerMiddleware<Provider<Http>, Wallet<SigningKey>>;
 type IBCContract = OwnableIBCHandler<ContractProvider>;
 type ERC20Contract = ERC20<ContractProvider>;
 type ICS20TransferERC20Contract = ICS20TransferERC20<ContractProvider>;
 
+/// Denom of the native token of Axon in balance queries
+pub const NATIVE_DENOM: &str = "axon";
+pub const NATIVE_SYMBOL: &str = "AXON";
+pub const NATIVE_DECIMALS: u8 = 18;
+
 use super::{
     client::ClientSettings,
     cosmos::encode::key_pair_to_signer,
@@ -106,12 +126,24 @@ use tokio::runtime::Runtime as TokioRuntime;
 
 pub mod contract;
 mod eth_err;
+mod gas;
+mod metadata;
 mod monitor;
 mod msg;
+mod proof;
 pub mod rpc;
+mod tokens;
 pub mod utils;
 
+#[cfg(test)]
+mod tests;
+
+use gas::{send_contract_call, Reverted};
+pub use metadata::MetadataCache;
+pub use monitor::{AxonEventMonitor, Checkpoint};
+use proof::{verify_storage_proof, ExpectedCommitment, PACKET_RECEIPT};
 pub use rpc::AxonRpc;
+pub use tokens::{TokenInfo, TokenRegistry};
 use utils::*;
 
 abigen!(
@@ -123,6 +155,8 @@ abigen!(
         function allowance(address owner, address spender) external view returns (uint256)
         function approve(address spender, uint256 amount) external returns (bool)
         function transferFrom(address from, address to, uint256 amount) external returns (bool)
+        function symbol() external view returns (string)
+        function decimals() external view returns (uint8)
     ]"
 );
 
@@ -131,6 +165,16 @@ abigen!(
     ICS20TransferERC20,
     r"[
         function denomTraces(bytes32 hash) external view returns (string)
+        function denomTokenContract(string denom) external view returns (address)
+        function sendTransfer(string denom, uint64 amount, address receiver, string sourcePort, string sourceChannel, uint64 timeoutHeight) external
+    ]"
+);
+
+// the entry point of the transfer contracts supporting timeout timestamps and memos
+abigen!(
+    ICS20TransferERC20WithMemo,
+    r"[
+        function sendTransfer(string denom, uint64 amount, address receiver, string sourcePort, string sourceChannel, uint64 timeoutHeight, uint64 timeoutTimestamp, string memo) external
     ]"
 );
 
@@ -143,9 +187,20 @@ pub struct AxonChain {
     client: Provider<Http>,
     keybase: KeyRing<Secp256k1KeyPair>,
     chain_id: u64,
+    metadata_cache: MetadataCache,
+    tokens: TokenRegistry,
 }
 
 impl AxonChain {
+    /// Address of the key named `key_name`, or `key_name` itself if it's
+    /// already an address
+    fn account_address(&self, key_name: &str) -> Result<H160, Error> {
+        if let Ok(address) = H160::from_str(key_name) {
+            return Ok(address);
+        }
+        Ok(self.get_wallet(key_name)?.address())
+    }
+
     fn get_wallet(&self, key_name: &str) -> Result<Wallet<SigningKey>, Error> {
         let key_entry = self.keybase.get_key(key_name).map_err(Error::key_base)?;
         let wallet = key_entry.into_ether_wallet().with_chain_id(self.chain_id);
@@ -174,6 +229,214 @@ impl AxonChain {
     fn erc20_contract(&self, address: H160) -> Result<ERC20Contract, Error> {
         Ok(ERC20::new(address, self.contract_provider()?))
     }
+
+    /// Registers the ERC-20 token at `address` with its metadata, reported
+    /// with `denom` or else its symbol
+    fn load_token(&self, address: H160, denom: Option<String>) -> Result<TokenInfo, Error> {
+        let contract = self.erc20_contract(address)?;
+        // `symbol()` and `decimals()` are optional in ERC-20
+        let symbol = match self.rt.block_on(contract.symbol().call()) {
+            Ok(symbol) => Some(symbol),
+            Err(err) => {
+                warn!("no symbol of token {address:#x}: {err:?}");
+                None
+            }
+        };
+        let decimals = match self.rt.block_on(contract.decimals().call()) {
+            Ok(decimals) => Some(decimals),
+            Err(err) => {
+                warn!("no decimals of token {address:#x}: {err:?}");
+                None
+            }
+        };
+        let token = TokenInfo {
+            denom: denom
+                .or_else(|| symbol.clone())
+                .unwrap_or_else(|| format!("{address:#x}")),
+            address,
+            symbol,
+            decimals,
+        };
+        self.tokens.insert(token.clone());
+        Ok(token)
+    }
+
+    /// Resolves `denom` into a registered token, an ERC-20 address, or the
+    /// token of the transfer contract for the denom, tracing `ibc/<hash>`
+    /// denoms back to their full path first
+    fn resolve_token(&self, denom: &str) -> Result<TokenInfo, Error> {
+        if let Some(token) = self.tokens.get(denom) {
+            return Ok(token);
+        }
+        if let Ok(address) = H160::from_str(denom) {
+            return self.load_token(address, Some(denom.to_owned()));
+        }
+
+        let full_path = self.full_denom(denom)?;
+        let address = self
+            .rt
+            .block_on(
+                self.transfer_contract()?
+                    .denom_token_contract(full_path)
+                    .call(),
+            )
+            .map_err(|err| Error::query(format!("{err:?}")))?;
+        if address.is_zero() {
+            return Err(Error::query(format!(
+                "transfer contract has no token of denom `{denom}`"
+            )));
+        }
+        self.load_token(address, Some(denom.to_owned()))
+    }
+
+    /// Traces `ibc/<hash>` denoms back to their full path, other denoms are
+    /// returned unchanged
+    fn full_denom(&self, denom: &str) -> Result<String, Error> {
+        if !denom.starts_with("ibc/") {
+            return Ok(denom.to_owned());
+        }
+        let trace = self.query_denom_trace(denom.to_owned())?;
+        if trace.path.is_empty() {
+            Ok(trace.base_denom)
+        } else {
+            Ok(format!("{}/{}", trace.path, trace.base_denom))
+        }
+    }
+
+    /// Sends an ICS-20 transfer through the transfer contract, which is first
+    /// approved to spend the tokens if its allowance doesn't cover them. The
+    /// timeout timestamp is passed to the contract in seconds
+    fn send_transfer(&mut self, message: Any) -> Result<IbcEventWithHeight, Error> {
+        let msg = MsgTransfer::from_any(message)
+            .map_err(|e| Error::protobuf_decode(transfer::TYPE_URL.to_owned(), e))?;
+        let amount: u64 = msg.token.amount.parse().map_err(|_| {
+            Error::other_error(format!("invalid token amount {}", msg.token.amount))
+        })?;
+        let receiver = H160::from_str(msg.receiver.as_ref())
+            .map_err(|_| Error::other_error(format!("invalid receiver {}", msg.receiver)))?;
+
+        // vouchers are known to the transfer contract by their full denom,
+        // while the other tokens are sent by address
+        let transfer_contract = self.transfer_contract()?;
+        let full_denom = self.full_denom(&msg.token.denom)?;
+        let voucher = self
+            .rt
+            .block_on(
+                transfer_contract
+                    .denom_token_contract(full_denom.clone())
+                    .call(),
+            )
+            .map_err(|err| Error::query(format!("{err:?}")))?;
+        let (token_address, denom) = if voucher.is_zero() {
+            let token = self.resolve_token(&msg.token.denom)?;
+            (token.address, format!("{:?}", token.address))
+        } else {
+            (voucher, full_denom)
+        };
+
+        let sender = self.account_address(&self.config.key_name)?;
+        let spender = self.config.transfer_contract_address;
+        let erc20 = self.erc20_contract(token_address)?;
+        let allowance = self
+            .rt
+            .block_on(erc20.allowance(sender, spender).call())
+            .map_err(|err| Error::query(format!("{err:?}")))?;
+        if allowance < amount.into() {
+            let tx_receipt = self
+                .rt
+                .block_on(send_contract_call(
+                    &self.config,
+                    erc20.approve(spender, amount.into()),
+                ))
+                .map_err(convert_err)?
+                .ok_or(Error::send_tx(String::from("fail to send tx")))?;
+            debug!(
+                "approved the transfer contract to spend {amount} of {token_address:?} in {}",
+                hex::encode(tx_receipt.transaction_hash)
+            );
+        }
+
+        let timeout_height = msg.timeout_height.commitment_revision_height();
+        let timeout_timestamp = msg.timeout_timestamp.nanoseconds() / SEC_TO_NANO;
+        let memo = msg.memo.unwrap_or_default();
+        // the original entry point is kept for the contracts without the
+        // extended one
+        let call = if timeout_timestamp == 0 && memo.is_empty() {
+            transfer_contract.send_transfer(
+                denom,
+                amount,
+                receiver,
+                msg.source_port.to_string(),
+                msg.source_channel.to_string(),
+                timeout_height,
+            )
+        } else {
+            ICS20TransferERC20WithMemo::new(
+                self.config.transfer_contract_address,
+                self.contract_provider()?,
+            )
+            .send_transfer(
+                denom,
+                amount,
+                receiver,
+                msg.source_port.to_string(),
+                msg.source_channel.to_string(),
+                timeout_height,
+                timeout_timestamp,
+                memo,
+            )
+        };
+        let tx_receipt = self
+            .rt
+            .block_on(send_contract_call(&self.config, call))
+            .map_err(convert_send_err)?
+            .ok_or(Error::send_tx(String::from("fail to send tx")))?;
+        let (tx_hash, height) = committed_at(&tx_receipt)?;
+        let event = tx_receipt
+            .logs
+            .into_iter()
+            .filter(|log| log.address == self.config.contract_address)
+            .map(|log| OwnableIBCHandlerEvents::decode_log(&log.into()))
+            .find(|event| matches!(event, Ok(OwnableIBCHandlerEvents::SendPacketFilter(_))))
+            .ok_or_else(|| {
+                Error::send_tx("not find send packet event in the transfer receipt".to_owned())
+            })?
+            .map_err(|e| Error::send_tx(e.to_string()))?
+            .into();
+        tracing::info!(
+            "transfer transaction {} committed to {}",
+            hex::encode(tx_hash),
+            self.id()
+        );
+        Ok(IbcEventWithHeight {
+            event,
+            height,
+            tx_hash,
+        })
+    }
+
+    /// Registers the tokens of the config which are not registered yet, the
+    /// ones failing to resolve are skipped until the next attempt
+    fn resolve_configured_tokens(&self) {
+        for token in &self.config.tokens {
+            let result = match token {
+                AxonToken::Erc20 { address, denom } => {
+                    let registered = match denom {
+                        Some(denom) => self.tokens.get(denom).is_some(),
+                        None => self.tokens.contains_address(address),
+                    };
+                    if registered {
+                        continue;
+                    }
+                    self.load_token(*address, denom.clone())
+                }
+                AxonToken::Ics20 { denom } => self.resolve_token(denom),
+            };
+            if let Err(e) = result {
+                warn!("skip balance of token {token:?}: {e}");
+            }
+        }
+    }
 }
 
 impl ChainEndpoint for AxonChain {
@@ -189,7 +452,7 @@ impl ChainEndpoint for AxonChain {
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
         let config: AxonChainConfig = config.try_into()?;
-        let keybase = KeyRing::new_secp256k1(Default::default(), "axon", &config.id)
+        let keybase = KeyRing::new_secp256k1(config.key_store_type, "axon", &config.id)
             .map_err(Error::key_base)?;
 
         let url = config.rpc_addr.clone();
@@ -199,16 +462,13 @@ impl ChainEndpoint for AxonChain {
             .block_on(client.get_chainid())
             .map_err(|e| Error::other_error(e.to_string()))?
             .as_u64();
-        let light_client = AxonLightClient::from_config(&config, rt.clone())?;
-
-        // TODO: since Ckb endpoint uses Axon metadata cell as its light client, Axon
-        //       endpoint has no need to monitor the update of its metadata
-        //
-        // let metadata = rt.block_on(rpc_client.get_current_metadata())?;
-        // let epoch_len = metadata.version.end - metadata.version.start + 1;
-        // light_client.bootstrap(client.clone(), rpc_client.clone(), epoch_len)?;
-
-        // FIXME remove the light client or fully implement it
+        let metadata_cache = MetadataCache::new();
+        let light_client = AxonLightClient::from_config(
+            &config,
+            rt.clone(),
+            rpc_client.clone(),
+            metadata_cache.clone(),
+        )?;
 
         Ok(Self {
             rt,
@@ -219,6 +479,8 @@ impl ChainEndpoint for AxonChain {
             chain_id,
             rpc_client,
             client,
+            metadata_cache,
+            tokens: TokenRegistry::default(),
         })
     }
 
@@ -280,11 +542,20 @@ impl ChainEndpoint for AxonChain {
         &mut self,
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
-        tracked_msgs
-            .msgs
-            .into_iter()
-            .map(|msg| self.send_message(msg))
-            .collect::<Result<Vec<_>, _>>()
+        let max_msgs_per_batch = self.config.max_msgs_per_batch;
+        let events = send_in_batches(
+            self,
+            tracked_msgs.msgs,
+            max_msgs_per_batch,
+            Self::send_messages_in_batch,
+            Self::send_message,
+            |e| {
+                // the height of a failed message is unknown, like on Cosmos
+                let height = Height::from_noncosmos_height(1);
+                IbcEventWithHeight::new(IbcEvent::ChainError(e.to_string()), height)
+            },
+        );
+        Ok(events)
     }
 
     fn send_messages_and_wait_check_tx(
@@ -308,7 +579,6 @@ impl ChainEndpoint for AxonChain {
         Ok(responses)
     }
 
-    // TODO the light client is unimplemented
     fn verify_header(
         &mut self,
         trusted: Height,
@@ -320,7 +590,6 @@ impl ChainEndpoint for AxonChain {
             .map(|v| v.target)
     }
 
-    // TODO the light client is unimplemented
     fn check_misbehaviour(
         &mut self,
         update: &UpdateClient,
@@ -330,32 +599,46 @@ impl ChainEndpoint for AxonChain {
     }
 
     fn query_balance(&self, key_name: Option<&str>, denom: Option<&str>) -> Result<Balance, Error> {
-        let key_name = key_name.unwrap_or(&self.config.key_name);
-        let denom: &str =
-            denom.ok_or_else(|| Error::other_error("do not support default denom".into()))?;
-        let erc20_address = {
-            let denom = denom.trim_start_matches("0x");
-            let bytes = hex::decode(denom).map_err(Error::other)?;
-            H160::from_slice(&bytes)
-        };
-        let contract = self.erc20_contract(erc20_address)?;
-        let wallet = self.get_wallet(key_name)?;
-        let amount = self
-            .rt
-            .block_on(contract.balance_of(wallet.address()).call())
-            .map_err(|err| Error::query(format!("{err:?}")))?;
-
-        Ok(Balance {
-            amount: format!("{amount:#x}"),
-            denom: denom.to_string(),
-        })
+        let account = self.account_address(key_name.unwrap_or(&self.config.key_name))?;
+        match denom {
+            None | Some(NATIVE_DENOM) => {
+                let amount = self
+                    .rt
+                    .block_on(self.client.get_balance(account, None))
+                    .map_err(|err| Error::query(format!("{err:?}")))?;
+                Ok(Balance {
+                    amount: amount.to_string(),
+                    denom: NATIVE_DENOM.to_owned(),
+                    symbol: Some(NATIVE_SYMBOL.to_owned()),
+                    decimals: Some(NATIVE_DECIMALS),
+                })
+            }
+            Some(denom) => {
+                let token = self.resolve_token(denom)?;
+                let contract = self.erc20_contract(token.address)?;
+                let amount = self
+                    .rt
+                    .block_on(contract.balance_of(account).call())
+                    .map_err(|err| Error::query(format!("{err:?}")))?;
+                Ok(token.balance(amount.to_string()))
+            }
+        }
     }
 
-    // FIXME implement this after use a real ics token contract
-    fn query_all_balances(&self, _key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
-        // TODO: implement the real `query_all_balances` function later
-        warn!("axon query_all_balances() cannot implement");
-        Ok(vec![])
+    // Axon's ERC-20 tokens, including the ones of the transfer contract, cannot
+    // be enumerated, the balances are the ones of the tokens of the config and
+    // of the tokens queried so far
+    fn query_all_balances(&self, key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
+        self.resolve_configured_tokens();
+        let mut balances = vec![self.query_balance(key_name, None)?];
+        for token in self.tokens.tokens() {
+            match self.query_balance(key_name, Some(&token.denom)) {
+                Ok(balance) if balance.amount != "0" => balances.push(balance),
+                Ok(_) => {}
+                Err(e) => warn!("skip balance of token {}: {e}", token.denom),
+            }
+        }
+        Ok(balances)
     }
 
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
@@ -368,8 +651,7 @@ impl ChainEndpoint for AxonChain {
         if full_path.is_empty() {
             return Err(Error::empty_denom_trace(hash));
         }
-        let dt: DenomTrace = parse_denom_trace(full_path)?;
-        Ok(dt)
+        Ok(parse_denom_trace(&full_path))
     }
 
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
@@ -415,44 +697,66 @@ impl ChainEndpoint for AxonChain {
         Ok(client_states)
     }
 
-    // TODO verify proof
     fn query_client_state(
         &self,
         request: QueryClientStateRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
-        let mut call_builder = self
-            .contract()?
-            .get_client_state(request.client_id.to_string());
-        if let QueryHeight::Specific(height) = request.height {
-            call_builder = call_builder.block(height.revision_height())
-        }
-        let (client_state, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
-
-        let (_, client_state) = to_any_client_state(&client_state)?;
-        Ok((client_state, None))
+        let path = ClientStatePath(request.client_id.clone()).to_string();
+        self.query_proven(
+            request.height,
+            include_proof,
+            &path,
+            |height| {
+                let mut call_builder = self
+                    .contract()?
+                    .get_client_state(request.client_id.to_string());
+                if let QueryHeight::Specific(height) = height {
+                    call_builder = call_builder.block(height.revision_height())
+                }
+                let (response, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+                let (_, client_state) = to_any_client_state(&response)?;
+                Ok((client_state, response))
+            },
+            |(_, response)| ExpectedCommitment::hash_of(encoded_state(response)),
+        )
+        .map(|((client_state, _), proof)| (client_state, proof))
     }
 
-    // TODO verify proof
     fn query_consensus_state(
         &self,
         request: QueryConsensusStateRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
-        let client_id: String = request.client_id.to_string();
-        let height = {
-            let height = request.consensus_height;
-            HeightData {
-                revision_number: height.revision_number(),
-                revision_height: height.revision_height(),
-            }
-        };
-        let mut call_builder = self.contract()?.get_consensus_state(client_id, height);
-        if let QueryHeight::Specific(height) = request.query_height {
-            call_builder = call_builder.block(height.revision_height());
+        let path = ClientConsensusStatePath {
+            client_id: request.client_id.clone(),
+            epoch: request.consensus_height.revision_number(),
+            height: request.consensus_height.revision_height(),
         }
-        let (consensus_state, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
-        Ok((to_any_consensus_state(&consensus_state)?, None))
+        .to_string();
+        self.query_proven(
+            request.query_height,
+            include_proof,
+            &path,
+            |query_height| {
+                let client_id: String = request.client_id.to_string();
+                let height = {
+                    let height = request.consensus_height;
+                    HeightData {
+                        revision_number: height.revision_number(),
+                        revision_height: height.revision_height(),
+                    }
+                };
+                let mut call_builder = self.contract()?.get_consensus_state(client_id, height);
+                if let QueryHeight::Specific(height) = query_height {
+                    call_builder = call_builder.block(height.revision_height());
+                }
+                let (response, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+                Ok((to_any_consensus_state(&response)?, response))
+            },
+            |(_, response)| ExpectedCommitment::hash_of(encoded_state(response)),
+        )
+        .map(|((consensus_state, _), proof)| (consensus_state, proof))
     }
 
     fn query_consensus_state_heights(
@@ -527,21 +831,32 @@ impl ChainEndpoint for AxonChain {
         Ok(connection_ids)
     }
 
-    // TODO verify proof
     fn query_connection(
         &self,
         request: QueryConnectionRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
-        let mut call_builder = self
-            .contract()?
-            .get_connection(request.connection_id.to_string());
-        if let QueryHeight::Specific(height) = request.height {
-            call_builder = call_builder.block(height.revision_height());
-        }
-        let (connection_end, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
-        let connection_end = connection_end.into();
-        Ok((connection_end, None))
+        let path = connection_path(request.connection_id.as_str());
+        self.query_proven(
+            request.height,
+            include_proof,
+            &path,
+            |height| {
+                let mut call_builder = self
+                    .contract()?
+                    .get_connection(request.connection_id.to_string());
+                if let QueryHeight::Specific(height) = height {
+                    call_builder = call_builder.block(height.revision_height());
+                }
+                let (connection_end, _) =
+                    self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+                Ok(connection_end.into())
+            },
+            |connection_end: &ConnectionEnd| {
+                let encoded = RawConnectionEnd::from(connection_end.clone()).encode_to_vec();
+                ExpectedCommitment::hash_of(&encoded)
+            },
+        )
     }
 
     fn query_connection_channels(
@@ -578,22 +893,33 @@ impl ChainEndpoint for AxonChain {
         Ok(channels)
     }
 
-    // TODO verify proof
     fn query_channel(
         &self,
         request: QueryChannelRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
-        let mut call_builder = self
-            .contract()?
-            .get_channel(request.port_id.to_string(), request.channel_id.to_string());
-        if let QueryHeight::Specific(height) = request.height {
-            call_builder = call_builder.block(height.revision_height())
-        }
+        let path = channel_path(request.port_id.as_str(), request.channel_id.as_str());
+        self.query_proven(
+            request.height,
+            include_proof,
+            &path,
+            |height| {
+                let mut call_builder = self
+                    .contract()?
+                    .get_channel(request.port_id.to_string(), request.channel_id.to_string());
+                if let QueryHeight::Specific(height) = height {
+                    call_builder = call_builder.block(height.revision_height())
+                }
 
-        let (channel_end, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
-        let channel_end = channel_end.into();
-        Ok((channel_end, None))
+                let (channel_end, _) =
+                    self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+                Ok(channel_end.into())
+            },
+            |channel_end: &ChannelEnd| {
+                let encoded = RawChannel::from(channel_end.clone()).encode_to_vec();
+                ExpectedCommitment::hash_of(&encoded)
+            },
+        )
     }
 
     fn query_channel_client_state(
@@ -619,22 +945,34 @@ impl ChainEndpoint for AxonChain {
         }
     }
 
-    // TODO verify proof
     fn query_packet_commitment(
         &self,
         request: QueryPacketCommitmentRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
-        let mut call_builder = self.contract()?.get_hashed_packet_commitment(
-            request.port_id.to_string(),
-            request.channel_id.to_string(),
+        let path = ckb_ics_axon::commitment::packet_commitment_path(
+            request.port_id.as_str(),
+            request.channel_id.as_str(),
             request.sequence.into(),
         );
-        if let QueryHeight::Specific(height) = request.height {
-            call_builder = call_builder.block(height.revision_height());
-        }
-        let (commitment, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
-        Ok((commitment.to_vec(), None))
+        let ((commitment, _), proof) = self.query_proven(
+            request.height,
+            include_proof,
+            &path,
+            |height| {
+                let mut call_builder = self.contract()?.get_hashed_packet_commitment(
+                    request.port_id.to_string(),
+                    request.channel_id.to_string(),
+                    request.sequence.into(),
+                );
+                if let QueryHeight::Specific(height) = height {
+                    call_builder = call_builder.block(height.revision_height());
+                }
+                self.rt.block_on(call_builder.call()).map_err(convert_err)
+            },
+            |(commitment, found)| hashed_commitment(*commitment, *found),
+        )?;
+        Ok((commitment.to_vec(), proof))
     }
 
     fn query_packet_commitments(
@@ -660,25 +998,44 @@ impl ChainEndpoint for AxonChain {
         Ok((commitment_sequences, Height::default()))
     }
 
-    // TODO verify proof
     fn query_packet_receipt(
         &self,
         request: QueryPacketReceiptRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
-        let mut call_builder = self.contract()?.has_packet_receipt(
-            request.port_id.to_string(),
-            request.channel_id.to_string(),
-            request.sequence.into(),
-        );
-        if let QueryHeight::Specific(height) = request.height {
-            call_builder = call_builder.block(height.revision_height());
+        let path = ReceiptsPath {
+            port_id: request.port_id.clone(),
+            channel_id: request.channel_id.clone(),
+            sequence: request.sequence,
         }
-        let has_receipt = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+        .to_string();
+        let (has_receipt, proof) = self.query_proven(
+            request.height,
+            include_proof,
+            &path,
+            |height| {
+                let mut call_builder = self.contract()?.has_packet_receipt(
+                    request.port_id.to_string(),
+                    request.channel_id.to_string(),
+                    request.sequence.into(),
+                );
+                if let QueryHeight::Specific(height) = height {
+                    call_builder = call_builder.block(height.revision_height());
+                }
+                self.rt.block_on(call_builder.call()).map_err(convert_err)
+            },
+            |has_receipt| {
+                if *has_receipt {
+                    ExpectedCommitment::hash_of(&[PACKET_RECEIPT])
+                } else {
+                    ExpectedCommitment::Absent
+                }
+            },
+        )?;
         if has_receipt {
-            Ok((vec![1u8], None))
+            Ok((vec![1u8], proof))
         } else {
-            Ok((vec![], None))
+            Ok((vec![], proof))
         }
     }
 
@@ -731,24 +1088,36 @@ impl ChainEndpoint for AxonChain {
         Ok(sequences)
     }
 
-    // TODO verify proof
     fn query_packet_acknowledgement(
         &self,
         request: QueryPacketAcknowledgementRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
-        let mut call_builder = self
-            .contract()?
-            .get_hashed_packet_acknowledgement_commitment(
-                request.port_id.to_string(),
-                request.channel_id.to_string(),
-                request.sequence.into(),
-            );
-        if let QueryHeight::Specific(height) = request.height {
-            call_builder = call_builder.block(height.revision_height());
-        }
-        let (commitment, _) = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
-        Ok((commitment.to_vec(), None))
+        let path = ckb_ics_axon::commitment::packet_acknowledgement_commitment_path(
+            request.port_id.as_str(),
+            request.channel_id.as_str(),
+            request.sequence.into(),
+        );
+        let ((commitment, _), proof) = self.query_proven(
+            request.height,
+            include_proof,
+            &path,
+            |height| {
+                let mut call_builder = self
+                    .contract()?
+                    .get_hashed_packet_acknowledgement_commitment(
+                        request.port_id.to_string(),
+                        request.channel_id.to_string(),
+                        request.sequence.into(),
+                    );
+                if let QueryHeight::Specific(height) = height {
+                    call_builder = call_builder.block(height.revision_height());
+                }
+                self.rt.block_on(call_builder.call()).map_err(convert_err)
+            },
+            |(commitment, found)| hashed_commitment(*commitment, *found),
+        )?;
+        Ok((commitment.to_vec(), proof))
     }
 
     fn query_packet_acknowledgements(
@@ -804,20 +1173,29 @@ impl ChainEndpoint for AxonChain {
         Ok(sequences)
     }
 
-    // TODO verify proof
     fn query_next_sequence_receive(
         &self,
         request: QueryNextSequenceReceiveRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(Sequence, Option<MerkleProof>), Error> {
-        let mut call_builder = self
-            .contract()?
-            .get_next_sequence_recvs(request.port_id.to_string(), request.channel_id.to_string());
-        if let QueryHeight::Specific(height) = request.height {
-            call_builder = call_builder.block(height.revision_height());
-        }
-        let sequence = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
-        Ok((sequence.into(), None))
+        let path = SeqRecvsPath(request.port_id.clone(), request.channel_id.clone()).to_string();
+        self.query_proven(
+            request.height,
+            include_proof,
+            &path,
+            |height| {
+                let mut call_builder = self.contract()?.get_next_sequence_recvs(
+                    request.port_id.to_string(),
+                    request.channel_id.to_string(),
+                );
+                if let QueryHeight::Specific(height) = height {
+                    call_builder = call_builder.block(height.revision_height());
+                }
+                let sequence = self.rt.block_on(call_builder.call()).map_err(convert_err)?;
+                Ok(sequence.into())
+            },
+            |sequence: &Sequence| ExpectedCommitment::hash_of(&u64::from(*sequence).to_be_bytes()),
+        )
     }
 
     fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEventWithHeight>, Error> {
@@ -1074,25 +1452,28 @@ impl ChainEndpoint for AxonChain {
         }
     }
 
-    // TODO do we need to implement this?
     fn build_consensus_state(
         &self,
-        _light_block: Self::LightBlock,
+        light_block: Self::LightBlock,
     ) -> Result<Self::ConsensusState, Error> {
         Ok(AxonConsensusState {
-            root: CommitmentRoot::from_bytes(&[]),
-            timestamp: Timestamp::default(),
+            root: CommitmentRoot::from_bytes(light_block.header.state_root.as_bytes()),
+            timestamp: light_block.header.timestamp,
         })
     }
 
-    // TODO do we need to implement this?
     fn build_header(
         &mut self,
-        _trusted_height: Height,
-        _target_height: Height,
-        _client_state: &AnyClientState,
+        trusted_height: Height,
+        target_height: Height,
+        client_state: &AnyClientState,
     ) -> Result<(Self::Header, Vec<Self::Header>), Error> {
-        Ok((AxonHeader {}, vec![]))
+        let Verified { target, supporting } = self.light_client.header_and_minimal_set(
+            trusted_height,
+            target_height,
+            client_state,
+        )?;
+        Ok((target, supporting))
     }
 
     // TODO do we need to implement this?
@@ -1179,60 +1560,19 @@ impl ChainEndpoint for AxonChain {
     }
 }
 
-/// Modified from ibc-go https://github.com/cosmos/ibc-go/blob/main/modules/apps/transfer/types/trace.go#L31
-fn parse_denom_trace(raw_denom: String) -> Result<DenomTrace, Error> {
-    let parts: Vec<_> = raw_denom.split('/').collect();
-    if parts[0] == raw_denom {
-        return Ok(DenomTrace {
-            path: Default::default(),
-            base_denom: raw_denom,
-        });
-    }
-    let (path, base_denom) = extract_path_and_base_from_full_denom(parts);
-    Ok(DenomTrace { path, base_denom })
-}
-
-fn extract_path_and_base_from_full_denom(parts: Vec<&str>) -> (String, String) {
-    fn is_valid_channel_id(c: &str) -> bool {
-        const PREFIX: &str = "channel-";
-        if !c.starts_with(PREFIX) {
-            return false;
-        }
-        let r = c[PREFIX.len()..].parse::<usize>();
-        r.is_ok()
-    }
-
-    let mut path = Vec::new();
-    let mut base = Vec::new();
-    let len = parts.len();
-
-    for i in (0..len).step_by(2) {
-        if i < len - 1 && len > 2 && is_valid_channel_id(parts[i + 1]) {
-            path.push(parts[i]);
-            path.push(parts[i + 1]);
-        } else {
-            base.extend_from_slice(&parts[i..]);
-            break;
-        }
-    }
-    let path = path.join("/");
-    let base = base.join("/");
-
-    (path, base)
-}
-
 impl AxonChain {
     fn init_event_monitor(&mut self) -> Result<TxMonitorCmd, Error> {
         crate::time!("axon_init_event_monitor");
-        // let header_receiver = self.light_client.subscribe();
 
-        // TODO: monitor should start from tip - restore_block_number. Or better
-        // yet, it should start from where it's shutdown.
+        let checkpoint = Checkpoint::in_home_dir(&self.config.id, &self.config.contract_address)
+            .map_err(Error::event_monitor)?;
         let (event_monitor, monitor_tx) = AxonEventMonitor::new(
             self.config.id.clone(),
             self.config.websocket_addr.clone(),
             self.config.contract_address,
             self.config.restore_block_count,
+            checkpoint,
+            self.config.rescan_from_height,
             self.rt.clone(),
         )
         .map_err(Error::event_monitor)?;
@@ -1241,43 +1581,47 @@ impl AxonChain {
         Ok(monitor_tx)
     }
 
-    fn get_proofs(&self, height: Height, commitment_path: &str) -> Result<Proofs, Error> {
+    /// Proves the commitment stored for `commitment_path` at `height` with
+    /// `eth_getProof`, verifying the storage proof against the state root of
+    /// the verified block. Returns the proven commitment and its proof.
+    fn prove_commitment(
+        &self,
+        height: Height,
+        commitment_path: &str,
+    ) -> Result<(U256, AxonCommitmentProof), Error> {
         let block_number = height.revision_height();
-        let (block, previous_state_root, block_proof, mut validators) = self
-            .rt
-            .block_on(self.get_proofs_ingredients(block_number.into()))?;
+        let VerifiedBlock {
+            block,
+            previous_state_root,
+            proof: block_proof,
+            ..
+        } = self.rt.block_on(fetch_verified_block(
+            &self.rpc_client,
+            &self.metadata_cache,
+            block_number,
+        ))?;
 
-        let debug_content =
-            generate_debug_content(&block, &previous_state_root, &block_proof, &validators);
+        let commitment_slot: U256 = commitment_slot(commitment_path.as_bytes()).into();
 
-        // check the validation of Axon block
-        axon_tools::verify_proof(
-            block.clone(),
-            previous_state_root,
-            &mut validators,
-            block_proof.clone(),
+        let mut commitment_proof = self.rt.block_on(self.rpc_client.eth_get_proof(
+            self.config.contract_address,
+            vec![commitment_slot],
+            Some(block_number.into()),
+        ))?;
+        let commitment = verify_storage_proof(
+            block.header.state_root.0,
+            self.config.contract_address,
+            commitment_slot,
+            &commitment_proof,
         )
-        .map_err(|err| {
-            std::fs::write(
-                format!("./debug/axon_block_{block_number}.log"),
-                debug_content,
-            )
-            .unwrap();
-            let err_msg = format!("unverified axon block #{block_number}, err: {:?}", err);
-            Error::rpc_response(err_msg)
+        .map_err(|reason| {
+            Error::axon_invalid_storage_proof(commitment_path.to_owned(), block_number, reason)
         })?;
-
-        let commitment_slot = commitment_slot(commitment_path.as_bytes());
-
-        let mut commitment_proof = self
-            .rt
-            .block_on(self.rpc_client.eth_get_proof(
-                self.config.contract_address,
-                vec![commitment_slot.into()],
-                Some(block_number.into()),
-            ))
-            .unwrap();
-        assert!(!commitment_proof.storage_proof.is_empty());
+        let index = commitment_proof
+            .storage_proof
+            .iter()
+            .position(|proof| proof.key == commitment_slot)
+            .expect("verified storage proof");
         let commitment_proof = AxonCommitmentProof {
             block,
             block_proof,
@@ -1289,12 +1633,60 @@ impl AxonChain {
                 .collect(),
             storage_proof: commitment_proof
                 .storage_proof
-                .remove(0)
+                .swap_remove(index)
                 .proof
                 .into_iter()
                 .map(|p| p.0.into())
                 .collect(),
         };
+        Ok((commitment, commitment_proof))
+    }
+
+    /// Runs `query` at `query_height`. If a proof is requested, the query is
+    /// pinned to a specific block, and the commitment of `commitment_path` is
+    /// proven at the same block and checked against the queried object.
+    fn query_proven<T>(
+        &self,
+        query_height: QueryHeight,
+        include_proof: IncludeProof,
+        commitment_path: &str,
+        query: impl FnOnce(QueryHeight) -> Result<T, Error>,
+        expected: impl FnOnce(&T) -> ExpectedCommitment,
+    ) -> Result<(T, Option<MerkleProof>), Error> {
+        if let IncludeProof::No = include_proof {
+            return Ok((query(query_height)?, None));
+        }
+        let height = match query_height {
+            QueryHeight::Specific(height) => height,
+            QueryHeight::Latest => {
+                let block_number = self
+                    .rt
+                    .block_on(self.client.get_block_number())
+                    .map_err(|e| Error::rpc_response(e.to_string()))?;
+                Height::from_noncosmos_height(block_number.as_u64())
+            }
+        };
+        let object = query(QueryHeight::Specific(height))?;
+        let (commitment, proof) = self.prove_commitment(height, commitment_path)?;
+        expected(&object).check(commitment).map_err(|reason| {
+            Error::axon_invalid_storage_proof(
+                commitment_path.to_owned(),
+                height.revision_height(),
+                reason,
+            )
+        })?;
+
+        let mut key = [0u8; 32];
+        U256::from(commitment_slot(commitment_path.as_bytes())).to_big_endian(&mut key);
+        let proof = StorageProof {
+            key: key.to_vec(),
+            proof: rlp::encode(&proof).to_vec(),
+        };
+        Ok((object, Some(proof.into())))
+    }
+
+    fn get_proofs(&self, height: Height, commitment_path: &str) -> Result<Proofs, Error> {
+        let (_, commitment_proof) = self.prove_commitment(height, commitment_path)?;
         let object_proof = rlp::encode(&commitment_proof)
             .freeze()
             .to_vec()
@@ -1315,250 +1707,155 @@ impl AxonChain {
 
         Ok(proofs)
     }
-
-    async fn get_proofs_ingredients(
-        &self,
-        block_number: U64,
-    ) -> Result<(AxonBlock, Hash256, AxonProof, Vec<ValidatorExtend>), Error> {
-        let previous_number = block_number
-            .checked_sub(1u64.into())
-            .expect("bad block_number");
-        let next_number = block_number
-            .checked_add(1u64.into())
-            .expect("bad block_number");
-
-        let block = self
-            .rpc_client
-            .get_block_by_id(block_number.into())
-            .await?
-            .ok_or_else(|| Error::other_error(format!("failed to get block {block_number}")))?;
-        let state_root = self
-            .rpc_client
-            .get_block_by_id(previous_number.into())
-            .await?
-            .ok_or_else(|| Error::other_error(format!("failed to get block {previous_number}")))?
-            .header
-            .state_root;
-        let proof = loop {
-            match self.rpc_client.get_proof_by_id(next_number.into()).await? {
-                None => {
-                    tokio::time::sleep(Duration::from_secs(1)).await;
-                }
-                Some(p) => break p,
-            }
-        };
-        let validators = self
-            .rpc_client
-            .get_current_metadata()
-            .await?
-            .verifier_list
-            .into_iter()
-            .map(|v| ValidatorExtend {
-                bls_pub_key: v.bls_pub_key.clone(),
-                pub_key: v.pub_key.clone(),
-                address: v.address,
-                propose_weight: v.propose_weight,
-                vote_weight: v.vote_weight,
-            })
-            .collect::<Vec<_>>();
-
-        Ok((block, state_root, proof, validators))
-    }
 }
 
-macro_rules! convert {
-    ($self:ident, $msg:ident, $eventy:ty, $method:ident) => {{
-        let msg: $eventy = $msg.try_into()?;
-        $self.rt.block_on(async {
-            Ok($self
-                .contract()?
-                .$method(msg.clone())
-                .send()
-                .await
-                .map_err(decode_revert_error)?
-                .await?)
-        })
-    }};
-}
-
-impl AxonChain {
-    fn send_message(&mut self, message: Any) -> Result<IbcEventWithHeight, Error> {
-        loop {
-            match self.send_message_inner(message.clone()) {
-                Ok(e) => return Ok(e),
-                Err(e) => {
-                    if e.to_string().contains("reverted: getHeader") {
-                        std::thread::sleep(Duration::from_secs(5));
-                        tracing::info!("getHeader failed, waiting for header sync");
-                        continue;
-                    }
-                    return Err(e);
-                }
-            }
-        }
+/// Expected commitment of a packet (acknowledgement) commitment returned by
+/// the IBC handler, which stores the hash of the commitment
+fn hashed_commitment(commitment: [u8; 32], found: bool) -> ExpectedCommitment {
+    if found {
+        ExpectedCommitment::Hash(commitment)
+    } else {
+        ExpectedCommitment::Absent
     }
+}
 
-    fn send_message_inner(&mut self, message: Any) -> Result<IbcEventWithHeight, Error> {
-        use contract::*;
-        let msg = message.clone();
-        let tx_receipt: eyre::Result<_> = match msg.type_url.as_str() {
+/// Builds the call of the IBC handler method which processes `$message` and
+/// evaluates `$apply` with the call bound to `$call`.
+macro_rules! dispatch_message {
+    ($contract:expr, $message:expr, |$call:ident| $apply:expr) => {{
+        let contract = $contract;
+        let message: Any = $message;
+        match message.type_url.as_str() {
             // client
             create_client::TYPE_URL => {
-                convert!(self, msg, MsgCreateClient, create_client)
+                let $call = contract.create_client(message.try_into()?);
+                $apply
             }
             // connection
             conn_open_init::TYPE_URL => {
-                convert!(self, msg, MsgConnectionOpenInit, connection_open_init)
+                let $call = contract.connection_open_init(message.try_into()?);
+                $apply
             }
             conn_open_try::TYPE_URL => {
-                convert!(self, msg, MsgConnectionOpenTry, connection_open_try)
+                let $call = contract.connection_open_try(message.try_into()?);
+                $apply
             }
             conn_open_ack::TYPE_URL => {
-                convert!(self, msg, MsgConnectionOpenAck, connection_open_ack)
+                let $call = contract.connection_open_ack(message.try_into()?);
+                $apply
             }
             conn_open_confirm::TYPE_URL => {
-                convert!(self, msg, MsgConnectionOpenConfirm, connection_open_confirm)
+                let $call = contract.connection_open_confirm(message.try_into()?);
+                $apply
             }
             // channel
             chan_open_init::TYPE_URL => {
-                convert!(self, msg, MsgChannelOpenInit, channel_open_init)
+                let $call = contract.channel_open_init(message.try_into()?);
+                $apply
             }
             chan_open_try::TYPE_URL => {
-                convert!(self, msg, MsgChannelOpenTry, channel_open_try)
+                let $call = contract.channel_open_try(message.try_into()?);
+                $apply
             }
             chan_open_ack::TYPE_URL => {
-                convert!(self, msg, MsgChannelOpenAck, channel_open_ack)
+                let $call = contract.channel_open_ack(message.try_into()?);
+                $apply
             }
             chan_open_confirm::TYPE_URL => {
-                convert!(self, msg, MsgChannelOpenConfirm, channel_open_confirm)
+                let $call = contract.channel_open_confirm(message.try_into()?);
+                $apply
             }
             chan_close_init::TYPE_URL => {
-                convert!(self, msg, MsgChannelCloseInit, channel_close_init)
+                let $call = contract.channel_close_init(message.try_into()?);
+                $apply
             }
             chan_close_confirm::TYPE_URL => {
-                convert!(self, msg, MsgChannelCloseConfirm, channel_close_confirm)
+                let $call = contract.channel_close_confirm(message.try_into()?);
+                $apply
             }
             // packet
             recv_packet::TYPE_URL => {
-                convert!(self, msg, MsgPacketRecv, recv_packet)
+                let $call = contract.recv_packet(message.try_into()?);
+                $apply
             }
             acknowledgement::TYPE_URL => {
-                convert!(self, msg, MsgPacketAcknowledgement, acknowledge_packet)
+                let $call = contract.acknowledge_packet(message.try_into()?);
+                $apply
             }
             timeout::TYPE_URL => {
-                let msg = {
-                    let msg = timeout::MsgTimeout::from_any(msg.clone())
-                        .map_err(|e| Error::protobuf_decode(timeout::TYPE_URL.into(), e))?;
-                    // FIXME: add recv_timeout methond into solidity contract to handle this message type
-                    recv_packet::MsgRecvPacket {
-                        packet: msg.packet,
-                        proofs: msg.proofs,
-                        signer: msg.signer,
-                    }
-                };
-                self.rt.block_on(async {
-                    Ok(self
-                        .contract()?
-                        .recv_packet(msg.into())
-                        .send()
-                        .await?
-                        .await?)
-                })
+                let $call = contract.timeout_packet(message.try_into()?);
+                $apply
+            }
+            timeout_on_close::TYPE_URL => {
+                let $call = contract.timeout_on_close(message.try_into()?);
+                $apply
             }
             url => {
                 return Err(Error::other_error(format!(
                     "non-support message type url: {url}"
                 )))
             }
-        };
-        let tx_receipt = tx_receipt
-            .map_err(convert_err)?
-            .ok_or(Error::send_tx(String::from("fail to send tx")))?;
-        let event: IbcEvent = {
-            use contract::OwnableIBCHandlerEvents::*;
-
-            let mut events = tx_receipt
-                .logs
-                .into_iter()
-                .map(Into::into)
-                .map(|log| OwnableIBCHandlerEvents::decode_log(&log));
-            debug!(
-                "Axon received '{}' with events of {}",
-                message.type_url.as_str(),
-                events.len()
-            );
-            match message.type_url.as_str() {
-                create_client::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(CreateClientFilter(_))))
-                }
-                update_client::TYPE_URL => {
-                    let msg = update_client::MsgUpdateClient::from_any(message).map_err(|e| {
-                        Error::send_tx(format!("fail to decode MsgUpdateClient {}", e))
-                    })?;
-                    Some(Ok(UpdateClientFilter(contract::UpdateClientFilter {
-                        client_id: msg.client_id.to_string(),
-                        client_message: "update client".parse().unwrap(), // FIXME
-                    })))
-                }
-                conn_open_init::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenInitConnectionFilter(_))))
-                }
-                conn_open_try::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenTryConnectionFilter(_))))
-                }
-                conn_open_ack::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenAckConnectionFilter(_))))
-                }
-                conn_open_confirm::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenConfirmConnectionFilter(_))))
-                }
-                chan_open_init::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenInitChannelFilter(_))))
-                }
-                chan_open_try::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenTryChannelFilter(_))))
-                }
-                chan_open_ack::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenAckChannelFilter(_))))
-                }
-                chan_open_confirm::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(OpenConfirmChannelFilter(_))))
-                }
-                chan_close_init::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(CloseInitChannelFilter(_))))
-                }
-                chan_close_confirm::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(CloseConfirmChannelFilter(_))))
-                }
-                recv_packet::TYPE_URL | timeout::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(ReceivePacketFilter(_))))
-                }
-                acknowledgement::TYPE_URL => {
-                    events.find(|event| matches!(event, Ok(AcknowledgePacketFilter(_))))
-                }
+        }
+    }};
+}
 
-                url => {
-                    return Err(Error::send_tx(format!(
-                        "non-support message type url: {url}"
-                    )))
+impl AxonChain {
+    /// Retries `send` while the IBC handler is still waiting for the header
+    /// of the counterparty chain to be synced.
+    fn send_with_header_sync<T>(
+        &mut self,
+        mut send: impl FnMut(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        loop {
+            match send(self) {
+                Ok(e) => return Ok(e),
+                Err(e) => {
+                    if e.to_string().contains("reverted: getHeader") {
+                        std::thread::sleep(Duration::from_secs(5));
+                        tracing::info!("getHeader failed, waiting for header sync");
+                        continue;
+                    }
+                    return Err(e);
                 }
             }
         }
-        .ok_or_else(|| {
-            Error::send_tx("not find right event from Axon transaction receipt.".to_owned())
-        })?
-        .unwrap()
-        .into();
-        let tx_hash = tx_receipt.transaction_hash.0;
-        let height = {
-            let block_height = tx_receipt.block_number.ok_or_else(|| {
-                Error::send_tx(format!(
-                    "transaction {} is still pending",
-                    hex::encode(tx_hash)
-                ))
-            })?;
-            Height::from_noncosmos_height(block_height.as_u64())
-        };
+    }
+
+    fn send_message(&mut self, message: Any) -> Result<IbcEventWithHeight, Error> {
+        // transfers don't go through the IBC handler, so no header is needed
+        if message.type_url == transfer::TYPE_URL {
+            return self.send_transfer(message);
+        }
+        self.send_with_header_sync(|chain| chain.send_message_inner(message.clone()))
+    }
+
+    fn send_messages_in_batch(
+        &mut self,
+        messages: &[Any],
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.send_with_header_sync(|chain| chain.send_messages_in_batch_inner(messages))
+    }
+
+    fn send_message_inner(&mut self, message: Any) -> Result<IbcEventWithHeight, Error> {
+        let contract = self.contract()?;
+        let tx_receipt = dispatch_message!(&contract, message.clone(), |call| {
+            self.rt.block_on(send_contract_call(&self.config, call))
+        });
+        let tx_receipt = tx_receipt
+            .map_err(convert_send_err)?
+            .ok_or(Error::send_tx(String::from("fail to send tx")))?;
+        let (tx_hash, height) = committed_at(&tx_receipt)?;
+        let mut events = tx_receipt
+            .logs
+            .into_iter()
+            .map(Into::into)
+            .map(|log| OwnableIBCHandlerEvents::decode_log(&log));
+        debug!(
+            "Axon received '{}' with events of {}",
+            message.type_url.as_str(),
+            events.len()
+        );
+        let event = find_message_event(&message, &mut events)?;
         tracing::info!(
             "{} transaciton {} committed to {}",
             event.event_type().as_str(),
@@ -1571,4 +1868,217 @@ impl AxonChain {
             tx_hash,
         })
     }
+
+    /// Packs `messages` into one `multicall` transaction of the IBC handler.
+    fn send_messages_in_batch_inner(
+        &mut self,
+        messages: &[Any],
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let contract = self.contract()?;
+        let calls = messages
+            .iter()
+            .map(|message| encode_message(&contract, message))
+            .collect::<Result<Vec<_>, _>>()?;
+        // the gas limit is estimated against the whole batch
+        let tx_receipt = self
+            .rt
+            .block_on(send_contract_call(&self.config, contract.multicall(calls)));
+        let tx_receipt = tx_receipt
+            .map_err(convert_send_err)?
+            .ok_or(Error::send_tx(String::from("fail to send tx")))?;
+        let (tx_hash, height) = committed_at(&tx_receipt)?;
+        let mut events = tx_receipt
+            .logs
+            .into_iter()
+            .map(Into::into)
+            .map(|log| OwnableIBCHandlerEvents::decode_log(&log));
+        debug!(
+            "Axon received a batch of {} messages with events of {}",
+            messages.len(),
+            events.len()
+        );
+        // messages are executed in order, so the events of each message are
+        // found right after the events of the previous one
+        let events = messages
+            .iter()
+            .map(|message| {
+                let event = find_message_event(message, &mut events)?;
+                Ok(IbcEventWithHeight {
+                    event,
+                    height,
+                    tx_hash,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        tracing::info!(
+            "batch of {} messages in transaciton {} committed to {}",
+            events.len(),
+            hex::encode(tx_hash),
+            self.id()
+        );
+        Ok(events)
+    }
+}
+
+/// Sends `messages` in `multicall` batches of at most `max_msgs_per_batch`
+/// messages. A batch reverts as a whole when one of its messages reverts, so
+/// the messages of a reverted batch are sent again one by one. Each message
+/// gets a result, the ones failing are turned into results by `failed`.
+fn send_in_batches<C, T>(
+    chain: &mut C,
+    messages: Vec<Any>,
+    max_msgs_per_batch: usize,
+    send_batch: impl Fn(&mut C, &[Any]) -> Result<Vec<T>, Error>,
+    send_one: impl Fn(&mut C, Any) -> Result<T, Error>,
+    failed: impl Fn(&Error) -> T,
+) -> Vec<T> {
+    // transfers are sent to the transfer contract instead of the IBC handler
+    let has_transfer = messages
+        .iter()
+        .any(|msg| msg.type_url == transfer::TYPE_URL);
+    if max_msgs_per_batch <= 1 || messages.len() <= 1 || has_transfer {
+        return messages
+            .into_iter()
+            .map(|msg| send_one(chain, msg).unwrap_or_else(|e| failed(&e)))
+            .collect();
+    }
+    let mut results = Vec::with_capacity(messages.len());
+    for batch in messages.chunks(max_msgs_per_batch) {
+        match send_batch(chain, batch) {
+            Ok(batch_results) => results.extend(batch_results),
+            Err(e) if matches!(e.detail(), ErrorDetail::AxonTxReverted(_)) => {
+                warn!(
+                    "batch of {} messages reverted, sending them one by one: {e}",
+                    batch.len()
+                );
+                for msg in batch {
+                    results.push(send_one(chain, msg.clone()).unwrap_or_else(|e| failed(&e)));
+                }
+            }
+            // the batch may have been sent, so its messages are not sent again
+            Err(e) => {
+                warn!("batch of {} messages failed: {e}", batch.len());
+                results.extend(batch.iter().map(|_| failed(&e)));
+            }
+        }
+    }
+    results
+}
+
+/// Encodes `message` into the calldata of its IBC handler method.
+fn encode_message<M: Middleware>(
+    contract: &OwnableIBCHandler<M>,
+    message: &Any,
+) -> Result<Bytes, Error> {
+    let calldata = dispatch_message!(contract, message.clone(), |call| call.calldata());
+    calldata.ok_or_else(|| Error::send_tx(String::from("fail to encode calldata")))
+}
+
+/// Keeps the calls reverting in the gas estimation apart from the other
+/// failures of sending them.
+fn convert_send_err(err: eyre::Report) -> Error {
+    match err.downcast_ref::<Reverted>() {
+        Some(Reverted(reason)) => Error::axon_tx_reverted(reason.clone()),
+        None => convert_err(err),
+    }
+}
+
+/// Returns the hash and the block height of a committed transaction, which
+/// fails if the transaction reverted.
+fn committed_at(tx_receipt: &TransactionReceipt) -> Result<([u8; 32], Height), Error> {
+    let tx_hash = tx_receipt.transaction_hash.0;
+    if tx_receipt.status == Some(0u64.into()) {
+        return Err(Error::axon_tx_reverted(format!(
+            "transaction {} failed",
+            hex::encode(tx_hash)
+        )));
+    }
+    let block_height = tx_receipt.block_number.ok_or_else(|| {
+        Error::send_tx(format!(
+            "transaction {} is still pending",
+            hex::encode(tx_hash)
+        ))
+    })?;
+    Ok((
+        tx_hash,
+        Height::from_noncosmos_height(block_height.as_u64()),
+    ))
+}
+
+/// Consumes `events` up to the event emitted by the IBC handler for `message`.
+fn find_message_event<E: ToString>(
+    message: &Any,
+    events: &mut impl Iterator<Item = Result<OwnableIBCHandlerEvents, E>>,
+) -> Result<IbcEvent, Error> {
+    use contract::OwnableIBCHandlerEvents::*;
+
+    let event: IbcEvent = match message.type_url.as_str() {
+        create_client::TYPE_URL => events.find(|event| matches!(event, Ok(CreateClientFilter(_)))),
+        update_client::TYPE_URL => {
+            let msg = update_client::MsgUpdateClient::from_any(message.clone())
+                .map_err(|e| Error::send_tx(format!("fail to decode MsgUpdateClient {}", e)))?;
+            Some(Ok(UpdateClientFilter(contract::UpdateClientFilter {
+                client_id: msg.client_id.to_string(),
+                client_message: "update client".parse().unwrap(), // FIXME
+            })))
+        }
+        conn_open_init::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(OpenInitConnectionFilter(_))))
+        }
+        conn_open_try::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(OpenTryConnectionFilter(_))))
+        }
+        conn_open_ack::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(OpenAckConnectionFilter(_))))
+        }
+        conn_open_confirm::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(OpenConfirmConnectionFilter(_))))
+        }
+        chan_open_init::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(OpenInitChannelFilter(_))))
+        }
+        chan_open_try::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(OpenTryChannelFilter(_))))
+        }
+        chan_open_ack::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(OpenAckChannelFilter(_))))
+        }
+        chan_open_confirm::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(OpenConfirmChannelFilter(_))))
+        }
+        chan_close_init::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(CloseInitChannelFilter(_))))
+        }
+        chan_close_confirm::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(CloseConfirmChannelFilter(_))))
+        }
+        recv_packet::TYPE_URL => events.find(|event| matches!(event, Ok(ReceivePacketFilter(_)))),
+        timeout::TYPE_URL | timeout_on_close::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(TimeoutPacketFilter(_))))
+        }
+        acknowledgement::TYPE_URL => {
+            events.find(|event| matches!(event, Ok(AcknowledgePacketFilter(_))))
+        }
+
+        url => {
+            return Err(Error::send_tx(format!(
+                "non-support message type url: {url}"
+            )))
+        }
+    }
+    .ok_or_else(|| {
+        Error::send_tx("not find right event from Axon transaction receipt.".to_owned())
+    })?
+    .map_err(|e| Error::send_tx(e.to_string()))?
+    .into();
+    // the handler emits the same event for both kinds of timeouts
+    let event = match event {
+        IbcEvent::TimeoutPacket(timeout) if message.type_url == timeout_on_close::TYPE_URL => {
+            IbcEvent::TimeoutOnClosePacket(TimeoutOnClosePacket {
+                packet: timeout.packet,
+            })
+        }
+        event => event,
+    };
+    Ok(event)
 }
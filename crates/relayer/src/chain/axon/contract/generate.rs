@@ -2194,6 +2194,42 @@ pub mod ownable_ibc_handler {
                         },
                     ],
                 ),
+                (
+                    ::std::borrow::ToOwned::to_owned("multicall"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("multicall"),
+                            inputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::borrow::ToOwned::to_owned("data"),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Array(
+                                        ::std::boxed::Box::new(
+                                            ::ethers::core::abi::ethabi::ParamType::Bytes,
+                                        ),
+                                    ),
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("bytes[]"),
+                                    ),
+                                },
+                            ],
+                            outputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::borrow::ToOwned::to_owned("results"),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Array(
+                                        ::std::boxed::Box::new(
+                                            ::ethers::core::abi::ethabi::ParamType::Bytes,
+                                        ),
+                                    ),
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("bytes[]"),
+                                    ),
+                                },
+                            ],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers::core::abi::ethabi::StateMutability::NonPayable,
+                        },
+                    ],
+                ),
                 (
                     ::std::borrow::ToOwned::to_owned("nextChannelSequence"),
                     ::std::vec![
@@ -2788,6 +2824,107 @@ pub mod ownable_ibc_handler {
                         },
                     ],
                 ),
+                (
+                    ::std::borrow::ToOwned::to_owned("timeoutOnClose"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("timeoutOnClose"),
+                            inputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::borrow::ToOwned::to_owned("msg_"),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Tuple(
+                                            ::std::vec![
+                                                ::ethers::core::abi::ethabi::ParamType::Tuple(
+                                                    ::std::vec![
+                                                        ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                        ::ethers::core::abi::ethabi::ParamType::String,
+                                                        ::ethers::core::abi::ethabi::ParamType::String,
+                                                        ::ethers::core::abi::ethabi::ParamType::String,
+                                                        ::ethers::core::abi::ethabi::ParamType::String,
+                                                        ::ethers::core::abi::ethabi::ParamType::Bytes,
+                                                        ::ethers::core::abi::ethabi::ParamType::Tuple(
+                                                            ::std::vec![
+                                                                ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                                ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                            ],
+                                                        ),
+                                                        ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                    ],
+                                                ),
+                                                ::ethers::core::abi::ethabi::ParamType::Bytes,
+                                                ::ethers::core::abi::ethabi::ParamType::Bytes,
+                                                ::ethers::core::abi::ethabi::ParamType::Tuple(
+                                                    ::std::vec![
+                                                        ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                        ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                    ],
+                                                ),
+                                                ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                            ],
+                                        ),
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned(
+                                            "struct IBCMsgs.MsgTimeoutOnClose",
+                                        ),
+                                    ),
+                                },
+                            ],
+                            outputs: ::std::vec![],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers::core::abi::ethabi::StateMutability::NonPayable,
+                        },
+                    ],
+                ),
+                (
+                    ::std::borrow::ToOwned::to_owned("timeoutPacket"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("timeoutPacket"),
+                            inputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::borrow::ToOwned::to_owned("msg_"),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Tuple(
+                                            ::std::vec![
+                                                ::ethers::core::abi::ethabi::ParamType::Tuple(
+                                                    ::std::vec![
+                                                        ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                        ::ethers::core::abi::ethabi::ParamType::String,
+                                                        ::ethers::core::abi::ethabi::ParamType::String,
+                                                        ::ethers::core::abi::ethabi::ParamType::String,
+                                                        ::ethers::core::abi::ethabi::ParamType::String,
+                                                        ::ethers::core::abi::ethabi::ParamType::Bytes,
+                                                        ::ethers::core::abi::ethabi::ParamType::Tuple(
+                                                            ::std::vec![
+                                                                ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                                ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                            ],
+                                                        ),
+                                                        ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                    ],
+                                                ),
+                                                ::ethers::core::abi::ethabi::ParamType::Bytes,
+                                                ::ethers::core::abi::ethabi::ParamType::Tuple(
+                                                    ::std::vec![
+                                                        ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                        ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                    ],
+                                                ),
+                                                ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                            ],
+                                        ),
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned(
+                                            "struct IBCMsgs.MsgTimeoutPacket",
+                                        ),
+                                    ),
+                                },
+                            ],
+                            outputs: ::std::vec![],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers::core::abi::ethabi::StateMutability::NonPayable,
+                        },
+                    ],
+                ),
                 (
                     ::std::borrow::ToOwned::to_owned("transferOwnership"),
                     ::std::vec![
@@ -3503,6 +3640,38 @@ pub mod ownable_ibc_handler {
                         },
                     ],
                 ),
+                (
+                    ::std::borrow::ToOwned::to_owned("TimeoutPacket"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Event {
+                            name: ::std::borrow::ToOwned::to_owned("TimeoutPacket"),
+                            inputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::EventParam {
+                                    name: ::std::borrow::ToOwned::to_owned("packet"),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Tuple(
+                                            ::std::vec![
+                                                ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                ::ethers::core::abi::ethabi::ParamType::String,
+                                                ::ethers::core::abi::ethabi::ParamType::String,
+                                                ::ethers::core::abi::ethabi::ParamType::String,
+                                                ::ethers::core::abi::ethabi::ParamType::String,
+                                                ::ethers::core::abi::ethabi::ParamType::Bytes,
+                                                ::ethers::core::abi::ethabi::ParamType::Tuple(
+                                                    ::std::vec![
+                                                        ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                        ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                                    ],
+                                                ),
+                                                ::ethers::core::abi::ethabi::ParamType::Uint(64usize),
+                                            ],
+                                        ),
+                                    indexed: false,
+                                },
+                            ],
+                            anonymous: false,
+                        },
+                    ],
+                ),
                 (
                     ::std::borrow::ToOwned::to_owned("UpdateClient"),
                     ::std::vec![
@@ -4121,6 +4290,18 @@ pub mod ownable_ibc_handler {
                 .method_hash([90, 154, 250, 195], (port_id, channel_id, sequence))
                 .expect("method not found (this should never happen)")
         }
+        ///Calls the contract's `multicall` (0xac9650d8) function
+        pub fn multicall(
+            &self,
+            data: ::std::vec::Vec<::ethers::core::types::Bytes>,
+        ) -> ::ethers::contract::builders::ContractCall<
+            M,
+            ::std::vec::Vec<::ethers::core::types::Bytes>,
+        > {
+            self.0
+                .method_hash([172, 150, 80, 216], data)
+                .expect("method not found (this should never happen)")
+        }
         ///Calls the contract's `nextChannelSequence` (0x7926b8a9) function
         pub fn next_channel_sequence(&self) -> ::ethers::contract::builders::ContractCall<M, u64> {
             self.0
@@ -4279,6 +4460,24 @@ pub mod ownable_ibc_handler {
                 .method_hash([39, 24, 76, 19], expected_time_per_block)
                 .expect("method not found (this should never happen)")
         }
+        ///Calls the contract's `timeoutOnClose` (0x9ebb2107) function
+        pub fn timeout_on_close(
+            &self,
+            msg: MsgTimeoutOnClose,
+        ) -> ::ethers::contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([158, 187, 33, 7], (msg,))
+                .expect("method not found (this should never happen)")
+        }
+        ///Calls the contract's `timeoutPacket` (0xaa18c8b1) function
+        pub fn timeout_packet(
+            &self,
+            msg: MsgTimeoutPacket,
+        ) -> ::ethers::contract::builders::ContractCall<M, ()> {
+            self.0
+                .method_hash([170, 24, 200, 177], (msg,))
+                .expect("method not found (this should never happen)")
+        }
         ///Calls the contract's `transferOwnership` (0xf2fde38b) function
         pub fn transfer_ownership(
             &self,
@@ -4431,6 +4630,13 @@ pub mod ownable_ibc_handler {
         ) -> ::ethers::contract::builders::Event<::std::sync::Arc<M>, M, SendPacketFilter> {
             self.0.event()
         }
+        ///Gets the contract's `TimeoutPacket` event
+        pub fn timeout_packet_filter(
+            &self,
+        ) -> ::ethers::contract::builders::Event<::std::sync::Arc<M>, M, TimeoutPacketFilter>
+        {
+            self.0.event()
+        }
         ///Gets the contract's `UpdateClient` event
         pub fn update_client_filter(
             &self,
@@ -4798,6 +5004,23 @@ pub mod ownable_ibc_handler {
         Eq,
         Hash,
     )]
+    #[ethevent(
+        name = "TimeoutPacket",
+        abi = "TimeoutPacket((uint64,string,string,string,string,bytes,(uint64,uint64),uint64))"
+    )]
+    pub struct TimeoutPacketFilter {
+        pub packet: PacketData,
+    }
+    #[derive(
+        Clone,
+        ::ethers::contract::EthEvent,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+    )]
     #[ethevent(name = "UpdateClient", abi = "UpdateClient(string,bytes)")]
     pub struct UpdateClientFilter {
         pub client_id: ::std::string::String,
@@ -4841,6 +5064,7 @@ pub mod ownable_ibc_handler {
         RegisterCellEmitterFilterFilter(RegisterCellEmitterFilterFilter),
         RemoveCellEmitterFilterFilter(RemoveCellEmitterFilterFilter),
         SendPacketFilter(SendPacketFilter),
+        TimeoutPacketFilter(TimeoutPacketFilter),
         UpdateClientFilter(UpdateClientFilter),
         WriteAcknowledgementFilter(WriteAcknowledgementFilter),
     }
@@ -4905,6 +5129,9 @@ pub mod ownable_ibc_handler {
             if let Ok(decoded) = SendPacketFilter::decode_log(log) {
                 return Ok(OwnableIBCHandlerEvents::SendPacketFilter(decoded));
             }
+            if let Ok(decoded) = TimeoutPacketFilter::decode_log(log) {
+                return Ok(OwnableIBCHandlerEvents::TimeoutPacketFilter(decoded));
+            }
             if let Ok(decoded) = UpdateClientFilter::decode_log(log) {
                 return Ok(OwnableIBCHandlerEvents::UpdateClientFilter(decoded));
             }
@@ -4938,6 +5165,7 @@ pub mod ownable_ibc_handler {
                     ::core::fmt::Display::fmt(element, f)
                 }
                 Self::SendPacketFilter(element) => ::core::fmt::Display::fmt(element, f),
+                Self::TimeoutPacketFilter(element) => ::core::fmt::Display::fmt(element, f),
                 Self::UpdateClientFilter(element) => ::core::fmt::Display::fmt(element, f),
                 Self::WriteAcknowledgementFilter(element) => ::core::fmt::Display::fmt(element, f),
             }
@@ -5028,6 +5256,11 @@ pub mod ownable_ibc_handler {
             Self::SendPacketFilter(value)
         }
     }
+    impl ::core::convert::From<TimeoutPacketFilter> for OwnableIBCHandlerEvents {
+        fn from(value: TimeoutPacketFilter) -> Self {
+            Self::TimeoutPacketFilter(value)
+        }
+    }
     impl ::core::convert::From<UpdateClientFilter> for OwnableIBCHandlerEvents {
         fn from(value: UpdateClientFilter) -> Self {
             Self::UpdateClientFilter(value)
@@ -5797,6 +6030,21 @@ pub mod ownable_ibc_handler {
         pub channel_id: ::std::string::String,
         pub sequence: u64,
     }
+    ///Container type for all input parameters for the `multicall` function with signature `multicall(bytes[])` and selector `0xac9650d8`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+    )]
+    #[ethcall(name = "multicall", abi = "multicall(bytes[])")]
+    pub struct MulticallCall {
+        pub data: ::std::vec::Vec<::ethers::core::types::Bytes>,
+    }
     ///Container type for all input parameters for the `nextChannelSequence` function with signature `nextChannelSequence()` and selector `0x7926b8a9`
     #[derive(
         Clone,
@@ -6065,6 +6313,42 @@ pub mod ownable_ibc_handler {
     pub struct SetExpectedTimePerBlockCall {
         pub expected_time_per_block: u64,
     }
+    ///Container type for all input parameters for the `timeoutOnClose` function with signature `timeoutOnClose(((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,bytes,(uint64,uint64),uint64))` and selector `0x9ebb2107`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+    )]
+    #[ethcall(
+        name = "timeoutOnClose",
+        abi = "timeoutOnClose(((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,bytes,(uint64,uint64),uint64))"
+    )]
+    pub struct TimeoutOnCloseCall {
+        pub msg: MsgTimeoutOnClose,
+    }
+    ///Container type for all input parameters for the `timeoutPacket` function with signature `timeoutPacket(((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,(uint64,uint64),uint64))` and selector `0xaa18c8b1`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+    )]
+    #[ethcall(
+        name = "timeoutPacket",
+        abi = "timeoutPacket(((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,(uint64,uint64),uint64))"
+    )]
+    pub struct TimeoutPacketCall {
+        pub msg: MsgTimeoutPacket,
+    }
     ///Container type for all input parameters for the `transferOwnership` function with signature `transferOwnership(address)` and selector `0xf2fde38b`
     #[derive(
         Clone,
@@ -6165,6 +6449,7 @@ pub mod ownable_ibc_handler {
         GetNextSequenceRecvs(GetNextSequenceRecvsCall),
         GetNextSequenceSend(GetNextSequenceSendCall),
         HasPacketReceipt(HasPacketReceiptCall),
+        Multicall(MulticallCall),
         NextChannelSequence(NextChannelSequenceCall),
         NextClientSequence(NextClientSequenceCall),
         NextConnectionSequence(NextConnectionSequenceCall),
@@ -6183,6 +6468,8 @@ pub mod ownable_ibc_handler {
         RenounceOwnership(RenounceOwnershipCall),
         SendPacket(SendPacketCall),
         SetExpectedTimePerBlock(SetExpectedTimePerBlockCall),
+        TimeoutOnClose(TimeoutOnCloseCall),
+        TimeoutPacket(TimeoutPacketCall),
         TransferOwnership(TransferOwnershipCall),
         UpdateClient(UpdateClientCall),
         WriteAcknowledgement(WriteAcknowledgementCall),
@@ -6410,6 +6697,9 @@ pub mod ownable_ibc_handler {
             {
                 return Ok(Self::HasPacketReceipt(decoded));
             }
+            if let Ok(decoded) = <MulticallCall as ::ethers::core::abi::AbiDecode>::decode(data) {
+                return Ok(Self::Multicall(decoded));
+            }
             if let Ok(decoded) =
                 <NextChannelSequenceCall as ::ethers::core::abi::AbiDecode>::decode(data)
             {
@@ -6492,6 +6782,15 @@ pub mod ownable_ibc_handler {
             {
                 return Ok(Self::SetExpectedTimePerBlock(decoded));
             }
+            if let Ok(decoded) =
+                <TimeoutOnCloseCall as ::ethers::core::abi::AbiDecode>::decode(data)
+            {
+                return Ok(Self::TimeoutOnClose(decoded));
+            }
+            if let Ok(decoded) = <TimeoutPacketCall as ::ethers::core::abi::AbiDecode>::decode(data)
+            {
+                return Ok(Self::TimeoutPacket(decoded));
+            }
             if let Ok(decoded) =
                 <TransferOwnershipCall as ::ethers::core::abi::AbiDecode>::decode(data)
             {
@@ -6596,6 +6895,7 @@ pub mod ownable_ibc_handler {
                     ::ethers::core::abi::AbiEncode::encode(element)
                 }
                 Self::HasPacketReceipt(element) => ::ethers::core::abi::AbiEncode::encode(element),
+                Self::Multicall(element) => ::ethers::core::abi::AbiEncode::encode(element),
                 Self::NextChannelSequence(element) => {
                     ::ethers::core::abi::AbiEncode::encode(element)
                 }
@@ -6628,6 +6928,8 @@ pub mod ownable_ibc_handler {
                 Self::SetExpectedTimePerBlock(element) => {
                     ::ethers::core::abi::AbiEncode::encode(element)
                 }
+                Self::TimeoutOnClose(element) => ::ethers::core::abi::AbiEncode::encode(element),
+                Self::TimeoutPacket(element) => ::ethers::core::abi::AbiEncode::encode(element),
                 Self::TransferOwnership(element) => ::ethers::core::abi::AbiEncode::encode(element),
                 Self::UpdateClient(element) => ::ethers::core::abi::AbiEncode::encode(element),
                 Self::WriteAcknowledgement(element) => {
@@ -6691,6 +6993,7 @@ pub mod ownable_ibc_handler {
                 Self::GetNextSequenceRecvs(element) => ::core::fmt::Display::fmt(element, f),
                 Self::GetNextSequenceSend(element) => ::core::fmt::Display::fmt(element, f),
                 Self::HasPacketReceipt(element) => ::core::fmt::Display::fmt(element, f),
+                Self::Multicall(element) => ::core::fmt::Display::fmt(element, f),
                 Self::NextChannelSequence(element) => ::core::fmt::Display::fmt(element, f),
                 Self::NextClientSequence(element) => ::core::fmt::Display::fmt(element, f),
                 Self::NextConnectionSequence(element) => ::core::fmt::Display::fmt(element, f),
@@ -6709,6 +7012,8 @@ pub mod ownable_ibc_handler {
                 Self::RenounceOwnership(element) => ::core::fmt::Display::fmt(element, f),
                 Self::SendPacket(element) => ::core::fmt::Display::fmt(element, f),
                 Self::SetExpectedTimePerBlock(element) => ::core::fmt::Display::fmt(element, f),
+                Self::TimeoutOnClose(element) => ::core::fmt::Display::fmt(element, f),
+                Self::TimeoutPacket(element) => ::core::fmt::Display::fmt(element, f),
                 Self::TransferOwnership(element) => ::core::fmt::Display::fmt(element, f),
                 Self::UpdateClient(element) => ::core::fmt::Display::fmt(element, f),
                 Self::WriteAcknowledgement(element) => ::core::fmt::Display::fmt(element, f),
@@ -6957,6 +7262,11 @@ pub mod ownable_ibc_handler {
             Self::HasPacketReceipt(value)
         }
     }
+    impl ::core::convert::From<MulticallCall> for OwnableIBCHandlerCalls {
+        fn from(value: MulticallCall) -> Self {
+            Self::Multicall(value)
+        }
+    }
     impl ::core::convert::From<NextChannelSequenceCall> for OwnableIBCHandlerCalls {
         fn from(value: NextChannelSequenceCall) -> Self {
             Self::NextChannelSequence(value)
@@ -7047,6 +7357,16 @@ pub mod ownable_ibc_handler {
             Self::SetExpectedTimePerBlock(value)
         }
     }
+    impl ::core::convert::From<TimeoutOnCloseCall> for OwnableIBCHandlerCalls {
+        fn from(value: TimeoutOnCloseCall) -> Self {
+            Self::TimeoutOnClose(value)
+        }
+    }
+    impl ::core::convert::From<TimeoutPacketCall> for OwnableIBCHandlerCalls {
+        fn from(value: TimeoutPacketCall) -> Self {
+            Self::TimeoutPacket(value)
+        }
+    }
     impl ::core::convert::From<TransferOwnershipCall> for OwnableIBCHandlerCalls {
         fn from(value: TransferOwnershipCall) -> Self {
             Self::TransferOwnership(value)
@@ -7652,6 +7972,20 @@ pub mod ownable_ibc_handler {
         Hash,
     )]
     pub struct HasPacketReceiptReturn(pub bool);
+    ///Container type for all return fields from the `multicall` function with signature `multicall(bytes[])` and selector `0xac9650d8`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+    )]
+    pub struct MulticallReturn {
+        pub results: ::std::vec::Vec<::ethers::core::types::Bytes>,
+    }
     ///Container type for all return fields from the `nextChannelSequence` function with signature `nextChannelSequence()` and selector `0x7926b8a9`
     #[derive(
         Clone,
@@ -8187,6 +8521,41 @@ pub mod ownable_ibc_handler {
         pub proof: ::ethers::core::types::Bytes,
         pub proof_height: HeightData,
     }
+    ///`MsgTimeoutOnClose((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,bytes,(uint64,uint64),uint64)`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+    )]
+    pub struct MsgTimeoutOnClose {
+        pub packet: PacketData,
+        pub proof_unreceived: ::ethers::core::types::Bytes,
+        pub proof_close: ::ethers::core::types::Bytes,
+        pub proof_height: HeightData,
+        pub next_sequence_recv: u64,
+    }
+    ///`MsgTimeoutPacket((uint64,string,string,string,string,bytes,(uint64,uint64),uint64),bytes,(uint64,uint64),uint64)`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+    )]
+    pub struct MsgTimeoutPacket {
+        pub packet: PacketData,
+        pub proof: ::ethers::core::types::Bytes,
+        pub proof_height: HeightData,
+        pub next_sequence_recv: u64,
+    }
     ///`MsgUpdateClient(string,bytes)`
     #[derive(
         Clone,
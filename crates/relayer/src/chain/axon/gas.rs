@@ -0,0 +1,282 @@
+use std::fmt;
+
+use ethers::{
+    abi::Detokenize,
+    contract::builders::ContractCall,
+    providers::{Middleware, PendingTransaction},
+    types::{transaction::eip2718::TypedTransaction, TransactionReceipt, H256, U256},
+};
+use tracing::{debug, warn};
+
+use super::{utils::decode_revert_error, ContractProvider};
+use crate::config::{
+    axon::{AxonChainConfig, GasPrice},
+    cosmos::gas_multiplier::GasMultiplier,
+};
+
+/// The call reverted in the gas estimation, so no transaction was sent
+#[derive(Debug)]
+pub struct Reverted(pub String);
+
+impl fmt::Display for Reverted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Reverted {}
+
+/// Sends `call` with the gas settings of `config` and waits for its receipt.
+///
+/// The gas limit is estimated and scaled by `gas_multiplier`, and the gas
+/// price follows `gas_price`. A transaction which isn't committed within
+/// `tx_replacement_timeout` is replaced by the same transaction with its price
+/// raised by `price_bump_percent`, as long as the fee stays under `max_fee`.
+/// Once it can't be replaced anymore, the transaction is given one more
+/// `tx_replacement_timeout` to be committed before giving up.
+pub async fn send_contract_call<D: Detokenize>(
+    config: &AxonChainConfig,
+    call: ContractCall<ContractProvider, D>,
+) -> eyre::Result<Option<TransactionReceipt>> {
+    let estimated_gas = call.estimate_gas().await.map_err(|err| {
+        if err.is_revert() {
+            eyre::Report::new(Reverted(decode_revert_error(err).to_string()))
+        } else {
+            err.into()
+        }
+    })?;
+    let call = match config.gas_price {
+        GasPrice::Legacy { .. } => call.legacy(),
+        GasPrice::Eip1559 { .. } => call,
+    };
+    let client = call.client.clone();
+    let mut tx = call.tx;
+
+    tx.set_gas(scale_gas(estimated_gas, config.gas_multiplier));
+    match (&mut tx, &config.gas_price) {
+        (TypedTransaction::Legacy(tx), GasPrice::Legacy { gas_price }) => {
+            tx.gas_price = gas_price.map(Into::into);
+        }
+        (
+            TypedTransaction::Eip1559(tx),
+            GasPrice::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            },
+        ) => {
+            tx.max_fee_per_gas = max_fee_per_gas.map(Into::into);
+            tx.max_priority_fee_per_gas = max_priority_fee_per_gas.map(Into::into);
+        }
+        _ => unreachable!("transaction type is chosen by the gas price config"),
+    }
+    // fills the nonce and the prices left unset, the nonce is kept unchanged
+    // by replacements
+    client.fill_transaction(&mut tx, None).await?;
+    check_max_fee(&tx, config.max_fee)?;
+
+    let mut sent_txs: Vec<H256> = vec![];
+    loop {
+        let pending = match client.send_transaction(tx.clone(), None).await {
+            Ok(pending) => pending,
+            Err(err) => {
+                // a previous transaction may have been committed before its
+                // replacement got sent
+                if let Some(receipt) = find_receipt(client.as_ref(), &sent_txs).await? {
+                    return Ok(Some(receipt));
+                }
+                return Err(err.into());
+            }
+        };
+        let tx_hash = *pending;
+        debug!(
+            "axon transaction {tx_hash:?} sent with {}",
+            describe_price(&tx)
+        );
+        sent_txs.push(tx_hash);
+
+        if let Ok(receipt) = tokio::time::timeout(config.tx_replacement_timeout, pending).await {
+            return Ok(receipt?);
+        }
+        if let Some(receipt) = find_receipt(client.as_ref(), &sent_txs).await? {
+            return Ok(Some(receipt));
+        }
+
+        let mut replacement = tx.clone();
+        bump_price(&mut replacement, config.price_bump_percent);
+        if let Err(err) = check_max_fee(&replacement, config.max_fee) {
+            warn!(
+                "axon transaction {tx_hash:?} is not committed after {:?} and cannot be replaced: {err}",
+                config.tx_replacement_timeout
+            );
+            let pending = PendingTransaction::new(tx_hash, client.provider());
+            if let Ok(receipt) = tokio::time::timeout(config.tx_replacement_timeout, pending).await
+            {
+                return Ok(receipt?);
+            }
+            if let Some(receipt) = find_receipt(client.as_ref(), &sent_txs).await? {
+                return Ok(Some(receipt));
+            }
+            eyre::bail!(
+                "axon transaction {tx_hash:?} is not committed after {} attempts and cannot be replaced under max_fee",
+                sent_txs.len()
+            );
+        }
+        warn!(
+            "axon transaction {tx_hash:?} is not committed after {:?}, replace it with {}",
+            config.tx_replacement_timeout,
+            describe_price(&replacement)
+        );
+        tx = replacement;
+    }
+}
+
+// the multiplier is applied in millionths, so that any gas is scaled in U256,
+// rounding up and saturating at the maximum
+fn scale_gas(gas: U256, gas_multiplier: Option<GasMultiplier>) -> U256 {
+    const PRECISION: u64 = 1_000_000;
+    let multiplier = gas_multiplier.unwrap_or_default().to_f64();
+    let numerator = U256::from((multiplier * PRECISION as f64).round() as u64);
+    let (quotient, remainder) = gas.saturating_mul(numerator).div_mod(PRECISION.into());
+    if remainder.is_zero() {
+        quotient
+    } else {
+        quotient.saturating_add(U256::one())
+    }
+}
+
+fn max_price_per_gas(tx: &TypedTransaction) -> Option<U256> {
+    match tx {
+        TypedTransaction::Eip1559(tx) => tx.max_fee_per_gas,
+        tx => tx.gas_price(),
+    }
+}
+
+fn check_max_fee(tx: &TypedTransaction, max_fee: Option<u64>) -> eyre::Result<()> {
+    let Some(max_fee) = max_fee else {
+        return Ok(());
+    };
+    let gas = tx.gas().copied().unwrap_or_default();
+    let price = max_price_per_gas(tx).unwrap_or_default();
+    let fee = gas.saturating_mul(price);
+    if fee > max_fee.into() {
+        eyre::bail!("transaction fee {fee} (gas {gas} at {price} wei) exceeds max_fee {max_fee}");
+    }
+    Ok(())
+}
+
+fn bump_price(tx: &mut TypedTransaction, percent: u64) {
+    // replacements must be strictly more expensive than the replaced one
+    let bump = |price: U256| (price * (100 + percent) / 100).max(price + 1);
+    match tx {
+        TypedTransaction::Eip1559(tx) => {
+            tx.max_fee_per_gas = tx.max_fee_per_gas.map(bump);
+            tx.max_priority_fee_per_gas = tx.max_priority_fee_per_gas.map(bump);
+        }
+        tx => {
+            if let Some(price) = tx.gas_price() {
+                tx.set_gas_price(bump(price));
+            }
+        }
+    }
+}
+
+fn describe_price(tx: &TypedTransaction) -> String {
+    match tx {
+        TypedTransaction::Eip1559(tx) => format!(
+            "max fee {:?} and priority fee {:?} per gas",
+            tx.max_fee_per_gas, tx.max_priority_fee_per_gas
+        ),
+        tx => format!("gas price {:?}", tx.gas_price()),
+    }
+}
+
+async fn find_receipt(
+    client: &ContractProvider,
+    tx_hashes: &[H256],
+) -> eyre::Result<Option<TransactionReceipt>> {
+    for tx_hash in tx_hashes {
+        if let Some(receipt) = client.get_transaction_receipt(*tx_hash).await? {
+            return Ok(Some(receipt));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{Eip1559TransactionRequest, TransactionRequest};
+
+    use super::*;
+
+    fn legacy(gas: u64, gas_price: u64) -> TypedTransaction {
+        TransactionRequest::new()
+            .gas(gas)
+            .gas_price(gas_price)
+            .into()
+    }
+
+    fn eip1559(gas: u64, max_fee_per_gas: u64, max_priority_fee_per_gas: u64) -> TypedTransaction {
+        Eip1559TransactionRequest::new()
+            .gas(gas)
+            .max_fee_per_gas(max_fee_per_gas)
+            .max_priority_fee_per_gas(max_priority_fee_per_gas)
+            .into()
+    }
+
+    #[test]
+    fn test_scale_gas() {
+        let multiplier = Some(GasMultiplier::unsafe_new(1.5));
+        assert_eq!(scale_gas(100_000.into(), multiplier), 150_000.into());
+        // rounded up
+        assert_eq!(scale_gas(3.into(), multiplier), 5.into());
+        assert_eq!(scale_gas(0.into(), multiplier), 0.into());
+        // the default multiplier is 1.1
+        assert_eq!(scale_gas(100_000.into(), None), 110_000.into());
+        // gas above u64 is scaled as well
+        let gas = U256::from(u64::MAX) * 2;
+        assert_eq!(scale_gas(gas, multiplier), U256::from(u64::MAX) * 3);
+        assert_eq!(scale_gas(U256::MAX, multiplier), U256::MAX);
+    }
+
+    #[test]
+    fn test_check_max_fee() {
+        let tx = legacy(21_000, 10);
+        assert!(check_max_fee(&tx, None).is_ok());
+        assert!(check_max_fee(&tx, Some(210_000)).is_ok());
+        assert!(check_max_fee(&tx, Some(209_999)).is_err());
+
+        // the max fee per gas is paid at worst
+        let tx = eip1559(21_000, 10, 2);
+        assert!(check_max_fee(&tx, Some(210_000)).is_ok());
+        assert!(check_max_fee(&tx, Some(209_999)).is_err());
+
+        // the fee doesn't overflow
+        let tx = legacy(u64::MAX, u64::MAX);
+        assert!(check_max_fee(&tx, Some(u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_bump_price() {
+        let mut tx = legacy(21_000, 100);
+        bump_price(&mut tx, 10);
+        assert_eq!(tx.gas_price(), Some(110.into()));
+        assert_eq!(tx.gas(), Some(&21_000.into()));
+
+        let mut tx = eip1559(21_000, 100, 10);
+        bump_price(&mut tx, 25);
+        assert_eq!(max_price_per_gas(&tx), Some(125.into()));
+        let TypedTransaction::Eip1559(request) = &tx else {
+            panic!("unexpected transaction type");
+        };
+        assert_eq!(request.max_priority_fee_per_gas, Some(12.into()));
+
+        // replacements are strictly more expensive
+        let mut tx = eip1559(21_000, 1, 0);
+        bump_price(&mut tx, 10);
+        let TypedTransaction::Eip1559(request) = &tx else {
+            panic!("unexpected transaction type");
+        };
+        assert_eq!(request.max_fee_per_gas, Some(2.into()));
+        assert_eq!(request.max_priority_fee_per_gas, Some(1.into()));
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use axon_tools::types::{Metadata, ValidatorExtend};
+use ethers::types::BlockNumber;
+use tracing::debug;
+
+use super::rpc::AxonRpc;
+use crate::error::Error;
+
+/// Axon metadata indexed by the first block number of its epoch.
+///
+/// The validator set of an Axon epoch never changes once the epoch is
+/// settled, so metadata fetched for any block of an epoch can be reused for
+/// every other block of the same epoch.
+#[derive(Clone, Default)]
+pub struct MetadataCache {
+    epochs: Arc<RwLock<BTreeMap<u64, Metadata>>>,
+}
+
+impl MetadataCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached metadata whose epoch covers `block_number`.
+    pub fn get(&self, block_number: u64) -> Option<Metadata> {
+        let epochs = self.epochs.read().expect("poisoned metadata cache");
+        epochs
+            .range(..=block_number)
+            .next_back()
+            .map(|(_, metadata)| metadata)
+            .filter(|metadata| block_number <= metadata.version.end)
+            .cloned()
+    }
+
+    pub fn insert(&self, metadata: Metadata) {
+        let mut epochs = self.epochs.write().expect("poisoned metadata cache");
+        epochs.insert(metadata.version.start, metadata);
+    }
+
+    /// Returns the metadata which was active at `block_number`, fetching it
+    /// from the Axon node if its epoch has not been seen yet.
+    pub async fn get_or_fetch<R: AxonRpc + Sync>(
+        &self,
+        rpc: &R,
+        block_number: u64,
+    ) -> Result<Metadata, Error> {
+        if let Some(metadata) = self.get(block_number) {
+            return Ok(metadata);
+        }
+        let metadata = rpc
+            .get_metadata_by_number(BlockNumber::Number(block_number.into()))
+            .await?;
+        if block_number < metadata.version.start || block_number > metadata.version.end {
+            return Err(Error::rpc_response(format!(
+                "metadata of epoch [{}, {}] doesn't cover axon block #{block_number}",
+                metadata.version.start, metadata.version.end
+            )));
+        }
+        debug!(
+            "cache axon metadata of epoch [{}, {}]",
+            metadata.version.start, metadata.version.end
+        );
+        self.insert(metadata.clone());
+        Ok(metadata)
+    }
+}
+
+/// Extracts the validator set of `metadata` in the form required by
+/// `axon_tools::verify_proof`.
+pub fn validators_of(metadata: &Metadata) -> Vec<ValidatorExtend> {
+    metadata
+        .verifier_list
+        .iter()
+        .map(|v| ValidatorExtend {
+            bls_pub_key: v.bls_pub_key.clone(),
+            pub_key: v.pub_key.clone(),
+            address: v.address,
+            propose_weight: v.propose_weight,
+            vote_weight: v.vote_weight,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use axon_tools::types::MetadataVersion;
+
+    use super::*;
+
+    fn metadata(epoch: u64, start: u64, end: u64) -> Metadata {
+        Metadata {
+            version: MetadataVersion { start, end },
+            epoch,
+            ..Default::default()
+        }
+    }
+
+    fn epoch_at(cache: &MetadataCache, block_number: u64) -> Option<u64> {
+        cache.get(block_number).map(|metadata| metadata.epoch)
+    }
+
+    #[test]
+    fn test_get_at_epoch_boundaries() {
+        let cache = MetadataCache::new();
+        cache.insert(metadata(0, 0, 99));
+        cache.insert(metadata(1, 100, 199));
+
+        assert_eq!(epoch_at(&cache, 0), Some(0));
+        assert_eq!(epoch_at(&cache, 99), Some(0));
+        assert_eq!(epoch_at(&cache, 100), Some(1));
+        assert_eq!(epoch_at(&cache, 199), Some(1));
+        // the next epoch has not been fetched yet
+        assert_eq!(epoch_at(&cache, 200), None);
+    }
+
+    #[test]
+    fn test_get_in_missing_epoch() {
+        let cache = MetadataCache::new();
+        cache.insert(metadata(2, 200, 299));
+
+        assert_eq!(epoch_at(&cache, 0), None);
+        assert_eq!(epoch_at(&cache, 199), None);
+        assert_eq!(epoch_at(&cache, 200), Some(2));
+        assert_eq!(epoch_at(&cache, 299), Some(2));
+        assert_eq!(epoch_at(&cache, 300), None);
+    }
+}
@@ -1,4 +1,8 @@
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, Weak};
 use std::time::Duration;
 
 use super::contract::*;
@@ -22,6 +26,107 @@ use tracing::{debug, error, info, instrument, warn};
 
 type Client = Provider<Ws>;
 
+const CHECKPOINT_DEFAULT_FOLDER: &str = ".forcerelay/checkpoints/";
+
+/// The last Axon block whose events have all been processed, stored on disk
+/// so that the event monitor can resume from it after a restart.
+pub struct Checkpoint {
+    /// Nothing is stored without a path
+    path: Option<PathBuf>,
+}
+
+impl Checkpoint {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path: Some(path) }
+    }
+
+    /// Checkpoint neither loaded nor stored, for monitors which must not
+    /// interfere with the one of a running relayer
+    pub fn disabled() -> Self {
+        Self { path: None }
+    }
+
+    /// Checkpoint of the event monitor of the IBC handler at
+    /// `contract_address` on `chain_id`, under the home directory
+    pub fn in_home_dir(chain_id: &ChainId, contract_address: &Address) -> Result<Self> {
+        let home = dirs_next::home_dir()
+            .ok_or_else(|| Error::others("cannot locate the home directory".to_owned()))?;
+        let path = home.join(CHECKPOINT_DEFAULT_FOLDER).join(format!(
+            "{chain_id}_{}.axon_event_monitor",
+            hex::encode(contract_address)
+        ));
+        Ok(Self::new(path))
+    }
+
+    pub fn load(&self) -> Option<u64> {
+        let path = self.path.as_ref()?;
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!("ignore unreadable checkpoint {}: {e}", path.display());
+                return None;
+            }
+        };
+        match content.trim().parse() {
+            Ok(block_number) => Some(block_number),
+            Err(e) => {
+                warn!("ignore malformed checkpoint {}: {e}", path.display());
+                None
+            }
+        }
+    }
+
+    pub fn store(&self, block_number: u64) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        // write to a temporary file first so that a crash never leaves a
+        // truncated checkpoint behind
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, block_number.to_string())?;
+        fs::rename(tmp_path, path)
+    }
+}
+
+/// Blocks whose events have been broadcast but may not have been handled by
+/// the subscribers yet. A batch is handled once every subscriber dropped it.
+#[derive(Default)]
+struct UnhandledBlocks {
+    /// Batches of the blocks which are not sealed yet
+    batches: Vec<Weak<Result<EventBatch>>>,
+    blocks: VecDeque<(u64, Vec<Weak<Result<EventBatch>>>)>,
+}
+
+impl UnhandledBlocks {
+    fn track(&mut self, batch: &Arc<Result<EventBatch>>) {
+        self.batches.push(Arc::downgrade(batch));
+    }
+
+    /// Marks the events of the blocks up to `block_number` as all broadcast
+    fn seal(&mut self, block_number: u64) {
+        let batches = std::mem::take(&mut self.batches);
+        self.blocks.push_back((block_number, batches));
+    }
+
+    /// Returns the last block whose events, along with the events of all the
+    /// blocks before it, have been handled since the last call
+    fn pop_handled(&mut self) -> Option<u64> {
+        let mut handled = None;
+        while let Some((block_number, batches)) = self.blocks.front() {
+            if batches.iter().any(|batch| batch.strong_count() > 0) {
+                break;
+            }
+            handled = Some(*block_number);
+            self.blocks.pop_front();
+        }
+        handled
+    }
+}
+
 // #[derive(Clone, Debug)]
 pub struct AxonEventMonitor {
     websocket_addr: WebSocketClientUrl,
@@ -30,6 +135,11 @@ pub struct AxonEventMonitor {
     chain_id: ChainId,
     contract_address: Address,
     start_block_number: u64,
+    checkpoint: Checkpoint,
+    unhandled: UnhandledBlocks,
+    /// The packet events from this block up to `start_block_number` are
+    /// replayed on start, in case relaying them failed before
+    reprocess_from: Option<u64>,
     rx_cmd: channel::Receiver<MonitorCmd>,
     event_bus: EventBus<Arc<Result<EventBatch>>>,
 }
@@ -47,6 +157,8 @@ impl AxonEventMonitor {
         websocket_addr: WebSocketClientUrl,
         contract_address: Address,
         reprocess_block_count: u64,
+        checkpoint: Checkpoint,
+        rescan_from_height: Option<u64>,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxMonitorCmd)> {
         let (tx_cmd, rx_cmd) = channel::unbounded();
@@ -55,12 +167,28 @@ impl AxonEventMonitor {
             .block_on(Provider::<Ws>::connect(websocket_addr.to_string()))
             .map_err(|_| Error::client_creation_failed(chain_id.clone(), websocket_addr.clone()))?;
 
-        let start_block_number = rt
-            .block_on(client.get_block_number())
-            .map_err(|e| Error::others(e.to_string()))?
-            .as_u64()
-            .checked_sub(reprocess_block_count)
-            .expect("check-sub axon block number");
+        // the packet events of the last `reprocess_block_count` blocks before
+        // the start are replayed, unless the events are explicitly rescanned
+        let (start_block_number, reprocess_from) = match (rescan_from_height, checkpoint.load()) {
+            (Some(height), _) => {
+                info!("rescan Axon events from block {height}");
+                (height, None)
+            }
+            (None, Some(last_block_number)) => {
+                info!("resume Axon events from checkpoint at block {last_block_number}");
+                let start_block_number = last_block_number + 1;
+                let reprocess_from = start_block_number.saturating_sub(reprocess_block_count);
+                (start_block_number, Some(reprocess_from))
+            }
+            (None, None) => {
+                let tip_block_number = rt
+                    .block_on(client.get_block_number())
+                    .map_err(|e| Error::others(e.to_string()))?
+                    .as_u64();
+                let reprocess_from = tip_block_number.saturating_sub(reprocess_block_count);
+                (tip_block_number + 1, Some(reprocess_from))
+            }
+        };
 
         let event_bus = EventBus::new();
         let monitor = Self {
@@ -70,6 +198,9 @@ impl AxonEventMonitor {
             chain_id,
             contract_address,
             start_block_number,
+            checkpoint,
+            unhandled: UnhandledBlocks::default(),
+            reprocess_from,
             rx_cmd,
             event_bus,
         };
@@ -90,23 +221,24 @@ impl AxonEventMonitor {
         Ok(client)
     }
 
-    pub fn reprocess_previous_events(&mut self) -> Result<()> {
+    pub fn reprocess_previous_events(&mut self, from_block_number: u64) -> Result<()> {
+        let Some(to_block_number) = self.start_block_number.checked_sub(1) else {
+            return Ok(());
+        };
+        if from_block_number > to_block_number {
+            return Ok(());
+        }
         let contract = Arc::new(Contract::new(
             self.contract_address,
             Arc::clone(&self.client),
         ));
-        let latest_block_number = self
-            .rt
-            .block_on(self.client.get_block_number())
-            .map_err(|e| Error::others(e.to_string()))?
-            .as_u64();
         let mut reprocessed = 0;
         self.rt
             .block_on(
                 contract
                     .events()
-                    .from_block(self.start_block_number)
-                    .to_block(latest_block_number)
+                    .from_block(from_block_number)
+                    .to_block(to_block_number)
                     .query_with_meta(),
             )
             .map_err(|e| Error::others(e.to_string()))?
@@ -121,7 +253,9 @@ impl AxonEventMonitor {
                     reprocessed += 1;
                 }
             });
-        debug!("Axon reprocessed {} events", reprocessed);
+        debug!(
+            "Axon reprocessed {reprocessed} events from block {from_block_number} to block {to_block_number}"
+        );
         Ok(())
     }
 
@@ -136,8 +270,10 @@ impl AxonEventMonitor {
         if let Next::Continue = self.update_subscribe(false) {
             info!("start Axon event monitor for {}", self.chain_id);
             // reprocess messages from Axon to CKB that have failed in accident
-            if let Err(e) = self.reprocess_previous_events() {
-                error!("Axon reprocess failed: {e}");
+            if let Some(reprocess_from) = self.reprocess_from {
+                if let Err(e) = self.reprocess_previous_events(reprocess_from) {
+                    error!("Axon reprocess failed: {e}");
+                }
             }
             let mut contract = Contract::new(self.contract_address, Arc::clone(&self.client));
             info!(
@@ -200,6 +336,7 @@ impl AxonEventMonitor {
         if let Next::Abort = self.update_subscribe(true) {
             return (Next::Abort, true);
         }
+        self.store_checkpoint();
 
         let tip_block_number = match self.rt.block_on(contract.client().get_block_number()) {
             Ok(tip) => tip.as_u64(),
@@ -233,14 +370,26 @@ impl AxonEventMonitor {
             .for_each(|(event, meta)| self.process_event(event, meta));
 
         self.start_block_number = tip_block_number + 1;
+        self.unhandled.seal(tip_block_number);
+        self.store_checkpoint();
         (Next::Continue, true)
     }
 
+    /// Checkpoints the last block whose events have all been handled by the
+    /// subscribers, so that no event is lost if the relayer stops before
+    fn store_checkpoint(&mut self) {
+        let Some(block_number) = self.unhandled.pop_handled() else {
+            return;
+        };
+        if let Err(err) = self.checkpoint.store(block_number) {
+            error!("failed to store checkpoint at block {block_number}: {err}");
+        }
+    }
+
     fn process_event(&mut self, event: ContractEvents, meta: LogMeta) {
         println!("\n{}\n[event] = {:?}", self.chain_id, event);
         println!("[event_meta] = {:?}\n", meta);
 
-        self.start_block_number = meta.block_number.as_u64();
         let event = IbcEventWithHeight::new_with_tx_hash(
             event.into(),
             Height::from_noncosmos_height(meta.block_number.as_u64()),
@@ -256,6 +405,90 @@ impl AxonEventMonitor {
     }
 
     fn process_batch(&mut self, batch: EventBatch) {
-        self.event_bus.broadcast(Arc::new(Ok(batch)));
+        let batch = Arc::new(Ok(batch));
+        self.unhandled.track(&batch);
+        self.event_bus.broadcast(batch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::Arc;
+
+    use tempfile::TempDir;
+
+    use super::{Address, Checkpoint, EventBatch, UnhandledBlocks};
+    use crate::chain::tracking::TrackingId;
+    use crate::event::monitor::Result;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use ibc_relayer_types::Height;
+
+    fn batch(block_number: u64) -> Arc<Result<EventBatch>> {
+        Arc::new(Ok(EventBatch {
+            chain_id: ChainId::new("axon".to_owned(), 0),
+            tracking_id: TrackingId::Static("test"),
+            height: Height::from_noncosmos_height(block_number),
+            events: vec![],
+        }))
+    }
+
+    #[test]
+    fn test_checkpoint_store_and_load() {
+        let dir = TempDir::new().unwrap();
+        let path = dir
+            .path()
+            .join("checkpoints")
+            .join("axon.axon_event_monitor");
+        let checkpoint = Checkpoint::new(path.clone());
+        assert_eq!(checkpoint.load(), None);
+
+        checkpoint.store(10).unwrap();
+        assert_eq!(checkpoint.load(), Some(10));
+        checkpoint.store(12).unwrap();
+        assert_eq!(checkpoint.load(), Some(12));
+
+        fs::write(&path, "not a block number").unwrap();
+        assert_eq!(checkpoint.load(), None);
+    }
+
+    #[test]
+    fn test_disabled_checkpoint() {
+        let chain_id = ChainId::new("disabled-checkpoint".to_owned(), 0);
+        let home_path = Checkpoint::in_home_dir(&chain_id, &Address::zero())
+            .unwrap()
+            .path
+            .unwrap();
+        let checkpoint = Checkpoint::disabled();
+        checkpoint.store(10).unwrap();
+        assert_eq!(checkpoint.load(), None);
+        assert!(!home_path.exists());
+    }
+
+    #[test]
+    fn test_checkpoint_after_handled_blocks() {
+        let mut unhandled = UnhandledBlocks::default();
+        assert_eq!(unhandled.pop_handled(), None);
+
+        let first = batch(5);
+        unhandled.track(&first);
+        unhandled.seal(6);
+        let second = batch(8);
+        unhandled.track(&second);
+        unhandled.seal(9);
+        unhandled.seal(10);
+        assert_eq!(unhandled.pop_handled(), None);
+
+        // the blocks after an unhandled one are not checkpointed either
+        drop(second);
+        assert_eq!(unhandled.pop_handled(), None);
+        drop(first);
+        assert_eq!(unhandled.pop_handled(), Some(10));
+        assert_eq!(unhandled.pop_handled(), None);
+
+        // without any subscriber, a block is handled once broadcast
+        unhandled.track(&batch(11));
+        unhandled.seal(11);
+        assert_eq!(unhandled.pop_handled(), Some(11));
     }
 }
@@ -25,7 +25,8 @@ use ibc_relayer_types::{
                 acknowledgement::MsgAcknowledgement, chan_close_confirm::MsgChannelCloseConfirm,
                 chan_close_init::MsgChannelCloseInit, chan_open_ack::MsgChannelOpenAck,
                 chan_open_confirm::MsgChannelOpenConfirm, chan_open_init::MsgChannelOpenInit,
-                chan_open_try::MsgChannelOpenTry, recv_packet::MsgRecvPacket,
+                chan_open_try::MsgChannelOpenTry, recv_packet::MsgRecvPacket, timeout::MsgTimeout,
+                timeout_on_close::MsgTimeoutOnClose,
             },
             packet::Packet,
             timeout::TimeoutHeight,
@@ -635,6 +636,61 @@ impl TryFrom<Any> for contract::MsgPacketAcknowledgement {
     }
 }
 
+impl From<MsgTimeout> for contract::MsgTimeoutPacket {
+    fn from(value: MsgTimeout) -> Self {
+        let (object_proof, _, _, height) = into_ethers_proofs(value.proofs);
+        Self {
+            packet: value.packet.into(),
+            proof: object_proof,
+            proof_height: height,
+            next_sequence_recv: value.next_sequence_recv.into(),
+        }
+    }
+}
+
+impl TryFrom<Any> for contract::MsgTimeoutPacket {
+    type Error = Error;
+
+    fn try_from(value: Any) -> Result<Self, Self::Error> {
+        let type_url = value.type_url.clone();
+        Ok(MsgTimeout::from_any(value)
+            .map_err(|e| Error::protobuf_decode(type_url, e))?
+            .into())
+    }
+}
+
+impl From<MsgTimeoutOnClose> for contract::MsgTimeoutOnClose {
+    fn from(value: MsgTimeoutOnClose) -> Self {
+        // the proof of the closed counterparty channel end
+        let proof_close = match value.proofs.other_proof() {
+            Some(v) => {
+                let v: Vec<_> = v.clone().into();
+                v.into()
+            }
+            None => Bytes::new(),
+        };
+        let (object_proof, _, _, height) = into_ethers_proofs(value.proofs);
+        Self {
+            packet: value.packet.into(),
+            proof_unreceived: object_proof,
+            proof_close,
+            proof_height: height,
+            next_sequence_recv: value.next_sequence_recv.into(),
+        }
+    }
+}
+
+impl TryFrom<Any> for contract::MsgTimeoutOnClose {
+    type Error = Error;
+
+    fn try_from(value: Any) -> Result<Self, Self::Error> {
+        let type_url = value.type_url.clone();
+        Ok(MsgTimeoutOnClose::from_any(value)
+            .map_err(|e| Error::protobuf_decode(type_url, e))?
+            .into())
+    }
+}
+
 impl From<contract::OwnableIBCHandlerEvents> for IbcEvent {
     fn from(value: contract::OwnableIBCHandlerEvents) -> Self {
         use contract::OwnableIBCHandlerEvents::*;
@@ -791,6 +847,12 @@ impl From<contract::OwnableIBCHandlerEvents> for IbcEvent {
                 };
                 IbcEvent::AcknowledgePacket(event)
             }
+            TimeoutPacketFilter(event) => {
+                let event = channel_events::TimeoutPacket {
+                    packet: event.packet.into(),
+                };
+                IbcEvent::TimeoutPacket(event)
+            }
             WriteAcknowledgementFilter(event) => {
                 let event = channel_events::WriteAcknowledgement {
                     packet: event.packet.into(),
@@ -852,3 +914,106 @@ fn into_connection_attributes(
         counterparty_client_id: counterparty_client_id.as_str().parse().unwrap(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ibc_relayer_types::{
+        core::{
+            ics04_channel::packet::{Packet, Sequence},
+            ics23_commitment::commitment::CommitmentProofBytes,
+        },
+        proofs::Proofs,
+        signer::Signer,
+        timestamp::Timestamp,
+        Height,
+    };
+
+    use super::*;
+
+    fn packet() -> Packet {
+        Packet {
+            sequence: Sequence::from(3),
+            source_port: "transfer".parse().unwrap(),
+            source_channel: "channel-0".parse().unwrap(),
+            destination_port: "transfer".parse().unwrap(),
+            destination_channel: "channel-1".parse().unwrap(),
+            data: vec![1, 2, 3],
+            timeout_height: TimeoutHeight::At(Height::new(0, 20).unwrap()),
+            timeout_timestamp: Timestamp::from_nanoseconds(1_700_000_000 * SEC_TO_NANO).unwrap(),
+        }
+    }
+
+    fn proof(bytes: &[u8]) -> CommitmentProofBytes {
+        bytes.to_vec().try_into().unwrap()
+    }
+
+    fn signer() -> Signer {
+        "axon-relayer".parse().unwrap()
+    }
+
+    fn expected_packet() -> contract::PacketData {
+        contract::PacketData {
+            sequence: 3,
+            source_port: "transfer".to_owned(),
+            source_channel: "channel-0".to_owned(),
+            destination_port: "transfer".to_owned(),
+            destination_channel: "channel-1".to_owned(),
+            data: vec![1, 2, 3].into(),
+            timeout_height: contract::HeightData {
+                revision_number: 0,
+                revision_height: 20,
+            },
+            timeout_timestamp: 1_700_000_000,
+        }
+    }
+
+    fn proof_height() -> contract::HeightData {
+        contract::HeightData {
+            revision_number: 0,
+            revision_height: 10,
+        }
+    }
+
+    #[test]
+    fn test_msg_timeout_packet() {
+        let proofs = Proofs::new(proof(&[1]), None, None, None, Height::new(0, 10).unwrap());
+        let msg = MsgTimeout::new(packet(), Sequence::from(3), proofs.unwrap(), signer());
+
+        let expected = contract::MsgTimeoutPacket {
+            packet: expected_packet(),
+            proof: vec![1].into(),
+            proof_height: proof_height(),
+            next_sequence_recv: 3,
+        };
+        assert_eq!(contract::MsgTimeoutPacket::from(msg.clone()), expected);
+        assert_eq!(
+            contract::MsgTimeoutPacket::try_from(msg.to_any()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_msg_timeout_on_close() {
+        let proofs = Proofs::new(
+            proof(&[1]),
+            None,
+            None,
+            Some(proof(&[2])),
+            Height::new(0, 10).unwrap(),
+        );
+        let msg = MsgTimeoutOnClose::new(packet(), Sequence::from(3), proofs.unwrap(), signer());
+
+        let expected = contract::MsgTimeoutOnClose {
+            packet: expected_packet(),
+            proof_unreceived: vec![1].into(),
+            proof_close: vec![2].into(),
+            proof_height: proof_height(),
+            next_sequence_recv: 3,
+        };
+        assert_eq!(contract::MsgTimeoutOnClose::from(msg.clone()), expected);
+        assert_eq!(
+            contract::MsgTimeoutOnClose::try_from(msg.to_any()).unwrap(),
+            expected
+        );
+    }
+}
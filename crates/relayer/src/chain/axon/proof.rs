@@ -0,0 +1,360 @@
+//! Verification of the Merkle Patricia trie proofs returned by `eth_getProof`.
+
+use ethers::types::{Bytes, H160, U256};
+use ethers::utils::keccak256;
+use rlp::Rlp;
+
+use super::rpc::EIP1186ProofResponse;
+
+/// Content the storage slot of a commitment must hold for a queried object
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExpectedCommitment {
+    /// The object doesn't exist, its commitment is unset
+    Absent,
+    /// The commitment is known, e.g. the hash of a packet commitment
+    Hash([u8; 32]),
+}
+
+/// Receipt of a received packet of an unordered channel, the IBC handler
+/// stores its hash as the commitment of the receipt
+pub const PACKET_RECEIPT: u8 = 1;
+
+impl ExpectedCommitment {
+    /// Commitment of an object encoded as `encoded`, the IBC handler stores
+    /// the keccak256 hash of the encoding of each object
+    pub fn hash_of(encoded: &[u8]) -> Self {
+        Self::Hash(keccak256(encoded))
+    }
+
+    pub fn check(self, value: U256) -> Result<(), String> {
+        let matched = match self {
+            Self::Absent => value.is_zero(),
+            Self::Hash(hash) => value == U256::from_big_endian(&hash),
+        };
+        if matched {
+            Ok(())
+        } else {
+            Err(format!(
+                "proven commitment {value:#x} doesn't match {self:?}"
+            ))
+        }
+    }
+}
+
+/// Verifies the proof of the storage `slot` of the contract at `address`
+/// against the `state_root` of a block, returning the proven value of the
+/// slot, zero if it's unset.
+pub fn verify_storage_proof(
+    state_root: [u8; 32],
+    address: H160,
+    slot: U256,
+    proof: &EIP1186ProofResponse,
+) -> Result<U256, String> {
+    let account = verify_trie_proof(state_root, &keccak256(address), &proof.account_proof)?
+        .ok_or_else(|| format!("account {address:?} doesn't exist"))?;
+    // accounts are encoded as [nonce, balance, storage_root, code_hash]
+    let storage_root = Rlp::new(&account)
+        .at(2)
+        .and_then(|root| root.data())
+        .map_err(|e| format!("malformed account: {e}"))?;
+    let storage_root = <[u8; 32]>::try_from(storage_root)
+        .map_err(|_| format!("malformed storage root {}", hex::encode(storage_root)))?;
+
+    let storage_proof = proof
+        .storage_proof
+        .iter()
+        .find(|storage_proof| storage_proof.key == slot)
+        .ok_or_else(|| format!("missing proof of slot {slot:#x}"))?;
+    let mut key = [0u8; 32];
+    slot.to_big_endian(&mut key);
+    let value = match verify_trie_proof(storage_root, &keccak256(key), &storage_proof.proof)? {
+        Some(value) => {
+            let value = Rlp::new(&value)
+                .data()
+                .map_err(|e| format!("malformed storage value: {e}"))?;
+            if value.len() > 32 {
+                return Err(format!("storage value of {} bytes", value.len()));
+            }
+            U256::from_big_endian(value)
+        }
+        None => U256::zero(),
+    };
+    if value != storage_proof.value {
+        return Err(format!(
+            "proven value {value:#x} of slot {slot:#x} differs from the returned {:#x}",
+            storage_proof.value
+        ));
+    }
+    Ok(value)
+}
+
+enum Step<'a> {
+    Found(&'a [u8]),
+    NotFound,
+    Hash([u8; 32]),
+    Embedded(Rlp<'a>),
+}
+
+/// Walks the trie of `root` along `key` through the nodes of `proof`, returning
+/// the value stored at `key`, or `None` if the proof shows it isn't set.
+fn verify_trie_proof(
+    root: [u8; 32],
+    key: &[u8],
+    proof: &[Bytes],
+) -> Result<Option<Vec<u8>>, String> {
+    let path = key
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect::<Vec<_>>();
+    let mut offset = 0;
+    let mut hash = root;
+    for encoded in proof {
+        if keccak256(encoded) != hash {
+            return Err(format!("unexpected trie node {}", hex::encode(encoded)));
+        }
+        // nodes shorter than 32 bytes are embedded in their parent node
+        let mut node = Rlp::new(encoded);
+        loop {
+            match step(&node, &path, &mut offset)
+                .map_err(|e| format!("malformed trie node: {e}"))?
+            {
+                Step::Found(value) => return Ok(Some(value.to_vec())),
+                Step::NotFound => return Ok(None),
+                Step::Hash(next) => {
+                    hash = next;
+                    break;
+                }
+                Step::Embedded(next) => node = next,
+            }
+        }
+    }
+    Err(format!(
+        "incomplete proof of key {}, missing trie node {}",
+        hex::encode(key),
+        hex::encode(hash)
+    ))
+}
+
+fn step<'a>(node: &Rlp<'a>, path: &[u8], offset: &mut usize) -> Result<Step<'a>, String> {
+    let rlp_err = |e: rlp::DecoderError| e.to_string();
+    match node.item_count().map_err(rlp_err)? {
+        // branch node
+        17 => {
+            if *offset == path.len() {
+                let value = node
+                    .at(16)
+                    .and_then(|value| value.data())
+                    .map_err(rlp_err)?;
+                return Ok(if value.is_empty() {
+                    Step::NotFound
+                } else {
+                    Step::Found(value)
+                });
+            }
+            let child = node.at(path[*offset] as usize).map_err(rlp_err)?;
+            *offset += 1;
+            child_step(child)
+        }
+        // leaf or extension node
+        2 => {
+            let encoded_path = node.at(0).and_then(|path| path.data()).map_err(rlp_err)?;
+            let (node_path, is_leaf) = decode_hex_prefix(encoded_path)?;
+            let rest = &path[*offset..];
+            if is_leaf {
+                if rest != node_path.as_slice() {
+                    return Ok(Step::NotFound);
+                }
+                let value = node.at(1).and_then(|value| value.data()).map_err(rlp_err)?;
+                Ok(Step::Found(value))
+            } else {
+                if !rest.starts_with(&node_path) {
+                    return Ok(Step::NotFound);
+                }
+                *offset += node_path.len();
+                child_step(node.at(1).map_err(rlp_err)?)
+            }
+        }
+        count => Err(format!("node of {count} items")),
+    }
+}
+
+fn child_step(child: Rlp<'_>) -> Result<Step<'_>, String> {
+    if child.is_list() {
+        return Ok(Step::Embedded(child));
+    }
+    let reference = child.data().map_err(|e| e.to_string())?;
+    match reference.len() {
+        0 => Ok(Step::NotFound),
+        32 => Ok(Step::Hash(reference.try_into().unwrap())),
+        len => Err(format!("child reference of {len} bytes")),
+    }
+}
+
+/// Decodes the hex-prefix encoding of the path of a leaf or extension node,
+/// returning its nibbles and whether it's a leaf
+fn decode_hex_prefix(encoded: &[u8]) -> Result<(Vec<u8>, bool), String> {
+    let (first, rest) = encoded
+        .split_first()
+        .ok_or_else(|| "empty node path".to_owned())?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(format!("invalid node path flag {flag}"));
+    }
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    nibbles.extend(rest.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]));
+    Ok((nibbles, flag & 2 == 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::rpc::StorageProof;
+    use super::*;
+    use rlp::RlpStream;
+
+    fn nibbles(key: &[u8]) -> Vec<u8> {
+        key.iter()
+            .flat_map(|byte| [byte >> 4, byte & 0x0f])
+            .collect()
+    }
+
+    fn hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+        let flag = if is_leaf { 2 } else { 0 };
+        let (mut encoded, rest) = if nibbles.len() % 2 == 1 {
+            (vec![(flag + 1) << 4 | nibbles[0]], &nibbles[1..])
+        } else {
+            (vec![flag << 4], nibbles)
+        };
+        encoded.extend(rest.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+        encoded
+    }
+
+    fn leaf(nibbles: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&hex_prefix(nibbles, true));
+        stream.append(&value.to_vec());
+        stream.out().to_vec()
+    }
+
+    fn branch(children: &[(u8, [u8; 32])]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(17);
+        for index in 0..16 {
+            match children.iter().find(|(i, _)| *i == index) {
+                Some((_, hash)) => stream.append(&hash.to_vec()),
+                None => stream.append_empty_data(),
+            };
+        }
+        stream.append_empty_data();
+        stream.out().to_vec()
+    }
+
+    #[test]
+    fn test_verify_trie_proof() {
+        let (key1, key2, key3) = ([0x10; 32], [0x20; 32], [0x30; 32]);
+        let leaf1 = leaf(&nibbles(&key1)[1..], b"value1");
+        let leaf2 = leaf(&nibbles(&key2)[1..], b"value2");
+        let root_node = branch(&[(1, keccak256(&leaf1)), (2, keccak256(&leaf2))]);
+        let root = keccak256(&root_node);
+
+        let proof = vec![Bytes::from(root_node.clone()), Bytes::from(leaf1.clone())];
+        let value = verify_trie_proof(root, &key1, &proof).unwrap();
+        assert_eq!(value.as_deref(), Some(&b"value1"[..]));
+        // the value of another key is not proven by the same nodes
+        assert!(verify_trie_proof(root, &key2, &proof).is_err());
+        // an empty child proves the absence of the key
+        let proof = vec![Bytes::from(root_node.clone())];
+        assert_eq!(verify_trie_proof(root, &key3, &proof).unwrap(), None);
+        assert!(verify_trie_proof(root, &key1, &proof).is_err());
+
+        let forged = leaf(&nibbles(&key1)[1..], b"forged");
+        let proof = vec![Bytes::from(root_node), Bytes::from(forged)];
+        assert!(verify_trie_proof(root, &key1, &proof).is_err());
+    }
+
+    /// Proof of the storage `slot` of the contract at `address` holding
+    /// `value`, along with the state root it's proven against
+    fn storage_proof(address: H160, slot: U256, value: U256) -> ([u8; 32], EIP1186ProofResponse) {
+        let mut slot_key = [0u8; 32];
+        slot.to_big_endian(&mut slot_key);
+        let mut value_bytes = [0u8; 32];
+        value.to_big_endian(&mut value_bytes);
+        let first = value_bytes.iter().position(|byte| *byte != 0).unwrap_or(32);
+
+        let encoded_value = rlp::encode(&value_bytes[first..].to_vec());
+        let storage_leaf = leaf(&nibbles(&keccak256(slot_key)), &encoded_value);
+        let storage_root = keccak256(&storage_leaf);
+        let mut account = RlpStream::new_list(4);
+        account.append(&1u64);
+        account.append(&0u64);
+        account.append(&storage_root.to_vec());
+        account.append(&keccak256(b"").to_vec());
+        let account_leaf = leaf(&nibbles(&keccak256(address)), &account.out());
+        let state_root = keccak256(&account_leaf);
+
+        let proof = EIP1186ProofResponse {
+            account_proof: vec![Bytes::from(account_leaf)],
+            storage_proof: vec![StorageProof {
+                key: slot,
+                value,
+                proof: vec![Bytes::from(storage_leaf)],
+            }],
+        };
+        (state_root, proof)
+    }
+
+    #[test]
+    fn test_verify_storage_proof() {
+        let address = H160::repeat_byte(0xab);
+        let slot = U256::from(7);
+        let value = U256::from(0xdead_beef_u64);
+        let (state_root, mut proof) = storage_proof(address, slot, value);
+
+        assert_eq!(
+            verify_storage_proof(state_root, address, slot, &proof),
+            Ok(value)
+        );
+        assert!(verify_storage_proof([0; 32], address, slot, &proof).is_err());
+        assert!(verify_storage_proof(state_root, address, U256::from(8), &proof).is_err());
+
+        // the value returned along with the proof must be the proven one
+        proof.storage_proof[0].value = U256::one();
+        assert!(verify_storage_proof(state_root, address, slot, &proof).is_err());
+    }
+
+    #[test]
+    fn test_tampered_object_is_rejected() {
+        let address = H160::repeat_byte(0xab);
+        let slot = U256::from(7);
+        let encoded = br#"{"chain_id":"axon","latest_height":100}"#;
+        let commitment = U256::from_big_endian(&keccak256(encoded));
+        let (state_root, proof) = storage_proof(address, slot, commitment);
+        let proven = verify_storage_proof(state_root, address, slot, &proof).unwrap();
+
+        assert!(ExpectedCommitment::hash_of(encoded).check(proven).is_ok());
+        // an RPC returning another object than the proven one is caught
+        let tampered = br#"{"chain_id":"axon","latest_height":101}"#;
+        assert!(ExpectedCommitment::hash_of(tampered).check(proven).is_err());
+
+        let sequence = U256::from_big_endian(&keccak256(5u64.to_be_bytes()));
+        let (state_root, proof) = storage_proof(address, slot, sequence);
+        let proven = verify_storage_proof(state_root, address, slot, &proof).unwrap();
+        assert!(ExpectedCommitment::hash_of(&5u64.to_be_bytes())
+            .check(proven)
+            .is_ok());
+        assert!(ExpectedCommitment::hash_of(&6u64.to_be_bytes())
+            .check(proven)
+            .is_err());
+    }
+
+    #[test]
+    fn test_expected_commitment() {
+        let hash = [0x11; 32];
+        assert!(ExpectedCommitment::Absent.check(U256::zero()).is_ok());
+        assert!(ExpectedCommitment::Absent.check(U256::one()).is_err());
+        let value = U256::from_big_endian(&hash);
+        assert!(ExpectedCommitment::Hash(hash).check(value).is_ok());
+        assert!(ExpectedCommitment::Hash(hash).check(U256::one()).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use ethers::{
+    providers::{Http, Provider},
+    types::H160,
+};
+use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::{
+    applications::transfer::msgs::transfer,
+    core::{
+        ics04_channel::msgs::chan_close_init::MsgChannelCloseInit,
+        ics24_host::identifier::{ChannelId, PortId},
+    },
+    tx_msg::Msg,
+};
+
+use super::{contract::OwnableIBCHandler, encode_message, send_in_batches};
+use crate::error::Error;
+
+/// Records how the messages are sent, reverting the ones of type `failing`
+#[derive(Default)]
+struct Sender {
+    batches: Vec<usize>,
+    singles: usize,
+    failing: Option<&'static str>,
+    failing_batches: bool,
+    // batches fail without reverting, e.g. when the node is unreachable
+    broken_batches: bool,
+}
+
+impl Sender {
+    fn send_batch(&mut self, messages: &[Any]) -> Result<Vec<String>, Error> {
+        let failing = messages
+            .iter()
+            .any(|msg| Some(msg.type_url.as_str()) == self.failing);
+        if self.broken_batches {
+            return Err(Error::send_tx("unreachable".to_owned()));
+        }
+        if failing || self.failing_batches {
+            return Err(Error::axon_tx_reverted("batch".to_owned()));
+        }
+        self.batches.push(messages.len());
+        Ok(messages.iter().map(|msg| msg.type_url.clone()).collect())
+    }
+
+    fn send_one(&mut self, message: Any) -> Result<String, Error> {
+        if Some(message.type_url.as_str()) == self.failing {
+            return Err(Error::axon_tx_reverted(message.type_url));
+        }
+        self.singles += 1;
+        Ok(message.type_url)
+    }
+}
+
+fn messages(type_urls: &[&str]) -> Vec<Any> {
+    type_urls
+        .iter()
+        .map(|type_url| Any {
+            type_url: type_url.to_string(),
+            value: vec![],
+        })
+        .collect()
+}
+
+fn send(sender: &mut Sender, type_urls: &[&str], max: usize) -> Vec<String> {
+    send_in_batches(
+        sender,
+        messages(type_urls),
+        max,
+        Sender::send_batch,
+        Sender::send_one,
+        |_| "failed".to_owned(),
+    )
+}
+
+#[test]
+fn test_send_in_batches() {
+    let mut sender = Sender::default();
+    let sent = send(&mut sender, &["a", "b", "c", "d", "e"], 2);
+    assert_eq!(sent, ["a", "b", "c", "d", "e"]);
+    assert_eq!(sender.batches, [2, 2, 1]);
+    assert_eq!(sender.singles, 0);
+
+    // batching is disabled
+    let mut sender = Sender::default();
+    send(&mut sender, &["a", "b"], 1);
+    assert!(sender.batches.is_empty());
+    assert_eq!(sender.singles, 2);
+
+    // transfers are not handled by the IBC handler
+    let mut sender = Sender::default();
+    send(&mut sender, &["a", transfer::TYPE_URL], 2);
+    assert!(sender.batches.is_empty());
+    assert_eq!(sender.singles, 2);
+}
+
+#[test]
+fn test_send_failed_batch_one_by_one() {
+    let mut sender = Sender {
+        failing: Some("c"),
+        ..Default::default()
+    };
+    // the messages around the failing one are still sent
+    let sent = send(&mut sender, &["a", "b", "c", "d"], 2);
+    assert_eq!(sent, ["a", "b", "failed", "d"]);
+    assert_eq!(sender.batches, [2]);
+    assert_eq!(sender.singles, 1);
+
+    // the messages of a reverted batch are sent again one by one
+    let mut sender = Sender {
+        failing_batches: true,
+        ..Default::default()
+    };
+    let sent = send(&mut sender, &["a", "b", "c"], 3);
+    assert_eq!(sent, ["a", "b", "c"]);
+    assert!(sender.batches.is_empty());
+    assert_eq!(sender.singles, 3);
+
+    // a batch which didn't revert may have been sent, so it isn't sent again
+    let mut sender = Sender {
+        broken_batches: true,
+        ..Default::default()
+    };
+    let sent = send(&mut sender, &["a", "b", "c"], 2);
+    assert_eq!(sent, ["failed", "failed", "failed"]);
+    assert_eq!(sender.singles, 0);
+
+    // failing messages sent one by one don't stop the others
+    let mut sender = Sender {
+        failing: Some("a"),
+        ..Default::default()
+    };
+    let sent = send(&mut sender, &["a", "b"], 1);
+    assert_eq!(sent, ["failed", "b"]);
+}
+
+#[test]
+fn test_encode_message() {
+    let provider = Provider::<Http>::try_from("http://127.0.0.1:8000").unwrap();
+    let contract = OwnableIBCHandler::new(H160::repeat_byte(1), Arc::new(provider));
+
+    let msg = MsgChannelCloseInit {
+        port_id: PortId::transfer(),
+        channel_id: ChannelId::new(0),
+        signer: "axon-relayer".parse().unwrap(),
+    };
+    let calldata = encode_message(&contract, &msg.clone().to_any()).unwrap();
+    let expected = contract.channel_close_init(msg.into()).calldata().unwrap();
+    assert_eq!(calldata, expected);
+
+    let transfer = messages(&[transfer::TYPE_URL]).remove(0);
+    assert!(encode_message(&contract, &transfer).is_err());
+}
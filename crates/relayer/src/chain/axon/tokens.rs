@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use ethers::types::H160;
+
+use crate::account::Balance;
+use crate::util::lock::LockExt;
+
+/// Metadata of an ERC-20 token on Axon, the optional `symbol()` and
+/// `decimals()` of the standard are unset if the token doesn't implement them
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenInfo {
+    /// Denom the balance of the token is reported with
+    pub denom: String,
+    pub address: H160,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+impl TokenInfo {
+    pub fn balance(&self, amount: String) -> Balance {
+        Balance {
+            amount,
+            denom: self.denom.clone(),
+            symbol: self.symbol.clone(),
+            decimals: self.decimals,
+        }
+    }
+}
+
+/// Registry of the ERC-20 tokens known to the relayer on Axon, keyed by denom.
+///
+/// It holds the tokens of the config once resolved, and the tokens of the
+/// ICS-20 transfer contract discovered when their denom is queried. The
+/// tokens of the transfer contract are not enumerated: its `denomTraces`
+/// mapping can't be iterated, and it emits no event listing them.
+#[derive(Clone, Debug, Default)]
+pub struct TokenRegistry {
+    tokens: Arc<RwLock<BTreeMap<String, TokenInfo>>>,
+}
+
+impl TokenRegistry {
+    pub fn get(&self, denom: &str) -> Option<TokenInfo> {
+        self.tokens.acquire_read().get(denom).cloned()
+    }
+
+    /// Registers `token`, replacing any token known under the same denom
+    pub fn insert(&self, token: TokenInfo) {
+        self.tokens
+            .acquire_write()
+            .insert(token.denom.clone(), token);
+    }
+
+    pub fn contains_address(&self, address: &H160) -> bool {
+        self.tokens
+            .acquire_read()
+            .values()
+            .any(|token| token.address == *address)
+    }
+
+    /// All the known tokens, ordered by denom
+    pub fn tokens(&self) -> Vec<TokenInfo> {
+        self.tokens.acquire_read().values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(denom: &str, address: u8) -> TokenInfo {
+        TokenInfo {
+            denom: denom.to_owned(),
+            address: H160::repeat_byte(address),
+            symbol: Some(denom.to_uppercase()),
+            decimals: Some(6),
+        }
+    }
+
+    #[test]
+    fn test_token_registry() {
+        let registry = TokenRegistry::default();
+        registry.insert(token("usdt", 2));
+        registry.insert(token("usdc", 1));
+        assert_eq!(registry.get("usdc"), Some(token("usdc", 1)));
+        assert_eq!(registry.get("dai"), None);
+        assert!(registry.contains_address(&H160::repeat_byte(2)));
+        assert!(!registry.contains_address(&H160::repeat_byte(3)));
+
+        // clones share the registered tokens
+        registry.clone().insert(token("usdc", 3));
+        assert_eq!(registry.tokens(), vec![token("usdc", 3), token("usdt", 2)]);
+
+        let balance = token("usdc", 1).balance("1000".to_owned());
+        assert_eq!(balance.to_string(), "1000 usdc (0.001 USDC)");
+
+        // tokens without metadata are reported in their smallest unit
+        let token = TokenInfo {
+            symbol: None,
+            decimals: None,
+            ..token("0xabab", 0xab)
+        };
+        assert_eq!(token.balance("1000".to_owned()).to_string(), "1000 0xabab");
+    }
+}
@@ -1,19 +1,36 @@
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
-use axon_tools::types::{Block as AxonBlock, Proof as AxonProof, ValidatorExtend};
+use axon_tools::types::{Block as AxonBlock, Metadata, Proof as AxonProof, ValidatorExtend};
+use blst::{
+    min_pk::{AggregatePublicKey, PublicKey, Signature},
+    BLST_ERROR,
+};
+use eth2_types::Hash256;
 
 use crate::{
-    chain::{axon::eth_err::Panic, SEC_TO_NANO},
+    chain::{
+        axon::{
+            eth_err::Panic,
+            metadata::{validators_of, MetadataCache},
+            rpc::AxonRpc,
+        },
+        SEC_TO_NANO,
+    },
     client_state::{AnyClientState, IdentifiedAnyClientState},
     consensus_state::AnyConsensusState,
     error::Error,
     event::IbcEventWithHeight,
     ibc_contract::OwnableIBCHandlerEvents,
 };
-use ethers::{abi::AbiDecode, contract::ContractError, providers::Middleware, types::H256};
+use ethers::{
+    abi::AbiDecode, contract::ContractError, providers::Middleware, types::H256, utils::keccak256,
+};
 use ibc_relayer_types::{
     clients::{
-        ics07_axon::{client_state::AxonClientState, consensus_state::AxonConsensusState},
+        ics07_axon::{
+            client_state::AxonClientState, consensus_state::AxonConsensusState, header::AxonHeader,
+            light_block::AxonLightBlock,
+        },
         ics07_ckb::{client_state::CkbClientState, consensus_state::CkbConsensusState},
     },
     core::{ics02_client::client_type::ClientType, ics24_host::identifier::ClientId},
@@ -52,6 +69,16 @@ pub fn to_identified_any_client_state(
     })
 }
 
+/// State of a client in a response of IBC solidity, "ClientId|JSON(State)",
+/// i.e. the encoding whose hash the IBC handler stores as its commitment
+pub fn encoded_state(response: &ethers::core::types::Bytes) -> &[u8] {
+    let start = response
+        .iter()
+        .position(|byte| *byte == b'|')
+        .map_or(0, |separator| separator + 1);
+    &response[start..]
+}
+
 // response format designed by IBC solidity: "ClientId|JSON(ClientState)"
 pub fn to_any_client_state(
     response: &ethers::core::types::Bytes,
@@ -391,6 +418,36 @@ pub fn ibc_event_from_ibc_handler_event(
                 },
             })
         }
+        OwnableIBCHandlerEvents::TimeoutPacketFilter(data) => {
+            let contract::TimeoutPacketFilter {
+                packet:
+                    contract::PacketData {
+                        sequence,
+                        source_port,
+                        source_channel,
+                        destination_port,
+                        destination_channel,
+                        data,
+                        timeout_height,
+                        timeout_timestamp,
+                    },
+            } = data;
+            let timeout_height = Height::from_noncosmos_height(timeout_height.revision_height);
+            IbcEvent::TimeoutPacket(channels::TimeoutPacket {
+                packet: Packet {
+                    sequence: sequence.into(),
+                    source_port: PortId::from_str(&source_port)?,
+                    source_channel: ChannelId::from_str(&source_channel)?,
+                    destination_port: PortId::from_str(&destination_port)?,
+                    destination_channel: ChannelId::from_str(&destination_channel)?,
+                    data: data.to_vec(),
+                    timeout_height: timeout_height.into(),
+                    timeout_timestamp: Timestamp::from_nanoseconds(
+                        timeout_timestamp * SEC_TO_NANO,
+                    )?,
+                },
+            })
+        }
         OwnableIBCHandlerEvents::UpdateClientFilter(data) => {
             let contract::UpdateClientFilter {
                 client_id,
@@ -467,3 +524,397 @@ pub fn generate_debug_content(
     let content = format!("[block]\n{block}\n[validators]\n{validators}\n[state_root]\n{state_root}\n[block_proof]\n{block_proof}");
     content
 }
+
+/// Longest wait for the child of a block, which carries the block's proof
+const CHILD_PROOF_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Domain separation tag of the BLS signatures of Axon validators
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Vote type of the precommits aggregated into a commit proof
+const PRECOMMIT_VOTE_TYPE: u8 = 2;
+
+/// An Axon block whose commit proof has been checked against the validator
+/// set of its epoch.
+pub struct VerifiedBlock {
+    pub block: AxonBlock,
+    pub previous_state_root: Hash256,
+    pub proof: AxonProof,
+    pub metadata: Metadata,
+}
+
+impl VerifiedBlock {
+    pub fn to_light_block(&self) -> Result<AxonLightBlock, Error> {
+        to_light_block(
+            &self.block,
+            self.previous_state_root,
+            &self.proof,
+            &self.metadata,
+        )
+    }
+}
+
+pub fn to_light_block(
+    block: &AxonBlock,
+    previous_state_root: Hash256,
+    proof: &AxonProof,
+    metadata: &Metadata,
+) -> Result<AxonLightBlock, Error> {
+    let header = AxonHeader {
+        height: Height::from_noncosmos_height(block.header.number),
+        timestamp: to_timestamp(block.header.timestamp)?,
+        block_hash: proof.block_hash,
+        state_root: block.header.state_root,
+        previous_state_root,
+        epoch_start: metadata.version.start,
+        proof_round: proof.round,
+        proof_signature: proof.signature.to_vec(),
+        proof_bitmap: proof.bitmap.to_vec(),
+    };
+    let validators = metadata.verifier_list.iter().map(|v| v.address.0).collect();
+    Ok(AxonLightBlock { header, validators })
+}
+
+/// Fetches the ingredients to verify the Axon block at `block_number`, i.e.
+/// the block itself, the state root of its parent, the proof carried by its
+/// child and the metadata of its epoch.
+pub async fn fetch_block_ingredients<R: AxonRpc + Sync>(
+    rpc: &R,
+    metadata_cache: &MetadataCache,
+    block_number: u64,
+) -> Result<(AxonBlock, Hash256, AxonProof, Metadata), Error> {
+    let previous_number = block_number.checked_sub(1).ok_or_else(|| {
+        Error::other_error("axon genesis block #0 carries no verifiable proof".to_owned())
+    })?;
+    let next_number = block_number
+        .checked_add(1)
+        .ok_or_else(|| Error::other_error(format!("bad axon block number {block_number}")))?;
+
+    let block = rpc
+        .get_block_by_id(block_number.into())
+        .await?
+        .ok_or_else(|| Error::other_error(format!("failed to get block {block_number}")))?;
+    let state_root = rpc
+        .get_block_by_id(previous_number.into())
+        .await?
+        .ok_or_else(|| Error::other_error(format!("failed to get block {previous_number}")))?
+        .header
+        .state_root;
+    let wait_proof = async {
+        loop {
+            match rpc.get_proof_by_id(next_number.into()).await? {
+                None => {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+                Some(p) => break Ok::<_, Error>(p),
+            }
+        }
+    };
+    let proof = tokio::time::timeout(CHILD_PROOF_TIMEOUT, wait_proof)
+        .await
+        .map_err(|_| {
+            Error::other_error(format!(
+                "proof of axon block #{block_number} not produced in {CHILD_PROOF_TIMEOUT:?}"
+            ))
+        })??;
+    // the proof of a block is signed by the validators of its own epoch,
+    // which are not necessarily the current ones
+    let metadata = metadata_cache.get_or_fetch(rpc, block_number).await?;
+
+    Ok((block, state_root, proof, metadata))
+}
+
+/// Fetches the Axon block at `block_number` and verifies its proof against
+/// the validator set of the epoch the block belongs to.
+pub async fn fetch_verified_block<R: AxonRpc + Sync>(
+    rpc: &R,
+    metadata_cache: &MetadataCache,
+    block_number: u64,
+) -> Result<VerifiedBlock, Error> {
+    let (block, previous_state_root, proof, metadata) =
+        fetch_block_ingredients(rpc, metadata_cache, block_number).await?;
+    let mut validators = validators_of(&metadata);
+
+    let debug_content = generate_debug_content(&block, &previous_state_root, &proof, &validators);
+
+    // check the validation of Axon block
+    axon_tools::verify_proof(
+        block.clone(),
+        previous_state_root,
+        &mut validators,
+        proof.clone(),
+    )
+    .map_err(|err| {
+        std::fs::write(
+            format!("./debug/axon_block_{block_number}.log"),
+            debug_content,
+        )
+        .unwrap();
+        let err_msg = format!("unverified axon block #{block_number}, err: {:?}", err);
+        Error::rpc_response(err_msg)
+    })?;
+
+    Ok(VerifiedBlock {
+        block,
+        previous_state_root,
+        proof,
+        metadata,
+    })
+}
+
+/// Checks the commit proof carried by `header` against the validator set of
+/// `metadata`, the way `axon_tools::verify_proof` does, except that the block
+/// itself is not needed. This allows verifying headers of blocks which are
+/// unknown to the Axon node, such as the conflicting header of a misbehaviour.
+pub fn verify_header_proof(header: &AxonHeader, metadata: &Metadata) -> Result<(), Error> {
+    let number = header.height.revision_height();
+    let version = &metadata.version;
+    if header.epoch_start != version.start || number < version.start || number > version.end {
+        return Err(Error::other_error(format!(
+            "axon header #{number} of epoch starting at {} is checked against epoch [{}, {}]",
+            header.epoch_start, version.start, version.end
+        )));
+    }
+
+    // the bitmap indexes the validators in the order `axon_tools` sorts them
+    let mut validators = validators_of(metadata);
+    validators.sort();
+    let signers = validators
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| {
+            let byte = header.proof_bitmap.get(i / 8).copied().unwrap_or_default();
+            byte & (0x80 >> (i % 8)) != 0
+        })
+        .map(|(_, validator)| validator)
+        .collect::<Vec<_>>();
+    // the quorum of Overlord is more than 2/3 of the total vote weight
+    let total_weight: u64 = validators.iter().map(|v| u64::from(v.vote_weight)).sum();
+    let signed_weight: u64 = signers.iter().map(|v| u64::from(v.vote_weight)).sum();
+    if signed_weight * 3 <= total_weight * 2 {
+        return Err(Error::other_error(format!(
+            "axon header #{number} is signed by {signed_weight} of {total_weight} vote weight"
+        )));
+    }
+    let signers = signers
+        .iter()
+        .map(|validator| PublicKey::from_bytes(validator.bls_pub_key.as_ref()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| Error::other_error(format!("invalid bls public key: {e:?}")))?;
+    let signers = signers.iter().collect::<Vec<_>>();
+    let public_key = AggregatePublicKey::aggregate(&signers, true)
+        .map_err(|e| Error::other_error(format!("bls aggregation: {e:?}")))?
+        .to_public_key();
+    let signature = Signature::from_bytes(&header.proof_signature)
+        .map_err(|e| Error::other_error(format!("invalid bls signature: {e:?}")))?;
+
+    let vote = encode_precommit(number, header.proof_round, &header.block_hash);
+    match signature.verify(true, &keccak256(vote), BLS_DST, &[], &public_key, true) {
+        BLST_ERROR::BLST_SUCCESS => Ok(()),
+        e => Err(Error::other_error(format!(
+            "invalid proof of axon header #{number}: {e:?}"
+        ))),
+    }
+}
+
+/// Encodes the precommit vote the validators sign for a block.
+fn encode_precommit(number: u64, round: u64, block_hash: &H256) -> Vec<u8> {
+    let mut stream = rlp::RlpStream::new_list(4);
+    stream
+        .append(&number)
+        .append(&round)
+        .append(&PRECOMMIT_VOTE_TYPE)
+        .append(&block_hash.as_bytes());
+    stream.out().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use axon_tools::types::{CkbRelatedInfo, Hex, MetadataVersion};
+    use blst::min_pk::{AggregateSignature, SecretKey};
+    use ethers::types::{BlockId, BlockNumber, H160, U256};
+
+    use super::*;
+    use crate::chain::axon::rpc::{EIP1186ProofResponse, Response};
+
+    const NUMBER: u64 = 150;
+
+    /// Axon node which produces blocks but never their proofs
+    struct StalledNode;
+
+    #[async_trait]
+    impl AxonRpc for StalledNode {
+        async fn get_block_by_id(&self, _: BlockId) -> Response<Option<AxonBlock>> {
+            Ok(Some(AxonBlock::default()))
+        }
+
+        async fn get_proof_by_id(&self, _: BlockId) -> Response<Option<AxonProof>> {
+            Ok(None)
+        }
+
+        async fn get_metadata_by_number(&self, _: BlockNumber) -> Response<Metadata> {
+            unreachable!()
+        }
+
+        async fn get_current_metadata(&self) -> Response<Metadata> {
+            unreachable!()
+        }
+
+        async fn get_ckb_related_info(&self) -> Response<CkbRelatedInfo> {
+            unreachable!()
+        }
+
+        async fn eth_get_proof(
+            &self,
+            _: H160,
+            _: Vec<U256>,
+            _: Option<BlockId>,
+        ) -> Response<EIP1186ProofResponse> {
+            unreachable!()
+        }
+    }
+
+    fn epoch(count: u8) -> (Vec<SecretKey>, Metadata) {
+        weighted_epoch(&vec![1; count as usize])
+    }
+
+    /// Epoch of a validator for each of `weights`, with that vote weight
+    fn weighted_epoch(weights: &[u32]) -> (Vec<SecretKey>, Metadata) {
+        let keys = (0..weights.len() as u8)
+            .map(|i| SecretKey::key_gen(&[i; 32], &[]).unwrap())
+            .collect::<Vec<_>>();
+        let verifier_list = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| ValidatorExtend {
+                bls_pub_key: Hex::encode(key.sk_to_pk().to_bytes()),
+                pub_key: Hex::encode([i as u8]),
+                address: H160::repeat_byte(i as u8),
+                propose_weight: 1,
+                vote_weight: weights[i].into(),
+            })
+            .collect();
+        let metadata = Metadata {
+            version: MetadataVersion {
+                start: 100,
+                end: 199,
+            },
+            epoch: 1,
+            verifier_list,
+            ..Default::default()
+        };
+        (keys, metadata)
+    }
+
+    /// Header of block `NUMBER` signed by the validators at `signers`, which
+    /// index the validator set in the order of the bitmap
+    fn signed_header(keys: &[SecretKey], metadata: &Metadata, signers: &[usize]) -> AxonHeader {
+        let mut validators = metadata.verifier_list.clone();
+        validators.sort();
+        let block_hash = H256::repeat_byte(7);
+        let vote = keccak256(encode_precommit(NUMBER, 1, &block_hash));
+
+        let mut bitmap = vec![0u8; (validators.len() + 7) / 8];
+        let mut signatures = vec![];
+        for &i in signers {
+            bitmap[i / 8] |= 0x80 >> (i % 8);
+            let bls_pub_key: &[u8] = validators[i].bls_pub_key.as_ref();
+            let key = keys
+                .iter()
+                .find(|key| key.sk_to_pk().to_bytes().as_slice() == bls_pub_key)
+                .unwrap();
+            signatures.push(key.sign(&vote, BLS_DST, &[]));
+        }
+        let signatures = signatures.iter().collect::<Vec<_>>();
+        let signature = AggregateSignature::aggregate(&signatures, true)
+            .unwrap()
+            .to_signature();
+
+        AxonHeader {
+            height: Height::from_noncosmos_height(NUMBER),
+            block_hash,
+            epoch_start: metadata.version.start,
+            proof_round: 1,
+            proof_signature: signature.to_bytes().to_vec(),
+            proof_bitmap: bitmap,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_header_proof() {
+        let (keys, metadata) = epoch(4);
+        let header = signed_header(&keys, &metadata, &[0, 1, 3]);
+        verify_header_proof(&header, &metadata).unwrap();
+
+        // the signature doesn't cover another block
+        let forged = AxonHeader {
+            block_hash: H256::repeat_byte(8),
+            ..header.clone()
+        };
+        assert!(verify_header_proof(&forged, &metadata).is_err());
+
+        // the bitmap doesn't match the signers
+        let forged = AxonHeader {
+            proof_bitmap: vec![0b1110_0000],
+            ..header.clone()
+        };
+        assert!(verify_header_proof(&forged, &metadata).is_err());
+
+        // the header is checked against the validators of another epoch
+        let forged = AxonHeader {
+            epoch_start: 0,
+            ..header
+        };
+        assert!(verify_header_proof(&forged, &metadata).is_err());
+    }
+
+    #[test]
+    fn test_verify_header_proof_without_quorum() {
+        let (keys, metadata) = epoch(4);
+        let header = signed_header(&keys, &metadata, &[1, 2]);
+        assert!(verify_header_proof(&header, &metadata).is_err());
+
+        let header = signed_header(&keys, &metadata, &[0, 1, 2, 3]);
+        verify_header_proof(&header, &metadata).unwrap();
+    }
+
+    #[test]
+    fn test_quorum_of_vote_weight() {
+        let (keys, metadata) = weighted_epoch(&[1, 1, 1, 10]);
+        let mut validators = metadata.verifier_list.clone();
+        validators.sort();
+        let heavy = validators.iter().position(|v| v.vote_weight == 10).unwrap();
+        let light = (0..4).filter(|&i| i != heavy).collect::<Vec<_>>();
+
+        // three of four validators hold only 3 of 13 vote weight
+        let header = signed_header(&keys, &metadata, &light);
+        assert!(verify_header_proof(&header, &metadata).is_err());
+
+        // 11 of 13 vote weight is a quorum
+        let header = signed_header(&keys, &metadata, &[heavy, light[0]]);
+        verify_header_proof(&header, &metadata).unwrap();
+    }
+
+    #[test]
+    fn test_encode_precommit() {
+        // RLP list of [height, round, vote type, block hash], as Overlord
+        // encodes its votes
+        let mut expected = vec![0xe5, 0x81, 0x96, 0x01, 0x02, 0xa0];
+        expected.extend([7u8; 32]);
+        assert_eq!(encode_precommit(150, 1, &H256::repeat_byte(7)), expected);
+    }
+
+    #[tokio::test]
+    async fn test_genesis_block_is_rejected() {
+        let result = fetch_block_ingredients(&StalledNode, &MetadataCache::new(), 0).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_for_proof_times_out() {
+        let result = fetch_block_ingredients(&StalledNode, &MetadataCache::new(), NUMBER).await;
+        assert!(result.is_err());
+    }
+}
@@ -1,9 +1,10 @@
 use ckb_jsonrpc_types::{OutputsValidator, TransactionView as JsonTx};
 use ckb_sdk::{Address, AddressPayload, NetworkType};
 use ckb_types::core::TransactionView;
-use ckb_types::packed::CellOutput;
+use ckb_types::packed::{CellOutput, Script};
 use ckb_types::prelude::*;
-use eth2_types::MainnetEthSpec;
+use ckb_types::H256;
+use eth2_types::{EthSpec, EthSpecId, MainnetEthSpec};
 use eth_light_client_in_ckb_verification::types::{
     packed::Client as PackedClient, packed::ClientInfo as PackedClientInfo,
     packed::ClientTypeArgs as PackedClientTypeArgs, packed::Hash as PackedHash,
@@ -12,7 +13,9 @@ use eth_light_client_in_ckb_verification::types::{
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
-use ibc_relayer_storage::prelude::{StorageAsMMRStore as _, StorageReader as _};
+use ibc_relayer_storage::prelude::{
+    StorageAsMMRStore as _, StorageReader as _, StorageWriter as _,
+};
 use ibc_relayer_storage::{Slot, Storage};
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
 use ibc_relayer_types::clients::ics07_ckb::{
@@ -20,9 +23,10 @@ use ibc_relayer_types::clients::ics07_ckb::{
     light_block::CkbLightBlock,
 };
 use ibc_relayer_types::clients::ics07_eth::{
-    client_state::EthClientState, types::Update as EthUpdate,
+    client_state::{EthClientState, EthMultiClientState},
+    types::Update as EthUpdate,
 };
-use ibc_relayer_types::core::ics02_client::height::Height;
+use ibc_relayer_types::core::ics02_client::{client_type::ClientType, height::Height};
 use ibc_relayer_types::{
     core::{
         ics02_client::events::UpdateClient,
@@ -32,15 +36,15 @@ use ibc_relayer_types::{
             packet::Sequence,
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
-        ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+        ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
     },
     signer::Signer,
+    timestamp::Timestamp,
     Height as ICSHeight,
 };
 use semver::Version;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
-use tendermint_light_client::errors::Error as LightClientError;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tokio::runtime::Runtime as TokioRuntime;
 
@@ -63,6 +67,7 @@ use crate::{
     misbehaviour::MisbehaviourEvidence,
 };
 
+use super::ckb4ibc::utils::get_prefix_search_key;
 use super::requests::{CrossChainQueryRequest, QueryConsensusStateHeightsRequest};
 use super::tracking::{NonCosmosTrackingId as NonCosmos, TrackedMsgs, TrackingId};
 use super::{
@@ -105,73 +110,53 @@ pub mod prelude {
 
 use assembler::TxAssembler;
 
-use prelude::{CkbReader as _, CkbWriter as _, UpdateCells};
+use prelude::{CkbReader as _, CkbWriter as _};
 
 use rpc_client::RpcClient;
 
 // Ref: https://github.com/satoshilabs/slips/pull/621
 pub const HD_PATH: &str = "m/44'/309'/0'/0/0";
 
-pub struct CkbChain {
+/// Chain of the Eth multi-client on CKB, whose headers are stored under the
+/// preset `S` of the relayed Ethereum network.
+pub struct CkbChain<S = MainnetEthSpec> {
     pub rt: Arc<TokioRuntime>,
     pub rpc_client: Arc<RpcClient>,
     pub config: CkbChainConfig,
     pub keybase: KeyRing<Secp256k1KeyPair>,
-    // TODO the spec of Ethereum should be selectable.
-    pub storage: Storage<MainnetEthSpec>,
+    // the network of the stored headers is checked against `eth_network` at
+    // bootstrap
+    pub storage: Storage<S>,
 
     pub cached_network: RwLock<Option<NetworkType>>,
     pub cached_tx_assembler_address: RwLock<Option<Address>>,
     pub cached_onchain_packed_client: Option<PackedClient>,
 }
 
-impl CkbChain {
+impl<S: EthSpec> CkbChain<S> {
     fn create_eth_multi_client(
         &mut self,
         mut header_updates: Vec<EthUpdate>,
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
         let chain_id = self.id().to_string();
         let minimal_updates_count = self.config.minimal_updates_count;
-        let client_type_args = &self.config.client_type_args;
 
-        if let Some(type_id) = client_type_args.type_id.as_ref() {
-            let client_type_args: PackedClientTypeArgs = {
-                let type_id = PackedHash::from_slice(type_id.0.as_slice()).expect("build type id");
-                PackedClientTypeArgs::new_builder()
-                    .cells_count(client_type_args.cells_count.into())
-                    .type_id(type_id)
-                    .build()
-            };
-
-            let update_cells = self.rt.block_on(self.rpc_client.fetch_update_cells(
-                &self.config.lightclient_contract_typeargs,
-                &client_type_args,
-            ))?;
-            if let Some(UpdateCells {
-                oldest: _,
-                latest,
-                info: _,
-            }) = update_cells
-            {
-                let latest_client = PackedClient::new_unchecked(latest.output_data);
-                self.cached_onchain_packed_client = Some(latest_client.clone());
-
-                let onchain_base_slot = latest_client.minimal_slot().unpack();
-                // This is for reporting that clients have been created at that slot.
-                // TODO: better error type to match the semantic.
-                return Err(Error::light_client_verification(
-                    chain_id,
-                    LightClientError::missing_last_block_id(utils::into_height(onchain_base_slot)),
-                ));
-            } else {
+        if self.config.client_type_args.type_id.is_some() {
+            let Some(latest_client) = self.fetch_onchain_client()? else {
                 return Err(Error::other_error(
                     "no multi-client cells found for config".to_owned(),
                 ));
-            }
+            };
+            self.cached_onchain_packed_client = Some(latest_client.clone());
+            return Err(Error::eth_multi_client_already_exists(
+                chain_id,
+                latest_client.minimal_slot().unpack(),
+                latest_client.maximal_slot().unpack(),
+            ));
         }
 
         let client_count = {
-            let cells_count = client_type_args.cells_count;
+            let cells_count = self.config.client_type_args.cells_count;
             cells_count.checked_sub(1).expect("invalid cells_count")
         };
 
@@ -203,8 +188,11 @@ impl CkbChain {
             err
         })?;
 
-        // TODO: Write back the type id to config.
+        // the type id is only known after creation, keep it in storage so that
+        // the multi-client is found again after a restart
         tracing::info!("new type_id: {}", type_id);
+        let packed_type_id = PackedHash::from_slice(type_id.0.as_slice()).expect("build type id");
+        self.storage.put_multi_client_type_id(&packed_type_id)?;
         self.config.client_type_args.type_id = Some(type_id);
 
         self.print_status_log()?;
@@ -414,6 +402,57 @@ impl CkbChain {
         Ok(address)
     }
 
+    fn packed_client_type_args(&self) -> Option<PackedClientTypeArgs> {
+        let client_type_args = &self.config.client_type_args;
+        let type_id = client_type_args.type_id.as_ref()?;
+        let type_id = PackedHash::from_slice(type_id.0.as_slice()).expect("build type id");
+        let packed_client_type_args = PackedClientTypeArgs::new_builder()
+            .cells_count(client_type_args.cells_count.into())
+            .type_id(type_id)
+            .build();
+        Some(packed_client_type_args)
+    }
+
+    /// Fetches the latest client of the on-chain Ethereum multi-client, `None`
+    /// is returned if the multi-client is not created yet.
+    fn fetch_onchain_client(&self) -> Result<Option<PackedClient>, Error> {
+        let Some(client_type_args) = self.packed_client_type_args() else {
+            return Ok(None);
+        };
+        let update_cells = self.rt.block_on(self.rpc_client.fetch_update_cells(
+            &self.config.lightclient_contract_typeargs,
+            &client_type_args,
+        ))?;
+        Ok(update_cells.map(|cells| PackedClient::new_unchecked(cells.latest.output_data)))
+    }
+
+    fn query_onchain_client_state(&self) -> Result<Option<EthMultiClientState>, Error> {
+        let client_state = self
+            .fetch_onchain_client()?
+            .map(|client| EthMultiClientState {
+                chain_id: self.id(),
+                minimal_slot: client.minimal_slot().unpack(),
+                maximal_slot: client.maximal_slot().unpack(),
+            });
+        Ok(client_state)
+    }
+
+    fn do_health_check(&self) -> Result<(), Error> {
+        self.rt.block_on(self.rpc_client.get_blockchain_info())?;
+        if let Some(type_id) = self.config.client_type_args.type_id.as_ref() {
+            if self.fetch_onchain_client()?.is_none() {
+                return Err(Error::other_error(format!(
+                    "no multi-client cells found for type id {type_id}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn unsupported<T>(&self, operation: &str) -> Result<T, Error> {
+        Err(Error::unsupported(self.id(), operation.to_owned()))
+    }
+
     fn print_status_log(&self) -> Result<(), Error> {
         let contract_typeid_args = &self.config.lightclient_contract_typeargs;
         let client_type_args = &self.config.client_type_args;
@@ -462,7 +501,7 @@ impl CkbChain {
     }
 }
 
-impl ChainEndpoint for CkbChain {
+impl<S: EthSpec> ChainEndpoint for CkbChain<S> {
     type LightBlock = CkbLightBlock;
     type Header = CkbHeader;
     type ConsensusState = CkbConsensusState;
@@ -474,10 +513,46 @@ impl ChainEndpoint for CkbChain {
     }
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
-        let config: CkbChainConfig = config.try_into()?;
+        let mut config: CkbChainConfig = config.try_into()?;
+        // the SSZ types of the Eth light client are sized for the mainnet preset
+        if S::spec_name() != EthSpecId::Mainnet {
+            return Err(Error::other_error(format!(
+                "the Eth light client does not support the {:?} preset",
+                S::spec_name()
+            )));
+        }
         let rpc_client = Arc::new(RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc));
         let storage = Storage::new(&config.data_dir)?;
 
+        let eth_network = config.eth_network.as_str();
+        match storage.get_eth_network()? {
+            Some(stored_network) if stored_network != eth_network => {
+                return Err(Error::other_error(format!(
+                    "data_dir {} holds headers of {stored_network} network, not {eth_network}",
+                    config.data_dir.display()
+                )));
+            }
+            Some(_) => {}
+            None => storage.put_eth_network(eth_network)?,
+        }
+
+        // fall back to the type id of the multi-client created by a previous run
+        let stored_type_id = storage
+            .get_multi_client_type_id()?
+            .map(|type_id| H256::from_slice(type_id.as_slice()).expect("stored type id"));
+        match (&config.client_type_args.type_id, stored_type_id) {
+            (None, Some(stored_type_id)) => {
+                tracing::info!("use stored type_id: {stored_type_id}");
+                config.client_type_args.type_id = Some(stored_type_id);
+            }
+            (Some(type_id), Some(stored_type_id)) if *type_id != stored_type_id => {
+                tracing::warn!(
+                    "configured type_id {type_id} overrides stored type_id {stored_type_id}"
+                );
+            }
+            _ => {}
+        }
+
         #[cfg(not(test))]
         {
             use ckb_sdk::constants::TYPE_ID_CODE_HASH;
@@ -539,6 +614,13 @@ impl ChainEndpoint for CkbChain {
     }
 
     fn health_check(&self) -> Result<HealthCheck, Error> {
+        if let Err(e) = self.do_health_check() {
+            tracing::warn!("Health checkup for chain '{}' failed", self.id());
+            tracing::warn!("    Reason: {}", e.detail());
+            tracing::warn!("    Some Forcerelay features may not work in this mode!");
+
+            return Ok(HealthCheck::Unhealthy(Box::new(e)));
+        }
         Ok(HealthCheck::Healthy)
     }
 
@@ -592,7 +674,7 @@ impl ChainEndpoint for CkbChain {
         &mut self,
         _tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<Response>, Error> {
-        todo!()
+        self.unsupported("send_messages_and_wait_check_tx")
     }
 
     fn verify_header(
@@ -601,7 +683,7 @@ impl ChainEndpoint for CkbChain {
         _target: ICSHeight,
         _client_state: &AnyClientState,
     ) -> Result<Self::LightBlock, Error> {
-        todo!()
+        self.unsupported("verify_header")
     }
 
     fn check_misbehaviour(
@@ -609,57 +691,90 @@ impl ChainEndpoint for CkbChain {
         _update: &UpdateClient,
         _client_state: &AnyClientState,
     ) -> Result<Option<MisbehaviourEvidence>, Error> {
-        todo!()
+        self.unsupported("check_misbehaviour")
     }
 
-    fn query_balance(
-        &self,
-        _key_name: Option<&str>,
-        _denom: Option<&str>,
-    ) -> Result<Balance, Error> {
-        todo!()
+    fn query_balance(&self, key_name: Option<&str>, denom: Option<&str>) -> Result<Balance, Error> {
+        if let Some(denom) = denom.filter(|denom| *denom != "ckb") {
+            return Err(Error::query(format!(
+                "balance of {denom}, only ckb is supported"
+            )));
+        }
+        let address = match key_name {
+            Some(key_name) => {
+                let key: Secp256k1KeyPair =
+                    self.keybase.get_key(key_name).map_err(Error::key_base)?;
+                let address_payload = AddressPayload::from_pubkey(&key.public_key);
+                Address::new(self.network()?, address_payload, true)
+            }
+            None => self.tx_assembler_address()?,
+        };
+        let lock_script: Script = address.payload().into();
+        let cells = self.rt.block_on(self.rpc_client.fetch_live_cells(
+            get_prefix_search_key(lock_script),
+            u32::MAX,
+            None,
+        ))?;
+        // cells with type script hold assets other than ckb
+        let capacity: u64 = cells
+            .objects
+            .into_iter()
+            .filter(|cell| cell.output.type_.is_none())
+            .map(|cell| cell.output.capacity.value())
+            .sum();
+        Ok(Balance::new(capacity.to_string(), "ckb".to_owned()))
     }
 
-    fn query_all_balances(&self, _key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
-        todo!()
+    fn query_all_balances(&self, key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
+        let ckb_balance = self.query_balance(key_name, None)?;
+        Ok(vec![ckb_balance])
     }
 
     fn query_denom_trace(&self, _hash: String) -> Result<DenomTrace, Error> {
-        todo!()
+        self.unsupported("query_denom_trace")
     }
 
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
-        todo!()
+        self.unsupported("query_commitment_prefix")
     }
 
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
-        todo!()
+        let header = self.rt.block_on(self.rpc_client.get_tip_header())?;
+        let height = Height::from_noncosmos_height(header.inner.number.value());
+        let ts_milisec = header.inner.timestamp.value();
+        let timestamp = Timestamp::from_nanoseconds(ts_milisec * 1_000_000)
+            .map_err(|e| Error::other_error(e.to_string()))?;
+        Ok(ChainStatus { height, timestamp })
     }
 
     fn query_clients(
         &self,
         _request: QueryClientStatesRequest,
     ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
-        let mut clients = vec![];
-        if self.cached_onchain_packed_client.is_some() {
-            let client_state = IdentifiedAnyClientState {
-                client_id: Default::default(),
-                client_state: AnyClientState::Ckb(CkbClientState {
-                    chain_id: self.id(),
-                    latest_height: Height::default(),
-                }),
-            };
-            clients.push(client_state);
-        }
+        // there is at most one Ethereum multi-client, identified by the type id in config
+        let clients = self
+            .query_onchain_client_state()?
+            .into_iter()
+            .map(|client_state| IdentifiedAnyClientState {
+                client_id: ClientId::new(ClientType::Eth, 0).expect("eth client id"),
+                client_state: client_state.into(),
+            })
+            .collect();
         Ok(clients)
     }
 
     fn query_client_state(
         &self,
-        _request: QueryClientStateRequest,
+        request: QueryClientStateRequest,
         _include_proof: IncludeProof,
     ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
-        todo!()
+        let client_state = self.query_onchain_client_state()?.ok_or_else(|| {
+            Error::query(format!(
+                "client state of {}, no multi-client cells found on chain",
+                request.client_id
+            ))
+        })?;
+        Ok((client_state.into(), None))
     }
 
     fn query_consensus_state(
@@ -667,42 +782,42 @@ impl ChainEndpoint for CkbChain {
         _request: QueryConsensusStateRequest,
         _include_proof: IncludeProof,
     ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
-        todo!()
+        self.unsupported("query_consensus_state")
     }
 
     fn query_consensus_state_heights(
         &self,
         _request: QueryConsensusStateHeightsRequest,
     ) -> Result<Vec<ICSHeight>, Error> {
-        todo!()
+        self.unsupported("query_consensus_state_heights")
     }
 
     fn query_upgraded_client_state(
         &self,
         _request: QueryUpgradedClientStateRequest,
     ) -> Result<(AnyClientState, MerkleProof), Error> {
-        todo!()
+        self.unsupported("query_upgraded_client_state")
     }
 
     fn query_upgraded_consensus_state(
         &self,
         _request: QueryUpgradedConsensusStateRequest,
     ) -> Result<(AnyConsensusState, MerkleProof), Error> {
-        todo!()
+        self.unsupported("query_upgraded_consensus_state")
     }
 
     fn query_connections(
         &self,
         _request: QueryConnectionsRequest,
     ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
-        todo!()
+        self.unsupported("query_connections")
     }
 
     fn query_client_connections(
         &self,
         _request: QueryClientConnectionsRequest,
     ) -> Result<Vec<ConnectionId>, Error> {
-        todo!()
+        self.unsupported("query_client_connections")
     }
 
     fn query_connection(
@@ -710,21 +825,21 @@ impl ChainEndpoint for CkbChain {
         _request: QueryConnectionRequest,
         _include_proof: IncludeProof,
     ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
-        todo!()
+        self.unsupported("query_connection")
     }
 
     fn query_connection_channels(
         &self,
         _request: super::requests::QueryConnectionChannelsRequest,
     ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
-        todo!()
+        self.unsupported("query_connection_channels")
     }
 
     fn query_channels(
         &self,
         _request: QueryChannelsRequest,
     ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
-        todo!()
+        self.unsupported("query_channels")
     }
 
     fn query_channel(
@@ -732,14 +847,14 @@ impl ChainEndpoint for CkbChain {
         _request: QueryChannelRequest,
         _include_proof: IncludeProof,
     ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
-        todo!()
+        self.unsupported("query_channel")
     }
 
     fn query_channel_client_state(
         &self,
         _request: QueryChannelClientStateRequest,
     ) -> Result<Option<IdentifiedAnyClientState>, Error> {
-        todo!()
+        self.unsupported("query_channel_client_state")
     }
 
     fn query_packet_commitment(
@@ -747,14 +862,14 @@ impl ChainEndpoint for CkbChain {
         _request: super::requests::QueryPacketCommitmentRequest,
         _include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
-        todo!()
+        self.unsupported("query_packet_commitment")
     }
 
     fn query_packet_commitments(
         &self,
         _request: QueryPacketCommitmentsRequest,
     ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
-        todo!()
+        self.unsupported("query_packet_commitments")
     }
 
     fn query_packet_receipt(
@@ -762,14 +877,14 @@ impl ChainEndpoint for CkbChain {
         _request: super::requests::QueryPacketReceiptRequest,
         _include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
-        todo!()
+        self.unsupported("query_packet_receipt")
     }
 
     fn query_unreceived_packets(
         &self,
         _request: QueryUnreceivedPacketsRequest,
     ) -> Result<Vec<Sequence>, Error> {
-        todo!()
+        self.unsupported("query_unreceived_packets")
     }
 
     fn query_packet_acknowledgement(
@@ -777,21 +892,21 @@ impl ChainEndpoint for CkbChain {
         _request: QueryPacketAcknowledgementRequest,
         _include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
-        todo!()
+        self.unsupported("query_packet_acknowledgement")
     }
 
     fn query_packet_acknowledgements(
         &self,
         _request: QueryPacketAcknowledgementsRequest,
     ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
-        todo!()
+        self.unsupported("query_packet_acknowledgements")
     }
 
     fn query_unreceived_acknowledgements(
         &self,
         _request: QueryUnreceivedAcksRequest,
     ) -> Result<Vec<Sequence>, Error> {
-        todo!()
+        self.unsupported("query_unreceived_acknowledgements")
     }
 
     fn query_next_sequence_receive(
@@ -799,28 +914,28 @@ impl ChainEndpoint for CkbChain {
         _request: QueryNextSequenceReceiveRequest,
         _include_proof: IncludeProof,
     ) -> Result<(Sequence, Option<MerkleProof>), Error> {
-        todo!()
+        self.unsupported("query_next_sequence_receive")
     }
 
     fn query_txs(
         &self,
         _request: super::requests::QueryTxRequest,
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
-        todo!()
+        self.unsupported("query_txs")
     }
 
     fn query_packet_events(
         &self,
         _request: super::requests::QueryPacketEventDataRequest,
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
-        todo!()
+        self.unsupported("query_packet_events")
     }
 
     fn query_host_consensus_state(
         &self,
         _request: QueryHostConsensusStateRequest,
     ) -> Result<Self::ConsensusState, Error> {
-        todo!()
+        self.unsupported("query_host_consensus_state")
     }
 
     fn build_client_state(
@@ -828,14 +943,14 @@ impl ChainEndpoint for CkbChain {
         _height: ICSHeight,
         _settings: ClientSettings,
     ) -> Result<Self::ClientState, Error> {
-        todo!()
+        self.unsupported("build_client_state")
     }
 
     fn build_consensus_state(
         &self,
         _light_block: Self::LightBlock,
     ) -> Result<Self::ConsensusState, Error> {
-        todo!()
+        self.unsupported("build_consensus_state")
     }
 
     fn build_header(
@@ -844,7 +959,7 @@ impl ChainEndpoint for CkbChain {
         _target_height: ICSHeight,
         _client_state: &AnyClientState,
     ) -> Result<(Self::Header, Vec<Self::Header>), Error> {
-        todo!()
+        self.unsupported("build_header")
     }
 
     fn maybe_register_counterparty_payee(
@@ -853,24 +968,26 @@ impl ChainEndpoint for CkbChain {
         _port_id: &PortId,
         _counterparty_payee: &Signer,
     ) -> Result<(), Error> {
-        todo!()
+        self.unsupported("maybe_register_counterparty_payee")
     }
 
     fn cross_chain_query(
         &self,
         _requests: Vec<CrossChainQueryRequest>,
     ) -> Result<Vec<CrossChainQueryResponse>, Error> {
-        todo!()
+        self.unsupported("cross_chain_query")
     }
 
     fn subscribe(&mut self) -> Result<super::handle::Subscription, Error> {
-        todo!()
+        // the Ethereum multi-client emits no IBC events, headers are relayed
+        // on the events of the Ethereum chain instead
+        Ok(crossbeam_channel::never())
     }
 
     fn query_incentivized_packet(
         &self,
         _: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
-        todo!()
+        self.unsupported("query_incentivized_packet")
     }
 }
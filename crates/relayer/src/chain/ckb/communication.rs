@@ -1,7 +1,7 @@
 use ckb_jsonrpc_types::{
-    BlockNumber, BlockView, CellWithStatus, ChainInfo, HeaderView, JsonBytes, OutPoint,
-    OutputsValidator, RawTxPool, Transaction, TransactionAndWitnessProof,
-    TransactionWithStatusResponse, TxPoolInfo,
+    BlockNumber, BlockView, CellWithStatus, ChainInfo, FeeRateStatistics, HeaderView, JsonBytes,
+    OutPoint, OutputsValidator, RawTxPool, Transaction, TransactionAndWitnessProof,
+    TransactionWithStatusResponse, TxPoolInfo, Uint64,
 };
 use ckb_sdk::rpc::ckb_indexer::{Cell, Pagination, SearchKey};
 use ckb_types::H256;
@@ -22,6 +22,8 @@ pub trait CkbReader {
 
     fn get_header(&self, hash: &H256) -> Response<Option<HeaderView>>;
 
+    fn get_header_by_number(&self, number: BlockNumber) -> Response<Option<HeaderView>>;
+
     fn get_transaction(&self, hash: &H256) -> Response<Option<TransactionWithStatusResponse>>;
 
     fn get_live_cell(&self, out_point: &OutPoint, with_data: bool) -> Response<CellWithStatus>;
@@ -48,6 +50,11 @@ pub trait CkbReader {
     fn get_raw_tx_pool(&self, verbose: bool) -> Response<RawTxPool>;
 
     fn tx_pool_info(&self) -> Response<TxPoolInfo>;
+
+    fn get_fee_rate_statistics(
+        &self,
+        target: Option<Uint64>,
+    ) -> Response<Option<FeeRateStatistics>>;
 }
 
 pub trait CkbWriter {
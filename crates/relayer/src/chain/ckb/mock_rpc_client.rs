@@ -1,74 +1,473 @@
+//! In-memory CKB node standing in for the RPC client in unit tests.
+//!
+//! A node keeps its blocks, live cells and tx-pool, and is shared by all the
+//! clients connected to the same CKB RPC URL, so that a test can prepare the
+//! cells a chain endpoint looks for on bootstrap. Scripts are not executed:
+//! a transaction is accepted as long as its inputs are live and cover its
+//! outputs.
+
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
 use ckb_jsonrpc_types::{
-    BlockNumber, BlockView, CellWithStatus, ChainInfo, Header, HeaderView, JsonBytes, OutPoint,
-    OutputsValidator, RawTxPool, ResponseFormat, Transaction, TransactionAndWitnessProof,
-    TransactionView, TransactionWithStatusResponse, TxPoolInfo, TxStatus,
+    BlockNumber, BlockView, CellData, CellInfo, CellWithStatus, ChainInfo, FeeRateStatistics,
+    HeaderView, JsonBytes, MerkleProof, OutPoint, OutputsValidator, RawTxPool, ResponseFormat,
+    Script, Transaction, TransactionAndWitnessProof, TransactionView,
+    TransactionWithStatusResponse, TxPoolIds, TxPoolInfo, TxStatus, Uint64,
 };
-use ckb_sdk::rpc::ckb_indexer::{Cell, Pagination, SearchKey};
-use ckb_types::{packed, prelude::*, H256};
+use ckb_sdk::rpc::ckb_indexer::{Cell, Pagination, ScriptSearchMode, SearchKey, SearchKeyFilter};
+use ckb_sdk::rpc::ckb_light_client::ScriptType;
+use ckb_types::{bytes::Bytes, core, packed, prelude::*, utilities::CBMT, H256};
+use serde_json::json;
 use std::{
-    collections::HashMap,
-    sync::{Arc, RwLock},
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Bound,
+    sync::{Arc, Mutex, RwLock, Weak},
 };
 use tendermint_rpc::Url;
 
 use super::prelude::{CkbReader, CkbWriter, Response as Rpc};
 use crate::error::Error;
 
+const GENESIS_TIMESTAMP: u64 = 1_700_000_000_000;
+const BLOCK_INTERVAL: u64 = 8_000;
+// defaults of the tx-pool of a CKB node, fee rates are in shannons per kilobyte
+const MIN_FEE_RATE: u64 = 1_000;
+const MIN_RBF_RATE: u64 = 1_500;
+const TX_SIZE_LIMIT: u64 = 512_000;
+const MAX_TX_POOL_SIZE: u64 = 180_000_000;
+
+lazy_static::lazy_static! {
+    /// Nodes started by the tests, keyed by their CKB RPC URL. A node stops
+    /// with its last client, so a test never sees the state of a previous one
+    static ref NODES: Mutex<HashMap<String, Weak<RwLock<MockChain>>>> = Mutex::default();
+}
+
+/// Block number, index of the transaction in the block and index of the
+/// output, the order the indexer returns cells in
+type CellPosition = (u64, u32, u32);
+
+type OutPointKey = (H256, u32);
+
+fn out_point_key(out_point: &packed::OutPoint) -> OutPointKey {
+    (out_point.tx_hash().unpack(), out_point.index().unpack())
+}
+
 #[derive(Clone)]
 pub struct RpcClient {
-    data: Arc<RwLock<RpcData>>,
+    chain: Arc<RwLock<MockChain>>,
+}
+
+struct PoolTx {
+    tx: core::TransactionView,
+    fee: u64,
 }
 
-#[derive(Default)]
-struct RpcData {
+struct MockChain {
     chain_info: Option<String>,
 
-    cells: HashMap<String, Vec<Cell>>,
+    blocks: Vec<core::BlockView>,
+    /// Block number and index in the block of the committed transactions
+    committed: HashMap<H256, (u64, usize)>,
+    /// Transactions replaced in the tx-pool, with the reason
+    rejected: HashMap<H256, String>,
+
+    live_cells: BTreeMap<CellPosition, Cell>,
+    positions: HashMap<OutPointKey, CellPosition>,
+    dead_cells: HashSet<OutPointKey>,
+
+    pool: Vec<PoolTx>,
+    /// Commits each accepted transaction in a block of its own
+    auto_mine: bool,
 
+    /// Transactions accepted by `send_transaction`, in order
     transactions: Vec<Transaction>,
 }
 
-impl RpcClient {
-    pub fn new(_ckb_uri: &Url, _indexer_uri: &Url) -> Self {
+impl Default for MockChain {
+    fn default() -> Self {
+        let genesis = core::BlockBuilder::default()
+            .number(0u64.pack())
+            .timestamp(GENESIS_TIMESTAMP.pack())
+            .build();
         Self {
-            data: Arc::new(RwLock::new(RpcData::default())),
+            chain_info: None,
+            blocks: vec![genesis],
+            committed: HashMap::new(),
+            rejected: HashMap::new(),
+            live_cells: BTreeMap::new(),
+            positions: HashMap::new(),
+            dead_cells: HashSet::new(),
+            pool: vec![],
+            auto_mine: true,
+            transactions: vec![],
+        }
+    }
+}
+
+impl MockChain {
+    fn tip(&self) -> &core::BlockView {
+        self.blocks.last().expect("genesis block")
+    }
+
+    fn block_by_hash(&self, hash: &H256) -> Option<&core::BlockView> {
+        let hash = hash.pack();
+        self.blocks.iter().find(|block| block.hash() == hash)
+    }
+
+    fn live_cell(&self, key: &OutPointKey) -> Option<&Cell> {
+        self.positions
+            .get(key)
+            .and_then(|position| self.live_cells.get(position))
+    }
+
+    fn insert_cell(&mut self, cell: Cell) {
+        let key = (cell.out_point.tx_hash.clone(), cell.out_point.index.value());
+        let position = (cell.block_number.value(), cell.tx_index.value(), key.1);
+        self.positions.insert(key, position);
+        self.live_cells.insert(position, cell);
+    }
+
+    /// Accepts `tx` into the tx-pool, rejecting it the way a node does if
+    /// its inputs can't be resolved or don't cover its outputs. Cell deps
+    /// aren't resolved, the system scripts are never deployed in the tests.
+    fn submit(&mut self, tx: core::TransactionView) -> Result<H256, Error> {
+        let tx_hash: H256 = tx.hash().unpack();
+        if self.committed.contains_key(&tx_hash)
+            || self
+                .pool
+                .iter()
+                .any(|pool_tx| pool_tx.tx.hash() == tx.hash())
+        {
+            return Err(Error::rpc_response(format!(
+                "PoolRejectedDuplicatedTransaction: Transaction({tx_hash:#x}) already exists in transaction_pool"
+            )));
+        }
+
+        let mut inputs_capacity = 0u64;
+        for input in tx.input_pts_iter() {
+            let key = out_point_key(&input);
+            if self.dead_cells.contains(&key) {
+                return Err(Error::rpc_response(format!(
+                    "TransactionFailedToResolve: Resolve failed Dead({input})"
+                )));
+            }
+            let cell = self.live_cell(&key).ok_or_else(|| {
+                Error::rpc_response(format!(
+                    "TransactionFailedToResolve: Resolve failed Unknown({input})"
+                ))
+            })?;
+            inputs_capacity += cell.output.capacity.value();
+        }
+        let outputs_capacity = tx
+            .outputs_capacity()
+            .map_err(|e| Error::rpc_response(e.to_string()))?
+            .as_u64();
+        if outputs_capacity > inputs_capacity {
+            return Err(Error::rpc_response(format!(
+                "TransactionFailedToVerify: Verification failed Transaction(OutputsSumOverflow({inputs_capacity} < {outputs_capacity}))"
+            )));
+        }
+        let fee = inputs_capacity - outputs_capacity;
+
+        // transactions spending the same cells are replaced by a higher fee
+        let inputs = tx.input_pts_iter().map(|input| out_point_key(&input));
+        let inputs = inputs.collect::<HashSet<_>>();
+        let (conflicts, pool): (Vec<_>, Vec<_>) = self.pool.drain(..).partition(|pool_tx| {
+            pool_tx
+                .tx
+                .input_pts_iter()
+                .any(|input| inputs.contains(&out_point_key(&input)))
+        });
+        self.pool = pool;
+        if let Some(max_fee) = conflicts.iter().map(|pool_tx| pool_tx.fee).max() {
+            if fee <= max_fee {
+                self.pool.extend(conflicts);
+                return Err(Error::rpc_response(format!(
+                    "PoolRejectedRBF: Tx's current fee is {fee}, expect it to be larger than {max_fee} to replace old txs"
+                )));
+            }
+            for pool_tx in conflicts {
+                self.rejected.insert(
+                    pool_tx.tx.hash().unpack(),
+                    format!("RBFRejected: replaced by {tx_hash:#x}"),
+                );
+            }
+        }
+
+        self.pool.push(PoolTx { tx, fee });
+        if self.auto_mine {
+            self.produce_block();
+        }
+        Ok(tx_hash)
+    }
+
+    /// Commits the transactions of the tx-pool in a new block
+    fn produce_block(&mut self) -> u64 {
+        let transactions = self.pool.drain(..).map(|pool_tx| pool_tx.tx).collect();
+        self.commit_block(transactions)
+    }
+
+    fn commit_block(&mut self, transactions: Vec<core::TransactionView>) -> u64 {
+        let number = self.blocks.len() as u64;
+        let parent = self.tip();
+        let block = core::BlockBuilder::default()
+            .parent_hash(parent.hash())
+            .number(number.pack())
+            .timestamp((parent.timestamp() + BLOCK_INTERVAL).pack())
+            .transactions(transactions)
+            .build();
+        for (tx_index, tx) in block.transactions().iter().enumerate() {
+            self.apply(number, tx_index, tx);
+        }
+        self.blocks.push(block);
+        number
+    }
+
+    fn apply(&mut self, number: u64, tx_index: usize, tx: &core::TransactionView) {
+        for input in tx.input_pts_iter().filter(|input| !input.is_null()) {
+            let key = out_point_key(&input);
+            if let Some(position) = self.positions.remove(&key) {
+                self.live_cells.remove(&position);
+            }
+            self.dead_cells.insert(key);
+        }
+        for (index, (output, data)) in tx.outputs_with_data_iter().enumerate() {
+            self.insert_cell(Cell {
+                output: output.into(),
+                output_data: Some(JsonBytes::from_bytes(data)),
+                out_point: packed::OutPoint::new(tx.hash(), index as u32).into(),
+                block_number: number.into(),
+                tx_index: (tx_index as u32).into(),
+            });
+        }
+        self.committed
+            .insert(tx.hash().unpack(), (number, tx_index));
+    }
+
+    fn transaction(&self, hash: &H256) -> TransactionWithStatusResponse {
+        let (tx, tx_status) = if let Some(&(number, index)) = self.committed.get(hash) {
+            let block = &self.blocks[number as usize];
+            let tx = block.transactions()[index].clone();
+            (Some(tx), TxStatus::committed(block.hash().unpack()))
+        } else if let Some(pool_tx) = self
+            .pool
+            .iter()
+            .find(|pool_tx| &Unpack::<H256>::unpack(&pool_tx.tx.hash()) == hash)
+        {
+            (Some(pool_tx.tx.clone()), TxStatus::pending())
+        } else if let Some(reason) = self.rejected.get(hash) {
+            (None, TxStatus::rejected(reason.clone()))
+        } else {
+            (None, TxStatus::unknown())
+        };
+        TransactionWithStatusResponse {
+            transaction: tx.map(|tx| ResponseFormat::json(TransactionView::from(tx))),
+            tx_status,
+            cycles: None,
+            time_added_to_pool: None,
+        }
+    }
+
+    fn fetch_live_cells(
+        &self,
+        search_key: &SearchKey,
+        limit: u32,
+        cursor: Option<JsonBytes>,
+    ) -> Result<Pagination<Cell>, Error> {
+        let start = match cursor.filter(|cursor| !cursor.is_empty()) {
+            Some(cursor) => Bound::Excluded(decode_cursor(cursor.as_bytes())?),
+            None => Bound::Unbounded,
+        };
+        let with_data = search_key.with_data.unwrap_or(true);
+        let mut last_position = None;
+        let objects = self
+            .live_cells
+            .range((start, Bound::Unbounded))
+            .filter(|(_, cell)| matches_search_key(search_key, cell))
+            .take(limit as usize)
+            .map(|(position, cell)| {
+                last_position = Some(*position);
+                let mut cell = cell.clone();
+                if !with_data {
+                    cell.output_data = None;
+                }
+                cell
+            })
+            .collect();
+        let last_cursor = last_position.map_or_else(Vec::new, encode_cursor);
+        Ok(Pagination {
+            objects,
+            last_cursor: JsonBytes::from_vec(last_cursor),
+        })
+    }
+}
+
+fn encode_cursor((number, tx_index, index): CellPosition) -> Vec<u8> {
+    [
+        &number.to_be_bytes()[..],
+        &tx_index.to_be_bytes(),
+        &index.to_be_bytes(),
+    ]
+    .concat()
+}
+
+fn decode_cursor(cursor: &[u8]) -> Result<CellPosition, Error> {
+    if cursor.len() != 16 {
+        return Err(Error::rpc_response(format!(
+            "invalid cursor 0x{}",
+            hex::encode(cursor)
+        )));
+    }
+    let number = u64::from_be_bytes(cursor[..8].try_into().unwrap());
+    let tx_index = u32::from_be_bytes(cursor[8..12].try_into().unwrap());
+    let index = u32::from_be_bytes(cursor[12..].try_into().unwrap());
+    Ok((number, tx_index, index))
+}
+
+fn matches_script(pattern: &Script, script: Option<&Script>, exact: bool) -> bool {
+    let Some(script) = script else {
+        return false;
+    };
+    let args = script.args.as_bytes();
+    script.code_hash == pattern.code_hash
+        && script.hash_type == pattern.hash_type
+        && if exact {
+            args == pattern.args.as_bytes()
+        } else {
+            args.starts_with(pattern.args.as_bytes())
         }
+}
+
+// ranges of the indexer are [start, end)
+fn in_range(range: &Option<[Uint64; 2]>, value: u64) -> bool {
+    range.as_ref().map_or(true, |[start, end]| {
+        start.value() <= value && value < end.value()
+    })
+}
+
+fn matches_search_key(search_key: &SearchKey, cell: &Cell) -> bool {
+    let (script, other_script) = match search_key.script_type {
+        ScriptType::Lock => (Some(&cell.output.lock), cell.output.type_.as_ref()),
+        ScriptType::Type => (cell.output.type_.as_ref(), Some(&cell.output.lock)),
+    };
+    // the indexer searches by prefix unless asked otherwise
+    let exact = matches!(search_key.script_search_mode, Some(ScriptSearchMode::Exact));
+    if !matches_script(&search_key.script, script, exact) {
+        return false;
+    }
+    let Some(SearchKeyFilter {
+        script,
+        script_len_range,
+        output_data_len_range,
+        output_capacity_range,
+        block_range,
+        ..
+    }) = &search_key.filter
+    else {
+        return true;
+    };
+    let script_len = other_script.map_or(0, |script| 33 + script.args.as_bytes().len() as u64);
+    let data_len = cell
+        .output_data
+        .as_ref()
+        .map_or(0, |data| data.as_bytes().len() as u64);
+    script
+        .as_ref()
+        .map_or(true, |script| matches_script(script, other_script, false))
+        && in_range(script_len_range, script_len)
+        && in_range(output_data_len_range, data_len)
+        && in_range(output_capacity_range, cell.output.capacity.value())
+        && in_range(block_range, cell.block_number.value())
+}
+
+impl RpcClient {
+    /// Connects to the node of `ckb_uri`, started if no client is connected
+    pub fn new(ckb_uri: &Url, _indexer_uri: &Url) -> Self {
+        let mut nodes = NODES.lock().unwrap();
+        nodes.retain(|_, node| node.strong_count() > 0);
+        let node = nodes.entry(ckb_uri.to_string()).or_default();
+        let chain = node.upgrade().unwrap_or_else(|| {
+            let chain = Arc::new(RwLock::new(MockChain::default()));
+            *node = Arc::downgrade(&chain);
+            chain
+        });
+        Self { chain }
     }
 
     pub fn set_blockchain_info(&self, chain_info: Option<&str>) {
-        self.data.write().unwrap().chain_info = chain_info.map(ToOwned::to_owned);
+        self.chain.write().unwrap().chain_info = chain_info.map(ToOwned::to_owned);
     }
 
+    /// Adds a live cell found by `key`, the transaction creating it is unknown
     pub fn add_cell(&self, key: &SearchKey, cell: Cell) {
-        let key_string = serde_json::to_string(key).unwrap();
-        self.data
-            .write()
-            .unwrap()
-            .cells
-            .entry(key_string)
-            .and_modify(|v| v.push(cell.clone()))
-            .or_insert_with(|| vec![cell]);
+        assert!(
+            matches_search_key(key, &cell),
+            "cell doesn't match the search key"
+        );
+        self.chain.write().unwrap().insert_cell(cell);
+    }
+
+    /// Restarts the node from its genesis block, dropping every block,
+    /// transaction and cell
+    pub fn reset(&self) {
+        *self.chain.write().unwrap() = MockChain::default();
     }
 
-    pub fn clear_cells(&self) {
-        self.data.write().unwrap().cells = HashMap::default();
+    /// Commits a transaction creating the cell in a new block
+    pub fn add_live_cell(&self, output: packed::CellOutput, data: Bytes) -> packed::OutPoint {
+        let mut chain = self.chain.write().unwrap();
+        let number = chain.blocks.len() as u64;
+        let tx = core::TransactionBuilder::default()
+            .input(packed::CellInput::new_cellbase_input(number))
+            .output(output)
+            .output_data(data.pack())
+            .build();
+        let out_point = packed::OutPoint::new(tx.hash(), 0);
+        chain.commit_block(vec![tx]);
+        out_point
+    }
+
+    /// Commits `tx` in a new block without checking its inputs, e.g. to deploy
+    /// the cells a test starts from
+    pub fn commit_transaction(&self, tx: packed::Transaction) -> H256 {
+        let tx = tx.into_view();
+        let tx_hash = tx.hash().unpack();
+        self.chain.write().unwrap().commit_block(vec![tx]);
+        tx_hash
+    }
+
+    /// Commits each sent transaction in a block of its own if enabled, which
+    /// is the default, otherwise they stay pending until `produce_block`
+    pub fn set_auto_mine(&self, auto_mine: bool) {
+        self.chain.write().unwrap().auto_mine = auto_mine;
+    }
+
+    /// Commits the pending transactions in a new block, returning its number
+    pub fn produce_block(&self) -> u64 {
+        self.chain.write().unwrap().produce_block()
+    }
+
+    pub fn is_live(&self, out_point: &packed::OutPoint) -> bool {
+        self.chain
+            .read()
+            .unwrap()
+            .live_cell(&out_point_key(out_point))
+            .is_some()
     }
 
     pub fn get_transaction_by_index(&self, index: usize) -> Option<Transaction> {
-        self.data.read().unwrap().transactions.get(index).cloned()
+        self.chain.read().unwrap().transactions.get(index).cloned()
     }
 
     pub fn get_transactions_len(&self) -> usize {
-        self.data.read().unwrap().transactions.len()
+        self.chain.read().unwrap().transactions.len()
     }
 }
 
 impl CkbReader for RpcClient {
     fn get_blockchain_info(&self) -> Rpc<ChainInfo> {
-        let resp = if let Some(ref chain_info) = self.data.read().unwrap().chain_info {
+        let resp = if let Some(ref chain_info) = self.chain.read().unwrap().chain_info {
             Ok(serde_json::from_str(chain_info).unwrap())
         } else {
             Err(Error::rpc_response("data is not set".to_owned()))
@@ -77,58 +476,99 @@ impl CkbReader for RpcClient {
     }
 
     fn get_block_by_number(&self, number: BlockNumber) -> Rpc<BlockView> {
-        todo!()
+        let resp = self
+            .chain
+            .read()
+            .unwrap()
+            .blocks
+            .get(number.value() as usize)
+            .map(|block| block.clone().into())
+            .ok_or_else(|| Error::rpc_response(format!("block {} not found", number.value())));
+        Box::pin(async { resp })
     }
 
     fn get_block(&self, hash: &H256) -> Rpc<BlockView> {
-        let resp = BlockView {
-            header: HeaderView {
-                inner: Header {
-                    number: 1u64.into(),
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-            ..Default::default()
-        };
+        let resp = self
+            .chain
+            .read()
+            .unwrap()
+            .block_by_hash(hash)
+            .map(|block| block.clone().into())
+            .ok_or_else(|| Error::rpc_response(format!("block {hash:#x} not found")));
+        Box::pin(async { resp })
+    }
+
+    fn get_header_by_number(&self, number: BlockNumber) -> Rpc<Option<HeaderView>> {
+        let resp = self
+            .chain
+            .read()
+            .unwrap()
+            .blocks
+            .get(number.value() as usize)
+            .map(|block| block.header().into());
         Box::pin(async { Ok(resp) })
     }
 
     fn get_tip_header(&self) -> Rpc<HeaderView> {
-        let resp = HeaderView {
-            inner: Header {
-                number: u64::MAX.into(),
-                ..Default::default()
-            },
-            ..Default::default()
-        };
+        let resp = self.chain.read().unwrap().tip().header().into();
         Box::pin(async { Ok(resp) })
     }
 
-    fn get_header(&self, _hash: &H256) -> Rpc<Option<HeaderView>> {
-        todo!()
+    fn get_header(&self, hash: &H256) -> Rpc<Option<HeaderView>> {
+        let resp = self
+            .chain
+            .read()
+            .unwrap()
+            .block_by_hash(hash)
+            .map(|block| block.header().into());
+        Box::pin(async { Ok(resp) })
     }
 
     fn get_transaction(&self, hash: &H256) -> Rpc<Option<TransactionWithStatusResponse>> {
-        let transaction = ResponseFormat::<TransactionView>::json(Default::default());
-        let resp = TransactionWithStatusResponse {
-            transaction: Some(transaction),
-            tx_status: TxStatus::committed(hash.clone()),
-            cycles: None,
-            time_added_to_pool: None,
-        };
+        let resp = self.chain.read().unwrap().transaction(hash);
         Box::pin(async { Ok(Some(resp)) })
     }
 
     fn get_live_cell(&self, out_point: &OutPoint, with_data: bool) -> Rpc<CellWithStatus> {
-        todo!()
+        let chain = self.chain.read().unwrap();
+        let key = out_point_key(&out_point.clone().into());
+        let resp = if let Some(cell) = chain.live_cell(&key) {
+            let data = with_data.then(|| {
+                let content = cell.output_data.clone().unwrap_or_default();
+                let hash = packed::CellOutput::calc_data_hash(content.as_bytes()).unpack();
+                CellData { content, hash }
+            });
+            CellWithStatus {
+                cell: Some(CellInfo {
+                    output: cell.output.clone(),
+                    data,
+                }),
+                status: "live".to_owned(),
+            }
+        } else {
+            let status = if chain.dead_cells.contains(&key) {
+                "dead"
+            } else {
+                "unknown"
+            };
+            CellWithStatus {
+                cell: None,
+                status: status.to_owned(),
+            }
+        };
+        Box::pin(async { Ok(resp) })
     }
 
     fn get_txs_by_hashes(
         &self,
         hashes: Vec<H256>,
     ) -> Rpc<Vec<Option<TransactionWithStatusResponse>>> {
-        todo!()
+        let chain = self.chain.read().unwrap();
+        let resp = hashes
+            .iter()
+            .map(|hash| Some(chain.transaction(hash)))
+            .collect();
+        Box::pin(async { Ok(resp) })
     }
 
     fn get_transaction_and_witness_proof(
@@ -136,7 +576,41 @@ impl CkbReader for RpcClient {
         tx_hashes: Vec<H256>,
         block_hash: H256,
     ) -> Rpc<TransactionAndWitnessProof> {
-        todo!()
+        let chain = self.chain.read().unwrap();
+        let resp = chain
+            .block_by_hash(&block_hash)
+            .ok_or_else(|| Error::rpc_response(format!("block {block_hash:#x} not found")))
+            .and_then(|block| {
+                let indices = tx_hashes
+                    .iter()
+                    .map(|hash| {
+                        let hash = hash.pack();
+                        block
+                            .tx_hashes()
+                            .iter()
+                            .position(|tx_hash| tx_hash == &hash)
+                            .map(|index| index as u32)
+                            .ok_or_else(|| {
+                                Error::rpc_response(format!(
+                                    "transaction {hash} not found in block {block_hash:#x}"
+                                ))
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let proof = |leaves: &[packed::Byte32]| {
+                    let proof = CBMT::build_merkle_proof(leaves, &indices).expect("merkle proof");
+                    MerkleProof {
+                        indices: proof.indices().iter().map(|&index| index.into()).collect(),
+                        lemmas: proof.lemmas().iter().map(Unpack::unpack).collect(),
+                    }
+                };
+                Ok(TransactionAndWitnessProof {
+                    block_hash: block_hash.clone(),
+                    transactions_proof: proof(&block.tx_hashes()),
+                    witnesses_proof: proof(&block.tx_witness_hashes()),
+                })
+            });
+        Box::pin(async { resp })
     }
 
     fn fetch_live_cells(
@@ -145,52 +619,61 @@ impl CkbReader for RpcClient {
         limit: u32,
         cursor: Option<JsonBytes>,
     ) -> Rpc<Pagination<Cell>> {
-        let key_string = serde_json::to_string(&search_key).unwrap();
-        let index = cursor
-            .map(|json_bytes| {
-                let bytes = json_bytes.as_bytes();
-                let mut u32_be_bytes = [0u8; 4];
-                u32_be_bytes[..].copy_from_slice(&bytes[..4]);
-                u32::from_be_bytes(u32_be_bytes)
-            })
-            .unwrap_or(0);
-        let mut cells = self
-            .data
+        let resp = self
+            .chain
             .read()
             .unwrap()
-            .cells
-            .get(&key_string)
-            .map(ToOwned::to_owned)
-            .unwrap_or_default();
-        let cells_count = cells.len() as u32;
-        let resp = if cells_count > index {
-            let mut objects = cells.split_off(index as usize);
-            objects.truncate(limit as usize);
-            let new_index = index + limit;
-            let new_index = if cells_count > new_index {
-                new_index
-            } else {
-                u32::MAX
-            };
-            Pagination {
-                objects,
-                last_cursor: JsonBytes::from_vec(new_index.to_be_bytes().to_vec()),
-            }
-        } else {
-            Pagination {
-                objects: Default::default(),
-                last_cursor: JsonBytes::from_vec(u32::MAX.to_be_bytes().to_vec()),
-            }
-        };
-        Box::pin(async { Ok(resp) })
+            .fetch_live_cells(&search_key, limit, cursor);
+        Box::pin(async { resp })
     }
 
+    // transactions are only listed by hash, even if `verbose` is set
     fn get_raw_tx_pool(&self, verbose: bool) -> Rpc<RawTxPool> {
-        todo!()
+        let pending = self
+            .chain
+            .read()
+            .unwrap()
+            .pool
+            .iter()
+            .map(|pool_tx| pool_tx.tx.hash().unpack())
+            .collect();
+        let resp = RawTxPool::Ids(TxPoolIds {
+            pending,
+            proposed: vec![],
+        });
+        Box::pin(async { Ok(resp) })
     }
 
     fn tx_pool_info(&self) -> Rpc<TxPoolInfo> {
-        todo!()
+        let chain = self.chain.read().unwrap();
+        let tip = chain.tip();
+        let tip_hash: H256 = tip.hash().unpack();
+        let total_tx_size = chain
+            .pool
+            .iter()
+            .map(|pool_tx| pool_tx.tx.data().serialized_size_in_block() as u64)
+            .sum::<u64>();
+        let resp = serde_json::from_value(json!({
+            "tip_hash": tip_hash,
+            "tip_number": Uint64::from(tip.number()),
+            "pending": Uint64::from(chain.pool.len() as u64),
+            "proposed": Uint64::from(0u64),
+            "orphan": Uint64::from(0u64),
+            "total_tx_size": Uint64::from(total_tx_size),
+            "total_tx_cycles": Uint64::from(0u64),
+            "min_fee_rate": Uint64::from(MIN_FEE_RATE),
+            "min_rbf_rate": Uint64::from(MIN_RBF_RATE),
+            "last_txs_updated_at": Uint64::from(tip.timestamp()),
+            "tx_size_limit": Uint64::from(TX_SIZE_LIMIT),
+            "max_tx_pool_size": Uint64::from(MAX_TX_POOL_SIZE),
+        }))
+        .map_err(|e| Error::rpc_response(e.to_string()));
+        Box::pin(async { resp })
+    }
+
+    // a node without enough committed transactions has no statistics
+    fn get_fee_rate_statistics(&self, target: Option<Uint64>) -> Rpc<Option<FeeRateStatistics>> {
+        Box::pin(async { Ok(None) })
     }
 }
 
@@ -201,8 +684,243 @@ impl CkbWriter for RpcClient {
         outputs_validator: Option<OutputsValidator>,
     ) -> Rpc<H256> {
         let packed_tx: packed::Transaction = tx.clone().into();
-        let tx_hash = packed_tx.calc_tx_hash();
-        self.data.write().unwrap().transactions.push(tx.clone());
-        Box::pin(async move { Ok(tx_hash.unpack()) })
+        let mut chain = self.chain.write().unwrap();
+        let resp = chain.submit(packed_tx.into_view());
+        if resp.is_ok() {
+            chain.transactions.push(tx.clone());
+        }
+        Box::pin(async { resp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ckb_jsonrpc_types::{Either, Status};
+    use ckb_sdk::traits::{CellQueryOptions, ValueRangeOption};
+    use ckb_types::core::{Capacity, ScriptHashType};
+    use ckb_types::utilities::merkle_root;
+    use futures::executor::block_on;
+
+    use super::*;
+
+    fn client(name: &str) -> RpcClient {
+        let url: Url = format!("http://mock_rpc_{name}").parse().unwrap();
+        RpcClient::new(&url, &url)
+    }
+
+    fn script(args: &[u8]) -> packed::Script {
+        packed::Script::new_builder()
+            .code_hash(h256(1).pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(args.pack())
+            .build()
+    }
+
+    fn h256(byte: u8) -> H256 {
+        H256([byte; 32])
+    }
+
+    fn output(lock: packed::Script, ckb: u64) -> packed::CellOutput {
+        packed::CellOutput::new_builder()
+            .lock(lock)
+            .capacity(Capacity::bytes(ckb as usize).unwrap().pack())
+            .build()
+    }
+
+    fn transfer(input: &packed::OutPoint, outputs: &[u64]) -> Transaction {
+        let tx = core::TransactionBuilder::default()
+            .input(packed::CellInput::new(input.clone(), 0))
+            .outputs(outputs.iter().map(|&ckb| output(script(b"to"), ckb)))
+            .outputs_data(outputs.iter().map(|_| Bytes::new().pack()))
+            .build();
+        tx.data().into()
+    }
+
+    fn send(client: &RpcClient, tx: &Transaction) -> Result<H256, Error> {
+        block_on(client.send_transaction(tx, None))
+    }
+
+    #[test]
+    fn test_send_transaction_consumes_inputs() {
+        let client = client("consume");
+        let input = client.add_live_cell(output(script(b"from"), 1_000), Bytes::new());
+        assert!(client.is_live(&input));
+
+        let tx_hash = send(&client, &transfer(&input, &[400, 500])).unwrap();
+        assert!(!client.is_live(&input));
+        assert!(client.is_live(&packed::OutPoint::new(tx_hash.pack(), 1)));
+        assert_eq!(client.get_transactions_len(), 1);
+
+        let err = send(&client, &transfer(&input, &[100])).unwrap_err();
+        assert!(err.to_string().contains("Dead"), "{err}");
+        let unknown = packed::OutPoint::new(h256(2).pack(), 0);
+        let err = send(&client, &transfer(&unknown, &[100])).unwrap_err();
+        assert!(err.to_string().contains("Unknown"), "{err}");
+
+        let input = packed::OutPoint::new(tx_hash.pack(), 0);
+        let err = send(&client, &transfer(&input, &[500])).unwrap_err();
+        assert!(err.to_string().contains("OutputsSumOverflow"), "{err}");
+        assert_eq!(client.get_transactions_len(), 1);
+
+        let tx = block_on(client.get_transaction(&tx_hash)).unwrap().unwrap();
+        let tip = block_on(client.get_tip_header()).unwrap();
+        assert_eq!(tx.tx_status.block_hash, Some(tip.hash));
+        let cell = block_on(client.get_live_cell(&input.into(), true)).unwrap();
+        assert_eq!(cell.status, "live");
+        let cell = block_on(client.get_live_cell(&unknown.into(), false)).unwrap();
+        assert_eq!(cell.status, "unknown");
+    }
+
+    #[test]
+    fn test_pending_transaction_replaced_by_fee() {
+        let client = client("pending");
+        client.set_auto_mine(false);
+        let input = client.add_live_cell(output(script(b"from"), 1_000), Bytes::new());
+
+        let first = send(&client, &transfer(&input, &[900])).unwrap();
+        let err = send(&client, &transfer(&input, &[950])).unwrap_err();
+        assert!(err.to_string().contains("PoolRejectedRBF"), "{err}");
+        let second = send(&client, &transfer(&input, &[800])).unwrap();
+        assert!(client.is_live(&input));
+
+        let status = |hash: &H256| {
+            let tx = block_on(client.get_transaction(hash)).unwrap().unwrap();
+            tx.tx_status.status
+        };
+        assert_eq!(status(&first), Status::Rejected);
+        assert_eq!(status(&second), Status::Pending);
+        let pool_info = block_on(client.tx_pool_info()).unwrap();
+        assert_eq!(pool_info.pending.value(), 1);
+
+        let number = client.produce_block();
+        assert_eq!(status(&second), Status::Committed);
+        assert!(!client.is_live(&input));
+        let header = block_on(client.get_header_by_number(number.into())).unwrap();
+        assert!(header.is_some());
+    }
+
+    #[test]
+    fn test_node_lifetime_and_reset() {
+        let node = client("lifetime");
+        let input = node.add_live_cell(output(script(b"from"), 1_000), Bytes::new());
+        // clients of the same URL share the node while one of them is alive
+        assert!(client("lifetime").is_live(&input));
+        drop(node);
+        let node = client("lifetime");
+        assert!(!node.is_live(&input));
+
+        let input = node.add_live_cell(output(script(b"from"), 1_000), Bytes::new());
+        let hash = send(&node, &transfer(&input, &[900])).unwrap();
+        node.reset();
+        assert!(!node.is_live(&input));
+        assert_eq!(node.get_transactions_len(), 0);
+        let tx = block_on(node.get_transaction(&hash)).unwrap().unwrap();
+        assert_eq!(tx.tx_status.status, Status::Unknown);
+        let tip = block_on(node.get_tip_header()).unwrap();
+        assert_eq!(tip.inner.number.value(), 0);
+    }
+
+    #[test]
+    fn test_fetch_live_cells_with_search_key() {
+        let client = client("search");
+        let typed = packed::CellOutput::new_builder()
+            .lock(script(b"lock-1"))
+            .type_(Some(script(b"type")).pack())
+            .capacity(Capacity::bytes(1_000).unwrap().pack())
+            .build();
+        client.add_live_cell(typed, Bytes::from(vec![0u8; 16]));
+        client.add_live_cell(output(script(b"lock-2"), 1_000), Bytes::new());
+        client.add_live_cell(output(script(b"lock"), 2_000), Bytes::new());
+
+        let search = |search_key: SearchKey| {
+            let cells = block_on(client.fetch_live_cells(search_key, 10, None));
+            cells.unwrap().objects
+        };
+        let prefix: SearchKey = CellQueryOptions::new_lock(script(b"lock")).into();
+        assert_eq!(search(prefix).len(), 3);
+        let mut exact = CellQueryOptions::new_lock(script(b"lock"));
+        exact.script_search_mode = Some(ScriptSearchMode::Exact);
+        assert_eq!(search(exact.into()).len(), 1);
+        let by_type: SearchKey = CellQueryOptions::new_type(script(b"type")).into();
+        assert_eq!(search(by_type).len(), 1);
+
+        let mut query = CellQueryOptions::new_lock(script(b"lock"));
+        query.secondary_script = Some(script(b""));
+        query.data_len_range = Some(ValueRangeOption::new_exact(16));
+        let cells = search(query.into());
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].output_data.as_ref().unwrap().len(), 16);
+
+        let mut query = CellQueryOptions::new_lock(script(b"lock"));
+        query.capacity_range = Some(ValueRangeOption::new_min(
+            Capacity::bytes(1_500).unwrap().as_u64(),
+        ));
+        query.with_data = Some(false);
+        let cells = search(query.into());
+        assert_eq!(cells.len(), 1);
+        assert!(cells[0].output_data.is_none());
+    }
+
+    #[test]
+    fn test_fetch_live_cells_by_page() {
+        let client = client("page");
+        let out_points = (0..5)
+            .map(|_| client.add_live_cell(output(script(b"lock"), 1_000), Bytes::new()))
+            .map(OutPoint::from)
+            .collect::<Vec<_>>();
+        let search_key: SearchKey = CellQueryOptions::new_lock(script(b"lock")).into();
+
+        let mut cursor = None;
+        let mut fetched = vec![];
+        loop {
+            let page = block_on(client.fetch_live_cells(search_key.clone(), 2, cursor)).unwrap();
+            if page.objects.is_empty() {
+                break;
+            }
+            fetched.extend(page.objects.into_iter().map(|cell| cell.out_point));
+            cursor = Some(page.last_cursor);
+        }
+        assert_eq!(fetched, out_points);
+    }
+
+    #[test]
+    fn test_transaction_and_witness_proof() {
+        let client = client("proof");
+        client.set_auto_mine(false);
+        let inputs = (0..3)
+            .map(|_| client.add_live_cell(output(script(b"from"), 1_000), Bytes::new()))
+            .collect::<Vec<_>>();
+        let hashes = inputs
+            .iter()
+            .map(|input| send(&client, &transfer(input, &[900])).unwrap())
+            .collect::<Vec<_>>();
+        client.produce_block();
+
+        let header = block_on(client.get_tip_header()).unwrap();
+        let proof =
+            client.get_transaction_and_witness_proof(vec![hashes[1].clone()], header.hash.clone());
+        let proof = block_on(proof).unwrap();
+        let tx = block_on(client.get_transaction(&hashes[1]))
+            .unwrap()
+            .unwrap();
+        let Some(Either::Left(tx)) = tx.transaction.map(|tx| tx.inner) else {
+            panic!("transaction in json");
+        };
+        let tx = packed::Transaction::from(tx.inner);
+
+        let root = |proof: &MerkleProof, leaf: packed::Byte32| {
+            let indices = proof.indices.iter().map(|index| index.value()).collect();
+            let lemmas = proof.lemmas.iter().map(Pack::pack).collect();
+            ckb_types::utilities::MerkleProof::new(indices, lemmas)
+                .root(&[leaf])
+                .unwrap()
+        };
+        let raw_root = root(&proof.transactions_proof, tx.calc_tx_hash());
+        let witnesses_root = root(&proof.witnesses_proof, tx.calc_witness_hash());
+        let transactions_root = merkle_root(&[raw_root, witnesses_root]);
+        assert_eq!(
+            Unpack::<H256>::unpack(&transactions_root),
+            header.inner.transactions_root
+        );
     }
 }
@@ -1,9 +1,9 @@
 #![allow(dead_code)]
 
 use ckb_jsonrpc_types::{
-    BlockNumber, BlockView, CellWithStatus, ChainInfo, HeaderView, JsonBytes, OutPoint,
-    OutputsValidator, RawTxPool, Transaction, TransactionAndWitnessProof,
-    TransactionWithStatusResponse, TxPoolInfo, Uint32,
+    BlockNumber, BlockView, CellWithStatus, ChainInfo, FeeRateStatistics, HeaderView, JsonBytes,
+    OutPoint, OutputsValidator, RawTxPool, Transaction, TransactionAndWitnessProof,
+    TransactionWithStatusResponse, TxPoolInfo, Uint32, Uint64,
 };
 use ckb_sdk::rpc::ckb_indexer::{Cell, Order, Pagination, SearchKey};
 use ckb_types::H256;
@@ -102,6 +102,17 @@ impl CkbReader for RpcClient {
         jsonrpc!("get_header", Target::CKB, self, Option<HeaderView>, hash).boxed()
     }
 
+    fn get_header_by_number(&self, number: BlockNumber) -> Rpc<Option<HeaderView>> {
+        jsonrpc!(
+            "get_header_by_number",
+            Target::CKB,
+            self,
+            Option<HeaderView>,
+            number
+        )
+        .boxed()
+    }
+
     fn get_transaction(&self, hash: &H256) -> Rpc<Option<TransactionWithStatusResponse>> {
         jsonrpc!(
             "get_transaction",
@@ -191,6 +202,17 @@ impl CkbReader for RpcClient {
     fn tx_pool_info(&self) -> Rpc<TxPoolInfo> {
         jsonrpc!("tx_pool_info", Target::CKB, self, TxPoolInfo).boxed()
     }
+
+    fn get_fee_rate_statistics(&self, target: Option<Uint64>) -> Rpc<Option<FeeRateStatistics>> {
+        jsonrpc!(
+            "get_fee_rate_statistics",
+            Target::CKB,
+            self,
+            Option<FeeRateStatistics>,
+            target
+        )
+        .boxed()
+    }
 }
 
 impl CkbWriter for RpcClient {
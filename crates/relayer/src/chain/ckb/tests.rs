@@ -104,10 +104,10 @@ fn test_create_eth_multi_client(case_id: usize) {
     let tmp_dir = TempDir::new().unwrap();
     let testdata_dir = format!("{}/case-{}", TESTDATA_DIR, case_id);
 
-    let mut chain = {
+    let mut chain: CkbChain = {
         let ckb_config = CkbChainConfig {
             id: ChainId::new("chainA".to_string(), 10),
-            ckb_rpc: Url::from_str("http://ckb_rpc").unwrap(),
+            ckb_rpc: Url::from_str(&format!("http://ckb_rpc_{case_id}")).unwrap(),
             ckb_indexer_rpc: Url::from_str("http://ckb_indexer_rpc").unwrap(),
             lightclient_contract_typeargs: h256!("0x123"),
             lightclient_lock_typeargs: h256!("0x123"),
@@ -118,6 +118,8 @@ fn test_create_eth_multi_client(case_id: usize) {
             minimal_updates_count: 1,
             key_name: "ckb-chain-test".to_string(),
             data_dir: tmp_dir.path().to_path_buf(),
+            eth_network: Default::default(),
+            eth_chain_id: None,
         };
         let config = ChainConfig::Ckb(ckb_config);
         let rt = Arc::new(TokioRuntime::new().unwrap());
@@ -204,7 +206,7 @@ fn test_create_eth_multi_client(case_id: usize) {
 //     let mut chain = {
 //         let ckb_config = CkbChainConfig {
 //             id: ChainId::new("chainA".to_string(), 10),
-//             ckb_rpc: Url::from_str("http://ckb_rpc").unwrap(),
+//             ckb_rpc: Url::from_str(&format!("http://ckb_rpc_{case_id}")).unwrap(),
 //             ckb_indexer_rpc: Url::from_str("http://ckb_indexer_rpc").unwrap(),
 //             lightclient_contract_typeargs: h256!("0x123"),
 //             lightclient_lock_typeargs: h256!("0x123"),
@@ -294,7 +296,7 @@ fn test_create_eth_multi_client(case_id: usize) {
 //         assert_eq!(expected_data, actual_data);
 //     }
 
-//     rpc_client.clear_cells();
+//     rpc_client.reset();
 
 //     {
 //         let contract_type_args = chain
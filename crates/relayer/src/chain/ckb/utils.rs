@@ -16,7 +16,6 @@ use ibc_relayer_storage::{
 use ibc_relayer_types::clients::ics07_eth::types::{Header as EthHeader, Update as EthUpdate};
 use std::sync::Arc;
 use std::time::Duration;
-use tendermint_light_client::errors::Error as LightClientError;
 use tracing::debug;
 
 use crate::chain::ckb::communication::CkbReader;
@@ -24,10 +23,6 @@ use crate::error::Error;
 
 use super::rpc_client::RpcClient;
 
-pub fn into_height(slot: u64) -> tendermint::block::Height {
-    slot.try_into().expect("slot too big")
-}
-
 fn into_cached_headers(header_updates: &[EthUpdate]) -> Vec<HeaderWithCache> {
     header_updates
         .iter()
@@ -64,7 +59,7 @@ where
     }
 
     let mut finalized_headers_iter = finalized_headers.iter();
-    let mut last_slot = if storage.is_initialized()? {
+    let last_slot = if storage.is_initialized()? {
         finalized_headers[0].inner.slot - 1
     } else {
         let first = finalized_headers_iter.next().expect("checked");
@@ -73,14 +68,12 @@ where
         first.inner.slot
     };
 
-    let mut mmr = storage.chain_root_mmr(last_slot)?;
-    for header in finalized_headers_iter {
-        last_slot = header.inner.slot;
-        mmr.push(header.digest()).map_err(StorageError::from)?;
-    }
-    mmr.commit().map_err(StorageError::from)?;
-
-    storage.put_tip_beacon_header_slot(last_slot)?;
+    // all digests are committed in one write batch, which matters when
+    // catching up with thousands of headers
+    let digests = finalized_headers_iter
+        .map(|header| (header.inner.slot, header.digest()))
+        .collect::<Vec<_>>();
+    storage.append_beacon_header_digests(last_slot, &digests)?;
     Ok(())
 }
 
@@ -119,12 +112,10 @@ where
     if let Some(stored_base_slot) = storage.get_base_beacon_header_slot()? {
         // unrecoverable condition
         if stored_base_slot != onchain_minimal_slot {
-            return Err(Error::light_client_verification(
+            return Err(Error::eth_multi_client_base_slot_mismatch(
                 chain_id.to_owned(),
-                LightClientError::target_lower_than_trusted_state(
-                    into_height(onchain_minimal_slot),
-                    into_height(stored_base_slot),
-                ),
+                onchain_minimal_slot,
+                stored_base_slot,
             ));
         }
     }
@@ -147,17 +138,17 @@ where
                     .get_tip_beacon_header_slot()?
                     .expect("reacquire stored tip slot");
             } else {
-                return Err(Error::light_client_verification(
+                return Err(Error::eth_multi_client_behind(
                     chain_id.to_owned(),
-                    LightClientError::missing_last_block_id(into_height(stored_tip_slot + 1)),
+                    stored_tip_slot + 1,
                 ));
             }
         }
         // recoverable condition: need to make native tip slot chases to onchain maximal slot
         if stored_tip_slot < onchain_maximal_slot {
-            return Err(Error::light_client_verification(
+            return Err(Error::eth_multi_client_behind(
                 chain_id.to_owned(),
-                LightClientError::missing_last_block_id(into_height(stored_tip_slot + 1)),
+                stored_tip_slot + 1,
             ));
         }
     } else {
@@ -169,9 +160,9 @@ where
                 upcoming_start_slot, upcoming_last_slot
             );
         } else {
-            return Err(Error::light_client_verification(
+            return Err(Error::eth_multi_client_behind(
                 chain_id.to_owned(),
-                LightClientError::missing_last_block_id(into_height(onchain_minimal_slot)),
+                onchain_minimal_slot,
             ));
         }
         let stored_tip_slot = storage
@@ -179,9 +170,9 @@ where
             .expect("reaquire stored tip slot");
         // recoverable condition: need to make native tip slot chases to onchain maximal slot
         if stored_tip_slot < onchain_maximal_slot {
-            return Err(Error::light_client_verification(
+            return Err(Error::eth_multi_client_behind(
                 chain_id.to_owned(),
-                LightClientError::missing_last_block_id(into_height(stored_tip_slot + 1)),
+                stored_tip_slot + 1,
             ));
         }
     }
@@ -216,9 +207,9 @@ where
     if let Some(client) = onchain_packed_client_opt {
         let onchain_tip_slot: u64 = client.maximal_slot().unpack();
         if start_slot != onchain_tip_slot + 1 {
-            return Err(Error::light_client_verification(
+            return Err(Error::eth_multi_client_behind(
                 chain_id.to_string(),
-                LightClientError::missing_last_block_id(into_height(onchain_tip_slot + 1)),
+                onchain_tip_slot + 1,
             ));
         }
         prev_tip_slot = Some(onchain_tip_slot);
@@ -408,7 +399,6 @@ mod tests {
     use ibc_relayer_storage::Storage;
     use ibc_relayer_types::clients::ics07_eth::types::{Header as EthHeader, Update as EthUpdate};
     use tempfile::TempDir;
-    use tendermint_light_client::errors::ErrorDetail::MissingLastBlockId;
     use tree_hash::TreeHash;
 
     use super::{
@@ -416,7 +406,7 @@ mod tests {
         commit_headers_into_mmr_storage, get_verified_packed_client_and_proof_update,
         into_cached_headers,
     };
-    use crate::error::ErrorDetail::LightClientVerification;
+    use crate::error::ErrorDetail::EthMultiClientBehind;
 
     const TESTDATA_DIR: &str = "src/testdata/test_update_eth_client";
 
@@ -477,13 +467,10 @@ mod tests {
         );
         if let Err(error) = result {
             match error.detail() {
-                LightClientVerification(error) => match &error.source {
-                    MissingLastBlockId(block) => {
-                        let missing_slot = updates_part_1.last().unwrap().finalized_header.slot + 1;
-                        assert_eq!(block.height, missing_slot.try_into().unwrap());
-                    }
-                    _ => panic!("unexpected error"),
-                },
+                EthMultiClientBehind(error) => {
+                    let missing_slot = updates_part_1.last().unwrap().finalized_header.slot + 1;
+                    assert_eq!(error.next_slot, missing_slot);
+                }
                 _ => panic!("unexpected error"),
             }
         }
@@ -1,8 +1,10 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::account::Balance;
 use crate::chain::ckb::prelude::{CellSearcher, CkbReader, CkbWriter, TxCompleter};
@@ -10,7 +12,7 @@ use crate::chain::ckb4ibc::extractor::extract_channel_end_from_tx;
 use crate::chain::ckb4ibc::utils::{get_connection_index_by_id, get_connection_search_key};
 use crate::chain::endpoint::ChainEndpoint;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
-use crate::config::ckb4ibc::{ChainConfig as Ckb4IbcChainConfig, LightClientItem};
+use crate::config::ckb4ibc::{ChainConfig as Ckb4IbcChainConfig, FeeRate, LightClientItem};
 use crate::config::ChainConfig;
 use crate::connection::ConnectionMsgType;
 use crate::consensus_state::AnyConsensusState;
@@ -21,6 +23,7 @@ use crate::event::IbcEventWithHeight;
 use crate::keyring::{KeyRing, Secp256k1KeyPair};
 use crate::misbehaviour::MisbehaviourEvidence;
 
+use ckb_chain_spec::consensus::Consensus;
 use ckb_ics_axon::commitment::{
     channel_path, connection_path, packet_acknowledgement_commitment_path, packet_commitment_path,
 };
@@ -30,21 +33,28 @@ use ckb_ics_axon::object::Ordering;
 use ckb_ics_axon::{ChannelArgs, ConnectionArgs};
 use ckb_jsonrpc_types::{Status, TransactionView};
 use ckb_sdk::constants::TYPE_ID_CODE_HASH;
+use ckb_sdk::rpc::ckb_indexer::Cell;
 use ckb_sdk::traits::SecpCkbRawKeySigner;
 use ckb_sdk::unlock::{ScriptSigner, SecpSighashScriptSigner};
 use ckb_sdk::{Address, AddressPayload, NetworkType, ScriptGroup, ScriptGroupType};
-use ckb_types::core::ScriptHashType;
 use ckb_types::core::TransactionView as CoreTransactionView;
+use ckb_types::core::{Cycle, ScriptHashType};
 use ckb_types::molecule::prelude::Entity;
 use ckb_types::packed::{CellInput, OutPoint, Script, WitnessArgs};
 use ckb_types::prelude::{Builder, Pack, Unpack};
 use ckb_types::H256;
+use ethers::types::{BlockId, BlockNumber};
 use futures::TryFutureExt;
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
+use ibc_relayer_storage::prelude::DenomTraceStore;
+use ibc_relayer_storage::{ReadOnlyStorage, Storage};
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::applications::transfer::msgs::transfer::{
+    MsgTransfer, TYPE_URL as TRANSFER_TYPE_URL,
+};
 use ibc_relayer_types::clients::ics07_ckb::{
     client_state::CkbClientState, consensus_state::CkbConsensusState, header::CkbHeader,
     light_block::CkbLightBlock,
@@ -66,25 +76,38 @@ use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::proofs::Proofs;
 use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::tx_msg::Msg;
 use ibc_relayer_types::Height;
 use rlp::Encodable;
 use semver::Version;
 use std::sync::RwLock;
 use tendermint::Hash as TxHash;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use tendermint_rpc::Url;
 use tokio::runtime::Runtime;
 use tokio::sync::watch::Sender as WatchSender;
 use tracing::{info, warn};
 
+use self::denom_trace::{extract_denom_trace, sudt_amount, sudt_denom};
+use self::dry_run::{build_consensus, resolve_transaction, verify_transaction};
 use self::extractor::{extract_connections_from_tx, extract_ibc_packet_from_tx};
-use self::message::{convert_msg_to_ckb_tx, CkbTxInfo, Converter, MsgToTxConverter};
-use self::monitor::{Ckb4IbcEventMonitor, WriteAckMonitorCmd};
+use self::message::{
+    convert_msg_to_ckb_tx, convert_transfer_to_tx, CkbTxInfo, Converter, MsgToTxConverter,
+    SudtTransferCells,
+};
+use self::monitor::WriteAckMonitorCmd;
+use self::scanner::{
+    fetch_all_live_cells, fetch_cell_transactions, fetch_committed_cell_transactions, CellTxCache,
+};
 use self::utils::{
-    fetch_transaction_by_hash, generate_ibc_packet_event, generate_tx_proof_from_block,
-    get_channel_search_key, get_encoded_object, get_ibc_merkle_proof, get_packet_search_key,
-    get_prefix_search_key, get_search_key_with_sudt, parse_transaction, transaction_to_event,
+    bump_tx_fee, decode_axon_metadata_cell, generate_ibc_packet_event,
+    generate_tx_proof_from_block, get_channel_search_key, get_encoded_object, get_ibc_merkle_proof,
+    get_packet_search_key, get_prefix_search_key, get_script_hash, get_search_key_with_sudt,
+    get_search_key_with_sudt_script, parse_transaction, transaction_to_event,
 };
 
+use super::axon::rpc::{AxonRpc as _, AxonRpcClient};
+use super::axon::utils::to_timestamp;
 use super::ckb::rpc_client::RpcClient;
 use super::ckb::utils::wait_ckb_transaction_committed;
 use super::client::ClientSettings;
@@ -107,11 +130,20 @@ use super::tracking::TrackedMsgs;
 use tokio::runtime::Runtime as TokioRuntime;
 
 mod cache_set;
+mod cursor;
+mod denom_trace;
+mod dry_run;
 pub mod extractor;
 pub mod message;
 mod monitor;
+mod scanner;
 pub mod utils;
 
+#[cfg(test)]
+mod tests;
+
+pub use cursor::CursorStore;
+pub use monitor::Ckb4IbcEventMonitor;
 pub use utils::keccak256;
 
 pub struct ConnectionCache {
@@ -126,12 +158,21 @@ type PacketInputData = HashMap<(ChannelId, PortId, Sequence), (CellInput, u64)>;
 type ChannelCacheKey = (ChannelId, PortId);
 type PacketCacheKey = (ChannelId, PortId, Sequence);
 
+/// A signed IBC transaction, along with what's needed to replace it with a
+/// higher paying one
+pub struct SignedTx {
+    pub tx: CoreTransactionView,
+    pub fee: u64,
+    pub first_sig_input_idx: usize,
+}
+
 pub struct Ckb4IbcChain {
     rt: Arc<TokioRuntime>,
     rpc_client: Arc<RpcClient>,
     config: Ckb4IbcChainConfig,
     keybase: KeyRing<Secp256k1KeyPair>,
     cached_network: RwLock<Option<NetworkType>>,
+    cached_consensus: RefCell<Option<Arc<Consensus>>>,
 
     tx_monitor_cmd: Option<TxMonitorCmd>,
     tx_write_ack_cmd: Option<WriteAckMonitorCmd>,
@@ -150,6 +191,9 @@ pub struct Ckb4IbcChain {
     packet_cache: RefCell<HashMap<PacketCacheKey, IbcPacket>>,
 
     ibc_transactions_cache: Arc<Mutex<HashMap<String, H256>>>,
+    cell_tx_cache: CellTxCache,
+    // read-only if the storage is locked by another relayer process
+    denom_traces: Option<Box<dyn DenomTraceStore>>,
 }
 
 impl Ckb4IbcChain {
@@ -179,11 +223,12 @@ impl Ckb4IbcChain {
     }
 
     pub fn tx_assembler_address(&self) -> Result<Address, Error> {
+        self.key_address(&self.config.key_name)
+    }
+
+    fn key_address(&self, key_name: &str) -> Result<Address, Error> {
         let network = self.network()?;
-        let key: Secp256k1KeyPair = self
-            .keybase
-            .get_key(&self.config.key_name)
-            .map_err(Error::key_base)?;
+        let key: Secp256k1KeyPair = self.keybase.get_key(key_name).map_err(Error::key_base)?;
         let address_payload = AddressPayload::from_pubkey(&key.public_key);
         let address = Address::new(network, address_payload, true);
         Ok(address)
@@ -205,6 +250,7 @@ impl Ckb4IbcChain {
             self.rpc_client.clone(),
             self.config.clone(),
             self.counterparty_client_type.subscribe(),
+            CursorStore::in_home_dir(&self.config.id).map_err(Error::event_monitor)?,
         );
         let ibc_transaction_cache = self.ibc_transactions_cache.clone();
         std::thread::spawn(move || loop {
@@ -226,7 +272,11 @@ impl Ckb4IbcChain {
         sequence: Option<Sequence>,
     ) -> Result<Vec<(IbcPacket, CellInput, u64, H256)>, Error> {
         // packets with particular sequence are only 4: Send, WriteAck, Recv and AckPacket
-        let limit = if sequence.is_some() { 4 } else { 20 };
+        let page_size = if sequence.is_some() {
+            4
+        } else {
+            self.config.indexer_page_size
+        };
         let search_key = get_packet_search_key(
             &self.config,
             self.counterparty_client_type(),
@@ -234,52 +284,79 @@ impl Ckb4IbcChain {
             port_id,
             sequence,
         )?;
+        let cells = self.rt.block_on(fetch_all_live_cells(
+            self.rpc_client.as_ref(),
+            search_key,
+            page_size,
+        ))?;
+        let cells = self.rt.block_on(fetch_cell_transactions(
+            self.rpc_client.as_ref(),
+            &self.cell_tx_cache,
+            cells,
+            self.config.max_concurrent_tx_fetches,
+        ))?;
         let mut result = vec![];
-        let mut cursor = None;
-        loop {
-            let cells = self.rt.block_on(self.rpc_client.fetch_live_cells(
-                search_key.clone(),
-                limit,
-                cursor,
-            ))?;
-            for cell in cells.objects {
-                let tx_hash = cell.out_point.tx_hash.clone();
-                let tx = self.rt.block_on(fetch_transaction_by_hash(
-                    self.rpc_client.as_ref(),
-                    &tx_hash,
-                ))?;
-                let (packet, _) = extract_ibc_packet_from_tx(&tx)?;
-                let cell_input = CellInput::new_builder()
-                    .previous_output(cell.out_point.into())
-                    .build();
+        for (cell, tx) in cells {
+            let tx_hash = cell.out_point.tx_hash.clone();
+            let (packet, _) = extract_ibc_packet_from_tx(&tx)?;
+            self.record_denom_trace(&packet, &tx);
+            let cell_input = CellInput::new_builder()
+                .previous_output(cell.out_point.into())
+                .build();
+
+            let channel_id: ChannelId = packet.packet.source_channel_id.parse().unwrap();
+            let port_id: PortId = packet.packet.source_port_id.parse().unwrap();
+            let sequence: Sequence = packet.packet.sequence.try_into().unwrap();
+
+            self.packet_input_data.borrow_mut().insert(
+                (channel_id.clone(), port_id.clone(), sequence),
+                (cell_input.clone(), u64::from(cell.output.capacity)),
+            );
+            self.packet_cache
+                .borrow_mut()
+                .insert((channel_id, port_id, sequence), packet.clone());
 
-                let channel_id: ChannelId = packet.packet.source_channel_id.parse().unwrap();
-                let port_id: PortId = packet.packet.source_port_id.parse().unwrap();
-                let sequence: Sequence = packet.packet.sequence.try_into().unwrap();
+            result.push((packet, cell_input, u64::from(cell.block_number), tx_hash));
+        }
 
-                self.packet_input_data.borrow_mut().insert(
-                    (channel_id.clone(), port_id.clone(), sequence),
-                    (cell_input.clone(), u64::from(cell.output.capacity)),
-                );
-                self.packet_cache
-                    .borrow_mut()
-                    .insert((channel_id, port_id, sequence), packet.clone());
+        Ok(result)
+    }
 
-                result.push((packet, cell_input, u64::from(cell.block_number), tx_hash));
-            }
-            // iterate if search all packets
-            if sequence.is_none() {
-                if cells.last_cursor.is_empty() {
-                    break;
-                } else {
-                    cursor = Some(cells.last_cursor);
-                }
-            } else {
-                break;
+    fn record_denom_trace(&self, packet: &IbcPacket, tx: &TransactionView) {
+        let (Some(storage), Some(sudt_transfer)) = (&self.denom_traces, &self.config.sudt_transfer)
+        else {
+            return;
+        };
+        let Some((denom, record)) = extract_denom_trace(
+            packet,
+            tx,
+            &sudt_transfer.lock_script(),
+            &sudt_transfer.sudt_code_hash(),
+        ) else {
+            return;
+        };
+        if let Ok(Some(recorded)) = storage.get_denom_trace(&denom) {
+            if recorded == record {
+                return;
             }
         }
+        match storage.put_denom_trace(&denom, &record) {
+            Ok(()) => info!("recorded denom trace {} of {denom}", record.full_denom()),
+            Err(err) => warn!("failed to record denom trace of {denom}: {err}"),
+        }
+    }
 
-        Ok(result)
+    // the type script of the sUDT of `denom` if it's recorded in the denom traces
+    fn recorded_sudt_type_script(&self, denom: &str) -> Result<Option<Script>, Error> {
+        let Some(storage) = &self.denom_traces else {
+            return Ok(None);
+        };
+        let Some(record) = storage.get_denom_trace(denom)? else {
+            return Ok(None);
+        };
+        let script = Script::from_slice(&record.sudt_type_script)
+            .map_err(|e| Error::other_error(format!("invalid sUDT of {denom}: {e}")))?;
+        Ok(Some(script))
     }
 
     fn fetch_channel_cell_and_extract(
@@ -339,42 +416,35 @@ impl Ckb4IbcChain {
 
     fn query_connection_and_cache(&self) -> Result<(), Error> {
         let search_key = get_connection_search_key(&self.config, None)?;
-        let future = self
-            .rpc_client
-            // FIXME: use `u32::MAX` to search all cells in one action may be a little heavy if cells are too much,
-            //        use `loop` expression to progressivly search
-            .fetch_live_cells(search_key, u32::MAX, None)
-            .and_then(|response| async {
-                let mut resps = vec![];
-                for cell in response.objects {
-                    let tx = self
-                        .rpc_client
-                        .get_transaction(&cell.out_point.tx_hash)
-                        .await?;
-                    let cell_input = CellInput::new_builder()
-                        .previous_output(cell.out_point.into())
-                        .build();
-                    let capacity: u64 = cell.output.capacity.into();
-                    let args = match ConnectionArgs::from_slice(cell.output.lock.args.as_bytes()) {
-                        Ok(a) => a,
-                        Err(_) => continue,
-                    };
-                    if let Ok(client_type) = self.config.lc_client_type(&args.client_id()) {
-                        resps.push((tx, cell_input, capacity, client_type, args));
-                    }
-                }
-                Ok(resps)
-            });
+        let cells = self.rt.block_on(fetch_all_live_cells(
+            self.rpc_client.as_ref(),
+            search_key,
+            self.config.indexer_page_size,
+        ))?;
+        let connection_of = |cell: &Cell| {
+            let args = ConnectionArgs::from_slice(cell.output.lock.args.as_bytes()).ok()?;
+            let client_type = self.config.lc_client_type(&args.client_id()).ok()?;
+            Some((client_type, args))
+        };
+        let cells = cells
+            .into_iter()
+            .filter(|cell| connection_of(cell).is_some())
+            .collect();
+        let cells = self.rt.block_on(fetch_cell_transactions(
+            self.rpc_client.as_ref(),
+            &self.cell_tx_cache,
+            cells,
+            self.config.max_concurrent_tx_fetches,
+        ))?;
         let mut cache = self.connection_cache.borrow_mut();
         let prefix = self.query_commitment_prefix()?;
-        for (transaction, cell_input, capacity, client_type, connection_args) in
-            self.rt.block_on(future)?
-        {
-            let tx = transaction
-                .expect("empty transaction response")
-                .transaction
-                .expect("empty transaction view");
-            let tx = parse_transaction(tx);
+        for (cell, tx) in cells {
+            let (client_type, connection_args) =
+                connection_of(&cell).expect("filtered connection cell");
+            let cell_input = CellInput::new_builder()
+                .previous_output(cell.out_point.into())
+                .build();
+            let capacity: u64 = cell.output.capacity.into();
             let (connections, ibc_connection) = extract_connections_from_tx(&tx, &prefix)?;
             cache.insert(
                 client_type,
@@ -390,6 +460,63 @@ impl Ckb4IbcChain {
         Ok(())
     }
 
+    /// Reads the cell of the light client of `client_type`. An Axon metadata
+    /// cell verifies the blocks of its current epoch against the validators
+    /// of that epoch, so the client is regarded as updated up to the first
+    /// block of the epoch, and its root is the hash of the validators.
+    /// The heights verified by the consensus state are returned as well.
+    fn query_onchain_light_client(
+        &self,
+        client_type: ClientType,
+    ) -> Result<(CkbClientState, CkbConsensusState, Range<u64>), Error> {
+        let item = self
+            .config
+            .onchain_light_clients
+            .get(&client_type)
+            .ok_or_else(|| {
+                Error::other_error(format!("config.toml missing client_type {client_type}"))
+            })?;
+        if client_type != ClientType::Axon {
+            return Err(Error::query(format!(
+                "decoding the light client cell of {client_type} is not supported"
+            )));
+        }
+        let cell = self
+            .rt
+            .block_on(self.rpc_client.search_cell_by_typescript(
+                &self.config.client_code_hash.pack(),
+                &item.client_cell_type_args.as_bytes().to_owned(),
+            ))?
+            .ok_or_else(|| Error::query(format!("client cell not found on {}", item.chain_id)))?;
+        // the cell gets replaced on each update of the client
+        self.client_outpoints
+            .borrow_mut()
+            .insert(client_type, cell.out_point.clone());
+
+        let (heights, root) = decode_axon_metadata_cell(&cell.output_data)?;
+        let client_state = CkbClientState {
+            chain_id: item.chain_id.clone(),
+            latest_height: Height::from_noncosmos_height(heights.start),
+        };
+        // the metadata cell stores no block time, which is the one of the
+        // first block of the epoch on Axon
+        let timestamp = match &item.rpc_addr {
+            Some(rpc_addr) => self.axon_block_timestamp(rpc_addr, heights.start)?,
+            None => Timestamp::none(),
+        };
+        let consensus_state = CkbConsensusState { root, timestamp };
+        Ok((client_state, consensus_state, heights))
+    }
+
+    fn axon_block_timestamp(&self, rpc_addr: &Url, number: u64) -> Result<Timestamp, Error> {
+        let block_id = BlockId::Number(BlockNumber::Number(number.into()));
+        let block = self
+            .rt
+            .block_on(AxonRpcClient::new(rpc_addr).get_block_by_id(block_id))?
+            .ok_or_else(|| Error::other_error(format!("Axon block {number} not found")))?;
+        to_timestamp(block.header.timestamp)
+    }
+
     fn clear_cache(&mut self) {
         self.channel_input_data.get_mut().clear();
         self.channel_cache.get_mut().clear();
@@ -397,13 +524,39 @@ impl Ckb4IbcChain {
         self.connection_cache.get_mut().clear();
     }
 
+    /// Returns the fee rate of the next transaction, in shannons per byte
+    fn fee_rate(&self) -> Result<u64, Error> {
+        let max_rate = match self.config.fee_rate {
+            FeeRate::Fixed { rate } => return Ok(rate),
+            FeeRate::Dynamic { max_rate } => max_rate,
+        };
+        let (statistics, pool_info) = self.rt.block_on(futures::future::try_join(
+            self.rpc_client.get_fee_rate_statistics(None),
+            self.rpc_client.tx_pool_info(),
+        ))?;
+        // the node counts fee rates in shannons per kilobyte
+        let median = statistics.map(|s| s.median.into()).unwrap_or(0u64);
+        let min_fee_rate: u64 = pool_info.min_fee_rate.into();
+        let fee_rate = (median.max(min_fee_rate) + 999) / 1000;
+        if fee_rate > max_rate {
+            warn!(
+                "fee rate {fee_rate} suggested by {} exceeds the ceiling {max_rate}, use the ceiling",
+                self.id()
+            );
+            return Ok(max_rate);
+        }
+        Ok(fee_rate)
+    }
+
+    /// Completes the transaction with capacity inputs and a change output,
+    /// returning it along with the fee it pays
     pub fn complete_tx_with_secp256k1_change_and_envelope(
         &self,
         tx: CoreTransactionView,
         input_capacity: u64,
         envelope: Envelope,
-    ) -> Result<CoreTransactionView, Error> {
-        let fee_rate = 3000;
+    ) -> Result<(CoreTransactionView, u64), Error> {
+        let fee_rate = self.fee_rate()?;
         let address = self.tx_assembler_address()?;
         let tx = self.rpc_client.complete_tx_with_secp256k1_change(
             tx,
@@ -435,7 +588,166 @@ impl Ckb4IbcChain {
             .witness(WitnessArgs::new_builder().build().as_bytes().pack())
             .witness(witness)
             .build();
-        Ok(tx)
+        Ok((tx, total_inputs_capacity - total_outputs_capacity))
+    }
+
+    fn sign_transaction(
+        &self,
+        tx: &CoreTransactionView,
+        first_sig_input_idx: usize,
+    ) -> Result<CoreTransactionView, Error> {
+        let last_input_idx = tx.inputs().len() - 1;
+        let secret_key = self
+            .keybase
+            .get_key(&self.config.key_name)
+            .map_err(Error::key_base)?
+            .into_ckb_keypair(self.network()?)
+            .private_key;
+        let signer = SecpSighashScriptSigner::new(Box::new(
+            SecpCkbRawKeySigner::new_with_secret_keys(vec![secret_key]),
+        ));
+        signer
+            .sign_tx(
+                tx,
+                &ScriptGroup {
+                    script: Script::from(&self.tx_assembler_address()?),
+                    group_type: ScriptGroupType::Lock,
+                    input_indices: (first_sig_input_idx..=last_input_idx).collect(),
+                    output_indices: vec![],
+                },
+            )
+            .map_err(|err| Error::other_error(err.to_string()))
+    }
+
+    /// Verifies the scripts of the transaction against the cells it currently
+    /// refers to, returning the consumed cycles
+    fn dry_run_transaction(&self, tx: &CoreTransactionView) -> Result<Cycle, Error> {
+        let (resolved_tx, loader) = self
+            .rt
+            .block_on(resolve_transaction(self.rpc_client.as_ref(), tx.clone()))?;
+        let tip = self.rt.block_on(self.rpc_client.get_tip_header())?;
+        let script_names = [
+            (
+                get_script_hash(&self.config.connection_type_args),
+                "connection",
+            ),
+            (get_script_hash(&self.config.channel_type_args), "channel"),
+            (get_script_hash(&self.config.packet_type_args), "packet"),
+            (self.config.client_code_hash.pack(), "light-client"),
+            (
+                Script::from(&self.tx_assembler_address()?).code_hash(),
+                "secp256k1-sighash",
+            ),
+        ];
+        let script_name = |script: &Script| {
+            let code_hash = script.code_hash();
+            match script_names.iter().find(|(hash, _)| hash == &code_hash) {
+                Some((_, name)) => name.to_string(),
+                None => format!("{:#x}", Unpack::<H256>::unpack(&code_hash)),
+            }
+        };
+        verify_transaction(
+            resolved_tx,
+            loader,
+            self.consensus()?,
+            &tip.into(),
+            script_name,
+        )
+    }
+
+    /// Consensus of the chain's network, the dry run follows its hardforks
+    fn consensus(&self) -> Result<Arc<Consensus>, Error> {
+        if let Some(consensus) = self.cached_consensus.borrow().as_ref() {
+            return Ok(Arc::clone(consensus));
+        }
+        let consensus = Arc::new(build_consensus(self.network()?)?);
+        *self.cached_consensus.borrow_mut() = Some(Arc::clone(&consensus));
+        Ok(consensus)
+    }
+
+    /// Builds the replacement of `signed` which pays the minimal extra fee
+    /// accepted by the tx-pool, the extra fee is taken from the change output
+    fn bump_fee(&self, signed: &SignedTx) -> Result<SignedTx, Error> {
+        let pool_info = self.rt.block_on(self.rpc_client.tx_pool_info())?;
+        let min_fee_rate: u64 = pool_info.min_fee_rate.into();
+        let min_rbf_rate: u64 = pool_info.min_rbf_rate.into();
+        if min_rbf_rate <= min_fee_rate {
+            return Err(Error::send_tx(format!("RBF is disabled on {}", self.id())));
+        }
+        let change_lock = Script::from(&self.tx_assembler_address()?);
+        let (tx, fee) = bump_tx_fee(
+            &signed.tx,
+            signed.fee,
+            &change_lock,
+            min_rbf_rate,
+            self.config.fee_rate.ceiling(),
+        )?;
+        let tx = self.sign_transaction(&tx, signed.first_sig_input_idx)?;
+        Ok(SignedTx {
+            tx,
+            fee,
+            first_sig_input_idx: signed.first_sig_input_idx,
+        })
+    }
+
+    /// Waits for the transaction to be committed and confirmed, replacing it
+    /// by RBF whenever it stays pending longer than `tx_replacement_timeout`.
+    /// Returns the hash of the committed transaction with its block number.
+    fn wait_committed_or_replace(
+        &self,
+        mut signed: SignedTx,
+        mut tx_hash: H256,
+    ) -> Result<(H256, u64), Error> {
+        let deadline = Instant::now() + self.config.tx_timeout;
+        loop {
+            let time_limit = deadline.saturating_duration_since(Instant::now());
+            let err = match self.rt.block_on(wait_ckb_transaction_committed(
+                &self.rpc_client,
+                tx_hash.clone(),
+                Duration::from_secs(10),
+                self.config.confirms,
+                time_limit.min(self.config.tx_replacement_timeout),
+            )) {
+                Ok(height) => return Ok((tx_hash, height)),
+                Err(err) => err,
+            };
+            if time_limit <= self.config.tx_replacement_timeout {
+                return Err(err);
+            }
+            let status = self
+                .rt
+                .block_on(self.rpc_client.get_transaction(&tx_hash))?
+                .map(|tx| tx.tx_status.status);
+            match status {
+                Some(Status::Pending) => {}
+                // proposed and committed transactions can't be replaced anymore
+                Some(Status::Proposed) | Some(Status::Committed) => continue,
+                _ => return Err(err),
+            }
+            let replacement = match self.bump_fee(&signed) {
+                Ok(replacement) => replacement,
+                Err(e) => {
+                    warn!("transaction {tx_hash:#x} can't be replaced: {e}");
+                    continue;
+                }
+            };
+            let tx: TransactionView = replacement.tx.clone().into();
+            match self
+                .rt
+                .block_on(self.rpc_client.send_transaction(&tx.inner, None))
+            {
+                Ok(hash) => {
+                    warn!(
+                        "transaction {tx_hash:#x} stays pending for {:?}, replaced by {hash:#x} paying fee {}",
+                        self.config.tx_replacement_timeout, replacement.fee
+                    );
+                    tx_hash = hash;
+                    signed = replacement;
+                }
+                // the replaced transaction may have been proposed in the meantime
+                Err(e) => warn!("failed to replace transaction {tx_hash:#x}: {e}"),
+            }
+        }
     }
 
     fn counterparty_client_type(&self) -> ClientType {
@@ -475,54 +787,166 @@ impl Ckb4IbcChain {
         Ok(packets.first().cloned())
     }
 
+    /// Collects the cells moving the sUDT of an ICS-20 transfer out of CKB,
+    /// returning them along with the denom of the sUDT in the packet
+    fn fetch_sudt_transfer_cells(
+        &self,
+        msg: &MsgTransfer,
+    ) -> Result<(String, SudtTransferCells), Error> {
+        let sudt_transfer = self.config.sudt_transfer.as_ref().ok_or_else(|| {
+            Error::other_error(format!(
+                "sUDT transfer application of {} isn't configured",
+                self.id()
+            ))
+        })?;
+        let denom = msg.token.denom.trim_start_matches("0x");
+        let amount: u128 =
+            msg.token.amount.parse().map_err(|_| {
+                Error::other_error(format!("invalid sUDT amount {}", msg.token.amount))
+            })?;
+
+        // take the sUDT cells of the sender until they cover the amount
+        let sender_lock: Script = self.tx_assembler_address()?.payload().into();
+        let cells = self.rt.block_on(fetch_all_live_cells(
+            self.rpc_client.as_ref(),
+            get_prefix_search_key(sender_lock.clone()),
+            self.config.indexer_page_size,
+        ))?;
+        let mut sudt_type_script = None;
+        let mut sender_cells = vec![];
+        let (mut sender_capacity, mut sender_amount) = (0, 0);
+        for cell in cells {
+            let (Some(type_script), Some(data)) = (cell.output.type_, cell.output_data) else {
+                continue;
+            };
+            let type_script: Script = type_script.into();
+            // the change cell holds no data after the amount, so the cells
+            // with such data are left untouched
+            if sudt_denom(&type_script) != denom || data.len() != 16 {
+                continue;
+            }
+            let cell_amount = sudt_amount(data.as_bytes())?;
+            sender_cells.push(
+                CellInput::new_builder()
+                    .previous_output(cell.out_point.into())
+                    .build(),
+            );
+            sender_capacity += cell.output.capacity.value();
+            sender_amount += cell_amount;
+            sudt_type_script = Some(type_script);
+            if sender_amount >= amount {
+                break;
+            }
+        }
+        let sudt_type_script = sudt_type_script.ok_or_else(|| {
+            Error::other_error(format!(
+                "key {} owns no sUDT of denom {denom}",
+                self.config.key_name
+            ))
+        })?;
+
+        let st_lock = sudt_transfer.lock_script();
+        let st_cell = self
+            .rt
+            .block_on(fetch_all_live_cells(
+                self.rpc_client.as_ref(),
+                get_search_key_with_sudt_script(st_lock.clone(), sudt_type_script.clone()),
+                self.config.indexer_page_size,
+            ))?
+            .into_iter()
+            .next()
+            .map(|cell| {
+                let data = cell.output_data.unwrap_or_default().into_bytes();
+                let escrowed = sudt_amount(&data)?;
+                let input = CellInput::new_builder()
+                    .previous_output(cell.out_point.into())
+                    .build();
+                let capacity = cell.output.capacity.value();
+                Ok::<_, Error>((input, capacity, escrowed, data.slice(16..)))
+            })
+            .transpose()?;
+
+        let mut cell_deps = vec![];
+        for (type_args, name) in [
+            (&sudt_transfer.sudt_type_args, "sUDT"),
+            (&sudt_transfer.contract_type_args, "sUDT transfer"),
+        ] {
+            let cell = self
+                .rt
+                .block_on(self.rpc_client.search_cell_by_typescript(
+                    &TYPE_ID_CODE_HASH.pack(),
+                    &type_args.as_bytes().to_owned(),
+                ))?
+                .ok_or_else(|| Error::other_error(format!("{name} contract not found")))?;
+            cell_deps.push(cell.out_point);
+        }
+
+        // vouchers are sent back with their full trace
+        let packet_denom = match &self.denom_traces {
+            Some(storage) => storage.get_denom_trace(denom)?.map(|r| r.full_denom()),
+            None => None,
+        }
+        .unwrap_or_else(|| denom.to_owned());
+
+        let cells = SudtTransferCells {
+            st_lock,
+            sudt_type_script,
+            st_cell,
+            sender_lock,
+            sender_cells,
+            sender_capacity,
+            sender_amount,
+            cell_deps,
+        };
+        Ok((packet_denom, cells))
+    }
+
     #[allow(clippy::type_complexity)]
     fn assemble_transaction_from_msg(
         &self,
         msg: &Any,
-    ) -> Result<(String, Option<IbcEvent>, Option<(TransactionView, MsgType)>), Error> {
+    ) -> Result<(String, Option<IbcEvent>, Option<(SignedTx, MsgType)>), Error> {
         let converter = self.get_converter()?;
+        // the sUDT cells of the sender are the last inputs of a transfer
+        let (tx_info, sender_inputs) = if msg.type_url == TRANSFER_TYPE_URL {
+            let msg = MsgTransfer::from_any(msg.clone())
+                .map_err(|e| Error::protobuf_decode(msg.type_url.clone(), e))?;
+            let (denom, cells) = self.fetch_sudt_transfer_cells(&msg)?;
+            let sender_inputs = cells.sender_cells.len();
+            (
+                convert_transfer_to_tx(msg, &converter, denom, cells)?,
+                sender_inputs,
+            )
+        } else {
+            (convert_msg_to_ckb_tx(msg, &converter)?, 0)
+        };
         let CkbTxInfo {
             unsigned_tx,
             envelope,
             input_capacity,
             event,
             commitment_path,
-        } = convert_msg_to_ckb_tx(msg, &converter)?;
+        } = tx_info;
         if unsigned_tx.is_none() {
             return Ok((commitment_path, event, None));
         }
         let unsigned_tx = unsigned_tx.unwrap();
         // Assuming all remaining inputs are sighash capacity inputs.
-        let first_sig_input_idx = unsigned_tx.inputs().len();
+        let first_sig_input_idx = unsigned_tx.inputs().len() - sender_inputs;
         let msg_type = envelope.msg_type;
         match self.complete_tx_with_secp256k1_change_and_envelope(
             unsigned_tx,
             input_capacity,
             envelope,
         ) {
-            Ok(tx) => {
-                let last_input_idx = tx.inputs().len() - 1;
-                let secret_key = self
-                    .keybase
-                    .get_key(&self.config.key_name)
-                    .map_err(Error::key_base)?
-                    .into_ckb_keypair(self.network()?)
-                    .private_key;
-                let signer = SecpSighashScriptSigner::new(Box::new(
-                    SecpCkbRawKeySigner::new_with_secret_keys(vec![secret_key]),
-                ));
-                let tx = signer
-                    .sign_tx(
-                        &tx,
-                        &ScriptGroup {
-                            script: Script::from(&self.tx_assembler_address()?),
-                            group_type: ScriptGroupType::Lock,
-                            input_indices: (first_sig_input_idx..=last_input_idx).collect(),
-                            output_indices: vec![],
-                        },
-                    )
-                    .map_err(|err| Error::other_error(err.to_string()))?;
-                Ok((commitment_path, event, Some((tx.into(), msg_type))))
+            Ok((tx, fee)) => {
+                let tx = self.sign_transaction(&tx, first_sig_input_idx)?;
+                let signed = SignedTx {
+                    tx,
+                    fee,
+                    first_sig_input_idx,
+                };
+                Ok((commitment_path, event, Some((signed, msg_type))))
             }
             Err(err) => {
                 // return signing error such as no enough ckb
@@ -532,6 +956,25 @@ impl Ckb4IbcChain {
     }
 }
 
+// The storage is held by the first process opening it, so the other ones,
+// e.g. the CLI while the relayer is running, fall back to read its records
+fn open_denom_traces(chain_id: &ChainId, storage_dir: &Path) -> Option<Box<dyn DenomTraceStore>> {
+    let err = match Storage::<()>::new(storage_dir) {
+        Ok(storage) => return Some(Box::new(storage)),
+        Err(err) => err,
+    };
+    match ReadOnlyStorage::open(storage_dir) {
+        Ok(storage) => {
+            warn!("denom traces of {chain_id} are not recorded: {err}");
+            Some(Box::new(storage))
+        }
+        Err(err) => {
+            warn!("denom traces of {chain_id} are unavailable: {err}");
+            None
+        }
+    }
+}
+
 impl ChainEndpoint for Ckb4IbcChain {
     type LightBlock = CkbLightBlock;
     type Header = CkbHeader;
@@ -602,13 +1045,16 @@ impl ChainEndpoint for Ckb4IbcChain {
         }
 
         let keybase =
-            KeyRing::new(Default::default(), "ckb", &config.id).map_err(Error::key_base)?;
+            KeyRing::new(config.key_store_type, "ckb", &config.id).map_err(Error::key_base)?;
+        let cell_tx_cache = CellTxCache::new(config.cell_cache_size);
+        let denom_traces = open_denom_traces(&config.id, &config.storage_dir()?);
         let chain = Ckb4IbcChain {
             rt,
             rpc_client,
             config,
             keybase,
             cached_network: RwLock::new(None),
+            cached_consensus: RefCell::new(None),
             tx_monitor_cmd: None,
             tx_write_ack_cmd: None,
             client_outpoints: RefCell::new(client_outpoints),
@@ -622,6 +1068,8 @@ impl ChainEndpoint for Ckb4IbcChain {
             packet_input_data: RefCell::new(HashMap::new()),
             packet_cache: RefCell::new(HashMap::new()),
             ibc_transactions_cache: Arc::new(Mutex::default()),
+            cell_tx_cache,
+            denom_traces,
         };
         Ok(chain)
     }
@@ -702,66 +1150,76 @@ impl ChainEndpoint for Ckb4IbcChain {
                         return Ok(vec![]);
                     }
                 }
-                (commitment_path, Some(event), Some((tx, msg_type))) => match self
-                    .rt
-                    .block_on(self.rpc_client.send_transaction(&tx.inner, None))
-                {
-                    Ok(tx_hash) => {
-                        // TODO: put confirms count into config
-                        let confirms = 1;
-                        info!(
-                            "{msg_type:?} transaction {} committed to {}, wait {confirms} blocks confirmation",
-                            hex::encode(&tx_hash),
-                            self.id()
-                        );
-                        retry_times = 0;
-                        match self.rt.block_on(wait_ckb_transaction_committed(
-                            &self.rpc_client,
-                            tx_hash.clone(),
-                            Duration::from_secs(10),
-                            confirms,
-                            Duration::from_secs(600),
-                        )) {
-                            Ok(height) => {
-                                if let Some(client_type) = sync_if_create_client(&event) {
-                                    self.sync_counterparty_client_type(client_type);
-                                }
-                                self.ibc_transactions_cache
-                                    .lock()
-                                    .unwrap()
-                                    .insert(commitment_path, tx_hash.clone());
-                                let ibc_event_with_height = IbcEventWithHeight {
-                                    event,
-                                    height: Height::from_noncosmos_height(height),
-                                    tx_hash: tx_hash.into(),
-                                };
-                                result_events.push(ibc_event_with_height);
-                            }
-                            Err(err) => {
+                (commitment_path, Some(event), Some((signed, msg_type))) => {
+                    let tx: TransactionView = signed.tx.clone().into();
+                    if self.config.dry_run {
+                        match self.dry_run_transaction(&signed.tx) {
+                            Ok(cycles) => info!(
+                                "{msg_type:?} transaction passed dry-run with {cycles} cycles"
+                            ),
+                            Err(e) => {
                                 let json_tx = serde_json::to_string_pretty(&tx).unwrap();
-                                let error =
-                                    format!("wait transaction failed: {err}\n\n======== transaction info ========\n\n{json_tx}\n");
-                                warn!("{error}");
-                                continue;
+                                warn!("{e}\n\n======== transaction info ========\n\n{json_tx}\n");
+                                self.clear_cache();
+                                return Err(e);
                             }
                         }
                     }
-                    Err(e) => {
-                        let json_tx = serde_json::to_string_pretty(&tx).unwrap();
-                        let error =
-                            format!("{e}\n\n======== transaction info ========\n\n{json_tx}\n");
-                        if error.contains("UnknowOutpoint") || error.contains("PoolRejectedRBF") {
-                            if retry_times < 3 {
-                                msgs.insert(0, msg);
+                    match self
+                        .rt
+                        .block_on(self.rpc_client.send_transaction(&tx.inner, None))
+                    {
+                        Ok(tx_hash) => {
+                            let confirms = self.config.confirms;
+                            info!(
+                                "{msg_type:?} transaction {} committed to {}, wait {confirms} blocks confirmation",
+                                hex::encode(&tx_hash),
+                                self.id()
+                            );
+                            retry_times = 0;
+                            match self.wait_committed_or_replace(signed, tx_hash) {
+                                Ok((tx_hash, height)) => {
+                                    if let Some(client_type) = sync_if_create_client(&event) {
+                                        self.sync_counterparty_client_type(client_type);
+                                    }
+                                    self.ibc_transactions_cache
+                                        .lock()
+                                        .unwrap()
+                                        .insert(commitment_path, tx_hash.clone());
+                                    let ibc_event_with_height = IbcEventWithHeight {
+                                        event,
+                                        height: Height::from_noncosmos_height(height),
+                                        tx_hash: tx_hash.into(),
+                                    };
+                                    result_events.push(ibc_event_with_height);
+                                }
+                                Err(err) => {
+                                    let json_tx = serde_json::to_string_pretty(&tx).unwrap();
+                                    let error =
+                                        format!("wait transaction failed: {err}\n\n======== transaction info ========\n\n{json_tx}\n");
+                                    warn!("{error}");
+                                    continue;
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            let json_tx = serde_json::to_string_pretty(&tx).unwrap();
+                            let error =
+                                format!("{e}\n\n======== transaction info ========\n\n{json_tx}\n");
+                            if error.contains("UnknowOutpoint") || error.contains("PoolRejectedRBF")
+                            {
+                                if retry_times < 3 {
+                                    msgs.insert(0, msg);
+                                }
+                                retry_times += 1;
+                                warn!("error occurred, clear cache and try again: {e}");
+                                self.clear_cache();
+                                continue;
                             }
-                            retry_times += 1;
-                            warn!("error occurred, clear cache and try again: {e}");
-                            self.clear_cache();
-                            continue;
+                            return Err(Error::other_error(error));
                         }
-                        return Err(Error::other_error(error));
                     }
-                },
+                }
                 _ => unreachable!(),
             }
         }
@@ -814,63 +1272,80 @@ impl ChainEndpoint for Ckb4IbcChain {
         Ok(None)
     }
 
-    fn query_balance(&self, address: Option<&str>, symbol: Option<&str>) -> Result<Balance, Error> {
-        let lock_script: Script = match address {
-            Some(address) => Address::from_str(address)
-                .map_err(|e| {
-                    Error::invalid_key_address(
-                        address.to_string(),
-                        tendermint::Error::invalid_key(e.to_string()),
-                    )
-                })?
-                .payload()
-                .into(),
+    // `key_name` is either the name of a key or a CKB address
+    fn query_balance(
+        &self,
+        key_name: Option<&str>,
+        symbol: Option<&str>,
+    ) -> Result<Balance, Error> {
+        let lock_script: Script = match key_name {
+            Some(key_name) => match Address::from_str(key_name) {
+                Ok(address) => address.payload().into(),
+                Err(_) => self.key_address(key_name)?.payload().into(),
+            },
             None => self.tx_assembler_address()?.payload().into(),
         };
         let search_key = match symbol {
-            Some(symbol) => get_search_key_with_sudt(lock_script, symbol, self.network()?)?,
+            Some(symbol) => match self.recorded_sudt_type_script(symbol)? {
+                Some(sudt_script) => get_search_key_with_sudt_script(lock_script, sudt_script),
+                None => get_search_key_with_sudt(lock_script, symbol, self.network()?)?,
+            },
             None => get_prefix_search_key(lock_script),
         };
-        let asset_cells =
-            self.rt
-                .block_on(self.rpc_client.fetch_live_cells(search_key, u32::MAX, None))?;
-        let balance: u128 = asset_cells
-            .objects
+        let asset_cells = self.rt.block_on(fetch_all_live_cells(
+            self.rpc_client.as_ref(),
+            search_key,
+            self.config.indexer_page_size,
+        ))?;
+        let balance = asset_cells
             .into_iter()
             .filter_map(|cell| {
                 if symbol.is_some() {
-                    let data: [u8; 16] = cell
-                        .output_data
-                        .unwrap()
-                        .as_bytes()
-                        .try_into()
-                        .expect("sudt capacity");
-                    Some(u128::from_le_bytes(data))
+                    Some(sudt_amount(cell.output_data.unwrap_or_default().as_bytes()))
                 } else if cell.output.type_.is_some() {
                     None
                 } else {
-                    Some(cell.output.capacity.value() as u128)
+                    Some(Ok(cell.output.capacity.value() as u128))
                 }
             })
-            .sum();
-        Ok(Balance {
-            amount: balance.to_string(),
-            denom: symbol.unwrap_or("ckb").to_owned(),
-        })
-    }
-
-    fn query_all_balances(&self, address: Option<&str>) -> Result<Vec<Balance>, Error> {
-        let ckb_balance = self.query_balance(address, None)?;
-        Ok(vec![ckb_balance])
+            .sum::<Result<u128, Error>>()?;
+        Ok(Balance::new(
+            balance.to_string(),
+            symbol.unwrap_or("ckb").to_owned(),
+        ))
     }
 
-    // TODO: Ckb4Ibc's denom is hash of `sudt_type_script`, we cannot recover it to type_script,
-    //       so it's hard to watch denom trace
-    fn query_denom_trace(&self, _hash: String) -> Result<DenomTrace, Error> {
-        warn!("ckb4ibc query_denom_trace() cannot implement");
+    fn query_all_balances(&self, key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
+        let mut balances = vec![self.query_balance(key_name, None)?];
+        let Some(storage) = &self.denom_traces else {
+            return Ok(balances);
+        };
+        for (denom, _) in storage.get_all_denom_traces()? {
+            let balance = self.query_balance(key_name, Some(&denom))?;
+            if balance.amount != "0" {
+                balances.push(balance);
+            }
+        }
+        Ok(balances)
+    }
+
+    // Ckb4Ibc's denom is hash of `sudt_type_script`, which is traced back from
+    // the ICS-20 packets relayed so far
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
+        let storage = self
+            .denom_traces
+            .as_ref()
+            .ok_or_else(|| Error::other_error("denom trace storage is unavailable".to_owned()))?;
+        let denom = hash
+            .trim_start_matches("ibc/")
+            .trim_start_matches("0x")
+            .to_lowercase();
+        let record = storage
+            .get_denom_trace(&denom)?
+            .ok_or_else(|| Error::empty_denom_trace(hash))?;
         Ok(DenomTrace {
-            path: "".to_owned(),
-            base_denom: "".to_owned(),
+            path: record.path,
+            base_denom: record.base_denom,
         })
     }
 
@@ -891,27 +1366,26 @@ impl ChainEndpoint for Ckb4IbcChain {
         &self,
         _request: QueryClientStatesRequest,
     ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
-        Ok(self
-            .config
+        self.config
             .onchain_light_clients
             .keys()
-            .map(|client_type| {
-                // TODO query latest_height from light client cell (for example Axon metadata cell)
-                let client_id = self.config.lc_client_id(*client_type).unwrap();
-                let chain_id = self
-                    .config
-                    .lc_chain_id_by_client_id(&client_id.to_string())
-                    .unwrap();
-                IdentifiedAnyClientState {
-                    client_id,
-                    client_state: CkbClientState {
-                        chain_id,
-                        latest_height: Height::default(),
+            .filter_map(|client_type| {
+                let client_id = match self.config.lc_client_id(*client_type) {
+                    Ok(client_id) => client_id,
+                    Err(e) => return Some(Err(e)),
+                };
+                match self.query_onchain_light_client(*client_type) {
+                    Ok((client_state, _, _)) => Some(Ok(IdentifiedAnyClientState {
+                        client_id,
+                        client_state: client_state.into(),
+                    })),
+                    Err(e) => {
+                        warn!("skip client {client_id}: {e}");
+                        None
                     }
-                    .into(),
                 }
             })
-            .collect())
+            .collect()
     }
 
     fn query_client_state(
@@ -919,34 +1393,36 @@ impl ChainEndpoint for Ckb4IbcChain {
         request: QueryClientStateRequest,
         _include_proof: IncludeProof,
     ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
-        let chain_id = self
-            .config
-            .lc_chain_id_by_client_id(&request.client_id.to_string())?;
-        // TODO query latest_height
-        let client_state = CkbClientState {
-            chain_id,
-            latest_height: Height::default(),
-        };
         let client_type = self.config.lc_client_type(&request.client_id.to_string())?;
+        let (client_state, _, _) = self.query_onchain_light_client(client_type)?;
         self.sync_counterparty_client_type(client_type);
         Ok((client_state.into(), None))
     }
 
     fn query_consensus_state(
         &self,
-        _request: QueryConsensusStateRequest,
+        request: QueryConsensusStateRequest,
         _include_proof: IncludeProof,
     ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
-        // TODO: fix it when Ckb4Ibc contract refactorred
-        Ok((CkbConsensusState {}.into(), None))
+        let client_type = self.config.lc_client_type(&request.client_id.to_string())?;
+        let (_, consensus_state, heights) = self.query_onchain_light_client(client_type)?;
+        // light client cells only keep the validators of the current epoch
+        if !heights.contains(&request.consensus_height.revision_height()) {
+            return Err(Error::query(format!(
+                "consensus state of {} at height {} is not stored, the stored heights are {}..{}",
+                request.client_id, request.consensus_height, heights.start, heights.end
+            )));
+        }
+        Ok((consensus_state.into(), None))
     }
 
     fn query_consensus_state_heights(
         &self,
-        _request: QueryConsensusStateHeightsRequest,
+        request: QueryConsensusStateHeightsRequest,
     ) -> Result<Vec<Height>, Error> {
-        // TODO: fix it when Ckb4Ibc contract refactorred
-        Ok(vec![Height::default()])
+        let client_type = self.config.lc_client_type(&request.client_id.to_string())?;
+        let (client_state, _, _) = self.query_onchain_light_client(client_type)?;
+        Ok(vec![client_state.latest_height])
     }
 
     fn query_upgraded_client_state(
@@ -1046,29 +1522,21 @@ impl ChainEndpoint for Ckb4IbcChain {
             .hash_type(ScriptHashType::Type.into())
             .build();
         let search_key = get_prefix_search_key(script);
-        let cells_rpc_result = self.rpc_client.fetch_live_cells(search_key, u32::MAX, None);
-        let txs_rpc_result = self
-            .rt
-            .block_on(cells_rpc_result)?
-            .objects
-            .into_iter()
-            .map(|cell| self.rpc_client.get_transaction(&cell.out_point.tx_hash));
+        let cells = self.rt.block_on(fetch_all_live_cells(
+            self.rpc_client.as_ref(),
+            search_key,
+            self.config.indexer_page_size,
+        ))?;
         let channel_ends = self
             .rt
-            .block_on(futures::future::join_all(txs_rpc_result))
+            .block_on(fetch_committed_cell_transactions(
+                self.rpc_client.as_ref(),
+                &self.cell_tx_cache,
+                cells,
+                self.config.max_concurrent_tx_fetches,
+            ))
             .into_iter()
-            .filter_map(|tx| {
-                if let Ok(Some(tx)) = tx {
-                    if tx.tx_status.status == Status::Committed {
-                        return Some(tx);
-                    }
-                }
-                None
-            })
-            .flat_map(|tx| {
-                let tx = parse_transaction(tx.transaction.unwrap());
-                extract_channel_end_from_tx(&tx)
-            })
+            .flat_map(|(_, tx)| extract_channel_end_from_tx(&tx))
             .map(|(channel, _)| channel)
             .collect();
         Ok(channel_ends)
@@ -1430,7 +1898,7 @@ impl ChainEndpoint for Ckb4IbcChain {
     ) -> Result<Self::ConsensusState, Error> {
         // TODO
         warn!("axon query_host_consensus_state() not support");
-        Ok(CkbConsensusState {})
+        Ok(CkbConsensusState::default())
     }
 
     fn build_client_state(
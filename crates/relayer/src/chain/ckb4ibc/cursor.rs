@@ -0,0 +1,270 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use ckb_jsonrpc_types::JsonBytes;
+use ckb_types::H256;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use serde_derive::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::monitor::IbcProtocolType;
+use crate::event::monitor::{Error, Result};
+
+const CURSOR_DEFAULT_FOLDER: &str = ".forcerelay/checkpoints/";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockRef {
+    pub number: u64,
+    pub hash: H256,
+}
+
+/// Progress of scanning the live cells of one kind of IBC objects. Cells are
+/// scanned in rounds, each one covering the cells created up to a confirmed
+/// block, so that the events of a block are only published once it's final.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanCursor {
+    /// Indexer cursor of the ongoing round
+    pub cursor: Option<JsonBytes>,
+    /// The ongoing round covers the cells created up to this block
+    pub round_to: Option<BlockRef>,
+    /// Events of the cells created up to this block have all been published
+    pub published_to: Option<BlockRef>,
+}
+
+impl ScanCursor {
+    pub fn is_published(&self, block_number: u64) -> bool {
+        self.published_to
+            .as_ref()
+            .map_or(false, |block| block_number <= block.number)
+    }
+
+    /// Finishes the ongoing round, all its events have been published
+    pub fn finish_round(&mut self) {
+        self.cursor = None;
+        if let Some(block) = self.round_to.take() {
+            self.published_to = Some(block);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonitorCursors {
+    pub connection: ScanCursor,
+    pub channel: ScanCursor,
+    pub packet: ScanCursor,
+}
+
+impl MonitorCursors {
+    /// Cursors starting over with the events after `block` regarded as unpublished
+    pub fn rescan_from(block: Option<BlockRef>) -> Self {
+        let cursor = ScanCursor {
+            published_to: block,
+            ..Default::default()
+        };
+        Self {
+            connection: cursor.clone(),
+            channel: cursor.clone(),
+            packet: cursor,
+        }
+    }
+
+    pub fn get_mut(&mut self, protocol: IbcProtocolType) -> &mut ScanCursor {
+        match protocol {
+            IbcProtocolType::Connection => &mut self.connection,
+            IbcProtocolType::Channel => &mut self.channel,
+            IbcProtocolType::Packet => &mut self.packet,
+        }
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut ScanCursor> {
+        [&mut self.connection, &mut self.channel, &mut self.packet].into_iter()
+    }
+
+    /// All blocks the cursors rely on
+    pub fn blocks(&self) -> Vec<BlockRef> {
+        let mut blocks: Vec<_> = [&self.connection, &self.channel, &self.packet]
+            .into_iter()
+            .flat_map(|cursor| [&cursor.round_to, &cursor.published_to])
+            .flatten()
+            .cloned()
+            .collect();
+        blocks.sort_by_key(|block| block.number);
+        blocks.dedup();
+        blocks
+    }
+
+    /// Rewinds the cursors relying on blocks which were reorganized out of the
+    /// chain: their rounds restart and their published events roll back to the
+    /// closest canonical block known by the cursors, or to the genesis.
+    ///
+    /// Returns the lowest block from which published events may come from
+    /// orphaned blocks, if any cursor was rewound. The monitor retracts them
+    /// by notifying its subscribers, which then drop the pending packets they
+    /// collected, before the events of the canonical blocks are published
+    /// again.
+    pub fn rewind<F>(&mut self, is_canonical: F) -> Option<u64>
+    where
+        F: Fn(&BlockRef) -> bool,
+    {
+        let blocks = self.blocks();
+        let canonical_before = |number: u64| {
+            blocks
+                .iter()
+                .filter(|block| block.number < number && is_canonical(block))
+                .last()
+                .cloned()
+        };
+        let mut retracted_from: Option<u64> = None;
+        for cursor in self.iter_mut() {
+            let mut rewound = false;
+            if let Some(block) = &cursor.round_to {
+                if !is_canonical(block) {
+                    cursor.cursor = None;
+                    cursor.round_to = None;
+                    rewound = true;
+                }
+            }
+            if let Some(block) = &cursor.published_to {
+                if !is_canonical(block) {
+                    cursor.published_to = canonical_before(block.number);
+                    cursor.cursor = None;
+                    cursor.round_to = None;
+                    rewound = true;
+                }
+            }
+            if rewound {
+                let from = cursor
+                    .published_to
+                    .as_ref()
+                    .map_or(0, |block| block.number + 1);
+                retracted_from = Some(retracted_from.map_or(from, |number| number.min(from)));
+            }
+        }
+        retracted_from
+    }
+}
+
+/// Stores the cursors of the event monitor on disk so that it can resume
+/// from them after a restart
+pub struct CursorStore {
+    /// Nothing is stored without a path
+    path: Option<PathBuf>,
+}
+
+impl CursorStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path: Some(path) }
+    }
+
+    /// Cursors neither loaded nor stored, for monitors which must not
+    /// interfere with the one of a running relayer
+    pub fn disabled() -> Self {
+        Self { path: None }
+    }
+
+    /// Cursors of the event monitor of `chain_id`, under the home directory
+    pub fn in_home_dir(chain_id: &ChainId) -> Result<Self> {
+        let home = dirs_next::home_dir()
+            .ok_or_else(|| Error::others("cannot locate the home directory".to_owned()))?;
+        let path = home
+            .join(CURSOR_DEFAULT_FOLDER)
+            .join(format!("{chain_id}.ckb4ibc_event_monitor.json"));
+        Ok(Self::new(path))
+    }
+
+    pub fn load(&self) -> Option<MonitorCursors> {
+        let path = self.path.as_ref()?;
+        let content = match fs::read(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!("ignore unreadable cursors {}: {e}", path.display());
+                return None;
+            }
+        };
+        match serde_json::from_slice(&content) {
+            Ok(cursors) => Some(cursors),
+            Err(e) => {
+                warn!("ignore malformed cursors {}: {e}", path.display());
+                None
+            }
+        }
+    }
+
+    pub fn store(&self, cursors: &MonitorCursors) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        let content = serde_json::to_vec(cursors)?;
+        // write to a temporary file first so that a crash never leaves
+        // truncated cursors behind
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(tmp_path, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(number: u64, hash: u8) -> BlockRef {
+        BlockRef {
+            number,
+            hash: H256([hash; 32]),
+        }
+    }
+
+    #[test]
+    fn test_rewind_cursors() {
+        let mut cursors = MonitorCursors::rescan_from(Some(block(100, 0)));
+        cursors.connection.round_to = Some(block(120, 0));
+        cursors.connection.cursor = Some(JsonBytes::default());
+        cursors.channel.finish_round();
+        cursors.packet.published_to = Some(block(110, 0));
+        cursors.packet.round_to = Some(block(120, 0));
+        cursors.packet.finish_round();
+        assert!(cursors.packet.is_published(120));
+        assert!(!cursors.packet.is_published(121));
+
+        // blocks since 105 are reorganized
+        let canonical = |block: &BlockRef| block.number < 105;
+        assert_eq!(cursors.rewind(canonical), Some(101));
+        assert_eq!(cursors.connection.cursor, None);
+        assert_eq!(cursors.connection.round_to, None);
+        assert_eq!(cursors.connection.published_to, Some(block(100, 0)));
+        assert_eq!(cursors.channel.published_to, Some(block(100, 0)));
+        assert_eq!(cursors.packet.published_to, Some(block(100, 0)));
+
+        assert_eq!(cursors.rewind(canonical), None);
+        assert_eq!(cursors.rewind(|_| false), Some(0));
+        assert_eq!(cursors, MonitorCursors::rescan_from(None));
+    }
+
+    #[test]
+    fn test_store_cursors() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CursorStore::new(dir.path().join("ckb4ibc_event_monitor.json"));
+        assert_eq!(store.load(), None);
+        let mut cursors = MonitorCursors::rescan_from(Some(block(7, 1)));
+        cursors.packet.cursor = Some(JsonBytes::from_vec(vec![1, 2, 3]));
+        store.store(&cursors).unwrap();
+        assert_eq!(store.load(), Some(cursors));
+    }
+
+    #[test]
+    fn test_disabled_store() {
+        let chain_id = ChainId::new("disabled-cursors".to_owned(), 0);
+        let home_path = CursorStore::in_home_dir(&chain_id).unwrap().path.unwrap();
+        let store = CursorStore::disabled();
+        store
+            .store(&MonitorCursors::rescan_from(Some(block(7, 1))))
+            .unwrap();
+        assert_eq!(store.load(), None);
+        assert!(!home_path.exists());
+    }
+}
@@ -0,0 +1,357 @@
+use ckb_ics_axon::handler::{IbcPacket, PacketStatus};
+use ckb_jsonrpc_types::{ScriptHashType, TransactionView};
+use ckb_types::packed::{Byte32, Script};
+use ckb_types::prelude::Entity;
+use ckb_types::H256;
+use ibc_relayer_storage::denom::DenomTraceRecord;
+use prost::Message;
+
+use crate::denom::parse_denom_trace;
+use crate::error::Error;
+
+/// Packet data of the ICS-20 sUDT transfer application on CKB
+#[derive(Message)]
+pub struct FungibleTokenPacketData {
+    /// hex(sUDT type script hash) for assets issued on CKB
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    #[prost(uint64, tag = "2")]
+    pub amount: u64,
+    #[prost(bytes, tag = "3")]
+    pub sender: Vec<u8>,
+    #[prost(bytes, tag = "4")]
+    pub receiver: Vec<u8>,
+    #[prost(string, tag = "5")]
+    pub memo: String,
+}
+
+/// The denom of an sUDT on CKB, the hex-encoded hash of its type script
+pub fn sudt_denom(sudt_type_script: &Script) -> String {
+    hex::encode(sudt_type_script.calc_script_hash().as_slice())
+}
+
+/// The amount held by an sUDT cell, the little-endian `u128` of the first 16
+/// bytes of its data; the sUDT standard lets data carry extra bytes after it
+pub fn sudt_amount(data: &[u8]) -> Result<u128, Error> {
+    let amount = data
+        .get(..16)
+        .and_then(|amount| <[u8; 16]>::try_from(amount).ok())
+        .ok_or_else(|| Error::other_error(format!("invalid sUDT data 0x{}", hex::encode(data))))?;
+    Ok(u128::from_le_bytes(amount))
+}
+
+// sUDT cells are the cells of the sUDT contract holding a 16 bytes amount,
+// yielded along with the hash of their lock script
+fn sudt_cells<'a>(
+    tx: &'a TransactionView,
+    sudt_code_hash: &'a H256,
+) -> impl Iterator<Item = (Byte32, Script)> + 'a {
+    tx.inner
+        .outputs
+        .iter()
+        .zip(tx.inner.outputs_data.iter())
+        .filter(|(_, data)| data.len() >= 16)
+        .filter_map(|(output, _)| {
+            let lock: Script = output.lock.clone().into();
+            Some((lock.calc_script_hash(), output.type_.clone()?))
+        })
+        .filter(move |(_, script)| {
+            script.code_hash == *sudt_code_hash && script.hash_type == ScriptHashType::Type
+        })
+        .map(|(lock_hash, script)| (lock_hash, script.into()))
+}
+
+/// Recovers the trace of the sUDT transferred by an ICS-20 packet from the
+/// transaction creating the packet cell, returning the denom of the sUDT on
+/// CKB along with its trace. Only the cells of the sUDT contract of
+/// `sudt_code_hash` are considered, and packets of other applications are
+/// ignored.
+///
+/// The transferred sUDT is the one escrowed under `st_lock`, the lock of the
+/// ibc-sudt-transfer contract, for packets sent from CKB, and the one paid to
+/// the receiver, whose lock hash starts with the receiver of the packet, for
+/// packets received on CKB. The trace is not recovered when the cells of
+/// several sUDTs match.
+pub fn extract_denom_trace(
+    packet: &IbcPacket,
+    tx: &TransactionView,
+    st_lock: &Script,
+    sudt_code_hash: &H256,
+) -> Option<(String, DenomTraceRecord)> {
+    let data = FungibleTokenPacketData::decode(packet.packet.data.as_slice()).ok()?;
+    let (full_denom, owner) = match packet.status {
+        // the packet is sent from CKB, so the denom is already the one on CKB
+        PacketStatus::Send | PacketStatus::Ack => {
+            (data.denom, st_lock.calc_script_hash().as_slice().to_vec())
+        }
+        PacketStatus::Recv | PacketStatus::WriteAck => {
+            let source_prefix = format!(
+                "{}/{}/",
+                packet.packet.source_port_id, packet.packet.source_channel_id
+            );
+            let full_denom = match data.denom.strip_prefix(&source_prefix) {
+                // the asset is returning to CKB
+                Some(denom) => denom.to_owned(),
+                None => format!(
+                    "{}/{}/{}",
+                    packet.packet.destination_port_id,
+                    packet.packet.destination_channel_id,
+                    data.denom
+                ),
+            };
+            (full_denom, data.receiver)
+        }
+    };
+    if owner.is_empty() {
+        return None;
+    }
+    let trace = parse_denom_trace(&full_denom);
+    let mut scripts = sudt_cells(tx, sudt_code_hash)
+        .filter(|(lock_hash, _)| lock_hash.as_slice().starts_with(&owner))
+        .map(|(_, script)| script)
+        .filter(|script| !trace.path.is_empty() || sudt_denom(script) == trace.base_denom);
+    let sudt_type_script = scripts.next()?;
+    if scripts.any(|script| script.as_slice() != sudt_type_script.as_slice()) {
+        return None;
+    }
+    let record = DenomTraceRecord {
+        path: trace.path,
+        base_denom: trace.base_denom,
+        sudt_type_script: sudt_type_script.as_slice().to_vec(),
+    };
+    Some((sudt_denom(&sudt_type_script), record))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ckb_ics_axon::object::Packet;
+    use ckb_types::core::TransactionBuilder;
+    use ckb_types::h256;
+    use ckb_types::packed::CellOutput;
+    use ckb_types::prelude::{Builder, Pack};
+    use ibc_relayer_storage::prelude::DenomTraceStore;
+    use ibc_relayer_storage::{ReadOnlyStorage, Storage};
+    use tempfile::TempDir;
+
+    const SUDT_CODE_HASH: H256 = h256!("0x5d0d");
+    const ST: u8 = 0x57;
+    const RECEIVER: u8 = 0x7e;
+
+    fn sudt(code_hash: &H256, owner: u8) -> Script {
+        Script::new_builder()
+            .code_hash(code_hash.pack())
+            .args(vec![owner; 32].pack())
+            .hash_type(ckb_types::core::ScriptHashType::Type.into())
+            .build()
+    }
+
+    fn lock(owner: u8) -> Script {
+        Script::new_builder()
+            .code_hash(h256!("0x10c4").pack())
+            .args(vec![owner].pack())
+            .hash_type(ckb_types::core::ScriptHashType::Type.into())
+            .build()
+    }
+
+    // the packet cell is followed by the sUDT cells, given as (lock, sUDT);
+    // the receiver of the packet is the lock hash prefix of `lock(RECEIVER)`
+    fn packet(
+        status: PacketStatus,
+        denom: &str,
+        sudts: &[(&Script, &Script)],
+    ) -> (IbcPacket, TransactionView) {
+        let data = FungibleTokenPacketData {
+            denom: denom.to_owned(),
+            amount: 100,
+            sender: vec![],
+            receiver: lock(RECEIVER).calc_script_hash().as_slice()[..20].to_vec(),
+            memo: String::new(),
+        };
+        let packet = IbcPacket {
+            packet: Packet {
+                sequence: 1,
+                source_port_id: "transfer".to_owned(),
+                source_channel_id: "channel-0".to_owned(),
+                destination_port_id: "transfer".to_owned(),
+                destination_channel_id: "channel-1".to_owned(),
+                data: data.encode_to_vec(),
+                timeout_height: 0,
+                timeout_timestamp: 0,
+            },
+            status,
+            ack: None,
+        };
+        let mut tx = TransactionBuilder::default()
+            .output(CellOutput::new_builder().build())
+            .output_data(Default::default());
+        for (lock, sudt) in sudts {
+            tx = tx
+                .output(
+                    CellOutput::new_builder()
+                        .lock((*lock).clone())
+                        .type_(Some((*sudt).clone()).pack())
+                        .build(),
+                )
+                .output_data(100u128.to_le_bytes().to_vec().pack());
+        }
+        (packet, tx.build().into())
+    }
+
+    fn extract(packet: &IbcPacket, tx: &TransactionView) -> Option<(String, DenomTraceRecord)> {
+        extract_denom_trace(packet, tx, &lock(ST), &SUDT_CODE_HASH)
+    }
+
+    #[test]
+    fn test_extract_denom_trace() {
+        let (st, receiver) = (lock(ST), lock(RECEIVER));
+        let native = sudt(&SUDT_CODE_HASH, 1);
+        let (send, tx) = packet(PacketStatus::Send, &sudt_denom(&native), &[(&st, &native)]);
+        let (denom, record) = extract(&send, &tx).unwrap();
+        assert_eq!(denom, sudt_denom(&native));
+        assert_eq!(record.full_denom(), denom);
+        assert_eq!(record.sudt_type_script, native.as_slice());
+
+        // the cells of other contracts holding 16 bytes are not vouchers
+        let voucher = sudt(&SUDT_CODE_HASH, 2);
+        let unrelated = sudt(&h256!("0x7e57"), 3);
+        let (recv, tx) = packet(
+            PacketStatus::Recv,
+            "transfer/channel-9/ERC20",
+            &[(&receiver, &unrelated), (&receiver, &voucher)],
+        );
+        let (denom, record) = extract(&recv, &tx).unwrap();
+        assert_eq!(denom, sudt_denom(&voucher));
+        assert_eq!(record.path, "transfer/channel-1/transfer/channel-9");
+        assert_eq!(record.base_denom, "ERC20");
+        let (recv, tx) = packet(
+            PacketStatus::Recv,
+            "transfer/channel-9/ERC20",
+            &[(&receiver, &unrelated)],
+        );
+        assert!(extract(&recv, &tx).is_none());
+
+        let returning = format!("transfer/channel-0/{}", sudt_denom(&native));
+        let (recv, tx) = packet(PacketStatus::WriteAck, &returning, &[(&receiver, &native)]);
+        let (_, record) = extract(&recv, &tx).unwrap();
+        assert!(record.path.is_empty());
+
+        let (send, tx) = packet(PacketStatus::Send, &sudt_denom(&native), &[(&st, &voucher)]);
+        assert!(extract(&send, &tx).is_none());
+    }
+
+    #[test]
+    fn test_extract_denom_trace_of_several_sudts() {
+        let (st, receiver, other) = (lock(ST), lock(RECEIVER), lock(3));
+        let voucher = sudt(&SUDT_CODE_HASH, 2);
+        let fee = sudt(&SUDT_CODE_HASH, 4);
+
+        // the voucher is the sUDT paid to the receiver, wherever it is
+        let (recv, tx) = packet(
+            PacketStatus::Recv,
+            "transfer/channel-9/ERC20",
+            &[(&other, &fee), (&st, &fee), (&receiver, &voucher)],
+        );
+        let (denom, _) = extract(&recv, &tx).unwrap();
+        assert_eq!(denom, sudt_denom(&voucher));
+
+        // the voucher is the sUDT escrowed by the transfer contract
+        let (send, tx) = packet(
+            PacketStatus::Send,
+            "transfer/channel-1/transfer/channel-9/ERC20",
+            &[(&other, &fee), (&st, &voucher), (&st, &voucher)],
+        );
+        let (denom, _) = extract(&send, &tx).unwrap();
+        assert_eq!(denom, sudt_denom(&voucher));
+
+        // an ambiguous voucher is not recorded
+        let (recv, tx) = packet(
+            PacketStatus::Recv,
+            "transfer/channel-9/ERC20",
+            &[(&receiver, &fee), (&receiver, &voucher)],
+        );
+        assert!(extract(&recv, &tx).is_none());
+
+        // a native sUDT is found by its denom among the sUDTs of the owner
+        let native = sudt(&SUDT_CODE_HASH, 1);
+        let (send, tx) = packet(
+            PacketStatus::Send,
+            &sudt_denom(&native),
+            &[(&st, &fee), (&st, &native), (&other, &native)],
+        );
+        let (denom, _) = extract(&send, &tx).unwrap();
+        assert_eq!(denom, sudt_denom(&native));
+    }
+
+    #[test]
+    fn test_sudt_amount() {
+        assert_eq!(sudt_amount(&100u128.to_le_bytes()).unwrap(), 100);
+        let mut data = u128::MAX.to_le_bytes().to_vec();
+        data.extend_from_slice(b"extra");
+        assert_eq!(sudt_amount(&data).unwrap(), u128::MAX);
+        assert!(sudt_amount(&[0; 15]).is_err());
+        assert!(sudt_amount(&[]).is_err());
+    }
+
+    #[test]
+    fn test_denom_trace_store() {
+        let data_dir = TempDir::new().unwrap();
+        let storage = Storage::<()>::new(data_dir.path()).unwrap();
+        assert_eq!(storage.get_denom_trace("unknown").unwrap(), None);
+
+        let native = sudt(&SUDT_CODE_HASH, 1);
+        let voucher = sudt(&SUDT_CODE_HASH, 2);
+        let records = [
+            (
+                sudt_denom(&native),
+                DenomTraceRecord {
+                    path: String::new(),
+                    base_denom: sudt_denom(&native),
+                    sudt_type_script: native.as_slice().to_vec(),
+                },
+            ),
+            (
+                sudt_denom(&voucher),
+                DenomTraceRecord {
+                    path: "transfer/channel-1".to_owned(),
+                    base_denom: "ERC20".to_owned(),
+                    sudt_type_script: voucher.as_slice().to_vec(),
+                },
+            ),
+        ];
+        for (denom, record) in &records {
+            storage.put_denom_trace(denom, record).unwrap();
+        }
+        for (denom, record) in &records {
+            assert_eq!(
+                storage.get_denom_trace(denom).unwrap().as_ref(),
+                Some(record)
+            );
+        }
+        let mut all = storage.get_all_denom_traces().unwrap();
+        all.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut expected = records.to_vec();
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(all, expected);
+
+        // the records are readable while the storage is held, but not writable
+        assert!(Storage::<()>::new(data_dir.path()).is_err());
+        let read_only = ReadOnlyStorage::open(data_dir.path()).unwrap();
+        let (denom, record) = &records[0];
+        assert_eq!(
+            read_only.get_denom_trace(denom).unwrap().as_ref(),
+            Some(record)
+        );
+        assert_eq!(read_only.get_all_denom_traces().unwrap().len(), 2);
+        assert!(read_only.put_denom_trace(denom, record).is_err());
+
+        // the records survive reopening the storage
+        drop(storage);
+        let storage = Storage::<()>::new(data_dir.path()).unwrap();
+        let (denom, record) = &records[1];
+        assert_eq!(
+            storage.get_denom_trace(denom).unwrap().as_ref(),
+            Some(record)
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use ckb_chain_spec::consensus::{Consensus, ConsensusBuilder};
+use ckb_chain_spec::ChainSpec;
+use ckb_jsonrpc_types::Status;
+use ckb_resource::Resource;
+use ckb_script::{TransactionScriptsVerifier, TxVerifyEnv};
+use ckb_sdk::NetworkType;
+use ckb_traits::{CellDataProvider, ExtensionProvider, HeaderProvider};
+use ckb_types::bytes::Bytes;
+use ckb_types::core::cell::{CellMeta, CellMetaBuilder, ResolvedTransaction};
+use ckb_types::core::hardfork::HardForks;
+use ckb_types::core::{Cycle, DepType, HeaderView, TransactionView};
+use ckb_types::packed::{Byte32, CellOutput, OutPoint, OutPointVec, Script};
+use ckb_types::prelude::*;
+
+use crate::chain::ckb::prelude::CkbReader;
+use crate::error::Error;
+
+use super::utils::parse_transaction;
+
+/// Provides the cells and headers fetched while resolving a transaction
+/// to the script verifier
+#[derive(Clone, Default)]
+pub struct ResolvedDataLoader {
+    cells: HashMap<OutPoint, Bytes>,
+    headers: HashMap<Byte32, HeaderView>,
+}
+
+impl CellDataProvider for ResolvedDataLoader {
+    fn get_cell_data(&self, out_point: &OutPoint) -> Option<Bytes> {
+        self.cells.get(out_point).cloned()
+    }
+
+    fn get_cell_data_hash(&self, out_point: &OutPoint) -> Option<Byte32> {
+        self.cells
+            .get(out_point)
+            .map(|data| CellOutput::calc_data_hash(data))
+    }
+}
+
+impl HeaderProvider for ResolvedDataLoader {
+    fn get_header(&self, hash: &Byte32) -> Option<HeaderView> {
+        self.headers.get(hash).cloned()
+    }
+}
+
+impl ExtensionProvider for ResolvedDataLoader {
+    fn get_block_extension(&self, _hash: &Byte32) -> Option<ckb_types::packed::Bytes> {
+        None
+    }
+}
+
+// `get_live_cell` knows only the committed cells, so the cells created by the
+// transactions still in the pool are taken from their transactions
+async fn resolve_pending_cell(
+    rpc_client: &impl CkbReader,
+    out_point: &OutPoint,
+) -> Result<Option<(CellOutput, Bytes)>, Error> {
+    let Some(tx) = rpc_client
+        .get_transaction(&out_point.tx_hash().unpack())
+        .await?
+    else {
+        return Ok(None);
+    };
+    if !matches!(tx.tx_status.status, Status::Pending | Status::Proposed) {
+        return Ok(None);
+    }
+    let Some(tx) = tx.transaction.map(parse_transaction) else {
+        return Ok(None);
+    };
+    let index: u32 = out_point.index().unpack();
+    let index = index as usize;
+    let (Some(output), Some(data)) = (
+        tx.inner.outputs.get(index),
+        tx.inner.outputs_data.get(index),
+    ) else {
+        return Ok(None);
+    };
+    Ok(Some((output.clone().into(), data.clone().into_bytes())))
+}
+
+async fn resolve_cell(
+    rpc_client: &impl CkbReader,
+    out_point: OutPoint,
+    loader: &mut ResolvedDataLoader,
+) -> Result<CellMeta, Error> {
+    let cell = rpc_client
+        .get_live_cell(&out_point.clone().into(), true)
+        .await?;
+    let (output, data) = match cell.cell {
+        Some(info) => {
+            let data = info
+                .data
+                .map(|data| data.content.into_bytes())
+                .unwrap_or_default();
+            (info.output.into(), data)
+        }
+        None => {
+            let pending = match cell.status.as_str() {
+                "unknown" => resolve_pending_cell(rpc_client, &out_point).await?,
+                _ => None,
+            };
+            pending.ok_or_else(|| {
+                Error::ckb_tx_dry_run(format!("cell {out_point} is {}", cell.status))
+            })?
+        }
+    };
+    loader.cells.insert(out_point.clone(), data.clone());
+    let cell = CellMetaBuilder::from_cell_output(output, data)
+        .out_point(out_point)
+        .build();
+    Ok(cell)
+}
+
+/// Fetches the inputs, cell deps and header deps of the transaction from the node,
+/// including the cells created by the transactions in its pool
+pub async fn resolve_transaction(
+    rpc_client: &impl CkbReader,
+    tx: TransactionView,
+) -> Result<(ResolvedTransaction, ResolvedDataLoader), Error> {
+    let mut loader = ResolvedDataLoader::default();
+
+    let mut resolved_inputs = vec![];
+    for input in tx.inputs() {
+        let cell = resolve_cell(rpc_client, input.previous_output(), &mut loader).await?;
+        resolved_inputs.push(cell);
+    }
+
+    let mut resolved_cell_deps = vec![];
+    let mut resolved_dep_groups = vec![];
+    for cell_dep in tx.cell_deps() {
+        let cell = resolve_cell(rpc_client, cell_dep.out_point(), &mut loader).await?;
+        if u8::from(cell_dep.dep_type()) != DepType::DepGroup as u8 {
+            resolved_cell_deps.push(cell);
+            continue;
+        }
+        let data = cell.mem_cell_data.clone().unwrap_or_default();
+        let out_points = OutPointVec::from_slice(&data).map_err(|_| {
+            Error::ckb_tx_dry_run(format!("invalid dep group {}", cell_dep.out_point()))
+        })?;
+        for out_point in out_points.into_iter() {
+            let cell = resolve_cell(rpc_client, out_point, &mut loader).await?;
+            resolved_cell_deps.push(cell);
+        }
+        resolved_dep_groups.push(cell);
+    }
+
+    for hash in tx.header_deps() {
+        let header = rpc_client
+            .get_header(&hash.unpack())
+            .await?
+            .ok_or_else(|| Error::ckb_tx_dry_run(format!("header dep {hash} not found")))?;
+        loader.headers.insert(hash, header.into());
+    }
+
+    let resolved_tx = ResolvedTransaction {
+        transaction: tx,
+        resolved_cell_deps,
+        resolved_inputs,
+        resolved_dep_groups,
+    };
+    Ok((resolved_tx, loader))
+}
+
+/// Consensus of the network, whose hardfork schedule decides the VM version
+/// and syscalls available to the scripts
+pub fn build_consensus(network: NetworkType) -> Result<Consensus, Error> {
+    let spec = match network {
+        NetworkType::Mainnet => "specs/mainnet.toml",
+        NetworkType::Testnet => "specs/testnet.toml",
+        // devnets activate all hardfork features from the genesis by default
+        _ => {
+            let consensus = ConsensusBuilder::default()
+                .hardfork_switch(HardForks::new_dev_default())
+                .build();
+            return Ok(consensus);
+        }
+    };
+    ChainSpec::load_from(&Resource::bundled(spec.to_owned()))
+        .and_then(|spec| spec.build_consensus())
+        .map_err(|e| Error::ckb_tx_dry_run(format!("invalid chain spec {spec}: {e}")))
+}
+
+/// Runs all lock and type scripts of the resolved transaction locally,
+/// returning the consumed cycles or the first failing script group
+pub fn verify_transaction<F>(
+    resolved_tx: ResolvedTransaction,
+    loader: ResolvedDataLoader,
+    consensus: Arc<Consensus>,
+    tip: &HeaderView,
+    script_name: F,
+) -> Result<Cycle, Error>
+where
+    F: Fn(&Script) -> String,
+{
+    let max_cycles = consensus.max_block_cycles();
+    let tx_env = TxVerifyEnv::new_submit(tip);
+    let verifier =
+        TransactionScriptsVerifier::new(Arc::new(resolved_tx), loader, consensus, Arc::new(tx_env));
+
+    let mut cycles = 0;
+    for (hash, group) in verifier.groups() {
+        match verifier.verify_single(group.group_type, hash, max_cycles - cycles) {
+            Ok(used) => cycles += used,
+            Err(err) => {
+                return Err(Error::ckb_tx_dry_run(format!(
+                    "{} script `{}` of inputs {:?} and outputs {:?} failed: {err}",
+                    group.group_type,
+                    script_name(&group.script),
+                    group.input_indices,
+                    group.output_indices,
+                )))
+            }
+        }
+    }
+    Ok(cycles)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ckb_sdk::NetworkType;
+    use ckb_types::bytes::Bytes;
+    use ckb_types::core::cell::{CellMetaBuilder, ResolvedTransaction};
+    use ckb_types::core::{Capacity, HeaderBuilder, ScriptHashType, TransactionBuilder};
+    use ckb_types::packed::{CellInput, CellOutput, OutPoint, Script};
+    use ckb_types::prelude::*;
+    use ckb_types::{h256, H256};
+    use futures::executor::block_on;
+    use tendermint_rpc::Url;
+
+    use super::{build_consensus, resolve_transaction, verify_transaction, ResolvedDataLoader};
+    use crate::chain::ckb::prelude::CkbWriter;
+    use crate::chain::ckb::rpc_client::RpcClient;
+
+    #[test]
+    fn test_build_consensus_of_network() {
+        let mainnet = build_consensus(NetworkType::Mainnet).unwrap();
+        assert_eq!(mainnet.id, "ckb");
+        let testnet = build_consensus(NetworkType::Testnet).unwrap();
+        assert_eq!(testnet.id, "ckb_testnet");
+        assert_ne!(mainnet.genesis_hash(), testnet.genesis_hash());
+        build_consensus(NetworkType::Dev).unwrap();
+    }
+
+    #[test]
+    fn test_verify_transaction_reports_failing_script() {
+        let lock = Script::new_builder()
+            .code_hash(h256!("0x42").pack())
+            .hash_type(ScriptHashType::Type.into())
+            .build();
+        let output = CellOutput::new_builder()
+            .lock(lock)
+            .build_exact_capacity(Capacity::zero())
+            .unwrap();
+        let out_point = OutPoint::new(h256!("0x1").pack(), 0);
+        let input = CellMetaBuilder::from_cell_output(output.clone(), Bytes::new())
+            .out_point(out_point.clone())
+            .build();
+        let tx = TransactionBuilder::default()
+            .input(CellInput::new(out_point, 0))
+            .output(output)
+            .output_data(Bytes::new().pack())
+            .build();
+        let resolved_tx = ResolvedTransaction {
+            transaction: tx,
+            resolved_cell_deps: vec![],
+            resolved_inputs: vec![input],
+            resolved_dep_groups: vec![],
+        };
+
+        let consensus = Arc::new(build_consensus(NetworkType::Testnet).unwrap());
+        let err = verify_transaction(
+            resolved_tx,
+            ResolvedDataLoader::default(),
+            consensus,
+            &HeaderBuilder::default().build(),
+            |script| {
+                let code_hash: H256 = script.code_hash().unpack();
+                assert_eq!(code_hash, h256!("0x42"));
+                "unknown-lock".to_owned()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("`unknown-lock`"), "{err}");
+    }
+
+    #[test]
+    fn test_resolve_outputs_of_pending_transaction() {
+        let url: Url = "http://dry_run_pending".parse().unwrap();
+        let client = RpcClient::new(&url, &url);
+        client.set_auto_mine(false);
+        let output = |ckb: usize| {
+            CellOutput::new_builder()
+                .capacity(Capacity::bytes(ckb).unwrap().pack())
+                .build()
+        };
+        let funding = client.add_live_cell(output(1_000), Bytes::new());
+        let pending = TransactionBuilder::default()
+            .input(CellInput::new(funding, 0))
+            .output(output(900))
+            .output_data(Bytes::from_static(b"pending").pack())
+            .build();
+        block_on(client.send_transaction(&pending.data().into(), None)).unwrap();
+
+        let spending = |index: u32| {
+            TransactionBuilder::default()
+                .input(CellInput::new(OutPoint::new(pending.hash(), index), 0))
+                .build()
+        };
+        let (resolved_tx, loader) = block_on(resolve_transaction(&client, spending(0))).unwrap();
+        let input = &resolved_tx.resolved_inputs[0];
+        assert_eq!(input.cell_output.as_slice(), output(900).as_slice());
+        assert_eq!(input.mem_cell_data.as_deref(), Some(b"pending".as_slice()));
+        assert_eq!(loader.cells.len(), 1);
+
+        assert!(block_on(resolve_transaction(&client, spending(1))).is_err());
+        let unknown = TransactionBuilder::default()
+            .input(CellInput::new(OutPoint::new(h256!("0x2").pack(), 0), 0))
+            .build();
+        assert!(block_on(resolve_transaction(&client, unknown)).is_err());
+    }
+}
@@ -2,6 +2,7 @@ mod channel;
 mod client;
 mod connection;
 mod packet;
+mod transfer;
 
 use crate::{config::ckb4ibc::ChainConfig, error::Error};
 use ckb_ics_axon::{
@@ -61,6 +62,7 @@ use client::{convert_create_client, convert_update_client};
 use channel::*;
 use connection::*;
 use packet::*;
+pub use transfer::{convert_transfer_to_tx, SudtTransferCells};
 
 macro_rules! convert {
     ($msg:ident, $conval:ident, $msgty:ty, $conv:ident) => {{
@@ -514,6 +516,19 @@ impl TxBuilder {
             .into()
     }
 
+    pub fn typed_output(self, lock: Script, type_: Script, data: PackedBytes) -> Self {
+        self.builder
+            .output(
+                CellOutput::new_builder()
+                    .lock(lock)
+                    .type_(Some(type_).pack())
+                    .build_exact_capacity(Capacity::bytes(data.len()).unwrap())
+                    .expect("transaction output capacity"),
+            )
+            .output_data(data)
+            .into()
+    }
+
     pub fn witness(self, input_type: BytesOpt, output_type: BytesOpt) -> Self {
         self.builder
             .witness(
@@ -115,7 +115,8 @@ pub fn convert_recv_packet_to_tx<C: MsgToTxConverter>(
     let mut write_ack_witness = BytesOpt::default();
 
     // fetch useless packet cell as input to save capacity
-    let useless_write_ack_packet = converter.require_useless_write_ack_packet(15); // TODO: make block number gap setup in config
+    let block_number_gap = converter.get_config().write_ack_reuse_block_gap;
+    let useless_write_ack_packet = converter.require_useless_write_ack_packet(block_number_gap);
     if let Some((packet, input, capacity)) = &useless_write_ack_packet {
         tracing::info!(
             "use useless WriteAck({}) to save CKB capacity",
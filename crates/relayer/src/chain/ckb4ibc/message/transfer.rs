@@ -0,0 +1,231 @@
+use ckb_ics_axon::commitment::packet_commitment_path;
+use ckb_ics_axon::handler::handle_msg_send_packet;
+use ckb_ics_axon::handler::IbcPacket;
+use ckb_ics_axon::handler::PacketStatus;
+use ckb_ics_axon::message::Envelope;
+use ckb_ics_axon::message::MsgSendPacket as CkbMsgSendPacket;
+use ckb_ics_axon::message::MsgType;
+use ckb_ics_axon::object::Packet as CkbPacket;
+use ckb_ics_axon::{ChannelArgs, PacketArgs};
+use ckb_types::bytes::Bytes;
+use ckb_types::packed::{BytesOpt, CellInput, OutPoint, Script};
+use ckb_types::prelude::Pack;
+use ibc_relayer_types::applications::transfer::msgs::transfer::MsgTransfer;
+use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+use ibc_relayer_types::core::ics04_channel::packet::Packet;
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+use prost::Message;
+
+use super::EmptyClient;
+use super::{CkbTxInfo, MsgToTxConverter, TxBuilder};
+use crate::chain::ckb4ibc::denom_trace::FungibleTokenPacketData;
+use crate::chain::ckb4ibc::utils::{
+    convert_port_id_to_array, get_channel_lock_script, get_channel_number, get_client_outpoint,
+    get_encoded_object, get_packet_lock_script,
+};
+use crate::chain::SEC_TO_NANO;
+use crate::error::Error;
+
+/// Cells moving the sUDT of a transfer from the sender into the st-cell, the
+/// cell of the st-lock escrowing the sUDT sent out of CKB
+pub struct SudtTransferCells {
+    pub st_lock: Script,
+    pub sudt_type_script: Script,
+    /// Existing st-cell of the sUDT with its capacity, amount and the data
+    /// following the amount, which is kept; a new one is created otherwise
+    pub st_cell: Option<(CellInput, u64, u128, Bytes)>,
+    pub sender_lock: Script,
+    /// sUDT cells of the sender holding no data after the amount, they are
+    /// signed along with the capacity cells
+    pub sender_cells: Vec<CellInput>,
+    pub sender_capacity: u64,
+    pub sender_amount: u128,
+    /// Code of the sUDT and of the st-lock
+    pub cell_deps: Vec<OutPoint>,
+}
+
+/// Converts an ICS-20 transfer of sUDT into the transaction sending its packet,
+/// the sUDT cells of the sender being the last inputs of the transaction.
+pub fn convert_transfer_to_tx<C: MsgToTxConverter>(
+    msg: MsgTransfer,
+    converter: &C,
+    denom: String,
+    cells: SudtTransferCells,
+) -> Result<CkbTxInfo, Error> {
+    let port_id = convert_port_id_to_array(&msg.source_port)?;
+    if cells.st_lock.calc_script_hash().raw_data().as_ref() != port_id.as_slice() {
+        return Err(Error::other_error(format!(
+            "port {} isn't the port of the sUDT transfer application",
+            msg.source_port
+        )));
+    }
+    let amount: u64 = msg
+        .token
+        .amount
+        .parse()
+        .map_err(|_| Error::other_error(format!("invalid sUDT amount {}", msg.token.amount)))?;
+    if cells.sender_amount < amount as u128 {
+        return Err(Error::other_error(format!(
+            "insufficient sUDT balance {} to transfer {amount}",
+            cells.sender_amount
+        )));
+    }
+    let receiver = hex::decode(msg.receiver.as_ref().trim_start_matches("0x"))
+        .map_err(|_| Error::other_error(format!("invalid receiver {}", msg.receiver)))?;
+
+    let channel_id = msg.source_channel.clone();
+    let old_channel_end = converter.get_ibc_channel(&channel_id, Some(&msg.source_port))?;
+    let (channel_input, channel_capacity, old_channel_args) =
+        converter.get_ibc_channel_input(&channel_id, &msg.source_port)?;
+    let mut new_channel_end = old_channel_end.clone();
+    let sequence = old_channel_end.sequence.next_sequence_sends;
+    new_channel_end.sequence.next_sequence_sends += 1;
+
+    let data = FungibleTokenPacketData {
+        denom,
+        amount,
+        sender: cells.sender_lock.calc_script_hash().raw_data()[..20].to_vec(),
+        receiver,
+        memo: msg.memo.clone().unwrap_or_default(),
+    }
+    .encode_to_vec();
+    let packet = CkbPacket {
+        sequence,
+        source_port_id: msg.source_port.to_string(),
+        source_channel_id: channel_id.to_string(),
+        destination_port_id: old_channel_end.counterparty.port_id.clone(),
+        destination_channel_id: old_channel_end.counterparty.channel_id.clone(),
+        data,
+        timeout_height: msg.timeout_height.commitment_revision_height(),
+        timeout_timestamp: msg.timeout_timestamp.nanoseconds() / SEC_TO_NANO,
+    };
+    let ibc_packet = Packet {
+        sequence: sequence.into(),
+        source_port: msg.source_port.clone(),
+        source_channel: channel_id.clone(),
+        destination_port: packet
+            .destination_port_id
+            .parse::<PortId>()
+            .map_err(|_| Error::ckb_port_id_invalid(packet.destination_port_id.clone()))?,
+        destination_channel: packet
+            .destination_channel_id
+            .parse::<ChannelId>()
+            .map_err(|_| {
+                Error::other_error(format!(
+                    "channel {channel_id} has no counterparty channel yet"
+                ))
+            })?,
+        data: packet.data.clone(),
+        timeout_height: msg.timeout_height,
+        timeout_timestamp: msg.timeout_timestamp,
+    };
+
+    let channel_number = get_channel_number(&channel_id)?;
+    let connection_id = new_channel_end.connection_hops[0].parse().unwrap();
+    let connection_args = converter
+        .get_ibc_connections_by_connection_id(&connection_id)?
+        .0;
+    let client_id = connection_args.client_id();
+    let new_channel_args = ChannelArgs {
+        metadata_type_id: connection_args.metadata_type_id,
+        ibc_handler_address: connection_args.ibc_handler_address,
+        open: true,
+        channel_id: channel_number,
+        port_id,
+    };
+    let packet_args = PacketArgs {
+        ibc_handler_address: connection_args.ibc_handler_address,
+        channel_id: channel_number,
+        port_id,
+        sequence,
+    };
+
+    let packet = IbcPacket {
+        packet,
+        status: PacketStatus::Send,
+        ack: None,
+    };
+    let old_channel = get_encoded_object(&old_channel_end);
+    let new_channel = get_encoded_object(&new_channel_end);
+    let packet_object = get_encoded_object(&packet);
+
+    let send_packet = CkbMsgSendPacket {};
+    let content = rlp::encode(&send_packet).to_vec();
+    let mut commitments = vec![];
+    handle_msg_send_packet(
+        EmptyClient,
+        old_channel_end,
+        old_channel_args,
+        new_channel_end,
+        new_channel_args,
+        packet,
+        packet_args,
+        &mut commitments,
+        send_packet,
+    )
+    .map_err(|err| Error::other_error(format!("handle error: {}", err as i8)))?;
+
+    let envelope = Envelope {
+        msg_type: MsgType::MsgSendPacket,
+        content,
+        commitments,
+    };
+
+    let channel_lock = get_channel_lock_script(converter, new_channel_args.to_args());
+    let packet_lock = get_packet_lock_script(converter, packet_args.to_args());
+
+    let mut input_capacity = channel_capacity + cells.sender_capacity;
+    let mut tx = TxBuilder::default()
+        .cell_dep(get_client_outpoint(converter, &client_id)?)
+        .cell_dep(converter.get_chan_contract_outpoint().clone())
+        .input(channel_input)
+        .witness(old_channel.witness, new_channel.witness)
+        .witness(BytesOpt::default(), packet_object.witness);
+    for cell_dep in cells.cell_deps {
+        tx = tx.cell_dep(cell_dep);
+    }
+    let (escrowed, st_cell_extra) = match cells.st_cell {
+        Some((input, capacity, escrowed, extra)) => {
+            tx = tx.input(input);
+            input_capacity += capacity;
+            (escrowed, extra)
+        }
+        None => (0, Bytes::new()),
+    };
+    for input in cells.sender_cells {
+        tx = tx.input(input);
+    }
+    let change = cells.sender_amount - amount as u128;
+    let tx = tx
+        .output(channel_lock, new_channel.data)
+        .output(packet_lock, packet_object.data)
+        .typed_output(
+            cells.st_lock,
+            cells.sudt_type_script.clone(),
+            [
+                &(escrowed + amount as u128).to_le_bytes()[..],
+                &st_cell_extra,
+            ]
+            .concat()
+            .pack(),
+        )
+        .typed_output(
+            cells.sender_lock,
+            cells.sudt_type_script,
+            change.to_le_bytes().pack(),
+        )
+        .build();
+
+    let commitment_path =
+        packet_commitment_path(msg.source_port.as_ref(), channel_id.as_ref(), sequence);
+    let event = IbcEvent::SendPacket(SendPacket { packet: ibc_packet });
+
+    Ok(CkbTxInfo {
+        unsigned_tx: Some(tx),
+        envelope,
+        input_capacity,
+        event: Some(event),
+        commitment_path,
+    })
+}
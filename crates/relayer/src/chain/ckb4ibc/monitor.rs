@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashSet};
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
@@ -10,8 +10,8 @@ use ckb_ics_axon::handler::{IbcPacket, PacketStatus};
 use ckb_ics_axon::message::MsgType;
 use ckb_ics_axon::object::State as CkbState;
 use ckb_ics_axon::{connection_id, ChannelArgs, PacketArgs};
-use ckb_jsonrpc_types::{JsonBytes, Status, TransactionView};
-use ckb_sdk::rpc::ckb_indexer::SearchKey;
+use ckb_jsonrpc_types::{Status, TransactionView};
+use ckb_sdk::rpc::ckb_indexer::{SearchKey, SearchKeyFilter};
 use ckb_types::core::ScriptHashType;
 use ckb_types::packed::{CellInput, Script};
 use ckb_types::prelude::{Builder, Entity, Pack};
@@ -34,7 +34,7 @@ use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::timestamp::Timestamp;
 use tokio::runtime::Runtime as TokioRuntime;
 use tokio::sync::watch::Receiver as WatchReceiver;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::chain::ckb::prelude::CkbReader;
 use crate::chain::ckb::rpc_client::RpcClient;
@@ -50,6 +50,7 @@ use crate::event::monitor::{Error, EventBatch, MonitorCmd, Next, Result, TxMonit
 use crate::event::IbcEventWithHeight;
 
 use super::cache_set::CacheSet;
+use super::cursor::{BlockRef, CursorStore, MonitorCursors};
 use super::utils::{get_prefix_search_key, get_script_hash, tip_block_number};
 
 #[derive(Eq, PartialOrd, Ord, PartialEq, Hash, Clone, Copy)]
@@ -75,7 +76,8 @@ pub struct Ckb4IbcEventMonitor {
     cache_set: RwLock<CacheSet<H256>>,
     counterparty_client_type_rx: WatchReceiver<Option<ClientType>>,
     counterparty_client_type: ClientType,
-    fetch_cursors: HashMap<IbcProtocolType, JsonBytes>,
+    cursors: MonitorCursors,
+    cursor_store: CursorStore,
     useless_write_ack_packets: BTreeMap<u64, UselessWriteAckCell>,
     ibc_transaction_notice: Sender<(String, H256)>,
 }
@@ -86,6 +88,7 @@ impl Ckb4IbcEventMonitor {
         rpc_client: Arc<RpcClient>,
         config: ChainConfig,
         counterparty_client_type_rx: WatchReceiver<Option<ClientType>>,
+        cursor_store: CursorStore,
     ) -> (
         Self,
         TxMonitorCmd,
@@ -105,7 +108,8 @@ impl Ckb4IbcEventMonitor {
             cache_set: RwLock::new(CacheSet::new(512)),
             counterparty_client_type_rx,
             counterparty_client_type: ClientType::Mock,
-            fetch_cursors: HashMap::new(),
+            cursors: MonitorCursors::default(),
+            cursor_store,
             useless_write_ack_packets: BTreeMap::new(),
             ibc_transaction_notice: tx_notice,
         };
@@ -128,6 +132,16 @@ impl Ckb4IbcEventMonitor {
             "{} async counterparty_client_type received: {}, starting IBC events listen process",
             self.config.id, self.counterparty_client_type
         );
+        self.cursors = match rt.block_on(self.restore_cursors()) {
+            Ok(cursors) => cursors,
+            Err(err) => {
+                error!(
+                    "{} failed to restore event cursors, scan from scratch: {err}",
+                    self.config.id
+                );
+                MonitorCursors::default()
+            }
+        };
         loop {
             std::thread::sleep(Duration::from_secs(1));
             let result = rt.block_on(self.run_once());
@@ -138,6 +152,68 @@ impl Ckb4IbcEventMonitor {
         }
     }
 
+    async fn restore_cursors(&self) -> Result<MonitorCursors> {
+        let Some(height) = self.config.rescan_from_height else {
+            return Ok(self.cursor_store.load().unwrap_or_default());
+        };
+        info!("{} rescans IBC events from block {height}", self.config.id);
+        let published_to = match height.checked_sub(1) {
+            Some(number) => Some(self.block_ref(number).await?),
+            None => None,
+        };
+        Ok(MonitorCursors::rescan_from(published_to))
+    }
+
+    async fn block_ref(&self, number: u64) -> Result<BlockRef> {
+        let header = self
+            .rpc_client
+            .get_header_by_number(number.into())
+            .await
+            .map_err(|err| Error::others(err.detail().to_string()))?
+            .ok_or_else(|| Error::others(format!("block {number} not found")))?;
+        Ok(BlockRef {
+            number,
+            hash: header.hash,
+        })
+    }
+
+    // Rewinds the cursors if the blocks they rely on are no longer in the chain,
+    // subscribers are told to drop the events published since the rewound
+    // blocks and the events of the new canonical blocks are published again
+    async fn detect_reorg(&mut self) -> Result<()> {
+        let mut canonical = HashSet::new();
+        for block in self.cursors.blocks() {
+            let header = self
+                .rpc_client
+                .get_header_by_number(block.number.into())
+                .await
+                .map_err(|err| Error::others(err.detail().to_string()))?;
+            if header.map(|header| header.hash) == Some(block.hash.clone()) {
+                canonical.insert(block.hash);
+            }
+        }
+        if let Some(height) = self.cursors.rewind(|block| canonical.contains(&block.hash)) {
+            warn!(
+                "{} chain reorganization detected, retract the events since block {height}",
+                self.config.id
+            );
+            self.cache_set = RwLock::new(CacheSet::new(512));
+            self.useless_write_ack_packets.clear();
+            self.event_bus
+                .broadcast(Arc::new(Err(Error::chain_reorganized(
+                    self.config.id.clone(),
+                    height,
+                ))));
+        }
+        Ok(())
+    }
+
+    fn store_cursors(&self) {
+        if let Err(err) = self.cursor_store.store(&self.cursors) {
+            warn!("{} failed to store event cursors: {err}", self.config.id);
+        }
+    }
+
     async fn handle_get_useless_write_ack_packet(&mut self) -> Result<()> {
         if let Ok((resposne, block_number_gap)) = self.rx_write_ack.try_recv() {
             let useless_key = self.useless_write_ack_packets.keys().next().cloned();
@@ -167,6 +243,11 @@ impl Ckb4IbcEventMonitor {
             }
         }
 
+        if let Err(err) = self.detect_reorg().await {
+            error!("{err}");
+            return Next::Continue;
+        }
+
         // 'mut self' cannot be used in tokio::join macro, it can only be handled in sequence
         let connection_events = self.fetch_connection_events().await;
         let channel_events = self.fetch_channel_events().await;
@@ -175,6 +256,9 @@ impl Ckb4IbcEventMonitor {
         self.process_batch(connection_events);
         self.process_batch(channel_events);
         self.process_batch(packet_events);
+        // events are published before the cursors are stored, so that none of
+        // them is lost after a restart
+        self.store_cursors();
 
         if let Err(err) = self.handle_get_useless_write_ack_packet().await {
             error!("{err}");
@@ -196,7 +280,7 @@ impl Ckb4IbcEventMonitor {
             .args(connection_args.encode().pack())
             .build();
         let key = get_prefix_search_key(script);
-        let connections = self
+        let (connections, published) = self
             .search_and_extract(
                 key,
                 &|tx| {
@@ -218,7 +302,7 @@ impl Ckb4IbcEventMonitor {
         }
         let (((_, connections), tx), (block_number, _, _)) =
             connections.into_iter().next().unwrap();
-        if self.cache_set.read().unwrap().has(&tx.hash) {
+        if published(block_number) || self.cache_set.read().unwrap().has(&tx.hash) {
             return Ok(EventBatch {
                 chain_id: self.config.id.clone(),
                 tracking_id: TrackingId::Static("ckb connection events collection"),
@@ -314,7 +398,7 @@ impl Ckb4IbcEventMonitor {
             .build();
 
         let key = get_prefix_search_key(script);
-        let identified_channel_ends = self
+        let (identified_channel_ends, published) = self
             .search_and_extract(
                 key,
                 &|tx| {
@@ -335,7 +419,7 @@ impl Ckb4IbcEventMonitor {
         let events = identified_channel_ends
             .into_iter()
             .filter(|((_, tx), (block_number, _, _))| {
-                if self.cache_set.read().unwrap().has(&tx.hash) {
+                if published(*block_number) || self.cache_set.read().unwrap().has(&tx.hash) {
                     return false;
                 }
                 self.cache_set.write().unwrap().insert(tx.hash.clone());
@@ -462,7 +546,7 @@ impl Ckb4IbcEventMonitor {
             .args(packet_args.get_prefix_for_all().pack())
             .build();
         let key = get_prefix_search_key(script);
-        let ibc_packets = self
+        let (ibc_packets, published) = self
             .search_and_extract(
                 key,
                 &|tx| {
@@ -483,6 +567,21 @@ impl Ckb4IbcEventMonitor {
             .await
             .map_err(|err| Error::others(err.detail().to_string()))?;
 
+        // write-ack cells whose events were published before a restart can
+        // still be consumed by later transactions
+        let (published_packets, ibc_packets): (Vec<_>, Vec<_>) = ibc_packets
+            .into_iter()
+            .partition(|(_, (block_number, _, _))| published(*block_number));
+        for (((packet, _), tx), (block_number, cell_input, capacity)) in published_packets {
+            if packet.status == PacketStatus::WriteAck
+                && !self.cache_set.read().unwrap().has(&tx.hash)
+            {
+                self.cache_set.write().unwrap().insert(tx.hash);
+                self.useless_write_ack_packets
+                    .insert(block_number, (packet, cell_input, capacity));
+            }
+        }
+
         let useless_packets = &mut self.useless_write_ack_packets;
         let events = ibc_packets
             .into_iter()
@@ -576,18 +675,36 @@ impl Ckb4IbcEventMonitor {
 
     async fn search_and_extract<T, F>(
         &mut self,
-        search_key: SearchKey,
+        mut search_key: SearchKey,
         extractor: &F,
         limit: u32,
         ibc_protocol: IbcProtocolType,
-    ) -> Result<Vec<((T, TransactionView), (u64, CellInput, u64))>>
+    ) -> Result<(
+        Vec<((T, TransactionView), (u64, CellInput, u64))>,
+        impl Fn(u64) -> bool,
+    )>
     where
         F: Fn(TransactionView) -> Result<(T, TransactionView)>,
     {
-        let cursor = self.fetch_cursors.get(&ibc_protocol).cloned();
+        // a round only covers the cells of blocks with enough confirmations
+        if self.cursors.get_mut(ibc_protocol).round_to.is_none() {
+            let tip = tip_block_number(self.rpc_client.as_ref())
+                .await
+                .map_err(|err| Error::others(err.detail().to_string()))?;
+            let block = self
+                .block_ref(tip.saturating_sub(self.config.event_confirmations))
+                .await?;
+            self.cursors.get_mut(ibc_protocol).round_to = Some(block);
+        }
+        let scan = self.cursors.get_mut(ibc_protocol).clone();
+        let round_to = scan.round_to.as_ref().map_or(0, |block| block.number);
+        search_key.filter = Some(SearchKeyFilter {
+            block_range: Some([0.into(), (round_to + 1).into()]),
+            ..Default::default()
+        });
         let cells = self
             .rpc_client
-            .fetch_live_cells(search_key, limit, cursor)
+            .fetch_live_cells(search_key, limit, scan.cursor.clone())
             .await
             .map_err(|_| Error::collect_events_failed("fetch ibc cells failed".to_string()))?;
 
@@ -634,12 +751,13 @@ impl Ckb4IbcEventMonitor {
             result.push((extractor(tx)?, number_input));
         }
 
-        if cells.objects.is_empty() {
-            self.fetch_cursors.remove(&ibc_protocol);
+        let cursor = self.cursors.get_mut(ibc_protocol);
+        if cells.objects.len() < limit as usize {
+            cursor.finish_round();
         } else {
-            self.fetch_cursors.insert(ibc_protocol, cells.last_cursor);
+            cursor.cursor = Some(cells.last_cursor);
         }
-        Ok(result)
+        Ok((result, move |block_number| scan.is_published(block_number)))
     }
 
     fn process_batch(&mut self, batch: Result<EventBatch>) {
@@ -0,0 +1,183 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use ckb_jsonrpc_types::{OutPoint, Status, TransactionView};
+use ckb_sdk::rpc::ckb_indexer::{Cell, SearchKey};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use tracing::{debug, warn};
+
+use super::utils::{fetch_transaction_by_hash, parse_transaction};
+use crate::chain::ckb::prelude::CkbReader;
+use crate::error::Error;
+
+/// Iterates all live cells matching `search_key`, requesting the indexer
+/// page by page following its cursor. Pages are only fetched on demand.
+pub fn live_cells<R: CkbReader>(
+    rpc_client: &R,
+    search_key: SearchKey,
+    page_size: u32,
+) -> impl Stream<Item = Result<Cell, Error>> + '_ {
+    // `None` state means the last page has been reached
+    stream::try_unfold(Some(None), move |cursor| {
+        let search_key = search_key.clone();
+        async move {
+            let Some(cursor) = cursor else {
+                return Ok(None);
+            };
+            let page = rpc_client
+                .fetch_live_cells(search_key, page_size, cursor)
+                .await?;
+            let next_cursor = if page.objects.len() < page_size as usize {
+                None
+            } else {
+                Some(Some(page.last_cursor))
+            };
+            Ok(Some((page.objects, next_cursor)))
+        }
+    })
+    .map_ok(|cells| stream::iter(cells.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+/// Collects all live cells matching `search_key`
+pub async fn fetch_all_live_cells(
+    rpc_client: &impl CkbReader,
+    search_key: SearchKey,
+    page_size: u32,
+) -> Result<Vec<Cell>, Error> {
+    live_cells(rpc_client, search_key, page_size)
+        .try_collect()
+        .await
+}
+
+/// Transactions creating the scanned cells, keyed by the out-points of the
+/// cells. The transaction of a live cell never changes, so entries stay valid
+/// across queries and the oldest ones are only dropped to bound the memory.
+pub struct CellTxCache {
+    capacity: usize,
+    inner: Mutex<CellTxCacheInner>,
+}
+
+#[derive(Default)]
+struct CellTxCacheInner {
+    order: VecDeque<OutPoint>,
+    txs: HashMap<OutPoint, Arc<TransactionView>>,
+}
+
+impl CellTxCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::default(),
+        }
+    }
+
+    pub fn get(&self, out_point: &OutPoint) -> Option<Arc<TransactionView>> {
+        self.inner.lock().unwrap().txs.get(out_point).cloned()
+    }
+
+    pub fn insert(&self, out_point: OutPoint, tx: Arc<TransactionView>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        if inner.txs.insert(out_point.clone(), tx).is_some() {
+            return;
+        }
+        inner.order.push_back(out_point);
+        if inner.order.len() > self.capacity {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.txs.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Fetches the transactions creating `cells` with at most `concurrency`
+/// requests in flight, the order of `cells` is kept in the result
+pub async fn fetch_cell_transactions(
+    rpc_client: &impl CkbReader,
+    cache: &CellTxCache,
+    cells: Vec<Cell>,
+    concurrency: usize,
+) -> Result<Vec<(Cell, Arc<TransactionView>)>, Error> {
+    stream::iter(cells)
+        .map(|cell| async move {
+            if let Some(tx) = cache.get(&cell.out_point) {
+                return Ok((cell, tx));
+            }
+            let tx = fetch_transaction_by_hash(rpc_client, &cell.out_point.tx_hash).await?;
+            let tx = Arc::new(tx);
+            cache.insert(cell.out_point.clone(), tx.clone());
+            Ok((cell, tx))
+        })
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await
+}
+
+/// Like `fetch_cell_transactions`, but the cells whose transactions can't be
+/// fetched or aren't committed yet are skipped instead of failing the scan
+pub async fn fetch_committed_cell_transactions(
+    rpc_client: &impl CkbReader,
+    cache: &CellTxCache,
+    cells: Vec<Cell>,
+    concurrency: usize,
+) -> Vec<(Cell, Arc<TransactionView>)> {
+    stream::iter(cells)
+        .map(|cell| async move {
+            if let Some(tx) = cache.get(&cell.out_point) {
+                return Some((cell, tx));
+            }
+            let tx_hash = &cell.out_point.tx_hash;
+            let tx = match rpc_client.get_transaction(tx_hash).await {
+                Ok(Some(tx)) if tx.tx_status.status == Status::Committed => tx.transaction?,
+                Ok(_) => {
+                    debug!("skip cell of uncommitted transaction {tx_hash:#x}");
+                    return None;
+                }
+                Err(err) => {
+                    warn!("skip cell of transaction {tx_hash:#x}: {err}");
+                    return None;
+                }
+            };
+            let tx = Arc::new(parse_transaction(tx));
+            cache.insert(cell.out_point.clone(), tx.clone());
+            Some((cell, tx))
+        })
+        .buffered(concurrency.max(1))
+        .filter_map(|cell| async move { cell })
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ckb_types::{h256, H256};
+
+    fn out_point(index: u32) -> OutPoint {
+        OutPoint {
+            tx_hash: H256::default(),
+            index: index.into(),
+        }
+    }
+
+    #[test]
+    fn test_cell_tx_cache_drops_oldest() {
+        let cache = CellTxCache::new(2);
+        let tx = Arc::new(TransactionView {
+            inner: Default::default(),
+            hash: h256!("0x1"),
+        });
+        cache.insert(out_point(0), tx.clone());
+        cache.insert(out_point(1), tx.clone());
+        cache.insert(out_point(1), tx.clone());
+        assert!(cache.get(&out_point(0)).is_some());
+
+        cache.insert(out_point(2), tx);
+        assert!(cache.get(&out_point(0)).is_none());
+        assert!(cache.get(&out_point(1)).is_some());
+        assert!(cache.get(&out_point(2)).is_some());
+    }
+}
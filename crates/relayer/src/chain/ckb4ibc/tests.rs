@@ -0,0 +1,788 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use axon_types::basic::{Uint32, Uint64};
+use axon_types::metadata::{Metadata, MetadataCellData, MetadataList, Validator, ValidatorList};
+use ckb_hash::blake2b_256;
+use ckb_ics_axon::handler::{IbcChannel, IbcConnections, Sequence as CkbSequence};
+use ckb_ics_axon::message::{Envelope, MsgType};
+use ckb_ics_axon::object::{
+    ChannelCounterparty, ConnectionCounterparty, ConnectionEnd as CkbConnectionEnd,
+    Ordering as CkbOrdering, State as CkbState,
+};
+use ckb_ics_axon::ChannelArgs;
+use ckb_sdk::constants::TYPE_ID_CODE_HASH;
+use ckb_types::bytes::Bytes;
+use ckb_types::core::{Capacity, ScriptHashType, TransactionBuilder};
+use ckb_types::packed::{self, BytesOpt, CellOutput, Script, WitnessArgs};
+use ckb_types::prelude::*;
+use ckb_types::{h256, H160, H256};
+use hdpath::StandardHDPath;
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_relayer_types::applications::transfer::msgs::transfer::MsgTransfer;
+use ibc_relayer_types::clients::ics07_ckb::consensus_state::CkbConsensusState;
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+use ibc_relayer_types::core::ics03_connection::connection::{Counterparty, State};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+use ibc_relayer_types::core::ics03_connection::version::Version;
+use ibc_relayer_types::core::ics04_channel::channel::{
+    ChannelEnd, Counterparty as ChannelEndCounterparty, Order, State as ChannelState,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+use ibc_relayer_types::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
+use ibc_relayer_types::core::ics04_channel::version::Version as ChannelVersion;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::proofs::Proofs;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::tx_msg::Msg;
+use ibc_relayer_types::Height;
+use prost::Message;
+use tempfile::TempDir;
+use tendermint_rpc::Url;
+use tokio::runtime::Builder as RuntimeBuilder;
+use tokio::sync::watch;
+
+use super::denom_trace::{sudt_amount, sudt_denom, FungibleTokenPacketData};
+use super::utils::{
+    bump_tx_fee, convert_port_id_to_array, decode_axon_metadata_cell, get_connection_lock_script,
+    get_encoded_object, get_script_hash,
+};
+use super::{Ckb4IbcChain, Ckb4IbcEventMonitor, CursorStore};
+use crate::chain::ckb::rpc_client::RpcClient;
+use crate::chain::ckb::HD_PATH;
+use crate::chain::endpoint::ChainEndpoint;
+use crate::chain::requests::{
+    IncludeProof, QueryChannelRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
+    QueryClientStatesRequest, QueryConnectionsRequest, QueryConsensusStateRequest, QueryHeight,
+    QueryPacketReceiptRequest, QueryUnreceivedPacketsRequest,
+};
+use crate::chain::tracking::TrackedMsgs;
+use crate::chain::SEC_TO_NANO;
+use crate::config::ckb4ibc::{
+    default, ChainConfig as Ckb4IbcChainConfig, LightClientItem, SudtTransferConfig,
+};
+use crate::config::{AddressType, ChainConfig};
+use crate::consensus_state::AnyConsensusState;
+use crate::keyring::{Secp256k1KeyPair, SigningKeyPair, Store};
+
+const MNEMONIC: &str =
+    "feed label choose question decrease slab regular humor salmon wheel slab inform";
+
+const CHAIN_INFO: &str = r#"
+    {
+      "alerts": [],
+      "chain": "ckb_testnet",
+      "difficulty": "0x10000",
+      "epoch": "0x100",
+      "is_initial_block_download": false,
+      "median_time": "0x5cd2b105"
+    }"#;
+
+fn chain_config(name: &str, data_dir: &TempDir) -> Ckb4IbcChainConfig {
+    let url: Url = format!("http://ckb4ibc_rpc_{name}").parse().unwrap();
+    let axon_client = LightClientItem {
+        chain_id: ChainId::new("axon".to_owned(), 0),
+        client_cell_type_args: h256!("0x14"),
+        ibc_handler_address: H160([1; 20]),
+        rpc_addr: None,
+    };
+    Ckb4IbcChainConfig {
+        id: ChainId::new("ckb4ibc".to_owned(), 0),
+        ckb_rpc: url.clone(),
+        ckb_indexer_rpc: url,
+        key_name: "ckb4ibc-chain-test".to_owned(),
+        key_store_type: Store::Memory,
+        store_prefix: "ibc".to_owned(),
+        client_code_hash: h256!("0x10"),
+        connection_type_args: h256!("0x11"),
+        channel_type_args: h256!("0x12"),
+        packet_type_args: h256!("0x13"),
+        fee_rate: Default::default(),
+        // each transaction is committed in a block of its own
+        confirms: 0,
+        event_confirmations: 0,
+        tx_timeout: default::tx_timeout(),
+        tx_replacement_timeout: default::tx_replacement_timeout(),
+        write_ack_reuse_block_gap: default::write_ack_reuse_block_gap(),
+        dry_run: false,
+        indexer_page_size: default::indexer_page_size(),
+        max_concurrent_tx_fetches: default::max_concurrent_tx_fetches(),
+        cell_cache_size: default::cell_cache_size(),
+        data_dir: Some(data_dir.path().to_path_buf()),
+        sudt_transfer: None,
+        packet_filter: Default::default(),
+        rescan_from_height: None,
+        onchain_light_clients: HashMap::from([(ClientType::Axon, axon_client)]),
+    }
+}
+
+fn typed_output(code_hash: &H256, args: &H256) -> CellOutput {
+    let type_script = Script::new_builder()
+        .code_hash(code_hash.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args(args.as_bytes().pack())
+        .build();
+    CellOutput::new_builder()
+        .type_(Some(type_script).pack())
+        .build_exact_capacity(Capacity::zero())
+        .unwrap()
+}
+
+/// Axon metadata cell data at `epoch`, with the epochs starting at the heights
+/// of `epochs` and lasting `epoch_len` blocks
+fn metadata_cell_data(epoch: u64, epochs: &[u64], epoch_len: u32) -> Bytes {
+    let validators = ValidatorList::new_builder()
+        .push(Validator::default())
+        .build();
+    let metadata = epochs.iter().map(|block_height| {
+        Metadata::new_builder()
+            .epoch_len(Uint32::from_slice(&epoch_len.to_le_bytes()).unwrap())
+            .block_height(Uint64::from_slice(&block_height.to_le_bytes()).unwrap())
+            .validators(validators.clone())
+            .build()
+    });
+    MetadataCellData::new_builder()
+        .epoch(Uint64::from_slice(&epoch.to_le_bytes()).unwrap())
+        .metadata(MetadataList::new_builder().extend(metadata).build())
+        .build()
+        .as_bytes()
+}
+
+fn witness(object: BytesOpt) -> packed::Bytes {
+    let witness = WitnessArgs::new_builder().output_type(object).build();
+    witness.as_bytes().pack()
+}
+
+/// Deploys the contracts and the Axon client, along with the connection cell
+/// of the client holding `connections`
+fn deploy_ibc(rpc_client: &RpcClient, config: &Ckb4IbcChainConfig, connections: &IbcConnections) {
+    for args in [
+        &config.connection_type_args,
+        &config.channel_type_args,
+        &config.packet_type_args,
+    ] {
+        rpc_client.add_live_cell(typed_output(&TYPE_ID_CODE_HASH, args), Bytes::new());
+    }
+    let client = &config.onchain_light_clients[&ClientType::Axon];
+    let client_cell = typed_output(&config.client_code_hash, &client.client_cell_type_args);
+    rpc_client.add_live_cell(client_cell, metadata_cell_data(1, &[50, 100], 50));
+
+    let client_id = config.lc_client_id(ClientType::Axon).unwrap();
+    let lock = get_connection_lock_script(config, Some(client_id.to_string())).unwrap();
+    let connections = get_encoded_object(connections);
+    let envelope = get_encoded_object(&Envelope {
+        msg_type: MsgType::MsgClientCreate,
+        content: vec![],
+        commitments: vec![],
+    });
+    let output = CellOutput::new_builder()
+        .lock(lock)
+        .build_exact_capacity(Capacity::bytes(connections.data.len()).unwrap())
+        .unwrap();
+    let tx = TransactionBuilder::default()
+        .output(output)
+        .output_data(connections.data)
+        .witness(witness(connections.witness))
+        .witness(witness(envelope.witness))
+        .build();
+    rpc_client.commit_transaction(tx.data());
+}
+
+/// Connections of the Axon client with a single open connection
+fn open_connection(next_channel_number: u64) -> IbcConnections {
+    IbcConnections {
+        connections: vec![CkbConnectionEnd {
+            state: CkbState::Open,
+            counterparty: ConnectionCounterparty {
+                client_id: ClientId::default().to_string(),
+                connection_id: "connection-0".to_owned(),
+                commitment_prefix: b"ibc".to_vec(),
+            },
+            ..Default::default()
+        }],
+        next_channel_number,
+    }
+}
+
+/// Commits an open channel of `port_id` on the first connection of the Axon
+/// client, as the handshake would leave it
+fn deploy_open_channel(
+    rpc_client: &RpcClient,
+    config: &Ckb4IbcChainConfig,
+    port_id: &PortId,
+) -> ChannelId {
+    let client_id = config.lc_client_id(ClientType::Axon).unwrap();
+    let connection_args = config.lc_connection_args(ClientType::Axon).unwrap();
+    let channel = IbcChannel {
+        number: 0,
+        port_id: port_id.to_string(),
+        state: CkbState::Open,
+        order: CkbOrdering::Unordered,
+        sequence: CkbSequence {
+            next_sequence_sends: 1,
+            next_sequence_recvs: 1,
+            next_sequence_acks: 1,
+            ..Default::default()
+        },
+        counterparty: ChannelCounterparty {
+            port_id: "transfer".to_owned(),
+            channel_id: "channel-0".to_owned(),
+            connection_id: "connection-0".to_owned(),
+        },
+        connection_hops: vec![ckb_ics_axon::connection_id(client_id.as_str(), 0)],
+        version: "ics20-1".to_owned(),
+    };
+    let channel_args = ChannelArgs {
+        metadata_type_id: connection_args.metadata_type_id,
+        ibc_handler_address: connection_args.ibc_handler_address,
+        open: true,
+        channel_id: channel.number,
+        port_id: convert_port_id_to_array(port_id).unwrap(),
+    };
+    let lock = Script::new_builder()
+        .code_hash(get_script_hash(&config.channel_type_args))
+        .hash_type(ScriptHashType::Type.into())
+        .args(channel_args.to_args().pack())
+        .build();
+    let channel = get_encoded_object(&channel);
+    let envelope = get_encoded_object(&Envelope {
+        msg_type: MsgType::MsgChannelOpenAck,
+        content: vec![],
+        commitments: vec![],
+    });
+    let output = CellOutput::new_builder()
+        .lock(lock)
+        .build_exact_capacity(Capacity::bytes(channel.data.len()).unwrap())
+        .unwrap();
+    let tx = TransactionBuilder::default()
+        .output(output)
+        .output_data(channel.data)
+        .witness(witness(channel.witness))
+        .witness(witness(envelope.witness))
+        .build();
+    rpc_client.commit_transaction(tx.data());
+    ChannelId::new(0)
+}
+
+/// Bootstraps the chain with the key of `MNEMONIC` as its signer
+fn bootstrap_with_key(config: &Ckb4IbcChainConfig) -> Ckb4IbcChain {
+    // the paused clock skips the waits for transactions to be committed
+    let rt = RuntimeBuilder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .unwrap();
+    let mut chain =
+        Ckb4IbcChain::bootstrap(ChainConfig::Ckb4Ibc(config.clone()), Arc::new(rt)).unwrap();
+    let key = {
+        let hd_path = StandardHDPath::from_str(HD_PATH).unwrap();
+        Secp256k1KeyPair::from_mnemonic(MNEMONIC, &hd_path, &AddressType::Ckb, "ckt").unwrap()
+    };
+    chain.keybase_mut().add_key(&config.key_name, key).unwrap();
+    chain
+}
+
+/// Gives the signer of `chain` a plain cell to pay the transactions with
+fn fund(rpc_client: &RpcClient, chain: &Ckb4IbcChain) -> packed::OutPoint {
+    let address = chain.tx_assembler_address().unwrap();
+    let funding = CellOutput::new_builder()
+        .lock(Script::from(&address))
+        .capacity(Capacity::bytes(100_000).unwrap().pack())
+        .build();
+    rpc_client.add_live_cell(funding, Bytes::new())
+}
+
+#[test]
+fn test_connection_open_init() {
+    let data_dir = TempDir::new().unwrap();
+    let config = chain_config("open_init", &data_dir);
+    let rpc_client = RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc);
+    rpc_client.set_blockchain_info(Some(CHAIN_INFO));
+    deploy_ibc(&rpc_client, &config, &IbcConnections::default());
+    let mut chain = bootstrap_with_key(&config);
+    let funding = fund(&rpc_client, &chain);
+
+    let msg = MsgConnectionOpenInit {
+        client_id: config.lc_client_id(ClientType::Axon).unwrap(),
+        counterparty: Counterparty::new(
+            ClientId::default(),
+            None,
+            chain.query_commitment_prefix().unwrap(),
+        ),
+        version: Some(Version::default()),
+        delay_period: Duration::ZERO,
+        signer: "ckb4ibc-relayer".parse().unwrap(),
+    };
+    let mut connection_ids = vec![];
+    for _ in 0..2 {
+        let msgs = TrackedMsgs::new_static(vec![msg.clone().to_any()], "test");
+        let events = chain.send_messages_and_wait_commit(msgs).unwrap();
+        assert_eq!(events.len(), 1);
+        let IbcEvent::OpenInitConnection(event) = &events[0].event else {
+            panic!("unexpected event {:?}", events[0].event);
+        };
+        connection_ids.push(event.connection_id().cloned().unwrap());
+    }
+    assert_ne!(connection_ids[0], connection_ids[1]);
+    assert!(!rpc_client.is_live(&funding));
+    assert_eq!(rpc_client.get_transactions_len(), 2);
+
+    // both connections are in the cell replacing the consumed ones
+    let connections = chain
+        .query_connections(QueryConnectionsRequest { pagination: None })
+        .unwrap();
+    let queried_ids = connections
+        .iter()
+        .map(|connection| connection.connection_id.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(queried_ids, connection_ids);
+    assert!(connections
+        .iter()
+        .all(|connection| *connection.connection_end.state() == State::Init));
+}
+
+#[test]
+fn test_monitor_without_cursor_store() {
+    let data_dir = TempDir::new().unwrap();
+    let config = chain_config("monitor", &data_dir);
+    let rpc_client = RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc);
+    rpc_client.set_blockchain_info(Some(CHAIN_INFO));
+    let connections = IbcConnections {
+        connections: vec![CkbConnectionEnd {
+            state: CkbState::Init,
+            counterparty: ConnectionCounterparty {
+                client_id: ClientId::default().to_string(),
+                connection_id: String::new(),
+                commitment_prefix: b"ibc".to_vec(),
+            },
+            ..Default::default()
+        }],
+        next_channel_number: 0,
+    };
+    deploy_ibc(&rpc_client, &config, &connections);
+
+    // the way `listen` runs a monitor next to the one of a running relayer
+    let rt = RuntimeBuilder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let (client_type_tx, client_type_rx) = watch::channel(Some(ClientType::Axon));
+    let (monitor, tx_cmd, _, ibc_transaction_rx) = Ckb4IbcEventMonitor::new(
+        Arc::new(rt),
+        Arc::new(rpc_client),
+        config.clone(),
+        client_type_rx,
+        CursorStore::disabled(),
+    );
+    let monitor = thread::spawn(move || {
+        let _client_type_tx = client_type_tx;
+        monitor.run()
+    });
+    let subscription = tx_cmd.subscribe().unwrap();
+    let batch = subscription.recv_timeout(Duration::from_secs(10)).unwrap();
+    let batch = batch.as_ref().as_ref().unwrap();
+    assert_eq!(batch.events.len(), 1);
+    let IbcEvent::OpenInitConnection(event) = &batch.events[0].event else {
+        panic!("unexpected event {:?}", batch.events[0].event);
+    };
+    let client_id = config.lc_client_id(ClientType::Axon).unwrap();
+    assert_eq!(event.attributes().client_id, client_id);
+    assert!(ibc_transaction_rx.try_recv().is_ok());
+
+    tx_cmd.shutdown().unwrap();
+    monitor.join().unwrap();
+}
+
+#[test]
+fn test_bump_tx_fee_from_change_output() {
+    let change_lock = Script::new_builder().args(vec![1u8; 20].pack()).build();
+    let other_lock = Script::new_builder().args(vec![2u8; 20].pack()).build();
+    let output = |lock: &Script, ckb: u64| {
+        CellOutput::new_builder()
+            .lock(lock.clone())
+            .capacity(Capacity::bytes(ckb as usize).unwrap().pack())
+            .build()
+    };
+    let sudt = typed_output(&h256!("0x20"), &h256!("0x21"))
+        .as_builder()
+        .lock(change_lock.clone())
+        .capacity(Capacity::bytes(200).unwrap().pack())
+        .build();
+    let tx = TransactionBuilder::default()
+        .output(output(&change_lock, 100))
+        .output_data(Bytes::new().pack())
+        .output(sudt)
+        .output_data(Bytes::from(vec![0u8; 16]).pack())
+        .output(output(&change_lock, 300))
+        .output_data(Bytes::new().pack())
+        .output(output(&other_lock, 400))
+        .output_data(Bytes::new().pack())
+        .build();
+    let tx_size = tx.data().serialized_size_in_block() as u64;
+    let extra_fee = (1500 * tx_size + 999) / 1000;
+
+    let (bumped, fee) = bump_tx_fee(&tx, 1000, &change_lock, 1500, None).unwrap();
+    assert_eq!(fee, 1000 + extra_fee);
+    // only the last plain output of the change lock pays the extra fee
+    let capacities = |tx: &ckb_types::core::TransactionView| {
+        tx.outputs()
+            .into_iter()
+            .map(|output| Unpack::<u64>::unpack(&output.capacity()))
+            .collect::<Vec<_>>()
+    };
+    let mut expected = capacities(&tx);
+    expected[2] -= extra_fee;
+    assert_eq!(capacities(&bumped), expected);
+    assert_eq!(bumped.data().serialized_size_in_block() as u64, tx_size);
+
+    // the ceiling is counted on the same size as the extra fee
+    let max_rate = (1000 + extra_fee - 1) / tx_size;
+    assert!(bump_tx_fee(&tx, 1000, &change_lock, 1500, Some(max_rate)).is_err());
+    assert!(bump_tx_fee(&tx, 1000, &change_lock, 1500, Some(max_rate + 1)).is_ok());
+
+    // the change output can't go below its occupied capacity
+    assert!(bump_tx_fee(&tx, 1000, &change_lock, 1_000_000_000_000, None).is_err());
+    assert!(bump_tx_fee(&tx, 1000, &Script::default(), 1500, None).is_err());
+}
+
+#[test]
+fn test_decode_axon_metadata_cell() {
+    // the metadata of the current epoch is the one at the epoch index
+    let (heights, _) = decode_axon_metadata_cell(&metadata_cell_data(0, &[50, 100], 50)).unwrap();
+    assert_eq!(heights, 50..100);
+    let (heights, _) = decode_axon_metadata_cell(&metadata_cell_data(1, &[50, 100], 50)).unwrap();
+    assert_eq!(heights, 100..150);
+    assert!(decode_axon_metadata_cell(&metadata_cell_data(2, &[50, 100], 50)).is_err());
+    assert!(decode_axon_metadata_cell(&[]).is_err());
+}
+
+#[test]
+fn test_query_onchain_light_client() {
+    let data_dir = TempDir::new().unwrap();
+    let mut config = chain_config("light_client", &data_dir);
+    // the cell of this client is never deployed
+    config.onchain_light_clients.insert(
+        ClientType::Eth,
+        LightClientItem {
+            chain_id: ChainId::new("eth".to_owned(), 0),
+            client_cell_type_args: h256!("0x15"),
+            ibc_handler_address: H160([2; 20]),
+            rpc_addr: None,
+        },
+    );
+    let rpc_client = RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc);
+    rpc_client.set_blockchain_info(Some(CHAIN_INFO));
+    deploy_ibc(&rpc_client, &config, &IbcConnections::default());
+
+    let rt = RuntimeBuilder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let chain =
+        Ckb4IbcChain::bootstrap(ChainConfig::Ckb4Ibc(config.clone()), Arc::new(rt)).unwrap();
+    let client_id = config.lc_client_id(ClientType::Axon).unwrap();
+
+    let (client_state, _) = chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .unwrap();
+    assert_eq!(client_state.latest_height(), Height::new(1, 100).unwrap());
+
+    // the missing client is skipped
+    let clients = chain
+        .query_clients(QueryClientStatesRequest { pagination: None })
+        .unwrap();
+    assert_eq!(clients.len(), 1);
+    assert_eq!(clients[0].client_id, client_id);
+
+    // the validators of the epoch verify each of its blocks
+    let consensus_state = |height: u64| {
+        chain.query_consensus_state(
+            QueryConsensusStateRequest {
+                client_id: client_id.clone(),
+                consensus_height: Height::new(1, height).unwrap(),
+                query_height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+    };
+    let (AnyConsensusState::Ckb(CkbConsensusState { root, .. }), _) = consensus_state(149).unwrap()
+    else {
+        panic!("unexpected consensus state");
+    };
+    let validators = ValidatorList::new_builder()
+        .push(Validator::default())
+        .build();
+    assert_eq!(root.as_bytes(), &blake2b_256(validators.as_slice()));
+    assert!(consensus_state(99).is_err());
+    assert!(consensus_state(150).is_err());
+}
+
+#[test]
+fn test_sudt_transfer() {
+    let data_dir = TempDir::new().unwrap();
+    let mut config = chain_config("sudt_transfer", &data_dir);
+    let sudt_transfer = SudtTransferConfig {
+        contract_type_args: h256!("0x16"),
+        sudt_type_args: h256!("0x17"),
+        lock_args: Default::default(),
+    };
+    config.sudt_transfer = Some(sudt_transfer.clone());
+    let rpc_client = RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc);
+    rpc_client.set_blockchain_info(Some(CHAIN_INFO));
+
+    let client_id = config.lc_client_id(ClientType::Axon).unwrap();
+    deploy_ibc(&rpc_client, &config, &open_connection(1));
+    for args in [
+        &sudt_transfer.contract_type_args,
+        &sudt_transfer.sudt_type_args,
+    ] {
+        rpc_client.add_live_cell(typed_output(&TYPE_ID_CODE_HASH, args), Bytes::new());
+    }
+    let st_lock = sudt_transfer.lock_script();
+    let port_id: PortId = hex::encode(st_lock.calc_script_hash().as_slice())
+        .parse()
+        .unwrap();
+    let channel_id = deploy_open_channel(&rpc_client, &config, &port_id);
+
+    let mut chain = bootstrap_with_key(&config);
+    fund(&rpc_client, &chain);
+    // the channels are searched on the connections of the counterparty client
+    chain
+        .query_client_connections(QueryClientConnectionsRequest {
+            client_id: client_id.clone(),
+        })
+        .unwrap();
+
+    // the sUDT standard lets cells carry data after the amount
+    let sudt_type_script = Script::new_builder()
+        .code_hash(sudt_transfer.sudt_code_hash().pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args(vec![7u8; 32].pack())
+        .build();
+    let sudt_cell = |lock: &Script, amount: u128, extra: &[u8]| {
+        let output = CellOutput::new_builder()
+            .lock(lock.clone())
+            .type_(Some(sudt_type_script.clone()).pack())
+            .capacity(Capacity::bytes(200).unwrap().pack())
+            .build();
+        let data = [&amount.to_le_bytes()[..], extra].concat();
+        rpc_client.add_live_cell(output, data.into())
+    };
+    let sender_lock = Script::from(&chain.tx_assembler_address().unwrap());
+    let sender_cells = [
+        sudt_cell(&sender_lock, 60, &[]),
+        sudt_cell(&sender_lock, 50, &[]),
+    ];
+    // the change cell can't keep the data of several cells
+    let sender_extra_cell = sudt_cell(&sender_lock, 40, b"extra");
+    let st_cell = sudt_cell(&st_lock, 1000, b"extra");
+
+    let receiver = "0x".to_owned() + &hex::encode([2u8; 20]);
+    let timeout_timestamp = Timestamp::from_nanoseconds(1_700_000_000 * SEC_TO_NANO).unwrap();
+    let msg = MsgTransfer {
+        source_port: port_id.clone(),
+        source_channel: channel_id.clone(),
+        token: Coin {
+            denom: sudt_denom(&sudt_type_script),
+            amount: "100".to_owned(),
+        },
+        sender: "ckb4ibc-relayer".parse().unwrap(),
+        receiver: receiver.parse().unwrap(),
+        timeout_height: TimeoutHeight::Never,
+        timeout_timestamp,
+        memo: Some("memo".to_owned()),
+    };
+    let msgs = TrackedMsgs::new_static(vec![msg.to_any()], "test");
+    let events = chain.send_messages_and_wait_commit(msgs).unwrap();
+    assert_eq!(events.len(), 1);
+    let IbcEvent::SendPacket(event) = &events[0].event else {
+        panic!("unexpected event {:?}", events[0].event);
+    };
+    assert_eq!(event.packet.source_channel, channel_id);
+    assert_eq!(u64::from(event.packet.sequence), 1);
+    assert_eq!(event.packet.timeout_timestamp, timeout_timestamp);
+    let data = FungibleTokenPacketData::decode(event.packet.data.as_slice()).unwrap();
+    assert_eq!(data.memo, "memo");
+    assert_eq!(data.receiver, [2u8; 20]);
+
+    // the plain sender cells and the st-cell are consumed
+    assert!(sender_cells.iter().all(|cell| !rpc_client.is_live(cell)));
+    assert!(rpc_client.is_live(&sender_extra_cell));
+    assert!(!rpc_client.is_live(&st_cell));
+    let tx = packed::Transaction::from(rpc_client.get_transaction_by_index(0).unwrap()).into_view();
+    let sudt_output = |index: usize| {
+        let output = tx.output(index).unwrap();
+        let data = tx.outputs_data().get(index).unwrap().raw_data();
+        assert_eq!(output.type_().to_opt(), Some(sudt_type_script.clone()));
+        (output.lock(), sudt_amount(&data).unwrap(), data.slice(16..))
+    };
+    // the st-cell escrows the transferred amount and keeps its data, the rest
+    // goes back as change
+    assert_eq!(
+        sudt_output(2),
+        (st_lock, 1100, Bytes::from_static(b"extra"))
+    );
+    assert_eq!(sudt_output(3), (sender_lock, 10, Bytes::new()));
+}
+
+#[test]
+fn test_channel_handshake() {
+    let data_dir = TempDir::new().unwrap();
+    let config = chain_config("channel_handshake", &data_dir);
+    let rpc_client = RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc);
+    rpc_client.set_blockchain_info(Some(CHAIN_INFO));
+    deploy_ibc(&rpc_client, &config, &open_connection(0));
+    let mut chain = bootstrap_with_key(&config);
+    fund(&rpc_client, &chain);
+    let client_id = config.lc_client_id(ClientType::Axon).unwrap();
+    chain
+        .query_client_connections(QueryClientConnectionsRequest {
+            client_id: client_id.clone(),
+        })
+        .unwrap();
+
+    let port_id: PortId = hex::encode([3u8; 32]).parse().unwrap();
+    let connection_id = ckb_ics_axon::connection_id(client_id.as_str(), 0);
+    let init = MsgChannelOpenInit::new(
+        port_id.clone(),
+        ChannelEnd::new(
+            ChannelState::Init,
+            Order::Unordered,
+            ChannelEndCounterparty::new(PortId::transfer(), None),
+            vec![connection_id.parse().unwrap()],
+            ChannelVersion::ics20(),
+        ),
+        "ckb4ibc-relayer".parse().unwrap(),
+    );
+    let msgs = TrackedMsgs::new_static(vec![init.to_any()], "test");
+    let events = chain.send_messages_and_wait_commit(msgs).unwrap();
+    assert_eq!(events.len(), 1);
+    let IbcEvent::OpenInitChannel(event) = &events[0].event else {
+        panic!("unexpected event {:?}", events[0].event);
+    };
+    let channel_id = event.channel_id().cloned().unwrap();
+    assert_eq!(channel_id, ChannelId::new(0));
+
+    let query_channel = |chain: &Ckb4IbcChain| {
+        let request = QueryChannelRequest {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            height: QueryHeight::Latest,
+        };
+        chain.query_channel(request, IncludeProof::No).unwrap().0
+    };
+    assert_eq!(*query_channel(&chain).state(), ChannelState::Init);
+
+    let proofs = Proofs::new(
+        vec![1u8].try_into().unwrap(),
+        None,
+        None,
+        None,
+        Height::new(1, 100).unwrap(),
+    )
+    .unwrap();
+    let ack = MsgChannelOpenAck {
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        counterparty_channel_id: ChannelId::new(0),
+        counterparty_version: ChannelVersion::ics20(),
+        proofs,
+        signer: "ckb4ibc-relayer".parse().unwrap(),
+    };
+    let msgs = TrackedMsgs::new_static(vec![ack.to_any()], "test");
+    let events = chain.send_messages_and_wait_commit(msgs).unwrap();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0].event, IbcEvent::OpenAckChannel(_)));
+    assert_eq!(rpc_client.get_transactions_len(), 2);
+
+    let channel_end = query_channel(&chain);
+    assert_eq!(*channel_end.state(), ChannelState::Open);
+    assert_eq!(
+        channel_end.counterparty().channel_id(),
+        Some(&ChannelId::new(0))
+    );
+}
+
+#[test]
+fn test_recv_packet() {
+    let data_dir = TempDir::new().unwrap();
+    let config = chain_config("recv_packet", &data_dir);
+    let rpc_client = RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc);
+    rpc_client.set_blockchain_info(Some(CHAIN_INFO));
+    deploy_ibc(&rpc_client, &config, &open_connection(1));
+    let port_id: PortId = hex::encode([3u8; 32]).parse().unwrap();
+    let channel_id = deploy_open_channel(&rpc_client, &config, &port_id);
+    let mut chain = bootstrap_with_key(&config);
+    fund(&rpc_client, &chain);
+    let client_id = config.lc_client_id(ClientType::Axon).unwrap();
+    chain
+        .query_client_connections(QueryClientConnectionsRequest { client_id })
+        .unwrap();
+
+    let packet = Packet {
+        sequence: Sequence::from(1u64),
+        source_port: PortId::transfer(),
+        source_channel: ChannelId::new(0),
+        destination_port: port_id.clone(),
+        destination_channel: channel_id.clone(),
+        data: b"packet".to_vec(),
+        timeout_height: TimeoutHeight::Never,
+        timeout_timestamp: Timestamp::none(),
+    };
+    let proofs = Proofs::new(
+        vec![1u8].try_into().unwrap(),
+        None,
+        None,
+        None,
+        Height::new(1, 100).unwrap(),
+    )
+    .unwrap();
+    let msg = MsgRecvPacket::new(packet, proofs, "ckb4ibc-relayer".parse().unwrap());
+    let msgs = TrackedMsgs::new_static(vec![msg.clone().to_any()], "test");
+    let events = chain.send_messages_and_wait_commit(msgs).unwrap();
+    assert_eq!(events.len(), 1);
+    let IbcEvent::ReceivePacket(event) = &events[0].event else {
+        panic!("unexpected event {:?}", events[0].event);
+    };
+    assert_eq!(u64::from(event.packet.sequence), 1);
+
+    let (receipt, _) = chain
+        .query_packet_receipt(
+            QueryPacketReceiptRequest {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                sequence: Sequence::from(1u64),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .unwrap();
+    assert_eq!(receipt, vec![1]);
+    let unreceived = chain
+        .query_unreceived_packets(QueryUnreceivedPacketsRequest {
+            port_id,
+            channel_id,
+            packet_commitment_sequences: vec![Sequence::from(1u64), Sequence::from(2u64)],
+        })
+        .unwrap();
+    assert_eq!(unreceived, vec![Sequence::from(2u64)]);
+
+    // the unordered channel records the sequence, a replay is rejected
+    let msgs = TrackedMsgs::new_static(vec![msg.to_any()], "test");
+    assert!(chain.send_messages_and_wait_commit(msgs).is_err());
+    assert_eq!(rpc_client.get_transactions_len(), 1);
+}
@@ -1,3 +1,4 @@
+use std::ops::Range;
 use std::str::FromStr;
 
 use crate::chain::axon::utils::convert_err;
@@ -7,6 +8,8 @@ use crate::config::ckb4ibc::ChainConfig;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use axon_tools::precompile::{verify_proof, Proof, VerifyProofPayload};
+use axon_types::metadata::MetadataCellData;
+use ckb_hash::blake2b_256;
 use ckb_ics_axon::consts::CHANNEL_ID_PREFIX;
 use ckb_ics_axon::handler::IbcPacket;
 use ckb_ics_axon::message::MsgType;
@@ -19,7 +22,7 @@ use ckb_sdk::rpc::ckb_indexer::ScriptSearchMode;
 use ckb_sdk::rpc::ckb_light_client::{ScriptType, SearchKey};
 use ckb_sdk::traits::{CellQueryOptions, ValueRangeOption};
 use ckb_sdk::NetworkType;
-use ckb_types::core::ScriptHashType;
+use ckb_types::core::{Capacity, ScriptHashType, TransactionView as CoreTransactionView};
 use ckb_types::packed::{Byte32, Bytes, BytesOpt, OutPoint, Script, Transaction};
 use ckb_types::prelude::{Builder, Entity, Pack, Unpack};
 use ckb_types::utilities::{merkle_root, MerkleProof};
@@ -32,7 +35,7 @@ use ibc_relayer_types::core::ics04_channel::events::{
 };
 use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
 use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
-use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
+use ibc_relayer_types::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
 use ibc_relayer_types::proofs::{ConsensusProof, Proofs};
@@ -65,6 +68,57 @@ pub struct EncodedObject {
     pub data: Bytes,
 }
 
+/// Returns `tx` paying at least `min_rbf_rate` shannons per kilobyte more than
+/// `fee`, along with the new fee. The extra fee is taken from the change
+/// output, which is the last output locked by `change_lock` without type
+/// script nor data.
+pub fn bump_tx_fee(
+    tx: &CoreTransactionView,
+    fee: u64,
+    change_lock: &Script,
+    min_rbf_rate: u64,
+    max_rate: Option<u64>,
+) -> Result<(CoreTransactionView, u64), Error> {
+    let tx_size = tx.data().serialized_size_in_block() as u64;
+    let extra_fee = (min_rbf_rate * tx_size + 999) / 1000;
+    let new_fee = fee + extra_fee;
+    if let Some(max_rate) = max_rate {
+        let max_fee = max_rate * tx_size;
+        if new_fee > max_fee {
+            return Err(Error::send_tx(format!(
+                "replacement fee {new_fee} exceeds the ceiling {max_fee}"
+            )));
+        }
+    }
+
+    let mut outputs = tx.outputs().into_iter().collect::<Vec<_>>();
+    let change_index = outputs
+        .iter()
+        .zip(tx.outputs_data())
+        .rposition(|(output, data)| {
+            &output.lock() == change_lock && output.type_().is_none() && data.is_empty()
+        })
+        .ok_or_else(|| Error::send_tx("no change output to pay the extra fee".to_owned()))?;
+    let change = &outputs[change_index];
+    let capacity: u64 = change.capacity().unpack();
+    let occupied_capacity = change
+        .occupied_capacity(Capacity::zero())
+        .map_err(|err| Error::send_tx(err.to_string()))?
+        .as_u64();
+    if capacity < occupied_capacity + extra_fee {
+        return Err(Error::send_tx(format!(
+            "change capacity {capacity} is too low to pay the extra fee {extra_fee}"
+        )));
+    }
+    outputs[change_index] = change
+        .clone()
+        .as_builder()
+        .capacity((capacity - extra_fee).pack())
+        .build();
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    Ok((tx, new_fee))
+}
+
 pub fn get_encoded_object<T: Encodable>(obj: &T) -> EncodedObject {
     let content = rlp::encode(obj);
     let slice = content.as_ref();
@@ -257,12 +311,17 @@ pub fn get_search_key_with_sudt(
         .hash_type(ScriptHashType::Type.into())
         .args(owner_lockhash.as_bytes().to_vec().pack())
         .build();
+    Ok(get_search_key_with_sudt_script(script, sudt_script))
+}
+
+pub fn get_search_key_with_sudt_script(script: Script, sudt_script: Script) -> SearchKey {
     let mut query = CellQueryOptions::new_lock(script);
     query.with_data = Some(true);
     query.script_search_mode = Some(ScriptSearchMode::Exact);
     query.secondary_script = Some(sudt_script);
-    query.data_len_range = Some(ValueRangeOption::new_exact(16));
-    Ok(query.into())
+    // the amount takes the first 16 bytes, the sUDT standard allows more data
+    query.data_len_range = Some(ValueRangeOption::new_min(16));
+    query.into()
 }
 
 pub fn get_client_outpoint(
@@ -641,3 +700,32 @@ fn jsonrpc_merkle_root(
     .map(|v| v.unpack().into())
     .ok_or(Error::other_error("invalid merkle proof".to_owned()))
 }
+
+/// Decodes the metadata cell of an Axon light client, returning the Axon heights
+/// verified by the validators of its current epoch and the root of those validators
+pub fn decode_axon_metadata_cell(data: &[u8]) -> Result<(Range<u64>, CommitmentRoot), Error> {
+    let cell_data = MetadataCellData::from_slice(data)
+        .map_err(|e| Error::other_error(format!("invalid Axon metadata cell data: {e}")))?;
+    let mut epoch = [0u8; 8];
+    epoch.copy_from_slice(cell_data.epoch().as_slice());
+    let epoch = u64::from_le_bytes(epoch);
+    let metadata = usize::try_from(epoch)
+        .ok()
+        .and_then(|index| cell_data.metadata().get(index))
+        .ok_or_else(|| {
+            Error::other_error(format!(
+                "Axon metadata cell holds no metadata of epoch {epoch}"
+            ))
+        })?;
+    let mut block_height = [0u8; 8];
+    block_height.copy_from_slice(metadata.block_height().as_slice());
+    let mut epoch_len = [0u8; 4];
+    epoch_len.copy_from_slice(metadata.epoch_len().as_slice());
+    let start = u64::from_le_bytes(block_height);
+    let epoch_len = u32::from_le_bytes(epoch_len).max(1) as u64;
+    let root = blake2b_256(metadata.validators().as_slice());
+    Ok((
+        start..start.saturating_add(epoch_len),
+        CommitmentRoot::from_bytes(&root),
+    ))
+}
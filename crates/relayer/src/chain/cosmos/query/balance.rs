@@ -32,10 +32,7 @@ pub async fn query_balance(
         .balance
         .ok_or_else(|| Error::empty_query_account(account_address.to_string()))?;
 
-    Ok(Balance {
-        amount: balance.amount,
-        denom: balance.denom,
-    })
+    Ok(Balance::new(balance.amount, balance.denom))
 }
 
 /// Uses the GRPC client to retrieve the account balance for all denom
@@ -61,10 +58,7 @@ pub async fn query_all_balances(
     let balances = response
         .balances
         .into_iter()
-        .map(|balance| Balance {
-            amount: balance.amount,
-            denom: balance.denom,
-        })
+        .map(|balance| Balance::new(balance.amount, balance.denom))
         .collect();
 
     Ok(balances)
@@ -15,7 +15,7 @@ use ibc_relayer_types::clients::ics07_ckb::client_state::{
     CkbClientState, CKB_CLIENT_STATE_TYPE_URL,
 };
 use ibc_relayer_types::clients::ics07_eth::client_state::{
-    EthClientState, ETH_CLIENT_STATE_TYPE_URL,
+    EthClientState, EthMultiClientState, ETH_CLIENT_STATE_TYPE_URL, ETH_MULTI_CLIENT_STATE_TYPE_URL,
 };
 use ibc_relayer_types::clients::ics07_tendermint::client_state::{
     ClientState as TmClientState, UpgradeOptions as TmUpgradeOptions,
@@ -65,6 +65,7 @@ impl UpgradeOptions for AnyUpgradeOptions {}
 pub enum AnyClientState {
     Tendermint(TmClientState),
     Eth(EthClientState),
+    EthMulti(EthMultiClientState),
     Ckb(CkbClientState),
     Axon(AxonClientState),
 
@@ -77,6 +78,7 @@ impl AnyClientState {
         match self {
             Self::Tendermint(tm_state) => tm_state.latest_height(),
             Self::Eth(state) => state.latest_height(),
+            Self::EthMulti(state) => state.latest_height(),
             Self::Ckb(state) => state.latest_height(),
             Self::Axon(state) => state.latest_height(),
 
@@ -89,6 +91,7 @@ impl AnyClientState {
         match self {
             Self::Tendermint(tm_state) => tm_state.frozen_height(),
             Self::Eth(state) => state.frozen_height(),
+            Self::EthMulti(state) => state.frozen_height(),
             Self::Ckb(state) => state.frozen_height(),
             Self::Axon(state) => state.frozen_height(),
 
@@ -104,6 +107,7 @@ impl AnyClientState {
             // `ONE_THRID` of TrustThreshold is just a mock value to pass runtime check
             // for non-cosmos chains
             AnyClientState::Eth(_) => Some(TrustThreshold::ONE_THIRD),
+            AnyClientState::EthMulti(_) => Some(TrustThreshold::ONE_THIRD),
             AnyClientState::Ckb(_) => Some(TrustThreshold::ONE_THIRD),
             AnyClientState::Axon(_) => Some(TrustThreshold::ONE_THIRD),
 
@@ -116,6 +120,7 @@ impl AnyClientState {
         match self {
             AnyClientState::Tendermint(state) => state.max_clock_drift,
             AnyClientState::Eth(_) => Duration::ZERO,
+            AnyClientState::EthMulti(_) => Duration::ZERO,
             AnyClientState::Ckb(_) => Duration::ZERO,
             AnyClientState::Axon(_) => Duration::ZERO,
 
@@ -128,6 +133,7 @@ impl AnyClientState {
         match self {
             Self::Tendermint(state) => state.client_type(),
             Self::Eth(state) => state.client_type(),
+            Self::EthMulti(state) => state.client_type(),
             Self::Ckb(state) => state.client_type(),
             Self::Axon(state) => state.client_type(),
 
@@ -140,6 +146,7 @@ impl AnyClientState {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.refresh_time(),
             AnyClientState::Eth(_) => None,
+            AnyClientState::EthMulti(_) => None,
             AnyClientState::Ckb(_) => None,
             AnyClientState::Axon(_) => None,
 
@@ -189,6 +196,13 @@ impl From<AnyClientState> for Any {
                     value: json.into_bytes(),
                 }
             }
+            AnyClientState::EthMulti(value) => {
+                let json = serde_json::to_string(&value).expect("jsonify eth multi clientstate");
+                Any {
+                    type_url: ETH_MULTI_CLIENT_STATE_TYPE_URL.to_owned(),
+                    value: json.into_bytes(),
+                }
+            }
             AnyClientState::Ckb(value) => {
                 let json = serde_json::to_string(&value).expect("jsonify ckb clientstate");
                 Any {
@@ -218,6 +232,7 @@ impl ClientState for AnyClientState {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.chain_id(),
             AnyClientState::Eth(state) => state.chain_id(),
+            AnyClientState::EthMulti(state) => state.chain_id(),
             AnyClientState::Ckb(state) => state.chain_id(),
             AnyClientState::Axon(state) => state.chain_id(),
 
@@ -255,6 +270,9 @@ impl ClientState for AnyClientState {
                 chain_id,
             ),
             AnyClientState::Eth(_) => todo!(),
+            AnyClientState::EthMulti(state) => {
+                state.upgrade(upgrade_height, upgrade_options, chain_id)
+            }
             AnyClientState::Ckb(_) => todo!(),
             AnyClientState::Axon(_) => todo!(),
 
@@ -269,6 +287,7 @@ impl ClientState for AnyClientState {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.expired(elapsed_since_latest),
             AnyClientState::Eth(_) => todo!(),
+            AnyClientState::EthMulti(_) => false,
             AnyClientState::Ckb(_) => false,
             AnyClientState::Axon(_) => false,
 
@@ -290,6 +309,12 @@ impl From<EthClientState> for AnyClientState {
     }
 }
 
+impl From<EthMultiClientState> for AnyClientState {
+    fn from(value: EthMultiClientState) -> Self {
+        Self::EthMulti(value)
+    }
+}
+
 impl From<CkbClientState> for AnyClientState {
     fn from(value: CkbClientState) -> Self {
         Self::Ckb(value)
@@ -331,6 +356,21 @@ impl<'a> TryFrom<&'a AnyClientState> for &'a EthClientState {
     }
 }
 
+impl<'a> TryFrom<&'a AnyClientState> for &'a EthMultiClientState {
+    type Error = RelayerError;
+
+    fn try_from(value: &'a AnyClientState) -> Result<Self, Self::Error> {
+        if let AnyClientState::EthMulti(value) = value {
+            Ok(value)
+        } else {
+            Err(RelayerError::client_type_mismatch(
+                ClientType::Eth,
+                value.client_type(),
+            ))
+        }
+    }
+}
+
 impl<'a> TryFrom<&'a AnyClientState> for &'a CkbClientState {
     type Error = RelayerError;
 
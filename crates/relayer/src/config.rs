@@ -31,6 +31,7 @@ use ibc_relayer_types::timestamp::ZERO_DURATION;
 use crate::chain::ChainType;
 use crate::error::Error as RelayerError;
 use crate::extension_options::ExtensionOptionDynamicFeeTx;
+use crate::keyring::Store;
 
 pub use crate::config::Error as ConfigError;
 use axon::AxonChainConfig;
@@ -232,6 +233,25 @@ impl ChainConfig {
         }
     }
 
+    pub fn set_key_name(&mut self, key_name: String) {
+        match self {
+            ChainConfig::Cosmos(c) => c.key_name = key_name,
+            ChainConfig::Eth(c) => c.key_name = key_name,
+            ChainConfig::Ckb(c) => c.key_name = key_name,
+            ChainConfig::Axon(c) => c.key_name = key_name,
+            ChainConfig::Ckb4Ibc(c) => c.key_name = key_name,
+        }
+    }
+
+    pub fn key_store_type(&self) -> Store {
+        match self {
+            ChainConfig::Cosmos(c) => c.key_store_type,
+            ChainConfig::Axon(c) => c.key_store_type,
+            ChainConfig::Ckb4Ibc(c) => c.key_store_type,
+            ChainConfig::Eth(_) | ChainConfig::Ckb(_) => Store::default(),
+        }
+    }
+
     pub fn downcast_cosmos(self) -> CosmosChainConfig {
         if let ChainConfig::Cosmos(c) = self {
             c
@@ -296,6 +316,14 @@ impl ChainConfig {
         }
     }
 
+    pub fn ckb(&self) -> &CkbChainConfig {
+        if let ChainConfig::Ckb(c) = self {
+            c
+        } else {
+            panic!("Not a ckb chain")
+        }
+    }
+
     pub fn r#type(&self) -> ChainType {
         match self {
             ChainConfig::Cosmos(_) => ChainType::CosmosSdk,
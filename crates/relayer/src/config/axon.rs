@@ -1,9 +1,13 @@
+use core::time::Duration;
+
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use serde_derive::{Deserialize, Serialize};
 use tendermint_rpc::Url;
 use tendermint_rpc::WebSocketClientUrl;
 
+use super::cosmos::gas_multiplier::GasMultiplier;
 use super::filter::PacketFilter;
+use crate::keyring::Store;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AxonChainConfig {
@@ -14,8 +18,98 @@ pub struct AxonChainConfig {
     pub transfer_contract_address: ethers::types::Address,
     pub restore_block_count: u64,
     pub key_name: String,
+    /// Backend of the keyring holding `key_name`
+    #[serde(default)]
+    pub key_store_type: Store,
     pub store_prefix: String,
 
+    /// Maximum number of IBC messages packed into a single `multicall`
+    /// transaction, setting it to 1 sends every message in its own transaction
+    #[serde(default = "default::max_msgs_per_batch")]
+    pub max_msgs_per_batch: usize,
+
+    /// Multiplier applied on top of the result of `eth_estimateGas`
+    pub gas_multiplier: Option<GasMultiplier>,
+    /// Upper bound of `gas_limit * gas_price` paid by a single transaction, in wei
+    pub max_fee: Option<u64>,
+    /// Percentage by which the gas price of a pending transaction is raised
+    /// when it gets replaced
+    #[serde(default = "default::price_bump_percent")]
+    pub price_bump_percent: u64,
+    /// How long to wait for a transaction to be committed before replacing
+    /// it with a higher priced one
+    #[serde(default = "default::tx_replacement_timeout", with = "humantime_serde")]
+    pub tx_replacement_timeout: Duration,
+    /// Gas price of transactions, EIP-1559 with node suggested fees by default
+    #[serde(default)]
+    pub gas_price: GasPrice,
+
     #[serde(default)]
     pub packet_filter: PacketFilter,
+
+    /// ERC-20 tokens whose balances are reported along with the native one,
+    /// the tokens of the transfer contract are only reported once listed here
+    /// or queried by denom
+    #[serde(default)]
+    pub tokens: Vec<AxonToken>,
+
+    /// Makes the event monitor ignore its checkpoint and scan events from
+    /// this block number, only settable from the command line
+    #[serde(skip)]
+    pub rescan_from_height: Option<u64>,
+}
+
+/// ERC-20 token on Axon, either deployed on its own or minted by the ICS-20
+/// transfer contract for a denom received over IBC.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum AxonToken {
+    /// ERC-20 contract at `address`, named `denom` or else by its symbol
+    Erc20 {
+        address: ethers::types::Address,
+        denom: Option<String>,
+    },
+    /// Token of the transfer contract for `denom`, which is either a full
+    /// denom trace or an `ibc/<hash>` denom
+    Ics20 { denom: String },
+}
+
+/// How the gas price of Axon transactions is chosen. Prices left unset are
+/// queried from the node.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum GasPrice {
+    /// Pre EIP-1559 transactions paying `gas_price` wei per gas
+    Legacy { gas_price: Option<u64> },
+    /// EIP-1559 transactions with the given max fee and priority fee per gas, in wei
+    Eip1559 {
+        max_fee_per_gas: Option<u64>,
+        max_priority_fee_per_gas: Option<u64>,
+    },
+}
+
+impl Default for GasPrice {
+    fn default() -> Self {
+        Self::Eip1559 {
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        }
+    }
+}
+
+pub mod default {
+    use super::*;
+
+    pub fn max_msgs_per_batch() -> usize {
+        1
+    }
+
+    pub fn price_bump_percent() -> u64 {
+        // the minimum bump accepted by geth-like mempools
+        10
+    }
+
+    pub fn tx_replacement_timeout() -> Duration {
+        Duration::from_secs(30)
+    }
 }
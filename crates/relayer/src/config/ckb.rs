@@ -5,6 +5,8 @@ use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use serde_derive::{Deserialize, Serialize};
 use tendermint_rpc::Url;
 
+use super::eth::EthNetwork;
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ChainConfig {
     pub id: ChainId,
@@ -15,6 +17,14 @@ pub struct ChainConfig {
     pub minimal_updates_count: u8,
     pub key_name: String,
     pub data_dir: PathBuf,
+    /// Ethereum network whose headers are relayed to the multi-client, a
+    /// `data_dir` only ever holds headers of one network
+    #[serde(default)]
+    pub eth_network: EthNetwork,
+    /// Ethereum chain whose headers the supervisor relays to the multi-client,
+    /// no header relay worker is spawned if unset
+    #[serde(default)]
+    pub eth_chain_id: Option<ChainId>,
     pub client_type_args: ClientTypeArgs,
 }
 
@@ -1,4 +1,6 @@
 use ckb_ics_axon::ConnectionArgs;
+use ckb_jsonrpc_types::JsonBytes;
+use ckb_sdk::constants::TYPE_ID_CODE_HASH;
 use ckb_types::{
     core::ScriptHashType,
     packed::Script,
@@ -12,9 +14,12 @@ use ibc_relayer_types::core::{
 use serde::ser::SerializeMap;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
 use tendermint_rpc::Url;
 
 use crate::error::Error;
+use crate::keyring::Store;
 
 use super::filter::PacketFilter;
 
@@ -23,6 +28,10 @@ pub struct LightClientItem {
     pub chain_id: ChainId,
     pub client_cell_type_args: H256,
     pub ibc_handler_address: H160,
+    /// JSON-RPC address of the counterparty chain, where the block times of
+    /// the consensus states are read from; they carry no time without it
+    #[serde(default)]
+    pub rpc_addr: Option<Url>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +40,9 @@ pub struct ChainConfig {
     pub ckb_rpc: Url,
     pub ckb_indexer_rpc: Url,
     pub key_name: String,
+    /// Backend of the keyring holding `key_name`
+    #[serde(default)]
+    pub key_store_type: Store,
     pub store_prefix: String,
 
     pub client_code_hash: H256,
@@ -38,14 +50,133 @@ pub struct ChainConfig {
     pub channel_type_args: H256,
     pub packet_type_args: H256,
 
+    /// Fee rate of IBC transactions
+    #[serde(default)]
+    pub fee_rate: FeeRate,
+    /// Number of blocks a transaction has to be buried under before it's
+    /// regarded as committed
+    #[serde(default = "default::confirms")]
+    pub confirms: u8,
+    /// Number of blocks a cell has to be buried under before the IBC events
+    /// of its transaction are published. Subscribers are told to drop the
+    /// events already published from blocks reorganized deeper than this.
+    #[serde(default = "default::event_confirmations")]
+    pub event_confirmations: u64,
+    /// How long to wait for a transaction to be committed before giving up
+    #[serde(default = "default::tx_timeout", with = "humantime_serde")]
+    pub tx_timeout: Duration,
+    /// How long a transaction may stay in the tx-pool before it gets replaced
+    /// by the same transaction paying a higher fee (RBF)
+    #[serde(default = "default::tx_replacement_timeout", with = "humantime_serde")]
+    pub tx_replacement_timeout: Duration,
+    /// Minimal number of blocks since a write-ack packet cell was created
+    /// before it can be consumed by a later recv-packet transaction
+    #[serde(default = "default::write_ack_reuse_block_gap")]
+    pub write_ack_reuse_block_gap: u64,
+    /// Runs the scripts of every transaction locally before broadcasting it,
+    /// so that transactions which would be rejected by the node are never sent
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Number of cells requested from the indexer at once while scanning
+    #[serde(default = "default::indexer_page_size")]
+    pub indexer_page_size: u32,
+    /// Maximum number of transactions fetched concurrently while scanning
+    #[serde(default = "default::max_concurrent_tx_fetches")]
+    pub max_concurrent_tx_fetches: usize,
+    /// Number of scanned cells whose transactions are kept in memory
+    #[serde(default = "default::cell_cache_size")]
+    pub cell_cache_size: usize,
+
+    /// Directory of the local storage recording the denom traces of the sUDT
+    /// assets transferred over ICS-20, `~/.forcerelay/storage/<chain-id>` if unset
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+
+    /// The ICS-20 sUDT transfer application, required to send tokens out of
+    /// CKB with `tx ft-transfer`
+    #[serde(default)]
+    pub sudt_transfer: Option<SudtTransferConfig>,
+
     #[serde(default)]
     pub packet_filter: PacketFilter,
 
+    /// Makes the event monitor ignore its cursors and scan events from this
+    /// block number, only settable from the command line
+    #[serde(skip)]
+    pub rescan_from_height: Option<u64>,
+
     #[serde(serialize_with = "light_client_serialize")]
     pub onchain_light_clients: HashMap<ClientType, LightClientItem>,
 }
 
+/// Deployment of the ICS-20 sUDT transfer application on CKB. Its lock script,
+/// the st-lock, escrows the sUDT sent out of CKB in st-cells, and the port id
+/// of the application is the hex-encoded hash of the st-lock.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SudtTransferConfig {
+    /// Type id args of the cell of the ibc-sudt-transfer contract
+    pub contract_type_args: H256,
+    /// Type id args of the cell of the sUDT contract
+    pub sudt_type_args: H256,
+    #[serde(default)]
+    pub lock_args: JsonBytes,
+}
+
+impl SudtTransferConfig {
+    pub fn lock_script(&self) -> Script {
+        Script::new_builder()
+            .code_hash(calc_type_hash(&TYPE_ID_CODE_HASH, &self.contract_type_args).pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(self.lock_args.as_bytes().pack())
+            .build()
+    }
+
+    /// Code hash of the type script of the sUDT cells the application handles
+    pub fn sudt_code_hash(&self) -> H256 {
+        calc_type_hash(&TYPE_ID_CODE_HASH, &self.sudt_type_args)
+    }
+}
+
+/// How the fee rate of transactions is chosen, rates are counted in shannons
+/// per byte of the serialized transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum FeeRate {
+    /// Always pay `rate` shannons per byte
+    Fixed { rate: u64 },
+    /// Follow the fee rate statistics and the minimal fee rate of the node's
+    /// tx-pool, never paying more than `max_rate` shannons per byte
+    Dynamic { max_rate: u64 },
+}
+
+impl Default for FeeRate {
+    fn default() -> Self {
+        Self::Fixed { rate: 3000 }
+    }
+}
+
+impl FeeRate {
+    /// The highest fee rate a transaction is allowed to pay, if any
+    pub fn ceiling(&self) -> Option<u64> {
+        match self {
+            Self::Fixed { .. } => None,
+            Self::Dynamic { max_rate } => Some(*max_rate),
+        }
+    }
+}
+
 impl ChainConfig {
+    /// Directory of the local storage, see `data_dir`
+    pub fn storage_dir(&self) -> Result<PathBuf, Error> {
+        if let Some(data_dir) = &self.data_dir {
+            return Ok(data_dir.clone());
+        }
+        let home = dirs_next::home_dir()
+            .ok_or_else(|| Error::other_error("cannot locate the home directory".to_owned()))?;
+        Ok(home.join(".forcerelay/storage").join(self.id.as_str()))
+    }
+
     pub fn lc_chain_id_by_client_id(&self, client_id: &str) -> Result<ChainId, Error> {
         let chain_id = self
             .onchain_light_clients
@@ -173,3 +304,64 @@ fn calc_type_hash(client_code_hash: &H256, client_type_args: &H256) -> H256 {
         .calc_script_hash();
     client_type_hash.unpack()
 }
+
+pub mod default {
+    use super::*;
+
+    pub fn confirms() -> u8 {
+        1
+    }
+
+    pub fn event_confirmations() -> u64 {
+        3
+    }
+
+    pub fn tx_timeout() -> Duration {
+        Duration::from_secs(600)
+    }
+
+    pub fn tx_replacement_timeout() -> Duration {
+        Duration::from_secs(120)
+    }
+
+    pub fn write_ack_reuse_block_gap() -> u64 {
+        15
+    }
+
+    pub fn indexer_page_size() -> u32 {
+        100
+    }
+
+    pub fn max_concurrent_tx_fetches() -> usize {
+        16
+    }
+
+    pub fn cell_cache_size() -> usize {
+        10_000
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeeRate;
+
+    #[derive(serde_derive::Deserialize)]
+    struct Wrapper {
+        #[serde(default)]
+        fee_rate: FeeRate,
+    }
+
+    #[test]
+    fn test_parse_fee_rate() {
+        let parse = |s: &str| toml::from_str::<Wrapper>(s).unwrap().fee_rate;
+        assert_eq!(parse(""), FeeRate::Fixed { rate: 3000 });
+        assert_eq!(
+            parse("fee_rate = { mode = 'fixed', rate = 1000 }"),
+            FeeRate::Fixed { rate: 1000 }
+        );
+        assert_eq!(
+            parse("fee_rate = { mode = 'dynamic', max_rate = 5000 }"),
+            FeeRate::Dynamic { max_rate: 5000 }
+        );
+    }
+}
@@ -4,12 +4,132 @@ use ibc_relayer_types::{
 };
 use serde_derive::{Deserialize, Serialize};
 
+/// Slots in an epoch of the mainnet preset, the only preset supported by the
+/// Eth light client
+pub const SLOTS_PER_EPOCH: u64 = 32;
+
+/// Ethereum network tracked by the light client.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EthNetwork {
+    #[default]
+    Mainnet,
+    Sepolia,
+    Holesky,
+    Goerli,
+    /// Networks without built-in spec, whose genesis and forks are all set in
+    /// config
+    Custom,
+}
+
+impl EthNetwork {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Sepolia => "sepolia",
+            Self::Holesky => "holesky",
+            Self::Goerli => "goerli",
+            Self::Custom => "custom",
+        }
+    }
+
+    /// Returns the built-in spec of known networks.
+    pub fn spec(&self) -> Option<EthNetworkSpec> {
+        let spec = match self {
+            Self::Mainnet => EthNetworkSpec::new(
+                1606824023,
+                "4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95",
+                [
+                    (0, "00000000"),
+                    (74240, "01000000"),
+                    (144896, "02000000"),
+                    (194048, "03000000"),
+                ],
+            ),
+            Self::Sepolia => EthNetworkSpec::new(
+                1655733600,
+                "d8ea171f3c94aea21ebc42a1ed61052acf3f9209c00e4efbaaddac09ed9b8078",
+                [
+                    (0, "90000069"),
+                    (50, "90000070"),
+                    (100, "90000071"),
+                    (56832, "90000072"),
+                ],
+            ),
+            Self::Holesky => EthNetworkSpec::new(
+                1695902400,
+                "9143aa7c615a7f7115e2b6aac319c03529df8242ae705fba9df39b79c59fa8b1",
+                [
+                    (0, "01017000"),
+                    (0, "02017000"),
+                    (0, "03017000"),
+                    (256, "04017000"),
+                ],
+            ),
+            Self::Goerli => EthNetworkSpec::new(
+                1616508000,
+                "043db0d9a83813551ee2f33450d23797757d430911a9320530ad8a0eabc43efb",
+                [
+                    (0, "00001020"),
+                    (36660, "01001020"),
+                    (112260, "02001020"),
+                    (162304, "03001020"),
+                ],
+            ),
+            Self::Custom => return None,
+        };
+        Some(spec)
+    }
+}
+
+impl std::fmt::Display for EthNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Genesis and fork schedule of an Ethereum network.
+#[derive(Clone, Debug)]
+pub struct EthNetworkSpec {
+    pub genesis_time: u64,
+    pub genesis_root: H256,
+    pub seconds_per_slot: u64,
+    pub forks: Forks,
+}
+
+impl EthNetworkSpec {
+    fn new(genesis_time: u64, genesis_root: &str, forks: [(u64, &str); 4]) -> Self {
+        let fork = |(epoch, fork_version): (u64, &str)| Fork {
+            epoch,
+            fork_version: hex::decode(fork_version).unwrap().into(),
+        };
+        let [genesis, altair, bellatrix, capella] = forks;
+        Self {
+            genesis_time,
+            genesis_root: <[u8; 32]>::try_from(hex::decode(genesis_root).unwrap())
+                .unwrap()
+                .into(),
+            seconds_per_slot: default::seconds_per_slot(),
+            forks: Forks {
+                genesis: fork(genesis),
+                altair: fork(altair),
+                bellatrix: fork(bellatrix),
+                capella: fork(capella),
+            },
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(try_from = "RawEthChainConfig")]
 pub struct EthChainConfig {
     pub id: ChainId,
+    /// Network of the beacon chain, the genesis and forks not set in config
+    /// are taken from its built-in spec
+    pub network: EthNetwork,
     pub genesis_time: u64,
     pub genesis_root: H256,
-    #[serde(deserialize_with = "array_hex_deserialize")]
+    pub seconds_per_slot: u64,
     pub initial_checkpoint: [u8; 32],
     pub key_name: String,
     pub rpc_addr_pool: Vec<String>,
@@ -17,6 +137,65 @@ pub struct EthChainConfig {
     pub forks: Forks,
 }
 
+/// `EthChainConfig` as written in the config file, before the spec of its
+/// network is applied.
+#[derive(Deserialize)]
+struct RawEthChainConfig {
+    id: ChainId,
+    #[serde(default)]
+    network: EthNetwork,
+    genesis_time: Option<u64>,
+    genesis_root: Option<H256>,
+    seconds_per_slot: Option<u64>,
+    #[serde(deserialize_with = "array_hex_deserialize")]
+    initial_checkpoint: [u8; 32],
+    key_name: String,
+    rpc_addr_pool: Vec<String>,
+    rpc_port: u16,
+    forks: Option<Forks>,
+}
+
+impl TryFrom<RawEthChainConfig> for EthChainConfig {
+    type Error = String;
+
+    fn try_from(raw: RawEthChainConfig) -> Result<Self, Self::Error> {
+        let spec = raw.network.spec();
+        let missing = |field: &str| {
+            format!(
+                "`{field}` of chain '{}' must be set for {} network",
+                raw.id, raw.network
+            )
+        };
+        let genesis_time = raw
+            .genesis_time
+            .or_else(|| spec.as_ref().map(|spec| spec.genesis_time))
+            .ok_or_else(|| missing("genesis_time"))?;
+        let genesis_root = raw
+            .genesis_root
+            .or_else(|| spec.as_ref().map(|spec| spec.genesis_root))
+            .ok_or_else(|| missing("genesis_root"))?;
+        let forks = raw
+            .forks
+            .or_else(|| spec.as_ref().map(|spec| spec.forks.clone()))
+            .ok_or_else(|| missing("forks"))?;
+        let seconds_per_slot = raw
+            .seconds_per_slot
+            .unwrap_or_else(default::seconds_per_slot);
+        Ok(Self {
+            id: raw.id,
+            network: raw.network,
+            genesis_time,
+            genesis_root,
+            seconds_per_slot,
+            initial_checkpoint: raw.initial_checkpoint,
+            key_name: raw.key_name,
+            rpc_addr_pool: raw.rpc_addr_pool,
+            rpc_port: raw.rpc_port,
+            forks,
+        })
+    }
+}
+
 pub fn array_hex_deserialize<'de, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -34,11 +213,42 @@ where
 
 impl EthChainConfig {
     pub fn mainnet() -> Self {
-        todo!()
+        Self::preset(ChainId::new(String::from("1"), 1), EthNetwork::Mainnet)
+    }
+
+    pub fn sepolia() -> Self {
+        Self::preset(
+            ChainId::new(String::from("11155111"), 1),
+            EthNetwork::Sepolia,
+        )
+    }
+
+    pub fn holesky() -> Self {
+        Self::preset(ChainId::new(String::from("17000"), 1), EthNetwork::Holesky)
+    }
+
+    pub fn goerli() -> Self {
+        Self::preset(ChainId::new(String::from("5"), 1), EthNetwork::Goerli)
+    }
+
+    fn preset(id: ChainId, network: EthNetwork) -> Self {
+        let spec = network.spec().expect("built-in network spec");
+        Self {
+            id,
+            network,
+            genesis_time: spec.genesis_time,
+            genesis_root: spec.genesis_root,
+            seconds_per_slot: spec.seconds_per_slot,
+            rpc_addr_pool: Default::default(),
+            rpc_port: 8545,
+            forks: spec.forks,
+            initial_checkpoint: Default::default(),
+            key_name: Default::default(),
+        }
     }
 
     pub fn fork_version(&self, slot: u64) -> FixedVector<u8, U4> {
-        let epoch = slot / 32;
+        let epoch = slot / SLOTS_PER_EPOCH;
 
         if epoch >= self.forks.capella.epoch {
             self.forks.capella.fork_version.clone()
@@ -50,39 +260,54 @@ impl EthChainConfig {
             self.forks.genesis.fork_version.clone()
         }
     }
+}
 
-    pub fn goerli() -> Self {
-        Self {
-            id: ChainId::new(String::from("5"), 1),
-            genesis_time: 1616508000,
-            genesis_root: <[u8; 32]>::try_from(
-                hex::decode("043db0d9a83813551ee2f33450d23797757d430911a9320530ad8a0eabc43efb")
-                    .unwrap(),
-            )
-            .unwrap()
-            .into(),
-            rpc_addr_pool: Default::default(),
-            rpc_port: 8545,
-            forks: Forks {
-                genesis: Fork {
-                    epoch: 0,
-                    fork_version: hex::decode("00001020").unwrap().into(),
-                },
-                altair: Fork {
-                    epoch: 36660,
-                    fork_version: hex::decode("01001020").unwrap().into(),
-                },
-                bellatrix: Fork {
-                    epoch: 112260,
-                    fork_version: hex::decode("02001020").unwrap().into(),
-                },
-                capella: Fork {
-                    epoch: 162304,
-                    fork_version: hex::decode("03001020").unwrap().into(),
-                },
-            },
-            initial_checkpoint: Default::default(),
-            key_name: Default::default(),
-        }
+pub mod default {
+    pub fn seconds_per_slot() -> u64 {
+        12
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKPOINT: &str = "0xa179cbd497b112acb057039601a75e2daafae994aa5f01d6e1a1d6f85e07a8ef";
+
+    #[test]
+    fn test_network_spec_fills_unset_fields() {
+        let config: EthChainConfig = toml::from_str(&format!(
+            r#"
+            id = "eth-sepolia"
+            network = "sepolia"
+            initial_checkpoint = "{CHECKPOINT}"
+            key_name = "eth"
+            rpc_addr_pool = []
+            rpc_port = 8545
+            "#
+        ))
+        .unwrap();
+        let sepolia = EthChainConfig::sepolia();
+        assert_eq!(config.network, EthNetwork::Sepolia);
+        assert_eq!(config.genesis_time, sepolia.genesis_time);
+        assert_eq!(config.genesis_root, sepolia.genesis_root);
+        assert_eq!(config.seconds_per_slot, 12);
+        assert_eq!(config.forks.capella.epoch, 56832);
+    }
+
+    #[test]
+    fn test_custom_network_requires_spec() {
+        let result = toml::from_str::<EthChainConfig>(&format!(
+            r#"
+            id = "eth-devnet"
+            network = "custom"
+            genesis_time = 1690000000
+            initial_checkpoint = "{CHECKPOINT}"
+            key_name = "eth"
+            rpc_addr_pool = []
+            rpc_port = 8545
+            "#
+        ));
+        assert!(result.is_err());
     }
 }
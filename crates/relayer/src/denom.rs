@@ -10,3 +10,65 @@ pub struct DenomTrace {
     /// The base denomination for that coin
     pub base_denom: String,
 }
+
+/// Modified from ibc-go https://github.com/cosmos/ibc-go/blob/main/modules/apps/transfer/types/trace.go#L31
+pub fn parse_denom_trace(raw_denom: &str) -> DenomTrace {
+    let parts: Vec<_> = raw_denom.split('/').collect();
+    if parts[0] == raw_denom {
+        return DenomTrace {
+            path: Default::default(),
+            base_denom: raw_denom.to_owned(),
+        };
+    }
+    let (path, base_denom) = extract_path_and_base_from_full_denom(parts);
+    DenomTrace { path, base_denom }
+}
+
+fn extract_path_and_base_from_full_denom(parts: Vec<&str>) -> (String, String) {
+    fn is_valid_channel_id(c: &str) -> bool {
+        const PREFIX: &str = "channel-";
+        if !c.starts_with(PREFIX) {
+            return false;
+        }
+        let r = c[PREFIX.len()..].parse::<usize>();
+        r.is_ok()
+    }
+
+    let mut path = Vec::new();
+    let mut base = Vec::new();
+    let len = parts.len();
+
+    for i in (0..len).step_by(2) {
+        if i < len - 1 && len > 2 && is_valid_channel_id(parts[i + 1]) {
+            path.push(parts[i]);
+            path.push(parts[i + 1]);
+        } else {
+            base.extend_from_slice(&parts[i..]);
+            break;
+        }
+    }
+    let path = path.join("/");
+    let base = base.join("/");
+
+    (path, base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_denom_trace() {
+        let trace = parse_denom_trace("transfer/channel-0/transfer/channel-12/uatom");
+        assert_eq!(trace.path, "transfer/channel-0/transfer/channel-12");
+        assert_eq!(trace.base_denom, "uatom");
+
+        let trace = parse_denom_trace("transfer/channel-0/gamm/pool/1");
+        assert_eq!(trace.path, "transfer/channel-0");
+        assert_eq!(trace.base_denom, "gamm/pool/1");
+
+        let trace = parse_denom_trace("uckb");
+        assert!(trace.path.is_empty());
+        assert_eq!(trace.base_denom, "uckb");
+    }
+}
@@ -594,6 +594,66 @@ define_error! {
             { error: String }
             |e| { e.error.clone() },
 
+        EthMultiClientAlreadyExists
+            {
+                chain_id: String,
+                minimal_slot: u64,
+                maximal_slot: u64,
+            }
+            |e| {
+                format!("ethereum multi-client already exists on chain '{}' with slots [{}, {}]",
+                    e.chain_id, e.minimal_slot, e.maximal_slot)
+            },
+
+        EthMultiClientBehind
+            {
+                chain_id: String,
+                next_slot: u64,
+            }
+            |e| {
+                format!("ethereum headers relayed to chain '{}' must continue from slot {}",
+                    e.chain_id, e.next_slot)
+            },
+
+        EthMultiClientBaseSlotMismatch
+            {
+                chain_id: String,
+                onchain_slot: u64,
+                stored_slot: u64,
+            }
+            |e| {
+                format!("base slot of ethereum multi-client on chain '{}' is {}, while the stored base slot is {}",
+                    e.chain_id, e.onchain_slot, e.stored_slot)
+            },
+
+        CkbTxDryRun
+            { reason: String }
+            |e| { format!("local dry-run of ckb transaction failed: {}", e.reason) },
+
+        AxonTxReverted
+            { reason: String }
+            |e| { format!("axon transaction reverted: {}", e.reason) },
+
+        AxonInvalidStorageProof
+            {
+                path: String,
+                height: u64,
+                reason: String,
+            }
+            |e| {
+                format!("invalid storage proof of '{}' at axon block {}: {}",
+                    e.path, e.height, e.reason)
+            },
+
+        Unsupported
+            {
+                chain_id: ChainId,
+                operation: String,
+            }
+            |e| {
+                format!("operation `{}` is not supported by chain '{}'", e.operation, e.chain_id)
+            },
+
         QueriedProofNotFound
             |_| { "Requested proof with query but no proof was returned." },
 
@@ -49,6 +49,10 @@ define_error! {
             [ TraceError<RpcError> ]
             |_| { "RPC error" },
 
+        ChainReorganized
+            { chain_id: ChainId, height: u64 }
+            |e| { format!("chain {0} was reorganized, the events published from height {1} on are retracted", e.chain_id, e.height) },
+
         Others
             { reason: String }
             |e| { format!("uncategorized error: {0}", e.reason) },
@@ -2,6 +2,10 @@ pub mod errors;
 pub use any_signing_key_pair::AnySigningKeyPair;
 pub use ed25519_key_pair::Ed25519KeyPair;
 pub use key_type::KeyType;
+pub use keystore::{
+    export_keystore, import_keystore, KeystoreFormat, Passphrase, KEYSTORE_PASSPHRASE_ENV,
+    KEYSTORE_PASSPHRASE_FILE_ENV,
+};
 pub use secp256k1_key_pair::{Secp256k1AddressType, Secp256k1KeyPair};
 pub use signing_key_pair::{SigningKeyPair, SigningKeyPairSized};
 
@@ -9,6 +13,7 @@ mod any_signing_key_pair;
 mod ed25519_key_pair;
 mod key_type;
 mod key_utils;
+mod keystore;
 mod pub_key;
 mod secp256k1_key_pair;
 mod signing_key_pair;
@@ -26,6 +31,7 @@ use errors::Error;
 
 pub const KEYSTORE_DEFAULT_FOLDER: &str = ".forcerelay/keys/";
 pub const KEYSTORE_DISK_BACKEND: &str = "keyring-test";
+pub const KEYSTORE_ENCRYPTED_BACKEND: &str = "keyring-encrypted";
 pub const KEYSTORE_FILE_EXTENSION: &str = "json";
 
 /// JSON key seed file
@@ -181,10 +187,77 @@ impl<S: SigningKeyPairSized> KeyStore<S> for Test {
     }
 }
 
+/// Key entries encrypted with a passphrase, each one in a keystore file of the
+/// Ethereum V3 format whose ciphertext is the raw secret key
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Encrypted {
+    account_prefix: String,
+    store: PathBuf,
+    #[serde(skip)]
+    passphrase: Passphrase,
+}
+
+impl Encrypted {
+    pub fn new(account_prefix: String, store: PathBuf, passphrase: Passphrase) -> Self {
+        Self {
+            account_prefix,
+            store,
+            passphrase,
+        }
+    }
+
+    fn key_file(&self, key_name: &str) -> PathBuf {
+        let mut key_file = self.store.join(key_name);
+        key_file.set_extension(KEYSTORE_FILE_EXTENSION);
+        key_file
+    }
+}
+
+impl<S: SigningKeyPairSized> KeyStore<S> for Encrypted {
+    fn get_key(&self, key_name: &str) -> Result<S, Error> {
+        keystore::load_key_pair(&self.key_file(key_name), &self.passphrase)
+    }
+
+    fn add_key(&mut self, key_name: &str, key_entry: S) -> Result<(), Error> {
+        keystore::store_key_pair(&self.key_file(key_name), &key_entry, &self.passphrase)
+    }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        let key_file = self.key_file(key_name);
+
+        fs::remove_file(&key_file)
+            .map_err(|e| Error::remove_io_fail(key_file.display().to_string(), e))?;
+
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<(String, S)>, Error> {
+        let dir = fs::read_dir(&self.store).map_err(|e| {
+            Error::key_file_io(
+                self.store.display().to_string(),
+                "failed to list keys".to_string(),
+                e,
+            )
+        })?;
+
+        let ext = OsStr::new(KEYSTORE_FILE_EXTENSION);
+
+        dir.into_iter()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension() == Some(ext))
+            .flat_map(|path| path.file_stem().map(OsStr::to_owned))
+            .flat_map(|stem| stem.to_str().map(ToString::to_string))
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Store {
     Memory,
     Test,
+    Encrypted,
 }
 
 impl Default for Store {
@@ -197,6 +270,7 @@ impl Default for Store {
 pub enum KeyRing<S> {
     Memory(Memory<S>),
     Test(Test),
+    Encrypted(Encrypted),
 }
 
 impl<S: SigningKeyPairSized> KeyRing<S> {
@@ -205,22 +279,30 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
             Store::Memory => Ok(Self::Memory(Memory::new(account_prefix.to_string()))),
 
             Store::Test => {
-                let keys_folder = disk_store_path(chain_id.as_str())?;
-
-                // Create keys folder if it does not exist
-                fs::create_dir_all(&keys_folder).map_err(|e| {
-                    Error::key_file_io(
-                        keys_folder.display().to_string(),
-                        "failed to create keys folder".to_string(),
-                        e,
-                    )
-                })?;
+                let keys_folder = disk_store_path(chain_id.as_str(), KEYSTORE_DISK_BACKEND)?;
 
                 Ok(Self::Test(Test::new(
                     account_prefix.to_string(),
                     keys_folder,
                 )))
             }
+
+            Store::Encrypted => {
+                // never prompt, the keyring is also opened by a running relayer
+                let passphrase = Passphrase::from_env()?.ok_or_else(|| {
+                    Error::keystore(format!(
+                        "no passphrase for the encrypted keys of {chain_id}, \
+                         set {KEYSTORE_PASSPHRASE_ENV} or {KEYSTORE_PASSPHRASE_FILE_ENV}"
+                    ))
+                })?;
+                let keys_folder = disk_store_path(chain_id.as_str(), KEYSTORE_ENCRYPTED_BACKEND)?;
+
+                Ok(Self::Encrypted(Encrypted::new(
+                    account_prefix.to_string(),
+                    keys_folder,
+                    passphrase,
+                )))
+            }
         }
     }
 
@@ -228,6 +310,7 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.get_key(key_name),
             Self::Test(d) => d.get_key(key_name),
+            Self::Encrypted(e) => e.get_key(key_name),
         }
     }
 
@@ -235,6 +318,7 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.add_key(key_name, key_entry),
             Self::Test(d) => d.add_key(key_name, key_entry),
+            Self::Encrypted(e) => e.add_key(key_name, key_entry),
         }
     }
 
@@ -242,6 +326,7 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.remove_key(key_name),
             Self::Test(d) => <Test as KeyStore<S>>::remove_key(d, key_name),
+            Self::Encrypted(e) => <Encrypted as KeyStore<S>>::remove_key(e, key_name),
         }
     }
 
@@ -249,6 +334,7 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.keys(),
             Self::Test(d) => d.keys(),
+            Self::Encrypted(e) => e.keys(),
         }
     }
 
@@ -256,6 +342,7 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => &m.account_prefix,
             Self::Test(d) => &d.account_prefix,
+            Self::Encrypted(e) => &e.account_prefix,
         }
     }
 }
@@ -289,7 +376,7 @@ pub fn list_keys(config: &ChainConfig) -> Result<Vec<(String, AnySigningKeyPair)
         ChainType::Ckb4Ibc => "ckb4ibc",
     };
     let keys = {
-        let keyring = KeyRing::new_secp256k1(Store::Test, account_prefix, config.id())?;
+        let keyring = KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
         keyring
             .keys()?
             .into_iter()
@@ -299,13 +386,77 @@ pub fn list_keys(config: &ChainConfig) -> Result<Vec<(String, AnySigningKeyPair)
     Ok(keys)
 }
 
-fn disk_store_path(folder_name: &str) -> Result<PathBuf, Error> {
+fn disk_store_path(folder_name: &str, backend: &str) -> Result<PathBuf, Error> {
     let home = dirs_next::home_dir().ok_or_else(Error::home_location_unavailable)?;
 
     let folder = Path::new(home.as_path())
         .join(KEYSTORE_DEFAULT_FOLDER)
         .join(folder_name)
-        .join(KEYSTORE_DISK_BACKEND);
+        .join(backend);
+
+    // Create keys folder if it does not exist
+    fs::create_dir_all(&folder).map_err(|e| {
+        Error::key_file_io(
+            folder.display().to_string(),
+            "failed to create keys folder".to_string(),
+            e,
+        )
+    })?;
 
     Ok(folder)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AddressType;
+
+    #[test]
+    fn test_encrypted_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let passphrase = Passphrase::new("secret".to_owned());
+        let mut store = Encrypted::new("axon".to_owned(), dir.path().to_path_buf(), passphrase);
+
+        let secp256k1_key =
+            Secp256k1KeyPair::from_secret_key(&hex::encode([7u8; 32]), &AddressType::Axon).unwrap();
+        store.add_key("secp256k1", secp256k1_key.clone()).unwrap();
+        let ed25519_key = Ed25519KeyPair::from_secret_key_bytes(
+            &[9u8; 32],
+            serde_json::json!({ "address_type": "Solana" }),
+        )
+        .unwrap();
+        store.add_key("ed25519", ed25519_key.clone()).unwrap();
+
+        // the ciphertext is the raw secret key, as in standard V3 keystores
+        let key_file = store.key_file("secp256k1");
+        let secret_key = import_keystore(&key_file, &store.passphrase).unwrap();
+        assert_eq!(secret_key, secp256k1_key.private_key);
+
+        let loaded: Secp256k1KeyPair = store.get_key("secp256k1").unwrap();
+        assert_eq!(loaded.account(), secp256k1_key.account());
+        assert_eq!(loaded.public_key, secp256k1_key.public_key);
+        let loaded: Ed25519KeyPair = store.get_key("ed25519").unwrap();
+        assert_eq!(loaded.account(), ed25519_key.account());
+        assert_eq!(loaded.secret_key_bytes(), vec![9u8; 32]);
+
+        let wrong = Encrypted::new(
+            "axon".to_owned(),
+            dir.path().to_path_buf(),
+            Passphrase::new("wrong".to_owned()),
+        );
+        assert!(KeyStore::<Ed25519KeyPair>::get_key(&wrong, "ed25519").is_err());
+    }
+    #[test]
+    fn test_encrypted_keyring_without_passphrase() {
+        assert_eq!(Store::default(), Store::Test);
+        if Passphrase::from_env().unwrap().is_some() {
+            return;
+        }
+        // opening the keyring fails instead of prompting for the passphrase
+        let chain_id = ChainId::new("encrypted".to_owned(), 0);
+        let err = KeyRing::<Secp256k1KeyPair>::new(Store::Encrypted, "axon", &chain_id)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(KEYSTORE_PASSPHRASE_ENV), "{err}");
+    }
+}
@@ -63,6 +63,12 @@ impl TryFrom<&AddressType> for Ed25519AddressType {
     }
 }
 
+// What keystore files keep next to the encrypted secret key
+#[derive(Debug, Deserialize, Serialize)]
+struct KeyInfo {
+    address_type: Ed25519AddressType,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Ed25519KeyPair {
     keypair: Keypair,
@@ -145,6 +151,30 @@ impl SigningKeyPair for Ed25519KeyPair {
         Self::from_mnemonic_internal(mnemonic, hd_path, address_type.try_into()?)
     }
 
+    fn from_secret_key_bytes(
+        secret_key: &[u8],
+        key_info: serde_json::Value,
+    ) -> Result<Self, Error> {
+        let KeyInfo { address_type } = serde_json::from_value(key_info).map_err(Error::encode)?;
+        let secret = SecretKey::from_bytes(secret_key)
+            .map_err(|e| Error::keystore(format!("invalid ed25519 secret key: {e}")))?;
+        let public = (&secret).into();
+        Ok(Self {
+            keypair: Keypair { secret, public },
+            address_type,
+        })
+    }
+
+    fn secret_key_bytes(&self) -> Vec<u8> {
+        self.keypair.secret.as_bytes().to_vec()
+    }
+
+    fn key_info(&self) -> serde_json::Value {
+        serde_json::json!(KeyInfo {
+            address_type: self.address_type,
+        })
+    }
+
     // Solana address: base58(pubkey)
     fn account(&self) -> String {
         match self.address_type {
@@ -144,6 +144,10 @@ define_error! {
             { message: String }
             |e| { format!("secp256k1 error: {}", e.message) },
 
+        Keystore
+            { reason: String }
+            |e| { format!("keystore error: {}", e.reason) },
+
         UnsupportedAddressType
           {
               address_type: AddressType,
@@ -0,0 +1,318 @@
+use core::fmt;
+use std::fs;
+use std::path::Path;
+
+use ckb_hash::blake2b_256;
+use secp256k1::rand::{thread_rng, RngCore};
+use secp256k1::{Secp256k1, SecretKey};
+use serde_json::{Map, Value};
+
+use super::errors::Error;
+use super::secp256k1_key_pair::{get_address, Secp256k1AddressType};
+use super::SigningKeyPair;
+
+/// Environment variable holding the passphrase of the encrypted keystores
+pub const KEYSTORE_PASSPHRASE_ENV: &str = "FORCERELAY_KEYSTORE_PASSPHRASE";
+/// Environment variable holding the path of a file containing the passphrase
+/// of the encrypted keystores
+pub const KEYSTORE_PASSPHRASE_FILE_ENV: &str = "FORCERELAY_KEYSTORE_PASSPHRASE_FILE";
+
+/// Field of the keystore files of the keyring holding the key info of the key pair
+const KEY_INFO_FIELD: &str = "key_info";
+/// `origin` of the keystore files written by ckb-cli
+const CKB_CLI_ORIGIN: &str = "ckb-cli";
+
+/// Passphrase protecting encrypted keystores, never printed nor serialized
+#[derive(Clone, Default)]
+pub struct Passphrase(String);
+
+impl Passphrase {
+    pub fn new(passphrase: String) -> Self {
+        Self(passphrase)
+    }
+
+    /// Reads the passphrase from the `FORCERELAY_KEYSTORE_PASSPHRASE` environment
+    /// variable or else from the file named by `FORCERELAY_KEYSTORE_PASSPHRASE_FILE`,
+    /// `None` if neither is set
+    pub fn from_env() -> Result<Option<Self>, Error> {
+        if let Ok(passphrase) = std::env::var(KEYSTORE_PASSPHRASE_ENV) {
+            return Ok(Some(Self(passphrase)));
+        }
+        if let Ok(path) = std::env::var(KEYSTORE_PASSPHRASE_FILE_ENV) {
+            let passphrase = fs::read_to_string(&path).map_err(|e| {
+                Error::key_file_io(path, "failed to read passphrase file".to_string(), e)
+            })?;
+            return Ok(Some(Self(
+                passphrase.trim_end_matches(['\r', '\n']).to_owned(),
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Reads the passphrase from the environment like [`Self::from_env`], or
+    /// else prompts for it on the terminal
+    pub fn resolve(prompt: &str) -> Result<Self, Error> {
+        if let Some(passphrase) = Self::from_env()? {
+            return Ok(passphrase);
+        }
+        let passphrase = dialoguer::Password::new()
+            .with_prompt(prompt)
+            .interact()
+            .map_err(|e| Error::keystore(format!("cannot prompt for passphrase: {e}")))?;
+        Ok(Self(passphrase))
+    }
+}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Passphrase(..)")
+    }
+}
+
+/// Encrypts `secret` into a keystore file of the Ethereum V3 format, with
+/// `fields` added to the top-level object of the file
+fn encrypt_keystore(
+    path: &Path,
+    secret: &[u8],
+    fields: Map<String, Value>,
+    passphrase: &Passphrase,
+) -> Result<(), Error> {
+    let file_path = path.display().to_string();
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return Err(Error::keystore(format!(
+            "invalid keystore path '{file_path}'"
+        )));
+    };
+    eth_keystore::encrypt_key(dir, &mut thread_rng(), secret, &passphrase.0, Some(name))
+        .map_err(|e| Error::keystore(format!("cannot encrypt keystore '{file_path}': {e}")))?;
+    if fields.is_empty() {
+        return Ok(());
+    }
+
+    let mut keystore = read_keystore(path)?;
+    keystore.extend(fields);
+    let content =
+        serde_json::to_vec(&keystore).map_err(|e| Error::key_file_encode(file_path.clone(), e))?;
+    fs::write(path, content)
+        .map_err(|e| Error::key_file_io(file_path, "failed to write file".to_string(), e))
+}
+
+/// Decrypts a keystore file of the Ethereum V3 format, returning the secret
+/// along with the top-level object of the file
+fn decrypt_keystore(
+    path: &Path,
+    passphrase: &Passphrase,
+) -> Result<(Vec<u8>, Map<String, Value>), Error> {
+    let keystore = read_keystore(path)?;
+    let secret = eth_keystore::decrypt_key(path, &passphrase.0).map_err(|e| {
+        Error::keystore(format!("cannot decrypt keystore '{}': {e}", path.display()))
+    })?;
+    Ok((secret, keystore))
+}
+
+fn read_keystore(path: &Path) -> Result<Map<String, Value>, Error> {
+    let file_path = path.display().to_string();
+    if !path.exists() {
+        return Err(Error::key_file_not_found(file_path));
+    }
+    let content = fs::read(path)
+        .map_err(|e| Error::key_file_io(file_path.clone(), "failed to read file".to_string(), e))?;
+    serde_json::from_slice(&content).map_err(|e| Error::key_file_decode(file_path, e))
+}
+
+/// Writes a key pair of the keyring into a keystore file, where the raw
+/// secret key is encrypted and the key info is kept in clear
+pub fn store_key_pair<S: SigningKeyPair>(
+    path: &Path,
+    key_pair: &S,
+    passphrase: &Passphrase,
+) -> Result<(), Error> {
+    let fields = Map::from_iter([(KEY_INFO_FIELD.to_owned(), key_pair.key_info())]);
+    encrypt_keystore(path, &key_pair.secret_key_bytes(), fields, passphrase)
+}
+
+/// Reads a key pair of the keyring from a keystore file
+pub fn load_key_pair<S: SigningKeyPair>(path: &Path, passphrase: &Passphrase) -> Result<S, Error> {
+    let (secret, mut keystore) = decrypt_keystore(path, passphrase)?;
+    let key_info = keystore.remove(KEY_INFO_FIELD).ok_or_else(|| {
+        Error::keystore(format!(
+            "keystore '{}' misses the key info of the key pair",
+            path.display()
+        ))
+    })?;
+    S::from_secret_key_bytes(&secret, key_info)
+}
+
+/// Standard keystore formats of secp256k1 keys
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeystoreFormat {
+    /// Ethereum V3 keystore of geth, holding the secret key
+    Ethereum,
+    /// Keystore of ckb-cli, a V3 keystore holding the master private key,
+    /// which is the secret key followed by the chain code
+    CkbCli,
+}
+
+impl KeystoreFormat {
+    fn secret_and_fields(self, secret_key: &SecretKey) -> (Vec<u8>, Map<String, Value>) {
+        let public_key = secret_key.public_key(&Secp256k1::signing_only());
+        let mut secret = secret_key.secret_bytes().to_vec();
+        let fields = match self {
+            Self::Ethereum => {
+                let address = get_address(&public_key, Secp256k1AddressType::Ethermint);
+                Map::from_iter([("address".to_owned(), hex::encode(address).into())])
+            }
+            Self::CkbCli => {
+                // the account of ckb-cli is the root key, the chain code only
+                // matters to the keys derived from it
+                let mut chain_code = [0u8; 32];
+                thread_rng().fill_bytes(&mut chain_code);
+                secret.extend_from_slice(&chain_code);
+                Map::from_iter([
+                    ("origin".to_owned(), CKB_CLI_ORIGIN.into()),
+                    (
+                        "hash160".to_owned(),
+                        hex::encode(hash160(secret_key)).into(),
+                    ),
+                ])
+            }
+        };
+        (secret, fields)
+    }
+}
+
+// the lock args of ckb-cli accounts, blake160 of the compressed public key
+fn hash160(secret_key: &SecretKey) -> [u8; 20] {
+    let public_key = secret_key.public_key(&Secp256k1::signing_only());
+    let mut hash160 = [0u8; 20];
+    hash160.copy_from_slice(&blake2b_256(public_key.serialize())[..20]);
+    hash160
+}
+
+/// Reads the secret key of a keystore file of any supported format, checking
+/// it against the address or the hash160 recorded in the file
+pub fn import_keystore(path: &Path, passphrase: &Passphrase) -> Result<SecretKey, Error> {
+    let (secret, keystore) = decrypt_keystore(path, passphrase)?;
+    let secret_key = match secret.len() {
+        32 | 64 => SecretKey::from_slice(&secret[..32])?,
+        len => {
+            return Err(Error::keystore(format!(
+                "unexpected secret of {len} bytes in keystore '{}'",
+                path.display()
+            )))
+        }
+    };
+
+    let public_key = secret_key.public_key(&Secp256k1::signing_only());
+    let recorded = |field: &str| {
+        keystore
+            .get(field)
+            .and_then(Value::as_str)
+            .map(|value| value.trim_start_matches("0x").to_lowercase())
+    };
+    let expected = [
+        (
+            "address",
+            hex::encode(get_address(&public_key, Secp256k1AddressType::Ethermint)),
+        ),
+        ("hash160", hex::encode(hash160(&secret_key))),
+    ];
+    for (field, expected) in expected {
+        if let Some(recorded) = recorded(field) {
+            if recorded != expected {
+                return Err(Error::keystore(format!(
+                    "{field} {recorded} of keystore '{}' doesn't match its key",
+                    path.display()
+                )));
+            }
+        }
+    }
+    Ok(secret_key)
+}
+
+/// Writes the secret key into a keystore file of the given format
+pub fn export_keystore(
+    path: &Path,
+    secret_key: &SecretKey,
+    format: KeystoreFormat,
+    passphrase: &Passphrase,
+) -> Result<(), Error> {
+    let (secret, fields) = format.secret_and_fields(secret_key);
+    encrypt_keystore(path, &secret, fields, passphrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/keystore/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_import_geth_keystore() {
+        let path = fixture("geth.json");
+        let secret_key = import_keystore(&path, &Passphrase::new("foobar".to_owned())).unwrap();
+        assert_eq!(
+            hex::encode(secret_key.secret_bytes()),
+            "539f9b4106fb452408e1ee43d177077f057a8fdc1e1fad92c61e68982b4e3c4b"
+        );
+        assert!(import_keystore(&path, &Passphrase::new("wrong".to_owned())).is_err());
+    }
+
+    #[test]
+    fn test_import_ckb_cli_keystore() {
+        let path = fixture("ckb-cli.json");
+        let passphrase = Passphrase::new("ckb-cli-keystore".to_owned());
+        let secret_key = import_keystore(&path, &passphrase).unwrap();
+        assert_eq!(
+            hex::encode(secret_key.secret_bytes()),
+            "d00c06bfd800d27397002dca6fb0993d5ba6399b4238b2f29ee9deb97593d2bc"
+        );
+        assert_eq!(
+            hex::encode(hash160(&secret_key)),
+            "c8328aabcd9b9e8e64fbc566c4385c3bdeb219d7"
+        );
+    }
+
+    #[test]
+    fn test_export_keystore() {
+        let dir = tempfile::tempdir().unwrap();
+        let passphrase = Passphrase::new("secret".to_owned());
+        let secret_key = SecretKey::from_slice(&[7; 32]).unwrap();
+
+        for (format, name, secret_len) in [
+            (KeystoreFormat::Ethereum, "eth.json", 32),
+            (KeystoreFormat::CkbCli, "ckb.json", 64),
+        ] {
+            let path = dir.path().join(name);
+            export_keystore(&path, &secret_key, format, &passphrase).unwrap();
+            let (secret, keystore) = decrypt_keystore(&path, &passphrase).unwrap();
+            assert_eq!(&secret[..32], secret_key.secret_bytes());
+            assert_eq!(secret.len(), secret_len);
+            assert_eq!(keystore["version"], 3);
+            assert_eq!(import_keystore(&path, &passphrase).unwrap(), secret_key);
+        }
+
+        let keystore = read_keystore(&dir.path().join("ckb.json")).unwrap();
+        assert_eq!(keystore["origin"], CKB_CLI_ORIGIN);
+        assert_eq!(keystore["hash160"], hex::encode(hash160(&secret_key)));
+    }
+
+    #[test]
+    fn test_import_keystore_of_other_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("eth.json");
+        let passphrase = Passphrase::new("secret".to_owned());
+        let secret_key = SecretKey::from_slice(&[7; 32]).unwrap();
+        export_keystore(&path, &secret_key, KeystoreFormat::Ethereum, &passphrase).unwrap();
+
+        let mut keystore = read_keystore(&path).unwrap();
+        keystore.insert("address".to_owned(), hex::encode([1u8; 20]).into());
+        fs::write(&path, serde_json::to_vec(&keystore).unwrap()).unwrap();
+        assert!(import_keystore(&path, &passphrase).is_err());
+    }
+}
@@ -156,6 +156,13 @@ pub struct Secp256k1KeyPair {
     account: String,
 }
 
+// What keystore files keep next to the encrypted secret key
+#[derive(Debug, Deserialize, Serialize)]
+struct KeyInfo {
+    address_type: Secp256k1AddressType,
+    account: String,
+}
+
 // The old `KeyEntry` type
 #[derive(Debug, Deserialize)]
 struct KeyPairV1 {
@@ -358,6 +365,36 @@ impl SigningKeyPair for Secp256k1KeyPair {
         Self::from_mnemonic_internal(mnemonic, hd_path, address_type.try_into()?, account_prefix)
     }
 
+    fn from_secret_key_bytes(
+        secret_key: &[u8],
+        key_info: serde_json::Value,
+    ) -> Result<Self, Error> {
+        let KeyInfo {
+            address_type,
+            account,
+        } = serde_json::from_value(key_info).map_err(Error::encode)?;
+        let private_key = SecretKey::from_slice(secret_key)?;
+        let public_key = private_key.public_key(&Secp256k1::signing_only());
+        Ok(Self {
+            private_key,
+            public_key,
+            address: get_address(&public_key, address_type),
+            address_type,
+            account,
+        })
+    }
+
+    fn secret_key_bytes(&self) -> Vec<u8> {
+        self.private_key.secret_bytes().to_vec()
+    }
+
+    fn key_info(&self) -> serde_json::Value {
+        serde_json::json!(KeyInfo {
+            address_type: self.address_type,
+            account: self.account.clone(),
+        })
+    }
+
     fn account(&self) -> String {
         self.account.to_owned()
     }
@@ -34,6 +34,18 @@ pub trait SigningKeyPair {
     where
         Self: Sized;
 
+    /// Restores a key pair from its raw secret key, as encrypted in keystore
+    /// files, and the `key_info` kept next to it
+    fn from_secret_key_bytes(secret_key: &[u8], key_info: serde_json::Value) -> Result<Self, Error>
+    where
+        Self: Sized;
+
+    /// The raw secret key, which is what keystore files encrypt
+    fn secret_key_bytes(&self) -> Vec<u8>;
+
+    /// What it takes besides the secret key to restore the key pair
+    fn key_info(&self) -> serde_json::Value;
+
     fn account(&self) -> String;
     fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error>;
 
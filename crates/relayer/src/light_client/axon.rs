@@ -1,20 +1,19 @@
-#![allow(dead_code, unused_variables, unused_imports)]
-
 use std::sync::Arc;
 
-use axon_tools::types::Header as AxonChainHeader;
-use ethers::prelude::k256::ecdsa::SigningKey;
-use ethers::prelude::*;
-use futures::TryFutureExt;
-use ibc_relayer_types::clients::ics07_axon::{header::AxonHeader, light_block::AxonLightBlock};
+use ethers::types::{BlockId, BlockNumber};
+use ibc_relayer_types::clients::ics07_axon::{
+    header::AxonHeader, light_block::AxonLightBlock, misbehaviour::Misbehaviour as AxonMisbehaviour,
+};
+use ibc_relayer_types::core::ics02_client::{events::UpdateClient, header::downcast_header};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::Height;
 use tokio::runtime::Runtime as TokioRuntime;
-use tokio::sync::mpsc::{channel, Receiver, Sender};
-use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{info, trace};
 
-use crate::chain::axon::{AxonChain, AxonRpc};
-use crate::chain::endpoint::ChainEndpoint;
+use crate::chain::axon::utils::{
+    fetch_block_ingredients, fetch_verified_block, to_light_block, verify_header_proof,
+};
+use crate::chain::axon::{rpc::AxonRpcClient, AxonChain, AxonRpc, MetadataCache};
 use crate::client_state::AnyClientState;
 use crate::config::axon::AxonChainConfig;
 use crate::error::Error;
@@ -25,100 +24,191 @@ use super::Verified;
 pub struct LightClient {
     rt: Arc<TokioRuntime>,
     chain_id: ChainId,
-    header_updaters: Arc<RwLock<Vec<Sender<AxonChainHeader>>>>,
+    rpc_client: AxonRpcClient,
+    metadata_cache: MetadataCache,
+    /// First block number of the latest epoch seen by the light client
+    current_epoch_start: Option<u64>,
 }
 
 impl LightClient {
-    pub fn from_config(config: &AxonChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
+    pub fn from_config(
+        config: &AxonChainConfig,
+        rt: Arc<TokioRuntime>,
+        rpc_client: AxonRpcClient,
+        metadata_cache: MetadataCache,
+    ) -> Result<Self, Error> {
         Ok(Self {
             rt,
             chain_id: config.id.clone(),
-            header_updaters: Arc::new(RwLock::new(vec![])),
+            rpc_client,
+            metadata_cache,
+            current_epoch_start: None,
         })
     }
 
-    pub fn subscribe(&mut self) -> Receiver<AxonChainHeader> {
-        let (tx, rx) = channel(1);
-        self.rt.block_on(self.header_updaters.write()).push(tx);
-        rx
+    fn verify_block(&mut self, number: u64) -> Result<AxonLightBlock, Error> {
+        let light_block = self
+            .rt
+            .block_on(fetch_verified_block(
+                &self.rpc_client,
+                &self.metadata_cache,
+                number,
+            ))?
+            .to_light_block()?;
+        self.track_epoch(&light_block);
+        Ok(light_block)
     }
 
-    pub fn bootstrap<T: AxonRpc + Sync + Send + 'static>(
-        &self,
-        provider: Arc<SignerMiddleware<Provider<Ws>, Wallet<SigningKey>>>,
-        rpc: T,
-        epoch_len: u64,
-    ) -> Result<(), Error> {
-        let rt = self.rt.clone();
-        let emitters = self.header_updaters.clone();
-        self.rt.spawn(async move {
-            info!("axon: start watching new block from axon chain");
-            let mut stream = provider
-                .watch_blocks()
-                .await
-                .expect("unsupported ws client");
-            while let Some(block_hash) = stream.next().await {
-                let block = rpc
-                    .get_block_by_id(block_hash.into())
-                    .await
-                    .expect("watch axon block failed")
-                    .expect("watch axon block failed");
+    fn track_epoch(&mut self, light_block: &AxonLightBlock) {
+        let epoch_start = light_block.header.epoch_start;
+        match self.current_epoch_start {
+            Some(current) if current >= epoch_start => {}
+            _ => {
                 info!(
-                    "axon: new block {} with hash {:?}",
-                    block.header.number, block_hash
+                    "axon {}: validator set of epoch starting at block {epoch_start} has {} validators",
+                    self.chain_id,
+                    light_block.validators.len()
                 );
-                // axon validators would refresh in case of the change of epoch
-                if block.header.number % epoch_len == 0 {
-                    info!(
-                        "axon: new epoch starting with block {}",
-                        block.header.number
-                    );
-                    for emitter in emitters.read().await.iter() {
-                        emitter
-                            .send(block.header.clone())
-                            .await
-                            .expect("send axon header");
-                    }
-                }
+                self.current_epoch_start = Some(epoch_start);
+            }
+        }
+    }
+
+    /// Verifies the last block of every epoch in `[trusted, target)`, which
+    /// hands over the validator set to the next epoch.
+    fn verify_epoch_transitions(
+        &mut self,
+        trusted: u64,
+        target: u64,
+    ) -> Result<Vec<AxonLightBlock>, Error> {
+        let mut supporting = vec![];
+        let mut number = trusted;
+        loop {
+            let metadata = self
+                .rt
+                .block_on(self.metadata_cache.get_or_fetch(&self.rpc_client, number))?;
+            let epoch_end = metadata.version.end;
+            if epoch_end >= target {
+                break;
             }
-            info!("axon: end watching new block from axon chain");
-        });
-        Ok(())
+            trace!(
+                "axon {}: verify epoch transition at block {epoch_end}",
+                self.chain_id
+            );
+            supporting.push(self.verify_block(epoch_end)?);
+            number = epoch_end + 1;
+        }
+        Ok(supporting)
+    }
+
+    fn latest_block_number(&self) -> Result<u64, Error> {
+        let block = self
+            .rt
+            .block_on(
+                self.rpc_client
+                    .get_block_by_id(BlockId::Number(BlockNumber::Latest)),
+            )?
+            .ok_or_else(Error::invalid_height_no_source)?;
+        Ok(block.header.number)
     }
 }
 
-// TO IMPLEMENT
 impl super::LightClient<AxonChain> for LightClient {
     fn header_and_minimal_set(
         &mut self,
-        trusted: ibc_relayer_types::Height,
-        target: ibc_relayer_types::Height,
+        trusted: Height,
+        target: Height,
         client_state: &AnyClientState,
     ) -> Result<Verified<AxonHeader>, Error> {
-        todo!()
+        let Verified { target, supporting } = self.verify(trusted, target, client_state)?;
+        Ok(Verified {
+            target: target.header,
+            supporting: supporting.into_iter().map(|lb| lb.header).collect(),
+        })
     }
 
     fn verify(
         &mut self,
-        trusted: ibc_relayer_types::Height,
-        target: ibc_relayer_types::Height,
-        client_state: &AnyClientState,
+        trusted: Height,
+        target: Height,
+        _client_state: &AnyClientState,
     ) -> Result<Verified<AxonLightBlock>, Error> {
-        Ok(Verified {
-            target: AxonLightBlock::default(),
-            supporting: vec![],
-        })
+        trace!(%trusted, %target, "light client verification");
+
+        let trusted = trusted.revision_height();
+        let target = target.revision_height();
+        let supporting = if trusted < target {
+            self.verify_epoch_transitions(trusted, target)?
+        } else {
+            vec![]
+        };
+        let target = self.verify_block(target)?;
+
+        Ok(Verified { target, supporting })
     }
 
     fn check_misbehaviour(
         &mut self,
-        update: &ibc_relayer_types::core::ics02_client::events::UpdateClient,
-        client_state: &AnyClientState,
+        update: &UpdateClient,
+        _client_state: &AnyClientState,
     ) -> Result<Option<MisbehaviourEvidence>, Error> {
-        Ok(None)
+        crate::time!("axon light client check_misbehaviour");
+
+        // updates of Axon clients on CKB are submitted without headers
+        let Some(update_header) = update.header.clone() else {
+            return Ok(None);
+        };
+        let update_header: &AxonHeader =
+            downcast_header(update_header.as_ref()).ok_or_else(|| {
+                Error::misbehaviour(format!(
+                    "header type incompatible for chain {}",
+                    self.chain_id
+                ))
+            })?;
+
+        // the proof of a block is carried by its child, so the tip block
+        // cannot be verified yet
+        let number = update_header.height.revision_height();
+        if number >= self.latest_block_number()? {
+            return Ok(None);
+        }
+
+        let local = self.verify_block(number)?;
+        if !update_header.conflicts_with(&local.header) {
+            return Ok(None);
+        }
+        // only a header signed by the validators of its epoch proves that
+        // they committed to two blocks at the same height
+        let metadata = self
+            .rt
+            .block_on(self.metadata_cache.get_or_fetch(&self.rpc_client, number))?;
+        verify_header_proof(update_header, &metadata).map_err(|e| {
+            Error::misbehaviour(format!(
+                "conflicting header #{number} of chain {} is not validly signed: {e}",
+                self.chain_id
+            ))
+        })?;
+
+        let misbehaviour = AxonMisbehaviour {
+            client_id: update.client_id().clone(),
+            header1: update_header.clone(),
+            header2: local.header,
+        };
+        Ok(Some(MisbehaviourEvidence {
+            misbehaviour: misbehaviour.into(),
+            supporting_headers: vec![],
+        }))
     }
 
-    fn fetch(&mut self, height: ibc_relayer_types::Height) -> Result<AxonLightBlock, Error> {
-        todo!()
+    fn fetch(&mut self, height: Height) -> Result<AxonLightBlock, Error> {
+        trace!(%height, "fetching header");
+
+        let (block, previous_state_root, proof, metadata) =
+            self.rt.block_on(fetch_block_ingredients(
+                &self.rpc_client,
+                &self.metadata_cache,
+                height.revision_height(),
+            ))?;
+        to_light_block(&block, previous_state_root, &proof, &metadata)
     }
 }
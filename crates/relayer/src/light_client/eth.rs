@@ -1,10 +1,12 @@
+pub mod rpc_pool;
 mod utils;
 
 use std::cmp;
 use std::collections::BTreeMap;
-use std::ops::Index;
+use std::future::Future;
+use std::ops::{Index, Range};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::runtime::Runtime as TokioRuntime;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use tokio::sync::Mutex;
@@ -13,6 +15,7 @@ use tracing::{error, warn};
 use async_trait::async_trait;
 use eyre::eyre;
 use eyre::Result;
+use futures::{StreamExt, TryStreamExt};
 use ibc_relayer_types::clients::ics07_eth::client_state::EthClientState;
 use ibc_relayer_types::clients::ics07_eth::types::{
     BitVector, Bootstrap, ConsensusError, FinalityUpdate, GenericUpdate, PublicKey, SignatureBytes,
@@ -24,13 +27,15 @@ use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::{
     clients::ics07_eth::header::Header, core::ics02_client::events::UpdateClient, Height,
 };
+use reqwest::header::ACCEPT;
 use reqwest_middleware::ClientBuilder;
 use reqwest_middleware::ClientWithMiddleware;
 use reqwest_retry::policies::ExponentialBackoff;
 use reqwest_retry::RetryTransientMiddleware;
+use serde::de::DeserializeOwned;
 use tracing::info;
 
-use crate::config::eth::EthChainConfig;
+use crate::config::eth::{EthChainConfig, SLOTS_PER_EPOCH};
 use crate::{
     chain::{endpoint::ChainEndpoint, eth::EthChain},
     client_state::AnyClientState,
@@ -40,6 +45,7 @@ use crate::{
 
 use super::Verified;
 
+use self::rpc_pool::RpcPool;
 use self::utils::calc_sync_period;
 use self::utils::compute_domain;
 use self::utils::compute_signing_root;
@@ -51,9 +57,11 @@ use self::utils::is_next_committee_proof_valid;
 pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u8 = 128;
 pub const MAX_CACHED_UPDATES: usize = 32 * 1024;
 pub const MAX_REQUEST_UPDATES: u64 = 64;
+/// Maximal number of header requests in flight per endpoint of the RPC pool
+pub const MAX_CONCURRENT_HEADER_REQUESTS: usize = 8;
 
 fn calc_epoch(slot: u64) -> u64 {
-    slot / 32
+    slot / SLOTS_PER_EPOCH
 }
 
 pub struct ConsensusClient<R: ConsensusRpc> {
@@ -107,9 +115,11 @@ impl<R: ConsensusRpc> ConsensusClient<R> {
                 .insert(update.finalized_header.slot, update.clone());
         }
 
-        let finality_update = self.rpc.get_finality_update().await?;
+        let finality_update = self
+            .rpc
+            .get_valid_finality_update(&|update| self.verify_finality_update(update))
+            .await?;
         let previous_stored_finalized_slot = self.store.finalized_header.slot;
-        self.verify_finality_update(&finality_update)?;
         self.apply_finality_update(&finality_update);
         if self.store.finalized_header.slot > previous_stored_finalized_slot {
             self.store_finality_update(&finality_update, false).await?;
@@ -134,11 +144,13 @@ impl<R: ConsensusRpc> ConsensusClient<R> {
             if let Some((_, last_update)) = self.store.finality_updates.last_key_value() {
                 let start_slot = last_update.finalized_header.slot;
                 let end_slot = finality_update.finalized_header.slot;
-                for slot in (start_slot + 1)..=end_slot {
-                    let update = self.get_finality_update(slot).await?;
-                    if let Some(update) = update {
-                        self.store.finality_updates.insert(slot, update);
-                    }
+                let updates = self
+                    .get_finality_updates((start_slot + 1)..(end_slot + 1))
+                    .await?;
+                for update in updates {
+                    self.store
+                        .finality_updates
+                        .insert(update.finalized_header.slot, update);
                 }
             }
         }
@@ -181,6 +193,47 @@ impl<R: ConsensusRpc> ConsensusClient<R> {
         Ok(update)
     }
 
+    /// Returns the finality updates of the slots in `slots` which are either cached
+    /// or finalized, in order. The missing finalized headers are fetched concurrently.
+    pub async fn get_finality_updates(&self, slots: Range<u64>) -> Result<Vec<Update>> {
+        let finalized_slot = self.store.finalized_header.slot;
+        let missing_slots = slots
+            .clone()
+            .filter(|slot| {
+                *slot < finalized_slot && !self.store.finality_updates.contains_key(slot)
+            })
+            .collect::<Vec<_>>();
+        let headers = self
+            .rpc
+            .get_headers(&missing_slots)
+            .await
+            .map_err(|error| eyre!("rpc error: {error}"))?;
+        let mut fetched_updates = missing_slots
+            .into_iter()
+            .zip(headers)
+            .map(|(slot, header)| {
+                let finalized_header = header.unwrap_or_else(|| {
+                    warn!("slot {slot} forked or skipped, replace with empty");
+                    Header {
+                        slot,
+                        ..Default::default()
+                    }
+                });
+                (slot, Update::from_finalized_header(finalized_header))
+            })
+            .collect::<BTreeMap<_, _>>();
+        let updates = slots
+            .filter_map(|slot| {
+                self.store
+                    .finality_updates
+                    .get(&slot)
+                    .cloned()
+                    .or_else(|| fetched_updates.remove(&slot))
+            })
+            .collect();
+        Ok(updates)
+    }
+
     pub fn cache_finality_update(&mut self, update: &Update) {
         self.store
             .finality_updates
@@ -241,13 +294,12 @@ impl<R: ConsensusRpc> ConsensusClient<R> {
     }
 
     async fn start_emiting_headers(&mut self, start_slot: u64, end_slot: u64) -> Result<()> {
-        let mut finalized_headers = vec![];
-        for slot in start_slot..end_slot {
-            let update = self.get_finality_update(slot).await?;
-            if let Some(update) = update {
-                finalized_headers.push(update.finalized_header);
-            }
-        }
+        let mut finalized_headers = self
+            .get_finality_updates(start_slot..end_slot)
+            .await?
+            .into_iter()
+            .map(|update| update.finalized_header)
+            .collect::<Vec<_>>();
         // same epoch means the incoming finality epoch has forked headers at the begining
         if calc_epoch(start_slot) == calc_epoch(end_slot) {
             let update = self.get_finality_update(end_slot).await?;
@@ -271,8 +323,10 @@ impl<R: ConsensusRpc> ConsensusClient<R> {
 
     pub async fn advance(&mut self) -> Result<()> {
         let previous_stored_finalized_slot = self.store.finalized_header.slot;
-        let finality_update = self.rpc.get_finality_update().await?;
-        self.verify_finality_update(&finality_update)?;
+        let finality_update = self
+            .rpc
+            .get_valid_finality_update(&|update| self.verify_finality_update(update))
+            .await?;
         self.apply_finality_update(&finality_update);
 
         if self.store.next_sync_committee.is_none() {
@@ -370,7 +424,7 @@ impl<R: ConsensusRpc> ConsensusClient<R> {
 
             if update_finalized_slot > self.store.finalized_header.slot {
                 self.store.finalized_header = update.finalized_header.clone().unwrap();
-                if self.store.finalized_header.slot % 32 == 0 {
+                if self.store.finalized_header.slot % SLOTS_PER_EPOCH == 0 {
                     let checkpoint_res = self.store.finalized_header.tree_hash_root();
                     self.last_checkpoint = Some(checkpoint_res.into())
                 }
@@ -512,7 +566,7 @@ impl<R: ConsensusRpc> ConsensusClient<R> {
     }
 
     fn slot_timestamp(&self, slot: u64) -> u64 {
-        slot * 12 + self.config.genesis_time
+        slot * self.config.seconds_per_slot + self.config.genesis_time
     }
 
     pub fn expected_current_slot(&self) -> u64 {
@@ -521,17 +575,72 @@ impl<R: ConsensusRpc> ConsensusClient<R> {
         let genesis_time = self.config.genesis_time;
         let since_genesis = now.saturating_sub(Duration::from_secs(genesis_time));
 
-        since_genesis.as_secs() / 12
+        since_genesis.as_secs() / self.config.seconds_per_slot
     }
 }
 
 #[async_trait]
-pub trait ConsensusRpc {
+pub trait ConsensusRpc: Send + Sync {
     fn new(rpcs: &[String]) -> Self;
     async fn get_bootstrap(&self, block_root: &[u8]) -> Result<Bootstrap>;
     async fn get_updates(&self, period: u64, count: u8) -> Result<Vec<Update>>;
     async fn get_finality_update(&self) -> Result<FinalityUpdate>;
+    /// Returns the latest finality update accepted by `validate`, the other
+    /// endpoints are tried if it's rejected. Endpoints proven to serve invalid
+    /// updates are demoted, not the ones rejected because of the local state.
+    async fn get_valid_finality_update(
+        &self,
+        validate: &(dyn Fn(&FinalityUpdate) -> Result<()> + Send + Sync),
+    ) -> Result<FinalityUpdate> {
+        let update = self.get_finality_update().await?;
+        validate(&update)?;
+        Ok(update)
+    }
     async fn get_header(&self, slot: u64) -> Result<Option<Header>>;
+    /// Returns the headers of `slots` in the same order, `None` for the skipped slots
+    async fn get_headers(&self, slots: &[u64]) -> Result<Vec<Option<Header>>>;
+}
+
+/// Whether the rejection of an update proves that the endpoint served invalid
+/// data. An update can also be rejected because of the local state: a period
+/// ahead of a store missing the next sync committee, a signature slot ahead of
+/// the local clock, or a slot the store already finalized.
+fn served_invalid_update(error: &eyre::Report) -> bool {
+    matches!(
+        error.downcast_ref::<ConsensusError>(),
+        Some(
+            ConsensusError::InsufficientParticipation
+                | ConsensusError::InvalidFinalityProof
+                | ConsensusError::InvalidNextSyncCommitteeProof
+                | ConsensusError::InvalidCurrentSyncCommitteeProof
+                | ConsensusError::InvalidSignature
+                | ConsensusError::InvalidHeaderHash(..)
+        )
+    )
+}
+
+/// Fetches the headers of `slots` with at most `concurrency` requests in flight,
+/// preserving their order. The requests are spread across the `pool_size`
+/// endpoints of the pool: `fetch(index, slot)` has to query the endpoint `index`
+/// first.
+async fn fetch_headers_concurrently<F, Fut>(
+    slots: &[u64],
+    pool_size: usize,
+    concurrency: usize,
+    fetch: F,
+) -> Result<Vec<Option<Header>>>
+where
+    F: Fn(usize, u64) -> Fut,
+    Fut: Future<Output = Result<Option<Header>>>,
+{
+    let requests = slots
+        .iter()
+        .enumerate()
+        .map(|(i, slot)| fetch(i % pool_size, *slot));
+    futures::stream::iter(requests)
+        .buffered(cmp::max(concurrency, 1))
+        .try_collect()
+        .await
 }
 
 #[derive(Default)]
@@ -545,17 +654,44 @@ pub struct LightClientStore {
     pub finality_updates: BTreeMap<u64, Update>,
 }
 
-pub struct NimbusRpc {
-    rpc: Vec<String>,
+/// Client of the standard `/eth/v1/beacon` API, spreading its requests over
+/// a [`RpcPool`] of beacon nodes
+pub struct BeaconRpc {
+    pool: RpcPool,
     client: ClientWithMiddleware,
 }
 
-impl NimbusRpc {
+impl BeaconRpc {
+    pub fn pool(&self) -> &RpcPool {
+        &self.pool
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, req: String) -> Result<T> {
+        let res = self
+            .client
+            .get(req)
+            .header(ACCEPT, "application/json")
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<T>()
+            .await?;
+        Ok(res)
+    }
+
+    async fn get_finality_update_from(&self, rpc: &str) -> Result<FinalityUpdate> {
+        let req = format!("{rpc}/eth/v1/beacon/light_client/finality_update");
+        let res = self.get_json::<FinalityUpdateResponse>(req).await?;
+        Ok(res.data)
+    }
+
     async fn get_header_inner(&self, rpc: &str, slot: u64) -> Result<Option<Header>> {
+        // a missing header is answered with an error status and a JSON body
         let req = format!("{}/eth/v1/beacon/headers/{slot}", rpc);
         let res = self
             .client
             .get(req)
+            .header(ACCEPT, "application/json")
             .send()
             .await?
             .json::<HeaderResponse::Response>()
@@ -564,113 +700,125 @@ impl NimbusRpc {
 
         Ok(res.header())
     }
+
+    /// Queries the header of `slot` from the endpoints of the pool, starting from
+    /// the `first` available one, falling back to the others if it's missing or
+    /// the request fails
+    async fn get_header_from(&self, first: usize, slot: u64) -> Result<Option<Header>> {
+        let mut first_error = None;
+        let mut find_none = false;
+        for index in self.pool.ranked_from(first) {
+            let start = Instant::now();
+            match self.get_header_inner(self.pool.url(index), slot).await {
+                Ok(header) => {
+                    self.pool.record_success(index, start.elapsed());
+                    match header {
+                        Some(header) => return Ok(Some(header)),
+                        None => find_none = true,
+                    }
+                }
+                Err(err) => {
+                    self.pool.record_failure(index);
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        match first_error {
+            Some(err) if !find_none => Err(err),
+            _ => Ok(None),
+        }
+    }
 }
 
 #[async_trait]
-impl ConsensusRpc for NimbusRpc {
+impl ConsensusRpc for BeaconRpc {
     fn new(rpcs: &[String]) -> Self {
+        // transient errors are retried once on the same endpoint, then the
+        // pool fails over to the other ones
         let retry_policy = ExponentialBackoff::builder()
             .backoff_exponent(1)
-            .build_with_max_retries(3);
+            .build_with_max_retries(1);
         let client = ClientBuilder::new(reqwest::Client::new())
             .with(RetryTransientMiddleware::new_with_policy(retry_policy))
             .build();
         assert!(!rpcs.is_empty());
-        NimbusRpc {
-            rpc: rpcs.to_owned(),
+        BeaconRpc {
+            pool: RpcPool::new(rpcs.to_owned()),
             client,
         }
     }
 
     async fn get_updates(&self, period: u64, count: u8) -> Result<Vec<Update>> {
         let count = cmp::min(count, MAX_REQUEST_LIGHT_CLIENT_UPDATES);
-        let req = format!(
-            "{}/eth/v1/beacon/light_client/updates?start_period={period}&count={count}",
-            self.rpc[0]
-        );
-
         let res = self
-            .client
-            .get(req)
-            .send()
-            .await?
-            .json::<UpdateResponse>()
+            .pool
+            .request(|rpc| {
+                self.get_json::<UpdateResponse>(format!(
+                    "{rpc}/eth/v1/beacon/light_client/updates?start_period={period}&count={count}"
+                ))
+            })
             .await?;
 
         Ok(res.iter().map(|d| d.data.clone()).collect())
     }
 
     async fn get_finality_update(&self) -> Result<FinalityUpdate> {
-        let req = format!("{}/eth/v1/beacon/light_client/finality_update", self.rpc[0]);
-        let res = self
-            .client
-            .get(req)
-            .send()
-            .await?
-            .json::<FinalityUpdateResponse>()
-            .await?;
+        self.pool
+            .request(|rpc| self.get_finality_update_from(rpc))
+            .await
+    }
 
-        Ok(res.data)
+    async fn get_valid_finality_update(
+        &self,
+        validate: &(dyn Fn(&FinalityUpdate) -> Result<()> + Send + Sync),
+    ) -> Result<FinalityUpdate> {
+        self.pool
+            .request_valid(
+                |rpc| self.get_finality_update_from(rpc),
+                |update| validate(update),
+                served_invalid_update,
+            )
+            .await
     }
 
     async fn get_bootstrap(&self, block_root: &[u8]) -> Result<Bootstrap> {
         let root_hex = hex::encode(block_root);
-        let req = format!(
-            "{}/eth/v1/beacon/light_client/bootstrap/0x{root_hex}",
-            self.rpc[0]
-        );
-
         let res = self
-            .client
-            .get(req)
-            .send()
-            .await?
-            .json::<BootstrapResponse>()
+            .pool
+            .request(|rpc| {
+                self.get_json::<BootstrapResponse>(format!(
+                    "{rpc}/eth/v1/beacon/light_client/bootstrap/0x{root_hex}"
+                ))
+            })
             .await?;
 
         Ok(res.data)
     }
 
     async fn get_header(&self, slot: u64) -> Result<Option<Header>> {
-        let result = self.get_header_inner(&self.rpc[0], slot).await;
-        match result {
-            Ok(Some(header)) => Ok(Some(header)),
-            Ok(None) => {
-                for rpc in self.rpc.iter().skip(1) {
-                    if let Ok(Some(header)) = self.get_header_inner(rpc, slot).await {
-                        return Ok(Some(header));
-                    }
-                }
-                Ok(None)
-            }
-            Err(err) => {
-                let mut find_none = false;
-                for rpc in self.rpc.iter().skip(1) {
-                    match self.get_header_inner(rpc, slot).await {
-                        Ok(Some(header)) => return Ok(Some(header)),
-                        Ok(None) => find_none = true,
-                        _ => {}
-                    }
-                }
-                if find_none {
-                    Ok(None)
-                } else {
-                    Err(err)
-                }
-            }
-        }
+        self.get_header_from(0, slot).await
+    }
+
+    async fn get_headers(&self, slots: &[u64]) -> Result<Vec<Option<Header>>> {
+        fetch_headers_concurrently(
+            slots,
+            self.pool.len(),
+            MAX_CONCURRENT_HEADER_REQUESTS * self.pool.len(),
+            |first, slot| self.get_header_from(first, slot),
+        )
+        .await
     }
 }
 
 pub struct LightClient {
     pub chain_id: ChainId,
-    pub consensus_client: Arc<Mutex<ConsensusClient<NimbusRpc>>>,
+    pub consensus_client: Arc<Mutex<ConsensusClient<BeaconRpc>>>,
     pub rt: Arc<TokioRuntime>,
 }
 
 impl LightClient {
     pub fn from_config(config: &EthChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
-        let client = ConsensusClient::<NimbusRpc>::new(
+        let client = ConsensusClient::<BeaconRpc>::new(
             &config.rpc_addr_pool,
             &config.initial_checkpoint,
             Arc::new(config.clone()),
@@ -731,18 +879,13 @@ impl LightClient {
             let mut count = limit;
             while count > 0 {
                 let n = std::cmp::min(count, MAX_REQUEST_UPDATES);
-                let futs = (begin..begin + n)
-                    .map(|i| consensus_client.get_finality_update(i))
-                    .collect::<Vec<_>>();
-                let fetched_updates = futures::future::try_join_all(futs)
+                let fetched_updates = consensus_client
+                    .get_finality_updates(begin..begin + n)
                     .await
-                    .map_err(|e| Error::rpc_response(e.to_string()))?
-                    .into_iter()
-                    .flatten();
+                    .map_err(|e| Error::rpc_response(e.to_string()))?;
 
-                let prev_len = updates.len();
+                let fetched = fetched_updates.len() as u64;
                 updates.extend(fetched_updates);
-                let fetched = (updates.len() - prev_len) as u64;
                 if fetched < n {
                     break;
                 }
@@ -878,13 +1021,15 @@ mod HeaderResponse {
 mod tests {
     use std::fs::read_to_string;
     use std::path::PathBuf;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     use super::{
-        Bootstrap, ConsensusClient, ConsensusRpc, FinalityUpdate, HeaderResponse, NimbusRpc,
-        Result, Update,
+        fetch_headers_concurrently, served_invalid_update, BeaconRpc, Bootstrap, ConsensusClient,
+        ConsensusRpc, FinalityUpdate, HeaderResponse, Result, Update,
+        MAX_CONCURRENT_HEADER_REQUESTS,
     };
-    use crate::config::eth::EthChainConfig;
+    use crate::config::eth::{EthChainConfig, SLOTS_PER_EPOCH};
     use crate::light_client::eth::utils::calc_sync_period;
     use crate::light_client::eth::MAX_REQUEST_LIGHT_CLIENT_UPDATES;
 
@@ -925,13 +1070,23 @@ mod tests {
             let response: Vec<HeaderResponse::Response> = serde_json::from_str(&header)?;
             Ok(response[slot as usize].clone().header())
         }
+
+        async fn get_headers(&self, slots: &[u64]) -> Result<Vec<Option<Header>>> {
+            let mut headers = Vec::with_capacity(slots.len());
+            for slot in slots {
+                headers.push(self.get_header(*slot).await?);
+            }
+            Ok(headers)
+        }
     }
 
     async fn get_client() -> ConsensusClient<MockRpc> {
         let base_config = EthChainConfig::goerli();
         let config = EthChainConfig {
             id: base_config.id,
+            network: base_config.network,
             genesis_time: base_config.genesis_time,
+            seconds_per_slot: base_config.seconds_per_slot,
             genesis_root: base_config.genesis_root,
             forks: base_config.forks,
             rpc_addr_pool: Default::default(),
@@ -1024,6 +1179,28 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_served_invalid_update() {
+        let mut client = get_client().await;
+        client.sync().await.unwrap();
+        let update = client.rpc.get_finality_update().await.unwrap();
+
+        let mut invalid_update = update.clone();
+        invalid_update.sync_aggregate.sync_committee_signature = FixedVector::default();
+        let err = client.verify_finality_update(&invalid_update).unwrap_err();
+        assert!(served_invalid_update(&err));
+
+        // signed ahead of the local clock, which may be skewed
+        let mut early_update = update;
+        early_update.signature_slot = client.expected_current_slot() + 1;
+        let err = client.verify_finality_update(&early_update).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            ConsensusError::InvalidTimestamp.to_string()
+        );
+        assert!(!served_invalid_update(&err));
+    }
+
     #[tokio::test]
     async fn test_verify_update_invalid_sig() {
         let client = get_client().await;
@@ -1084,6 +1261,64 @@ mod tests {
         assert!(update.unwrap().is_finalized_empty());
     }
 
+    #[tokio::test]
+    async fn test_get_finality_updates() {
+        let client = get_client().await;
+        let updates = client.get_finality_updates(0..2).await.unwrap();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].finalized_header.slot, 5595002);
+        assert!(updates[1].is_finalized_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_catch_up_sync_committee_period() {
+        // headers of a whole sync committee period
+        const SLOTS: u64 = SLOTS_PER_EPOCH * 256;
+        const POOL_SIZE: usize = 4;
+        const LATENCY: Duration = Duration::from_millis(100);
+
+        let slots = (0..SLOTS).collect::<Vec<_>>();
+        let requests = Arc::new(Mutex::new(vec![0; POOL_SIZE]));
+        let start = tokio::time::Instant::now();
+        let headers = fetch_headers_concurrently(
+            &slots,
+            POOL_SIZE,
+            MAX_CONCURRENT_HEADER_REQUESTS * POOL_SIZE,
+            |endpoint, slot| {
+                let requests = requests.clone();
+                async move {
+                    requests.lock().unwrap()[endpoint] += 1;
+                    tokio::time::sleep(LATENCY).await;
+                    let header = Header {
+                        slot,
+                        ..Default::default()
+                    };
+                    // some slots are skipped
+                    Ok((slot % 7 != 0).then_some(header))
+                }
+            },
+        )
+        .await
+        .unwrap();
+        let elapsed = start.elapsed();
+
+        assert_eq!(headers.len(), slots.len());
+        for (slot, header) in slots.iter().zip(&headers) {
+            match header {
+                Some(header) => assert_eq!(header.slot, *slot),
+                None => assert_eq!(slot % 7, 0),
+            }
+        }
+        let requests = requests.lock().unwrap();
+        assert!(requests
+            .iter()
+            .all(|count| *count == slots.len() / POOL_SIZE));
+
+        // fetching the headers one by one would take more than 13 minutes
+        let rounds = slots.len() / (MAX_CONCURRENT_HEADER_REQUESTS * POOL_SIZE);
+        assert!(elapsed <= LATENCY * (rounds as u32 + 1));
+    }
+
     #[ignore]
     #[tokio::test]
     async fn pull_beacon_headers_range() {
@@ -1091,7 +1326,7 @@ mod tests {
         const END_SLOT: u64 = 5687712;
         const URL: &str = "https://www.lightclientdata.org";
 
-        let rpc = NimbusRpc::new(&[URL.to_owned()]);
+        let rpc = BeaconRpc::new(&[URL.to_owned()]);
         let mut headers = vec![];
         for slot in START_SLOT..=END_SLOT {
             let header = rpc.get_header(slot).await.expect("get header");
@@ -0,0 +1,340 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use eyre::{eyre, Report, Result};
+use tracing::warn;
+
+/// Backoff of an endpoint after its first failure, doubled by each consecutive one
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+/// Score penalty of each consecutive failure of an endpoint
+const FAILURE_PENALTY: Duration = Duration::from_secs(1);
+/// Score penalty of each invalid response served by an endpoint
+const DEMOTION_PENALTY: Duration = Duration::from_secs(10);
+/// Weight of the latest sample in the moving average of the latency, in percent
+const LATENCY_WEIGHT: u32 = 20;
+
+/// Health of a beacon API endpoint, as observed from its responses
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// Moving average of the latency of the successful requests
+    pub latency: Option<Duration>,
+    /// Failures since the last successful request
+    pub failures: u32,
+    /// Invalid responses served, each successful request forgives one
+    pub demotions: u32,
+    /// The endpoint is only tried after the others until this instant
+    pub backoff_until: Option<Instant>,
+}
+
+impl EndpointHealth {
+    /// The lower the better
+    fn score(&self) -> Duration {
+        self.latency.unwrap_or_default()
+            + FAILURE_PENALTY.saturating_mul(self.failures)
+            + DEMOTION_PENALTY.saturating_mul(self.demotions)
+    }
+
+    fn backing_off(&self, now: Instant) -> Option<Instant> {
+        self.backoff_until.filter(|until| *until > now)
+    }
+
+    fn on_success(&mut self, latency: Duration) {
+        self.latency = Some(match self.latency {
+            Some(average) => (average * (100 - LATENCY_WEIGHT) + latency * LATENCY_WEIGHT) / 100,
+            None => latency,
+        });
+        self.failures = 0;
+        self.demotions = self.demotions.saturating_sub(1);
+        self.backoff_until = None;
+    }
+
+    fn on_failure(&mut self, now: Instant) {
+        self.failures = self.failures.saturating_add(1);
+        let backoff = INITIAL_BACKOFF
+            .saturating_mul(2u32.saturating_pow(self.failures - 1))
+            .min(MAX_BACKOFF);
+        self.backoff_until = Some(now + backoff);
+    }
+}
+
+/// Pool of the beacon API endpoints of a chain, which routes the requests to
+/// the fastest healthy endpoints and fails over to the others.
+///
+/// A failing endpoint is backed off exponentially: it's only tried after the
+/// other endpoints until its backoff expires.
+pub struct RpcPool {
+    urls: Vec<String>,
+    health: Mutex<Vec<EndpointHealth>>,
+}
+
+impl RpcPool {
+    pub fn new(urls: Vec<String>) -> Self {
+        let health = Mutex::new(vec![EndpointHealth::default(); urls.len()]);
+        Self { urls, health }
+    }
+
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+
+    pub fn url(&self, index: usize) -> &str {
+        &self.urls[index]
+    }
+
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        self.health.lock().unwrap().clone()
+    }
+
+    /// Indexes of the endpoints in order of preference
+    pub fn ranked(&self) -> Vec<usize> {
+        self.ranked_at(Instant::now(), 0)
+    }
+
+    /// Same as [`ranked`](Self::ranked), with the endpoints which are not
+    /// backing off rotated by `first` to spread concurrent requests
+    pub fn ranked_from(&self, first: usize) -> Vec<usize> {
+        self.ranked_at(Instant::now(), first)
+    }
+
+    fn ranked_at(&self, now: Instant, first: usize) -> Vec<usize> {
+        let health = self.health.lock().unwrap();
+        let mut indexes = (0..self.urls.len()).collect::<Vec<_>>();
+        indexes.sort_by_key(|i| (health[*i].backing_off(now), health[*i].score()));
+        let available = indexes
+            .iter()
+            .take_while(|i| health[**i].backing_off(now).is_none())
+            .count();
+        if available > 0 {
+            indexes[..available].rotate_left(first % available);
+        }
+        indexes
+    }
+
+    pub fn record_success(&self, index: usize, latency: Duration) {
+        self.health.lock().unwrap()[index].on_success(latency);
+    }
+
+    pub fn record_failure(&self, index: usize) {
+        self.record_failure_at(index, Instant::now());
+    }
+
+    fn record_failure_at(&self, index: usize, now: Instant) {
+        self.health.lock().unwrap()[index].on_failure(now);
+    }
+
+    /// Demotes an endpoint which served an invalid or stale response
+    pub fn demote(&self, index: usize) {
+        let mut health = self.health.lock().unwrap();
+        health[index].demotions = health[index].demotions.saturating_add(1);
+        health[index].on_failure(Instant::now());
+    }
+
+    /// Sends `request` to the endpoints in order of preference until one of
+    /// them succeeds, returning the last error if they all fail
+    pub async fn request<'a, T, F, Fut>(&'a self, request: F) -> Result<T>
+    where
+        F: Fn(&'a str) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut last_error = None;
+        for index in self.ranked() {
+            let url = self.url(index);
+            let start = Instant::now();
+            match request(url).await {
+                Ok(response) => {
+                    self.record_success(index, start.elapsed());
+                    return Ok(response);
+                }
+                Err(error) => {
+                    warn!("beacon endpoint {url} failed: {error}");
+                    self.record_failure(index);
+                    last_error = Some(error);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| eyre!("no beacon endpoint is configured")))
+    }
+
+    /// Same as [`request`](Self::request), also trying the other endpoints if
+    /// a response is rejected by `validate`. Only the endpoints whose rejected
+    /// response proves they served invalid data, according to `is_invalid`,
+    /// are demoted: a rejection caused by the local state is no fault of theirs.
+    pub async fn request_valid<'a, T, F, Fut, V, I>(
+        &'a self,
+        request: F,
+        validate: V,
+        is_invalid: I,
+    ) -> Result<T>
+    where
+        F: Fn(&'a str) -> Fut,
+        Fut: Future<Output = Result<T>>,
+        V: Fn(&T) -> Result<()>,
+        I: Fn(&Report) -> bool,
+    {
+        let mut last_error = None;
+        for index in self.ranked() {
+            let url = self.url(index);
+            let start = Instant::now();
+            let response = match request(url).await {
+                Ok(response) => response,
+                Err(error) => {
+                    warn!("beacon endpoint {url} failed: {error}");
+                    self.record_failure(index);
+                    last_error = Some(error);
+                    continue;
+                }
+            };
+            match validate(&response) {
+                Ok(()) => {
+                    self.record_success(index, start.elapsed());
+                    return Ok(response);
+                }
+                Err(error) if is_invalid(&error) => {
+                    warn!(
+                        "demote beacon endpoint {url}, which served an invalid response: {error}"
+                    );
+                    self.demote(index);
+                    last_error = Some(error);
+                }
+                Err(error) => {
+                    warn!("response of beacon endpoint {url} is rejected: {error}");
+                    last_error = Some(error);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| eyre!("no beacon endpoint is configured")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(size: usize) -> RpcPool {
+        RpcPool::new((0..size).map(|i| format!("http://node{i}")).collect())
+    }
+
+    #[test]
+    fn test_rank_by_latency() {
+        let pool = pool(3);
+        assert_eq!(pool.ranked(), vec![0, 1, 2]);
+
+        pool.record_success(0, Duration::from_millis(300));
+        pool.record_success(1, Duration::from_millis(100));
+        pool.record_success(2, Duration::from_millis(200));
+        assert_eq!(pool.ranked(), vec![1, 2, 0]);
+        assert_eq!(pool.ranked_from(1), vec![2, 0, 1]);
+
+        // the moving average smooths out a single slow response
+        pool.record_success(1, Duration::from_millis(400));
+        assert_eq!(pool.health()[1].latency, Some(Duration::from_millis(160)));
+        assert_eq!(pool.ranked(), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let pool = pool(2);
+        let now = Instant::now();
+        let mut backoffs = vec![];
+        for _ in 0..11 {
+            pool.record_failure_at(0, now);
+            backoffs.push(pool.health()[0].backoff_until.unwrap() - now);
+        }
+        let expected = [1, 2, 4, 8, 16, 32, 64, 128, 256, 300, 300].map(Duration::from_secs);
+        assert_eq!(backoffs, expected);
+
+        // backing off endpoints come last, until their backoff expires
+        assert_eq!(pool.ranked_at(now, 0), vec![1, 0]);
+        assert_eq!(pool.ranked_at(now, 1), vec![1, 0]);
+        assert_eq!(pool.ranked_at(now + MAX_BACKOFF, 0), vec![1, 0]);
+        pool.record_failure_at(1, now);
+        assert_eq!(pool.ranked_at(now + MAX_BACKOFF, 0), vec![1, 0]);
+
+        pool.record_success(0, Duration::from_millis(100));
+        assert_eq!(pool.health()[0].failures, 0);
+        assert_eq!(pool.ranked_at(now, 0), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_demotion() {
+        let pool = pool(2);
+        pool.record_success(0, Duration::from_millis(100));
+        pool.record_success(1, Duration::from_millis(500));
+        pool.demote(0);
+
+        let now = Instant::now() + MAX_BACKOFF;
+        assert_eq!(pool.ranked_at(now, 0), vec![1, 0]);
+
+        // a successful request forgives the demotion
+        pool.record_success(0, Duration::from_millis(100));
+        assert_eq!(pool.health()[0].demotions, 0);
+        assert_eq!(pool.ranked_at(now, 0), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_request_failover() {
+        let pool = pool(3);
+        let response = pool
+            .request(|url| async move {
+                if url == "http://node2" {
+                    Ok(url.len())
+                } else {
+                    Err(eyre!("{url} is down"))
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(response, "http://node2".len());
+        assert_eq!(pool.ranked(), vec![2, 0, 1]);
+
+        let error = pool
+            .request(|url| async move { Err::<(), _>(eyre!("{url} is down")) })
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "http://node1 is down");
+    }
+
+    #[tokio::test]
+    async fn test_request_valid_demotion() {
+        let pool = pool(3);
+        let is_invalid = |error: &Report| error.to_string() == "invalid";
+
+        // node0 serves invalid data, node1 can't be validated locally
+        let response = pool
+            .request_valid(
+                |url| async move { Ok(url) },
+                |url| match *url {
+                    "http://node0" => Err(eyre!("invalid")),
+                    "http://node1" => Err(eyre!("not relevant")),
+                    _ => Ok(()),
+                },
+                is_invalid,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response, "http://node2");
+        let health = pool.health();
+        assert_eq!(health[0].demotions, 1);
+        assert!(health[0].backoff_until.is_some());
+        assert_eq!(health[1], EndpointHealth::default());
+        assert_eq!(health[2].demotions, 0);
+
+        // the error of the last endpoint is returned if none is valid
+        let error = pool
+            .request_valid(
+                |url| async move { Ok(url) },
+                |_| Err(eyre!("not relevant")),
+                is_invalid,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "not relevant");
+        assert_eq!(pool.health()[1], EndpointHealth::default());
+    }
+}
@@ -8,6 +8,8 @@ use sha2::{Digest, Sha256};
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
+use crate::config::eth::SLOTS_PER_EPOCH;
+
 pub fn is_current_committee_proof_valid(
     attested_header: &Header,
     current_committee: &mut SyncCommittee,
@@ -23,7 +25,7 @@ pub fn is_current_committee_proof_valid(
 }
 
 pub fn calc_sync_period(slot: u64) -> u64 {
-    let epoch = slot / 32;
+    let epoch = slot / SLOTS_PER_EPOCH;
     epoch / 256
 }
 
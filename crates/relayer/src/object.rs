@@ -145,6 +145,25 @@ impl CrossChainQuery {
     }
 }
 
+/// A header relay worker which relays the beacon headers of an Ethereum chain
+/// to the multi-client hosted on a CKB chain
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct HeaderRelay {
+    /// Destination chain identifier.
+    /// This is the CKB chain hosting the multi-client.
+    pub dst_chain_id: ChainId,
+
+    /// Source chain identifier.
+    /// This is the Ethereum chain whose headers are relayed.
+    pub src_chain_id: ChainId,
+}
+
+impl HeaderRelay {
+    pub fn short_name(&self) -> String {
+        format!("header_relay::{}->{}", self.src_chain_id, self.dst_chain_id)
+    }
+}
+
 /// An object determines the amount of parallelism that can
 /// be exercised when processing [`IbcEvent`](ibc_relayer_types::events::IbcEvent)
 /// between two chains. For each [`Object`], a corresponding
@@ -166,6 +185,8 @@ pub enum Object {
     Wallet(Wallet),
     /// See [`CrossChainQuery`]
     CrossChainQuery(CrossChainQuery),
+    /// See [`HeaderRelay`]
+    HeaderRelay(HeaderRelay),
 }
 
 define_error! {
@@ -216,6 +237,7 @@ impl Object {
             Object::Packet(p) => &p.src_chain_id == src_chain_id,
             Object::Wallet(_) => false,
             Object::CrossChainQuery(c) => &c.src_chain_id == src_chain_id,
+            Object::HeaderRelay(_) => false,
         }
     }
 
@@ -230,6 +252,7 @@ impl Object {
             Object::CrossChainQuery(c) => {
                 &c.src_chain_id == chain_id || &c.dst_chain_id == chain_id
             }
+            Object::HeaderRelay(h) => &h.src_chain_id == chain_id || &h.dst_chain_id == chain_id,
         }
     }
 
@@ -242,6 +265,7 @@ impl Object {
             Object::Packet(_) => ObjectType::Packet,
             Object::Wallet(_) => ObjectType::Wallet,
             Object::CrossChainQuery(_) => ObjectType::CrossChainQuery,
+            Object::HeaderRelay(_) => ObjectType::HeaderRelay,
         }
     }
 }
@@ -255,6 +279,7 @@ pub enum ObjectType {
     Packet,
     Wallet,
     CrossChainQuery,
+    HeaderRelay,
 }
 
 impl From<Client> for Object {
@@ -293,6 +318,12 @@ impl From<CrossChainQuery> for Object {
     }
 }
 
+impl From<HeaderRelay> for Object {
+    fn from(h: HeaderRelay) -> Self {
+        Self::HeaderRelay(h)
+    }
+}
+
 impl Object {
     pub fn src_chain_id(&self) -> &ChainId {
         match self {
@@ -302,6 +333,7 @@ impl Object {
             Self::Packet(ref path) => &path.src_chain_id,
             Self::Wallet(ref wallet) => &wallet.chain_id,
             Self::CrossChainQuery(ref query) => &query.src_chain_id,
+            Self::HeaderRelay(ref relay) => &relay.src_chain_id,
         }
     }
 
@@ -313,6 +345,7 @@ impl Object {
             Self::Packet(ref path) => &path.dst_chain_id,
             Self::Wallet(ref wallet) => &wallet.chain_id,
             Self::CrossChainQuery(ref query) => &query.dst_chain_id,
+            Self::HeaderRelay(ref relay) => &relay.dst_chain_id,
         }
     }
 
@@ -324,6 +357,7 @@ impl Object {
             Self::Packet(ref path) => path.short_name(),
             Self::Wallet(ref wallet) => wallet.short_name(),
             Self::CrossChainQuery(ref query) => query.short_name(),
+            Self::HeaderRelay(ref relay) => relay.short_name(),
         }
     }
 
@@ -1,5 +1,6 @@
 use alloc::sync::Arc;
 
+use eth2_types::MainnetEthSpec;
 use flex_error::define_error;
 use tokio::runtime::Runtime as TokioRuntime;
 
@@ -58,7 +59,9 @@ pub fn spawn_chain_runtime<Handle: ChainHandle>(
     let handle = match chain_config.r#type() {
         ChainType::CosmosSdk => ChainRuntime::<CosmosSdkChain>::spawn::<Handle>(chain_config, rt),
         ChainType::Eth => ChainRuntime::<EthChain>::spawn::<Handle>(chain_config, rt),
-        ChainType::Ckb => ChainRuntime::<CkbChain>::spawn::<Handle>(chain_config, rt),
+        ChainType::Ckb => {
+            ChainRuntime::<CkbChain<MainnetEthSpec>>::spawn::<Handle>(chain_config, rt)
+        }
         ChainType::Axon => ChainRuntime::<AxonChain>::spawn::<Handle>(chain_config, rt),
         ChainType::Ckb4Ibc => ChainRuntime::<Ckb4IbcChain>::spawn(chain_config, rt),
     }
@@ -348,6 +348,7 @@ fn relay_on_object<Chain: ChainHandle>(
         Object::Packet(packet) => client_state_filter.control_packet_object(registry, packet),
         Object::Wallet(_wallet) => Ok(Permission::Allow),
         Object::CrossChainQuery(_) => Ok(Permission::Allow),
+        Object::HeaderRelay(_) => Ok(Permission::Allow),
     };
 
     match client_filter_outcome {
@@ -736,6 +737,17 @@ fn process_batch<Chain: ChainHandle>(
         workers.notify_new_block(&src_chain.id(), batch.height, new_block);
     }
 
+    // Forward the whole batch to the workers relaying the headers of the source chain.
+    let src_chain_id = src_chain.id();
+    for worker in workers.header_relays(&src_chain_id) {
+        worker.send_events(
+            batch.height,
+            batch.events.clone(),
+            batch.chain_id.clone(),
+            batch.tracking_id,
+        );
+    }
+
     // Forward the IBC events.
     for (object, events_with_heights) in collected.per_object.into_iter() {
         if !relay_on_object(
@@ -874,6 +886,15 @@ fn handle_batch<Chain: ChainHandle>(
             let _ = clear_pending_packets(workers, &chain_id)
                 .map_err(|e| error!("error during clearing pending packets: {}", e));
         }
+        Err(EventError(EventErrorDetail::ChainReorganized(e), _)) => {
+            warn!(
+                "chain was reorganized from height {}, clearing pending packets",
+                e.height
+            );
+
+            let _ = clear_pending_packets(workers, &chain_id)
+                .map_err(|e| error!("error during clearing pending packets: {}", e));
+        }
         Err(e) => {
             error!("error when receiving event batch: {}", e)
         }
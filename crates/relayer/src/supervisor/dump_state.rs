@@ -38,7 +38,7 @@ impl SupervisorState {
         chains.sort();
 
         let workers = workers
-            .map(|h| WorkerDesc::new(h.id(), h.object().clone(), h.data().cloned()))
+            .map(|h| WorkerDesc::new(h.id(), h.object().clone(), h.data()))
             .into_group_map_by(|desc| desc.object.object_type())
             .into_iter()
             .update(|(_, os)| os.sort_by_key(|desc| desc.object.short_name()))
@@ -62,12 +62,27 @@ impl Display for SupervisorState {
             writeln!(f, "* {tpe:?} workers:")?;
             for desc in objects {
                 writeln!(f, "  - {} (id: {})", desc.object.short_name(), desc.id)?;
-                if let Some(WorkerData::Client {
-                    misbehaviour,
-                    refresh,
-                }) = desc.data
-                {
-                    writeln!(f, "    | misbehaviour: {misbehaviour}, refresh: {refresh}")?;
+                match desc.data {
+                    Some(WorkerData::Client {
+                        misbehaviour,
+                        refresh,
+                    }) => {
+                        writeln!(f, "    | misbehaviour: {misbehaviour}, refresh: {refresh}")?;
+                    }
+                    Some(WorkerData::HeaderRelay {
+                        relayed_slot,
+                        target_slot,
+                    }) => {
+                        let slot =
+                            |slot: Option<u64>| slot.map_or("-".to_owned(), |s| s.to_string());
+                        writeln!(
+                            f,
+                            "    | relayed slot: {}, target slot: {}",
+                            slot(relayed_slot),
+                            slot(target_slot)
+                        )?;
+                    }
+                    None => {}
                 }
             }
         }
@@ -1,7 +1,5 @@
 use ibc_relayer_types::events::IbcEvent;
-use std::sync::Arc;
-use std::time::Duration;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, info, warn};
 
 use crate::chain::client::ClientSettings;
 use crate::chain::handle::ChainHandle;
@@ -9,197 +7,186 @@ use crate::chain::requests::{PageRequest, QueryClientStatesRequest};
 use crate::chain::tracking::{NonCosmosTrackingId, TrackedMsgs, TrackingId};
 use crate::client_state::IdentifiedAnyClientState;
 use crate::config::ChainConfig;
-use crate::error::{Error, ErrorDetail::LightClientVerification};
+use crate::error::{Error, ErrorDetail};
 use crate::event::monitor::EventBatch;
-use tendermint_light_client::errors::ErrorDetail;
 
 const MAX_HEADERS_IN_BATCH: u64 = 256;
-const MAX_SLEEP_SECONDS: u64 = 5;
-const MAX_RETRY_NUMBER: u8 = 5;
-
-pub fn handle_eth_ckb_event_batch<ChainA: ChainHandle, ChainB: ChainHandle>(
-    src_chain: &Arc<ChainA>,
-    dst_chain: &Arc<ChainB>,
-    event_batch: &EventBatch,
-) {
-    if !matches!(src_chain.config().unwrap(), ChainConfig::Eth(_))
-        || !matches!(dst_chain.config().unwrap(), ChainConfig::Ckb(_))
-    {
-        error!("ignore header relay while src chain is not eth or dst chain is not ckb");
-        error!("src_chain: {src_chain:?}");
-        error!("dst_chain: {dst_chain:?}");
-        return;
-    }
-
-    if event_batch.events.is_empty() {
-        warn!("CAUTION: start relaying EMPTY headers");
-        return;
-    }
-
-    match event_batch.events[0].event {
-        IbcEvent::CreateClient(_) => {
-            let request = QueryClientStatesRequest { pagination: None };
-            if dst_chain
-                .query_clients(request)
-                .expect("ckb query_clients")
-                .is_empty()
-            {
-                create_ethereum_light_client(src_chain, dst_chain, event_batch);
-            }
+
+/// Relays the beacon headers announced by the event monitor of an Ethereum
+/// chain to the multi-client hosted on a CKB chain
+pub struct HeaderRelay<ChainA: ChainHandle, ChainB: ChainHandle> {
+    src_chain: ChainA,
+    dst_chain: ChainB,
+    /// Headers up to this slot are known to be relayed
+    relayed_slot: Option<u64>,
+}
+
+impl<ChainA: ChainHandle, ChainB: ChainHandle> HeaderRelay<ChainA, ChainB> {
+    pub fn new(src_chain: ChainA, dst_chain: ChainB) -> Result<Self, Error> {
+        if !matches!(src_chain.config()?, ChainConfig::Eth(_))
+            || !matches!(dst_chain.config()?, ChainConfig::Ckb(_))
+        {
+            return Err(Error::other_error(format!(
+                "cannot relay headers from {} to {}, which are not an Ethereum chain and a CKB chain",
+                src_chain.id(),
+                dst_chain.id()
+            )));
         }
-        IbcEvent::NewBlock(_) => update_ethereum_headers(src_chain, dst_chain, event_batch),
-        _ => warn!("receiving unrecognized event"),
+        Ok(Self {
+            src_chain,
+            dst_chain,
+            relayed_slot: None,
+        })
     }
-}
 
-fn create_ethereum_light_client<ChainA: ChainHandle, ChainB: ChainHandle>(
-    src_chain: &Arc<ChainA>,
-    dst_chain: &Arc<ChainB>,
-    event_batch: &EventBatch,
-) {
-    let checkpoint_slot = event_batch.height;
-    let client_state = {
-        let client_state = src_chain.build_client_state(checkpoint_slot, ClientSettings::Other);
-        match client_state {
-            Ok(value) => value,
-            Err(err) => {
-                error!("src_chain.build_client_state: {err}");
-                return;
+    pub fn relayed_slot(&self) -> Option<u64> {
+        self.relayed_slot
+    }
+
+    pub fn handle_event_batch(&mut self, event_batch: &EventBatch) -> Result<(), Error> {
+        let Some(first_event) = event_batch.events.first() else {
+            warn!("CAUTION: start relaying EMPTY headers");
+            return Ok(());
+        };
+
+        match first_event.event {
+            IbcEvent::CreateClient(_) => {
+                let request = QueryClientStatesRequest { pagination: None };
+                if self.dst_chain.query_clients(request)?.is_empty() {
+                    self.create_ethereum_light_client(event_batch)?;
+                }
+                Ok(())
+            }
+            IbcEvent::NewBlock(_) => self.update_ethereum_headers(event_batch),
+            _ => {
+                warn!("receiving unrecognized event");
+                Ok(())
             }
         }
-    };
+    }
 
-    let tracked_msgs = TrackedMsgs {
-        msgs: vec![client_state.into()],
-        tracking_id: TrackingId::Static(NonCosmosTrackingId::ETH_CREATE_CLIENT),
-    };
+    fn create_ethereum_light_client(&mut self, event_batch: &EventBatch) -> Result<(), Error> {
+        let checkpoint_slot = event_batch.height;
+        let client_state = self
+            .src_chain
+            .build_client_state(checkpoint_slot, ClientSettings::Other)?;
 
-    match dst_chain.send_messages_and_wait_commit(tracked_msgs) {
-        Ok(_) => info!(
-            "finish creating light-client at slot {}",
-            checkpoint_slot.revision_height()
-        ),
-        Err(error) => {
-            if let Some(slot) = extract_missing_slot_from_error(&error) {
-                info!("light-client already created at slot {slot}");
-            }
+        let tracked_msgs = TrackedMsgs {
+            msgs: vec![client_state.into()],
+            tracking_id: TrackingId::Static(NonCosmosTrackingId::ETH_CREATE_CLIENT),
+        };
+
+        match self.dst_chain.send_messages_and_wait_commit(tracked_msgs) {
+            Ok(_) => info!(
+                "finish creating light-client at slot {}",
+                checkpoint_slot.revision_height()
+            ),
+            Err(error) => match error.detail() {
+                ErrorDetail::EthMultiClientAlreadyExists(detail) => info!(
+                    "light-client already created at slot {}",
+                    detail.minimal_slot
+                ),
+                _ => return Err(error),
+            },
         }
+        self.relayed_slot = Some(checkpoint_slot.revision_height());
+        Ok(())
     }
-}
 
-fn update_ethereum_headers<ChainA: ChainHandle, ChainB: ChainHandle>(
-    src_chain: &Arc<ChainA>,
-    dst_chain: &Arc<ChainB>,
-    event_batch: &EventBatch,
-) {
-    // assemble client states which are transformed from finality headers
-    let mut start_slot = 0;
-    let target_slot = event_batch.height.revision_height();
-    let msgs = event_batch
-        .events
-        .iter()
-        .filter_map(|event| {
+    fn update_ethereum_headers(&mut self, event_batch: &EventBatch) -> Result<(), Error> {
+        // assemble client states which are transformed from finality headers
+        let mut start_slot = 0;
+        let target_slot = event_batch.height.revision_height();
+        let mut msgs = vec![];
+        for event in &event_batch.events {
             if let IbcEvent::NewBlock(new_block) = event.event {
                 if start_slot == 0 {
                     start_slot = new_block.height.revision_height();
                 }
-                let client_state = {
-                    let client_state =
-                        src_chain.build_client_state(new_block.height, ClientSettings::Other);
-                    match client_state {
-                        Ok(value) => value,
-                        Err(err) => {
-                            error!("src_chain.build_client_state: {err}");
-                            return None;
-                        }
-                    }
-                };
-                return Some(client_state.into());
+                let client_state = self
+                    .src_chain
+                    .build_client_state(new_block.height, ClientSettings::Other)?;
+                msgs.push(client_state.into());
             }
-            None
-        })
-        .collect();
-
-    let tracked_msgs = TrackedMsgs {
-        msgs,
-        tracking_id: TrackingId::Static(NonCosmosTrackingId::ETH_UPDATE_CLIENT),
-    };
+        }
 
-    // try sending headers
-    let result = dst_chain.send_messages_and_wait_commit(tracked_msgs);
-    if result.is_ok() {
-        info!("finish relaying headers [{start_slot}, {target_slot}]");
-        return;
-    }
+        let tracked_msgs = TrackedMsgs {
+            msgs,
+            tracking_id: TrackingId::Static(NonCosmosTrackingId::ETH_UPDATE_CLIENT),
+        };
 
-    // returned err indicates headers falling behind
-    start_slot = match extract_missing_slot_from_error(&result.unwrap_err()) {
-        Some(slot) => {
-            if slot >= target_slot {
+        // try sending headers
+        let error = match self.dst_chain.send_messages_and_wait_commit(tracked_msgs) {
+            Ok(_) => {
                 info!("finish relaying headers [{start_slot}, {target_slot}]");
-                return;
+                self.relayed_slot = Some(target_slot);
+                return Ok(());
             }
-            warn!("upcoming header {start_slot} not match native tip header {slot}, start chasing");
-            slot
-        }
-        None => return,
-    };
-
-    // chasing lost headers
-    let mut retry = 0;
-    while start_slot < target_slot {
-        if retry > 0 {
-            debug!("{retry} time retry for [{start_slot}, {target_slot}] after sleeping {MAX_SLEEP_SECONDS}s");
-            std::thread::sleep(Duration::from_secs(MAX_SLEEP_SECONDS));
-        }
-        let limit = std::cmp::min(MAX_HEADERS_IN_BATCH, target_slot - start_slot + 1);
-        let request = QueryClientStatesRequest {
-            pagination: Some(PageRequest {
-                offset: start_slot,
-                limit,
-                ..Default::default()
-            }),
+            Err(error) => error,
         };
-        let client_states = match src_chain.query_clients(request) {
-            Ok(value) => value,
-            Err(err) => {
-                error!("src_chain.query_clients: {err}, skip this try");
-                return;
+
+        // returned err indicates headers falling behind
+        start_slot = match next_slot_from_error(&error) {
+            Some(slot) => {
+                self.relayed_slot = slot.checked_sub(1);
+                if slot > target_slot {
+                    info!("finish relaying headers [{start_slot}, {target_slot}]");
+                    return Ok(());
+                }
+                warn!("upcoming header {start_slot} not match native tip header {slot}, start chasing");
+                slot
             }
+            None => return Err(error),
         };
-        let end_slot = start_slot + client_states.len() as u64 - 1;
-        info!("send chasing headers [{start_slot}, {end_slot}]");
-        match send_messages(dst_chain, client_states) {
-            Ok(_) => {
-                let mut retry_log =
-                    format!("headers [{start_slot}, {end_slot}] are relayed to ckb, ");
-                if end_slot < target_slot - 1 {
-                    retry_log += &format!("keep chasing to {target_slot}");
-                } else {
-                    retry_log += "chasing complete";
-                }
-                debug!("{retry_log}");
-                retry = 0;
-                start_slot = end_slot + 1;
+
+        // chasing lost headers, a failure is left to the retry strategy of the worker
+        while start_slot <= target_slot {
+            let limit = std::cmp::min(MAX_HEADERS_IN_BATCH, target_slot - start_slot + 1);
+            let request = QueryClientStatesRequest {
+                pagination: Some(PageRequest {
+                    offset: start_slot,
+                    limit,
+                    ..Default::default()
+                }),
+            };
+            let client_states = self.src_chain.query_clients(request)?;
+            if client_states.is_empty() {
+                return Err(Error::other_error(format!(
+                    "no header of slots [{start_slot}, {target_slot}] is available"
+                )));
             }
-            Err(error) => {
-                if let Some(slot) = extract_missing_slot_from_error(&error) {
-                    debug!("adjust start_slot and continue retry: {error}");
-                    start_slot = slot;
-                } else {
-                    retry += 1;
+            let end_slot = start_slot + client_states.len() as u64 - 1;
+            info!("send chasing headers [{start_slot}, {end_slot}]");
+            match send_messages(&self.dst_chain, client_states) {
+                Ok(_) => {
+                    let mut chase_log =
+                        format!("headers [{start_slot}, {end_slot}] are relayed to ckb, ");
+                    if end_slot < target_slot {
+                        chase_log += &format!("keep chasing to {target_slot}");
+                    } else {
+                        chase_log += "chasing complete";
+                    }
+                    debug!("{chase_log}");
+                    self.relayed_slot = Some(end_slot);
+                    start_slot = end_slot + 1;
                 }
+                Err(error) => match next_slot_from_error(&error) {
+                    // resending the same headers would fail the same way
+                    Some(slot) if slot != start_slot => {
+                        debug!("adjust start_slot and continue chasing: {error}");
+                        self.relayed_slot = slot.checked_sub(1);
+                        start_slot = slot;
+                    }
+                    _ => return Err(error),
+                },
             }
         }
-        if retry >= MAX_RETRY_NUMBER {
-            error!("retry number {retry} exceeds the max {MAX_RETRY_NUMBER}, stop and listening to the next batch of headers");
-            return;
-        }
+        Ok(())
     }
 }
 
 fn send_messages<Chain: ChainHandle>(
-    chain: &Arc<Chain>,
+    chain: &Chain,
     client_states: Vec<IdentifiedAnyClientState>,
 ) -> Result<Vec<crate::event::IbcEventWithHeight>, Error> {
     let tracked_msgs = TrackedMsgs {
@@ -212,20 +199,226 @@ fn send_messages<Chain: ChainHandle>(
     chain.send_messages_and_wait_commit(tracked_msgs)
 }
 
-fn extract_missing_slot_from_error(error: &Error) -> Option<u64> {
-    if let LightClientVerification(verify_error) = error.detail() {
-        match &verify_error.source {
-            ErrorDetail::MissingLastBlockId(detail) => return Some(detail.height.into()),
-            ErrorDetail::TargetLowerThanTrustedState(detail) => panic!(
-                "base slot mismatch: on-chain base slot {}, native base slot {}",
-                detail.target_height, detail.trusted_height
-            ),
-            _ => panic!(
-                "unsupported LightClientVerification error: {}",
-                verify_error.source
-            ),
+/// Returns the slot from which headers have to be resent, if the error
+/// reports that the multi-client is behind the relayed headers.
+fn next_slot_from_error(error: &Error) -> Option<u64> {
+    match error.detail() {
+        ErrorDetail::EthMultiClientBehind(detail) => Some(detail.next_slot),
+        ErrorDetail::EthMultiClientBaseSlotMismatch(_) => {
+            warn!("{error}, the native storage has to be cleared");
+            None
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    use ckb_types::h256;
+    use crossbeam_channel as channel;
+    use ibc_relayer_types::clients::ics07_eth::client_state::EthClientState;
+    use ibc_relayer_types::clients::ics07_eth::types::Update;
+    use ibc_relayer_types::core::ics02_client::events::NewBlock;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+    use ibc_relayer_types::events::IbcEvent;
+    use ibc_relayer_types::Height;
+    use tempfile::TempDir;
+    use tendermint_rpc::Url;
+    use tracing::Span;
+
+    use super::HeaderRelay;
+    use crate::chain::handle::{BaseChainHandle, ChainRequest};
+    use crate::chain::tracking::TrackingId;
+    use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
+    use crate::config::ckb::{ChainConfig as CkbChainConfig, ClientTypeArgs};
+    use crate::config::eth::EthChainConfig;
+    use crate::config::ChainConfig;
+    use crate::error::Error;
+    use crate::event::monitor::EventBatch;
+    use crate::event::IbcEventWithHeight;
+
+    /// Slots of the headers sent in each transaction to the CKB chain
+    type SentSlots = Arc<Mutex<Vec<Vec<u64>>>>;
+
+    fn eth_client_state(slot: u64) -> AnyClientState {
+        let mut lightclient_update = Update::default();
+        lightclient_update.attested_header.slot = slot;
+        AnyClientState::Eth(EthClientState {
+            chain_id: EthChainConfig::goerli().id,
+            lightclient_update,
+        })
+    }
+
+    /// Spawns a chain runtime replying to config requests with `config`
+    /// and passing the other requests to `handle`
+    fn spawn_chain<F>(config: ChainConfig, mut handle: F) -> BaseChainHandle
+    where
+        F: FnMut(ChainRequest) + Send + 'static,
+    {
+        let (sender, receiver) = channel::unbounded::<(Span, ChainRequest)>();
+        let chain = BaseChainHandle::new(config.id().clone(), sender);
+        thread::spawn(move || {
+            for (_, request) in receiver {
+                match request {
+                    ChainRequest::Config { reply_to } => reply_to.send(Ok(config.clone())).unwrap(),
+                    request => handle(request),
+                }
+            }
+        });
+        chain
+    }
+
+    /// Ethereum chain whose headers are available up to `latest_slot`
+    fn spawn_eth_chain(latest_slot: u64) -> BaseChainHandle {
+        spawn_chain(
+            ChainConfig::Eth(EthChainConfig::goerli()),
+            move |request| match request {
+                ChainRequest::BuildClientState {
+                    height, reply_to, ..
+                } => reply_to
+                    .send(Ok(eth_client_state(height.revision_height())))
+                    .unwrap(),
+                ChainRequest::QueryClients { request, reply_to } => {
+                    let page = request.pagination.unwrap();
+                    let end_slot = latest_slot.min(page.offset + page.limit - 1);
+                    let client_states = (page.offset..=end_slot)
+                        .map(|slot| IdentifiedAnyClientState {
+                            client_id: ClientId::default(),
+                            client_state: eth_client_state(slot),
+                        })
+                        .collect();
+                    reply_to.send(Ok(client_states)).unwrap();
+                }
+                request => panic!("unexpected request to eth chain: {request:?}"),
+            },
+        )
+    }
+
+    /// CKB chain failing its transactions with `errors` in turn
+    fn spawn_ckb_chain(errors: Vec<Error>) -> (BaseChainHandle, SentSlots, TempDir) {
+        let data_dir = TempDir::new().unwrap();
+        let config = ChainConfig::Ckb(CkbChainConfig {
+            id: ChainId::new("ckb".to_owned(), 1),
+            ckb_rpc: Url::from_str("http://ckb_rpc").unwrap(),
+            ckb_indexer_rpc: Url::from_str("http://ckb_indexer_rpc").unwrap(),
+            lightclient_contract_typeargs: h256!("0x123"),
+            lightclient_lock_typeargs: h256!("0x123"),
+            minimal_updates_count: 1,
+            key_name: "ckb-chain-test".to_owned(),
+            data_dir: data_dir.path().to_path_buf(),
+            eth_network: Default::default(),
+            eth_chain_id: None,
+            client_type_args: ClientTypeArgs {
+                type_id: None,
+                cells_count: 3,
+            },
+        });
+        let sent_slots = SentSlots::default();
+        let sent = Arc::clone(&sent_slots);
+        let mut errors = VecDeque::from(errors);
+        let chain = spawn_chain(config, move |request| match request {
+            ChainRequest::SendMessagesAndWaitCommit {
+                tracked_msgs,
+                reply_to,
+            } => {
+                let slots = tracked_msgs
+                    .msgs
+                    .iter()
+                    .map(|msg| {
+                        let state: EthClientState = serde_json::from_slice(&msg.value).unwrap();
+                        state.lightclient_update.attested_header.slot
+                    })
+                    .collect();
+                sent.lock().unwrap().push(slots);
+                let result = match errors.pop_front() {
+                    Some(error) => Err(error),
+                    None => Ok(vec![]),
+                };
+                reply_to.send(result).unwrap();
+            }
+            request => panic!("unexpected request to ckb chain: {request:?}"),
+        });
+        (chain, sent_slots, data_dir)
+    }
+
+    fn new_block_batch(slots: std::ops::RangeInclusive<u64>) -> EventBatch {
+        let height = |slot| Height::new(0, slot).unwrap();
+        EventBatch {
+            chain_id: EthChainConfig::goerli().id,
+            tracking_id: TrackingId::new_static("test"),
+            height: height(*slots.end()),
+            events: slots
+                .map(|slot| {
+                    IbcEventWithHeight::new(
+                        IbcEvent::NewBlock(NewBlock::new(height(slot))),
+                        height(slot),
+                    )
+                })
+                .collect(),
         }
     }
-    error!("unexpected error: {error}");
-    None
+
+    fn behind(next_slot: u64) -> Error {
+        Error::eth_multi_client_behind("ckb".to_owned(), next_slot)
+    }
+
+    #[test]
+    fn test_relay_headers() {
+        let (ckb_chain, sent_slots, _data_dir) = spawn_ckb_chain(vec![]);
+        let mut relay = HeaderRelay::new(spawn_eth_chain(10), ckb_chain).unwrap();
+
+        relay.handle_event_batch(&new_block_batch(8..=10)).unwrap();
+        assert_eq!(relay.relayed_slot(), Some(10));
+        assert_eq!(*sent_slots.lock().unwrap(), vec![vec![8, 9, 10]]);
+    }
+
+    #[test]
+    fn test_chase_lost_headers() {
+        let (ckb_chain, sent_slots, _data_dir) = spawn_ckb_chain(vec![behind(5)]);
+        let mut relay = HeaderRelay::new(spawn_eth_chain(10), ckb_chain).unwrap();
+
+        relay.handle_event_batch(&new_block_batch(8..=10)).unwrap();
+        assert_eq!(relay.relayed_slot(), Some(10));
+        assert_eq!(
+            *sent_slots.lock().unwrap(),
+            vec![vec![8, 9, 10], vec![5, 6, 7, 8, 9, 10]]
+        );
+    }
+
+    #[test]
+    fn test_chase_headers_from_slot_zero() {
+        let (ckb_chain, sent_slots, _data_dir) = spawn_ckb_chain(vec![behind(0)]);
+        let mut relay = HeaderRelay::new(spawn_eth_chain(3), ckb_chain).unwrap();
+
+        relay.handle_event_batch(&new_block_batch(2..=3)).unwrap();
+        assert_eq!(relay.relayed_slot(), Some(3));
+        assert_eq!(
+            *sent_slots.lock().unwrap(),
+            vec![vec![2, 3], vec![0, 1, 2, 3]]
+        );
+    }
+
+    #[test]
+    fn test_failed_chasing_is_not_retried() {
+        let errors = vec![behind(5), Error::other_error("rpc failure".to_owned())];
+        let (ckb_chain, sent_slots, _data_dir) = spawn_ckb_chain(errors);
+        let mut relay = HeaderRelay::new(spawn_eth_chain(10), ckb_chain).unwrap();
+
+        // the worker retries the whole batch, the relay itself gives up at once
+        assert!(relay.handle_event_batch(&new_block_batch(8..=10)).is_err());
+        assert_eq!(relay.relayed_slot(), Some(4));
+        assert_eq!(sent_slots.lock().unwrap().len(), 2);
+
+        let errors = vec![behind(5), behind(5)];
+        let (ckb_chain, sent_slots, _data_dir) = spawn_ckb_chain(errors);
+        let mut relay = HeaderRelay::new(spawn_eth_chain(10), ckb_chain).unwrap();
+
+        assert!(relay.handle_event_batch(&new_block_batch(8..=10)).is_err());
+        assert_eq!(sent_slots.lock().unwrap().len(), 2);
+    }
 }
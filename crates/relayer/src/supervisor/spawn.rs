@@ -8,8 +8,8 @@ use ibc_relayer_types::core::{
 use crate::{
     chain::{counterparty::connection_state_on_destination, handle::ChainHandle},
     client_state::IdentifiedAnyClientState,
-    config::Config,
-    object::{Channel, Client, Connection, Object, Packet, Wallet},
+    config::{ChainConfig, Config},
+    object::{Channel, Client, Connection, HeaderRelay, Object, Packet, Wallet},
     registry::Registry,
     supervisor::error::Error as SupervisorError,
     telemetry,
@@ -50,6 +50,49 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                 Err(e) => error!("failed to spawn worker for a chain, reason: {}", e), // TODO: Show chain id
             }
         }
+
+        if self.config.mode.clients.enabled {
+            self.spawn_header_relay_workers();
+        }
+    }
+
+    /// Spawns a header relay worker for each CKB chain configured with
+    /// the Ethereum chain whose headers it hosts.
+    pub fn spawn_header_relay_workers(&mut self) {
+        let config = self.config;
+        let header_relays = config.chains.iter().filter_map(|config| match config {
+            ChainConfig::Ckb(ckb) => ckb.eth_chain_id.as_ref().map(|eth_chain_id| HeaderRelay {
+                dst_chain_id: ckb.id.clone(),
+                src_chain_id: eth_chain_id.clone(),
+            }),
+            _ => None,
+        });
+
+        for header_relay in header_relays {
+            let chains = self
+                .registry
+                .get_or_spawn(&header_relay.src_chain_id)
+                .and_then(|src| {
+                    let dst = self.registry.get_or_spawn(&header_relay.dst_chain_id)?;
+                    Ok((src, dst))
+                });
+            let (src, dst) = match chains {
+                Ok(chains) => chains,
+                Err(e) => {
+                    error!(
+                        "skipping header relay worker {}, reason: failed to spawn chain runtime with error: {}",
+                        header_relay.short_name(),
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let object = Object::HeaderRelay(header_relay);
+            self.workers.spawn(src, dst, &object, config).then(|| {
+                info!("spawning HeaderRelay worker: {}", object.short_name());
+            });
+        }
     }
 
     pub fn spawn_workers_for_chain(&mut self, scan: ChainScan) {
@@ -4,6 +4,9 @@ use std::str::FromStr;
 
 use core::time::Duration;
 
+use ckb_sdk::Address;
+use ckb_types::packed::Script;
+
 use flex_error::{define_error, DetailOnly};
 use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::google::protobuf::Any;
@@ -20,6 +23,7 @@ use ibc_relayer_types::tx_msg::Msg;
 use crate::chain::endpoint::ChainStatus;
 use crate::chain::handle::ChainHandle;
 use crate::chain::tracking::TrackedMsgs;
+use crate::chain::ChainType;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 
@@ -28,6 +32,11 @@ define_error! {
         ReceiverAddress
             [ SignerError ]
             |_| { "receiver address error "},
+
+        CkbReceiverAddress
+            { address: String }
+            |e| { format!("invalid CKB receiver address {}", e.address) },
+
         Relayer
             [ Error ]
             |_| { "relayer error" },
@@ -163,8 +172,11 @@ pub fn build_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHandle>(
     opts: &TransferOptions,
 ) -> Result<Vec<Any>, TransferError> {
     let receiver = match &opts.receiver {
-        Some(receiver) => Signer::from_str(receiver).map_err(TransferError::receiver_address)?,
-        None => dst_chain.get_signer().map_err(TransferError::key)?,
+        Some(receiver) => receiver_on(dst_chain, receiver)?,
+        None => {
+            let signer = dst_chain.get_signer().map_err(TransferError::key)?;
+            receiver_on(dst_chain, signer.as_ref())?
+        }
     };
 
     let sender = src_chain.get_signer().map_err(TransferError::key)?;
@@ -196,6 +208,34 @@ pub fn build_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHandle>(
     Ok(msgs)
 }
 
+/// The receiver of a transfer to `chain`. The ICS-20 packets of CKB refer to
+/// an account by the first 20 bytes of the hash of its lock script rather than
+/// by its address, so CKB addresses are converted, while lock hash prefixes
+/// are kept as they are.
+fn receiver_on<Chain: ChainHandle>(chain: &Chain, receiver: &str) -> Result<Signer, TransferError> {
+    match chain.config().map_err(TransferError::relayer)?.r#type() {
+        ChainType::Ckb4Ibc => ckb_receiver(receiver),
+        _ => Signer::from_str(receiver).map_err(TransferError::receiver_address),
+    }
+}
+
+fn ckb_receiver(receiver: &str) -> Result<Signer, TransferError> {
+    let lock_hash_prefix = match Address::from_str(receiver) {
+        Ok(address) => {
+            let lock_hash = Script::from(address.payload()).calc_script_hash();
+            hex::encode(&lock_hash.raw_data()[..20])
+        }
+        Err(_) => {
+            let prefix = receiver.trim_start_matches("0x");
+            match hex::decode(prefix) {
+                Ok(bytes) if bytes.len() == 20 => prefix.to_lowercase(),
+                _ => return Err(TransferError::ckb_receiver_address(receiver.to_owned())),
+            }
+        }
+    };
+    Signer::from_str(&lock_hash_prefix).map_err(TransferError::receiver_address)
+}
+
 pub fn send_messages<Chain: ChainHandle>(
     chain: &Chain,
     msgs: Vec<Any>,
@@ -232,3 +272,24 @@ pub fn build_and_send_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHa
     let msgs = build_transfer_messages(src_chain, dst_chain, opts)?;
     send_messages(src_chain, msgs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ckb_receiver() {
+        let address = "ckt1qzda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xwsqwgx292hnvmn68xf779vmzrshpmm6epn4c0cgwga";
+        let lock_script = Script::from(Address::from_str(address).unwrap().payload());
+        let prefix = hex::encode(&lock_script.calc_script_hash().raw_data()[..20]);
+        assert_eq!(ckb_receiver(address).unwrap().as_ref(), prefix);
+
+        // lock hash prefixes are kept
+        assert_eq!(ckb_receiver(&prefix).unwrap().as_ref(), prefix);
+        let upper = format!("0x{}", prefix.to_uppercase());
+        assert_eq!(ckb_receiver(&upper).unwrap().as_ref(), prefix);
+
+        assert!(ckb_receiver("0x1234").is_err());
+        assert!(ckb_receiver("cosmos1receiver").is_err());
+    }
+}
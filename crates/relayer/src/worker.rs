@@ -7,6 +7,8 @@ use tracing::error;
 
 use crate::foreign_client::ForeignClient;
 use crate::link::{Link, LinkParameters, Resubmit};
+use crate::supervisor::forcerelay::HeaderRelay;
+use crate::util::lock::{LockExt, RwArc};
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
@@ -31,6 +33,7 @@ pub mod channel;
 pub mod client;
 pub mod connection;
 pub mod cross_chain_query;
+pub mod header_relay;
 pub mod packet;
 pub mod wallet;
 
@@ -61,6 +64,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     config: &Config,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
+    let shared_data = <RwArc<Option<WorkerData>>>::new_lock(None);
 
     let (cmd_tx, data) = match &object {
         Object::Client(client) => {
@@ -202,7 +206,35 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
 
             (Some(cmd_tx), None)
         }
+
+        Object::HeaderRelay(header_relay) => match HeaderRelay::new(chains.a, chains.b) {
+            Ok(relay) => {
+                *shared_data.acquire_write() = Some(WorkerData::HeaderRelay {
+                    relayed_slot: None,
+                    target_slot: None,
+                });
+
+                let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
+                let header_relay_task = header_relay::spawn_header_relay_worker(
+                    header_relay.clone(),
+                    relay,
+                    cmd_rx,
+                    shared_data.clone(),
+                );
+                task_handles.push(header_relay_task);
+
+                (Some(cmd_tx), None)
+            }
+            Err(e) => {
+                error!("error initializing header relay worker: {}", e);
+                (None, None)
+            }
+        },
     };
 
-    WorkerHandle::new(id, object, data, cmd_tx, task_handles)
+    if data.is_some() {
+        *shared_data.acquire_write() = data;
+    }
+
+    WorkerHandle::new(id, object, shared_data, cmd_tx, task_handles)
 }
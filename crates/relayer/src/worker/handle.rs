@@ -22,13 +22,20 @@ use super::{WorkerCmd, WorkerId};
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum WorkerData {
-    Client { misbehaviour: bool, refresh: bool },
+    Client {
+        misbehaviour: bool,
+        refresh: bool,
+    },
+    HeaderRelay {
+        relayed_slot: Option<u64>,
+        target_slot: Option<u64>,
+    },
 }
 
 pub struct WorkerHandle {
     id: WorkerId,
     object: Object,
-    data: Option<WorkerData>,
+    data: RwArc<Option<WorkerData>>,
     tx: RwArc<Option<Sender<WorkerCmd>>>,
     task_handles: Vec<TaskHandle>,
 }
@@ -37,7 +44,7 @@ impl WorkerHandle {
     pub fn new(
         id: WorkerId,
         object: Object,
-        data: Option<WorkerData>,
+        data: RwArc<Option<WorkerData>>,
         tx: Option<Sender<WorkerCmd>>,
         task_handles: Vec<TaskHandle>,
     ) -> Self {
@@ -136,9 +143,10 @@ impl WorkerHandle {
         &self.object
     }
 
-    /// Get a reference to the worker handle's data.
-    pub fn data(&self) -> Option<&WorkerData> {
-        self.data.as_ref()
+    /// Get a snapshot of the worker handle's data, which
+    /// may be kept up to date by the worker tasks.
+    pub fn data(&self) -> Option<WorkerData> {
+        self.data.acquire_read().clone()
     }
 }
 
@@ -0,0 +1,83 @@
+use core::time::Duration;
+use std::panic::{self, AssertUnwindSafe};
+
+use crossbeam_channel::Receiver;
+use tracing::{debug, error_span, warn};
+
+use crate::chain::handle::ChainHandle;
+use crate::object::HeaderRelay as HeaderRelayObject;
+use crate::supervisor::forcerelay::HeaderRelay;
+use crate::telemetry;
+use crate::util::lock::{LockExt, RwArc};
+use crate::util::retry::{retry_with_index, RetryResult};
+use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
+use crate::worker::retry_strategy;
+
+use super::error::RunError;
+use super::{WorkerCmd, WorkerData};
+
+/// Spawns the task relaying the headers of the event batches it receives.
+///
+/// Failing or panicking batches are retried following the
+/// [`header_relay_strategy`](retry_strategy::header_relay_strategy),
+/// a batch still failing afterwards is dropped, leaving the missing
+/// headers to be chased when relaying the next batches.
+pub fn spawn_header_relay_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
+    header_relay: HeaderRelayObject,
+    mut relay: HeaderRelay<ChainA, ChainB>,
+    cmd_rx: Receiver<WorkerCmd>,
+    data: RwArc<Option<WorkerData>>,
+) -> TaskHandle {
+    spawn_background_task(
+        error_span!("worker.header_relay", header_relay = %header_relay.short_name()),
+        Some(Duration::from_millis(200)),
+        move || {
+            let batch = match cmd_rx.try_recv() {
+                Ok(WorkerCmd::IbcEvents { batch }) => batch,
+                // nothing to do
+                _ => return Ok(Next::Continue),
+            };
+            let target_slot = batch.height.revision_height();
+            debug!("relaying headers up to slot {target_slot}");
+
+            let result = retry_with_index(retry_strategy::header_relay_strategy(), |index| {
+                match panic::catch_unwind(AssertUnwindSafe(|| relay.handle_event_batch(&batch))) {
+                    Ok(Ok(())) => RetryResult::Ok(()),
+                    Ok(Err(e)) => {
+                        warn!("failed to relay headers up to slot {target_slot} (attempt {index}): {e}");
+                        RetryResult::Retry(index)
+                    }
+                    Err(panic) => {
+                        let reason = panic
+                            .downcast_ref::<&str>()
+                            .map(|s| s.to_string())
+                            .or_else(|| panic.downcast_ref::<String>().cloned())
+                            .unwrap_or_default();
+                        warn!("restarting header relay up to slot {target_slot} after panic (attempt {index}): {reason}");
+                        RetryResult::Retry(index)
+                    }
+                }
+            });
+
+            let relayed_slot = relay.relayed_slot();
+            *data.acquire_write() = Some(WorkerData::HeaderRelay {
+                relayed_slot,
+                target_slot: Some(target_slot),
+            });
+            if let Some(relayed_slot) = relayed_slot {
+                debug!("headers are relayed up to slot {relayed_slot}");
+                telemetry!(
+                    header_relay_slots,
+                    &header_relay.src_chain_id,
+                    &header_relay.dst_chain_id,
+                    relayed_slot,
+                    target_slot
+                );
+            }
+
+            result
+                .map(|()| Next::Continue)
+                .map_err(|e| TaskError::Ignore(RunError::retry(e)))
+        },
+    )
+}
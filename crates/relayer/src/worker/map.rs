@@ -113,6 +113,18 @@ impl WorkerMap {
         }
     }
 
+    /// Returns all the [`WorkerHandle`]s relaying the headers of the chain
+    /// with the given [`ChainId`], which are fed with all of its event batches.
+    pub fn header_relays<'a>(
+        &'a self,
+        src_chain_id: &'a ChainId,
+    ) -> impl Iterator<Item = &'a WorkerHandle> {
+        self.workers.iter().filter_map(move |(o, w)| match o {
+            Object::HeaderRelay(h) if !w.is_stopped() && &h.src_chain_id == src_chain_id => Some(w),
+            _ => None,
+        })
+    }
+
     /// Get a handle to the worker in charge of handling events associated
     /// with the given [`Object`].
     ///
@@ -238,5 +250,6 @@ fn metric_type(o: &Object) -> ibc_telemetry::state::WorkerType {
         Object::Packet(_) => WorkerType::Packet,
         Object::Wallet(_) => WorkerType::Wallet,
         Object::CrossChainQuery(_) => WorkerType::CrossChainQuery,
+        Object::HeaderRelay(_) => WorkerType::HeaderRelay,
     }
 }
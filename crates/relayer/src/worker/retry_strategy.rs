@@ -14,11 +14,23 @@ pub fn worker_default_strategy() -> impl Iterator<Item = Duration> {
     clamp_total(strategy, Duration::from_millis(500), Duration::from_secs(2))
 }
 
+/// Retry strategy of the header relay worker.
+///
+/// Relaying headers is slower than the other worker steps,
+/// so the backoff delay is initially 1s and grows by 1s
+/// at each step, capped at 5s. The overall amount of time
+/// spent backing off is capped to 30 seconds.
+/// See the `header_relay_strategy` test below.
+pub fn header_relay_strategy() -> impl Iterator<Item = Duration> {
+    let strategy = ConstantGrowth::new(Duration::from_secs(1), Duration::from_secs(1));
+    clamp_total(strategy, Duration::from_secs(5), Duration::from_secs(30))
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
 
-    use crate::worker::retry_strategy::worker_default_strategy;
+    use crate::worker::retry_strategy::{header_relay_strategy, worker_default_strategy};
 
     #[test]
     fn default_strategy() {
@@ -37,4 +49,23 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn header_relay_strategy_delays() {
+        let delays = header_relay_strategy().take(10).collect::<Vec<_>>();
+        // This strategy has exactly 8 retry steps
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+                Duration::from_secs(3),
+                Duration::from_secs(4),
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+            ]
+        );
+    }
 }
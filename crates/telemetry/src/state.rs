@@ -76,6 +76,7 @@ pub enum WorkerType {
     Packet,
     Wallet,
     CrossChainQuery,
+    HeaderRelay,
 }
 
 impl Display for WorkerType {
@@ -87,6 +88,7 @@ impl Display for WorkerType {
             Self::Packet => write!(f, "packet"),
             Self::Wallet => write!(f, "wallet"),
             Self::CrossChainQuery => write!(f, "cross-chain-query"),
+            Self::HeaderRelay => write!(f, "header-relay"),
         }
     }
 }
@@ -192,6 +194,12 @@ pub struct TelemetryState {
 
     /// Sum of rewarded fees over the past FEE_LIFETIME seconds
     period_fees: ObservableGauge<u64>,
+
+    /// The latest slot of the headers relayed to the destination chain, per header relay
+    header_relay_slot: ObservableGauge<u64>,
+
+    /// How many slots the relayed headers lag behind the source chain, per header relay
+    header_relay_slot_lag: ObservableGauge<u64>,
 }
 
 impl TelemetryState {
@@ -484,6 +492,27 @@ impl TelemetryState {
         self.wallet_balance.observe(&cx, amount, labels);
     }
 
+    /// The latest slot relayed by the header relay from `src_chain` to `dst_chain`,
+    /// along with its lag behind `target_slot`, the latest slot of `src_chain`
+    pub fn header_relay_slots(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        relayed_slot: u64,
+        target_slot: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("dst_chain", dst_chain.to_string()),
+        ];
+
+        self.header_relay_slot.observe(&cx, relayed_slot, labels);
+        self.header_relay_slot_lag
+            .observe(&cx, target_slot.saturating_sub(relayed_slot), labels);
+    }
+
     pub fn received_event_batch(&self, tracking_id: impl ToString) {
         self.in_flight_events
             .insert(tracking_id.to_string(), Instant::now());
@@ -873,6 +902,8 @@ impl AggregatorSelector for CustomAggregatorSelector {
                 1000.0, 5000.0, 9000.0, 13000.0, 17000.0, 20000.0,
             ]))),
             "ics29_period_fees" => Some(Arc::new(last_value())),
+            "header_relay_slot" => Some(Arc::new(last_value())),
+            "header_relay_slot_lag" => Some(Arc::new(last_value())),
             _ => Some(Arc::new(sum())),
         }
     }
@@ -1038,6 +1069,16 @@ impl Default for TelemetryState {
                 .u64_observable_gauge("ics29_period_fees")
                 .with_description("Amount of ICS29 fees rewarded over the past 7 days")
                 .init(),
+
+            header_relay_slot: meter
+                .u64_observable_gauge("header_relay_slot")
+                .with_description("The latest slot of the headers relayed to the destination chain")
+                .init(),
+
+            header_relay_slot_lag: meter
+                .u64_observable_gauge("header_relay_slot_lag")
+                .with_description("How many slots the relayed headers lag behind the source chain")
+                .init(),
         }
     }
 }
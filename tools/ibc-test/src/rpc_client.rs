@@ -1,9 +1,9 @@
 #![allow(dead_code)]
 
 use ckb_jsonrpc_types::{
-    BlockNumber, BlockView, CellWithStatus, ChainInfo, HeaderView, JsonBytes, OutPoint,
-    OutputsValidator, RawTxPool, Transaction, TransactionAndWitnessProof,
-    TransactionWithStatusResponse, TxPoolInfo, Uint32,
+    BlockNumber, BlockView, CellWithStatus, ChainInfo, FeeRateStatistics, HeaderView, JsonBytes,
+    OutPoint, OutputsValidator, RawTxPool, Transaction, TransactionAndWitnessProof,
+    TransactionWithStatusResponse, TxPoolInfo, Uint32, Uint64,
 };
 use ckb_sdk::rpc::ckb_indexer::{Cell, Order, Pagination, SearchKey};
 use ckb_types::H256;
@@ -109,6 +109,17 @@ impl CkbReader for RpcClient {
         .boxed()
     }
 
+    fn get_header_by_number(&self, number: BlockNumber) -> Rpc<Option<HeaderView>> {
+        jsonrpc!(
+            "get_header_by_number",
+            Target::CKB,
+            self,
+            Option<HeaderView>,
+            number
+        )
+        .boxed()
+    }
+
     fn get_transaction(&self, hash: &H256) -> Rpc<Option<TransactionWithStatusResponse>> {
         jsonrpc!(
             "get_transaction",
@@ -198,6 +209,17 @@ impl CkbReader for RpcClient {
     fn tx_pool_info(&self) -> Rpc<TxPoolInfo> {
         jsonrpc!("tx_pool_info", Target::CKB, self, TxPoolInfo).boxed()
     }
+
+    fn get_fee_rate_statistics(&self, target: Option<Uint64>) -> Rpc<Option<FeeRateStatistics>> {
+        jsonrpc!(
+            "get_fee_rate_statistics",
+            Target::CKB,
+            self,
+            Option<FeeRateStatistics>,
+            target
+        )
+        .boxed()
+    }
 }
 
 impl CkbWriter for RpcClient {
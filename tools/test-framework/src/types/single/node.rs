@@ -173,6 +173,7 @@ impl FullNode {
                     chain_id: counterparty_chain_id,
                     client_cell_type_args: h256_env("CLIENT_TYPE_ARGS").into(),
                     ibc_handler_address: h160_env("AXON_IBC_HANDLER_ADDRESS").into(),
+                    rpc_addr: None,
                 },
             );
         } else {
@@ -187,6 +188,7 @@ impl FullNode {
                     chain_id: counterparty_chain_id,
                     client_cell_type_args: h256_env("CLIENT_TYPE_ARGS").into(),
                     ibc_handler_address: h160_env("AXON_IBC_HANDLER_ADDRESS").into(),
+                    rpc_addr: None,
                 },
             );
         }
@@ -196,13 +198,27 @@ impl FullNode {
             ckb_rpc: ckb_rpc.clone(),
             ckb_indexer_rpc: ckb_rpc,
             key_name: "relayer_ckb_wallet".to_string(),
+            key_store_type: Store::Test,
             store_prefix: "ibc".to_string(),
             client_code_hash: h256_env("CLIENT_CODE_HASH").into(),
             connection_type_args: h256_env("CONNECTION_TYPE_ARGS").into(),
             channel_type_args: h256_env("CHANNEL_TYPE_ARGS").into(),
             packet_type_args: h256_env("PACKET_TYPE_ARGS").into(),
+            fee_rate: Default::default(),
+            confirms: config::ckb4ibc::default::confirms(),
+            event_confirmations: 0,
+            tx_timeout: config::ckb4ibc::default::tx_timeout(),
+            tx_replacement_timeout: config::ckb4ibc::default::tx_replacement_timeout(),
+            write_ack_reuse_block_gap: config::ckb4ibc::default::write_ack_reuse_block_gap(),
+            dry_run: true,
+            indexer_page_size: config::ckb4ibc::default::indexer_page_size(),
+            max_concurrent_tx_fetches: config::ckb4ibc::default::max_concurrent_tx_fetches(),
+            cell_cache_size: config::ckb4ibc::default::cell_cache_size(),
+            data_dir: Some(PathBuf::from(&self.chain_driver.home_path).join("storage")),
+            sudt_transfer: None,
             onchain_light_clients,
             packet_filter: Default::default(),
+            rescan_from_height: None,
         };
 
         Ok(config::ChainConfig::Ckb4Ibc(ckb_config))
@@ -234,6 +250,7 @@ impl FullNode {
         let axon_config = config::axon::AxonChainConfig {
             id: self.chain_driver.chain_id.clone(),
             key_name: "relayer".to_string(),
+            key_store_type: Store::Test,
             store_prefix: "ibc".to_string(),
             packet_filter: Default::default(),
             websocket_addr,
@@ -241,6 +258,14 @@ impl FullNode {
             contract_address,
             transfer_contract_address,
             restore_block_count,
+            max_msgs_per_batch: config::axon::default::max_msgs_per_batch(),
+            gas_price: Default::default(),
+            gas_multiplier: None,
+            max_fee: None,
+            price_bump_percent: config::axon::default::price_bump_percent(),
+            tx_replacement_timeout: config::axon::default::tx_replacement_timeout(),
+            tokens: vec![],
+            rescan_from_height: None,
         };
         Ok(config::ChainConfig::Axon(axon_config))
     }